@@ -5,7 +5,7 @@
 use rustc_hir::def_id::LocalDefId;
 use rustc_index::{IndexSlice, IndexVec};
 use rustc_infer::infer::TyCtxtInferExt;
-use rustc_middle::mir::{Body, Promoted};
+use rustc_middle::mir::{Body, BorrowCheckResult, Promoted};
 use rustc_middle::traits::DefiningAnchor;
 use rustc_middle::ty::TyCtxt;
 use std::rc::Rc;
@@ -113,3 +113,25 @@ pub fn get_body_with_borrowck_facts(
     let promoted: &IndexSlice<_, _> = &promoted.borrow();
     *super::do_mir_borrowck(&infcx, input_body, promoted, Some(options)).1.unwrap()
 }
+
+/// Borrow checks the given body like the `mir_borrowck` query, and returns its result together
+/// with the facts determined by the [`ConsumerOptions`].
+///
+/// This is meant for overrides of the `mir_borrowck` query which want to collect the facts of
+/// every body, without borrow checking each body twice. Unlike the query, this doesn't skip
+/// bodies injected by `#[custom_mir]`, callers have to check
+/// [`Body::should_skip`](rustc_middle::mir::Body::should_skip) themselves.
+pub fn get_borrowck_result_with_facts(
+    tcx: TyCtxt<'_>,
+    def: LocalDefId,
+    options: ConsumerOptions,
+) -> (BorrowCheckResult<'_>, BodyWithBorrowckFacts<'_>) {
+    let (input_body, promoted) = tcx.mir_promoted(def);
+    let hir_owner = tcx.hir().local_def_id_to_hir_id(def).owner;
+    let infcx =
+        tcx.infer_ctxt().with_opaque_type_inference(DefiningAnchor::Bind(hir_owner.def_id)).build();
+    let input_body: &Body<'_> = &input_body.borrow();
+    let promoted: &IndexSlice<_, _> = &promoted.borrow();
+    let (result, body) = super::do_mir_borrowck(&infcx, input_body, promoted, Some(options));
+    (result, *body.unwrap())
+}
@@ -90,7 +90,7 @@
 
 use crate::session_diagnostics::{
     RLinkEmptyVersionNumber, RLinkEncodingVersionMismatch, RLinkRustcVersionMismatch,
-    RLinkWrongFileType, RlinkNotAFile, RlinkUnableToRead,
+    RLinkWrongFileType, RlinkNotAFile, RlinkUnableToRead, StableMirWriteFailed,
 };
 
 fluent_messages! { "../messages.ftl" }
@@ -443,6 +443,21 @@ fn run_compiler(
 
             queries.global_ctxt()?.enter(|tcx| tcx.analysis(()))?;
 
+            if sess.opts.unstable_opts.emit_stable_mir {
+                queries.global_ctxt()?.enter(|tcx| {
+                    let path = tcx.output_filenames(()).with_extension("smir");
+                    let result = fs::File::create(&path).and_then(|file| {
+                        rustc_smir::rustc_internal::write_smir_pretty(
+                            tcx,
+                            &mut io::BufWriter::new(file),
+                        )
+                    });
+                    if let Err(err) = result {
+                        sess.emit_err(StableMirWriteFailed { path: &path, err });
+                    }
+                });
+            }
+
             if callbacks.after_analysis(&handler, compiler, queries) == Compilation::Stop {
                 return early_exit();
             }
@@ -32,6 +32,13 @@ pub(crate) struct RLinkRustcVersionMismatch<'a> {
 #[diag(driver_impl_rlink_no_a_file)]
 pub(crate) struct RlinkNotAFile;
 
+#[derive(Diagnostic)]
+#[diag(driver_impl_stable_mir_write_failed)]
+pub(crate) struct StableMirWriteFailed<'a> {
+    pub path: &'a std::path::Path,
+    pub err: std::io::Error,
+}
+
 #[derive(Diagnostic)]
 #[diag(driver_impl_ice)]
 pub(crate) struct Ice;
@@ -1487,6 +1487,8 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "version of DWARF debug information to emit (default: 2 or 4, depending on platform)"),
     dylib_lto: bool = (false, parse_bool, [UNTRACKED],
         "enables LTO for dylib crate type"),
+    emit_stable_mir: bool = (false, parse_bool, [UNTRACKED],
+        "write the stable MIR of all local items to a `.smir` file after analysis (default: no)"),
     emit_stack_sizes: bool = (false, parse_bool, [UNTRACKED],
         "emit a section containing stack size metadata (default: no)"),
     emit_thin_lto: bool = (true, parse_bool, [TRACKED],
@@ -1867,6 +1869,8 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "use legacy .ctors section for initializers rather than .init_array"),
     validate_mir: bool = (false, parse_bool, [UNTRACKED],
         "validate MIR after each transformation"),
+    validate_stable_mir: bool = (false, parse_bool, [UNTRACKED],
+        "check the invariants of every body converted to stable MIR (default: no)"),
     #[rustc_lint_opt_deny_field_access("use `Session::verbose` instead of this field")]
     verbose: bool = (false, parse_bool, [UNTRACKED],
         "in general, enable more debug printouts (default: no)"),
@@ -13,15 +13,20 @@
 #![cfg_attr(not(feature = "default"), feature(rustc_private))]
 #![feature(local_key_cell_methods)]
 #![feature(ptr_metadata)]
-#![feature(type_alias_impl_trait)] // Used to define opaque types.
 #![feature(intra_doc_pointers)]
 
 // Declare extern rustc_* crates to enable building this crate separately from the compiler.
 #[cfg(not(feature = "default"))]
+extern crate rustc_borrowck;
+#[cfg(not(feature = "default"))]
+extern crate rustc_data_structures;
+#[cfg(not(feature = "default"))]
 extern crate rustc_hir;
 #[cfg(not(feature = "default"))]
 extern crate rustc_middle;
 #[cfg(not(feature = "default"))]
+extern crate rustc_session;
+#[cfg(not(feature = "default"))]
 extern crate rustc_span;
 #[cfg(not(feature = "default"))]
 extern crate rustc_target;
@@ -3,17 +3,19 @@
 //! For that, we define APIs that will temporarily be public to 3P that exposes rustc internal APIs
 //! until stable MIR is complete.
 
-use std::fmt::Debug;
-use std::string::ToString;
+use std::fmt::{Debug, Display};
+use std::io;
 
 use crate::{
     rustc_smir::Tables,
     stable_mir::{self, with},
 };
+use rustc_middle::query::Providers;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::TyCtxt;
 pub use rustc_span::def_id::{CrateNum, DefId};
 
-fn with_tables<R>(mut f: impl FnMut(&mut Tables<'_>) -> R) -> R {
+fn with_tables<R>(mut f: impl FnMut(&Tables<'_>) -> R) -> R {
     let mut ret = None;
     with(|tables| tables.rustc_tables(&mut |t| ret = Some(f(t))));
     ret.unwrap()
@@ -47,6 +49,26 @@ pub fn generator_def(did: DefId) -> stable_mir::ty::GeneratorDef {
     with_tables(|t| t.generator_def(did))
 }
 
+pub fn alias_def(did: DefId) -> stable_mir::ty::AliasDef {
+    with_tables(|t| t.alias_def(did))
+}
+
+pub fn const_def(did: DefId) -> stable_mir::ty::ConstDef {
+    with_tables(|t| t.const_def(did))
+}
+
+pub fn static_def(did: DefId) -> stable_mir::ty::StaticDef {
+    with_tables(|t| t.static_def(did))
+}
+
+pub fn trait_def(did: DefId) -> stable_mir::ty::TraitDef {
+    with_tables(|t| t.trait_def(did))
+}
+
+pub fn impl_def(did: DefId) -> stable_mir::ty::ImplDef {
+    with_tables(|t| t.impl_def(did))
+}
+
 pub fn param_def(did: DefId) -> stable_mir::ty::ParamDef {
     with_tables(|t| t.param_def(did))
 }
@@ -57,51 +79,83 @@ pub fn br_named_def(did: DefId) -> stable_mir::ty::BrNamedDef {
 
 impl<'tcx> Tables<'tcx> {
     pub fn item_def_id(&self, item: &stable_mir::CrateItem) -> DefId {
-        self.def_ids[item.0]
+        self.def_ids.get(item.0)
+    }
+
+    pub fn adt_def_id(&self, def: &stable_mir::ty::AdtDef) -> DefId {
+        self.def_ids.get(def.0)
     }
 
-    pub fn crate_item(&mut self, did: DefId) -> stable_mir::CrateItem {
+    pub fn fn_def_id(&self, def: &stable_mir::ty::FnDef) -> DefId {
+        self.def_ids.get(def.0)
+    }
+
+    pub fn closure_def_id(&self, def: &stable_mir::ty::ClosureDef) -> DefId {
+        self.def_ids.get(def.0)
+    }
+
+    pub fn impl_def_id(&self, def: &stable_mir::ty::ImplDef) -> DefId {
+        self.def_ids.get(def.0)
+    }
+
+    pub fn crate_item(&self, did: DefId) -> stable_mir::CrateItem {
         stable_mir::CrateItem(self.create_def_id(did))
     }
 
-    pub fn adt_def(&mut self, did: DefId) -> stable_mir::ty::AdtDef {
+    pub fn adt_def(&self, did: DefId) -> stable_mir::ty::AdtDef {
         stable_mir::ty::AdtDef(self.create_def_id(did))
     }
 
-    pub fn foreign_def(&mut self, did: DefId) -> stable_mir::ty::ForeignDef {
+    pub fn foreign_def(&self, did: DefId) -> stable_mir::ty::ForeignDef {
         stable_mir::ty::ForeignDef(self.create_def_id(did))
     }
 
-    pub fn fn_def(&mut self, did: DefId) -> stable_mir::ty::FnDef {
+    pub fn fn_def(&self, did: DefId) -> stable_mir::ty::FnDef {
         stable_mir::ty::FnDef(self.create_def_id(did))
     }
 
-    pub fn closure_def(&mut self, did: DefId) -> stable_mir::ty::ClosureDef {
+    pub fn closure_def(&self, did: DefId) -> stable_mir::ty::ClosureDef {
         stable_mir::ty::ClosureDef(self.create_def_id(did))
     }
 
-    pub fn generator_def(&mut self, did: DefId) -> stable_mir::ty::GeneratorDef {
+    pub fn generator_def(&self, did: DefId) -> stable_mir::ty::GeneratorDef {
         stable_mir::ty::GeneratorDef(self.create_def_id(did))
     }
 
-    pub fn param_def(&mut self, did: DefId) -> stable_mir::ty::ParamDef {
+    pub fn alias_def(&self, did: DefId) -> stable_mir::ty::AliasDef {
+        stable_mir::ty::AliasDef(self.create_def_id(did))
+    }
+
+    pub fn const_def(&self, did: DefId) -> stable_mir::ty::ConstDef {
+        stable_mir::ty::ConstDef(self.create_def_id(did))
+    }
+
+    pub fn static_def(&self, did: DefId) -> stable_mir::ty::StaticDef {
+        stable_mir::ty::StaticDef(self.create_def_id(did))
+    }
+
+    pub fn trait_def(&self, did: DefId) -> stable_mir::ty::TraitDef {
+        stable_mir::ty::TraitDef(self.create_def_id(did))
+    }
+
+    pub fn impl_def(&self, did: DefId) -> stable_mir::ty::ImplDef {
+        stable_mir::ty::ImplDef(self.create_def_id(did))
+    }
+
+    pub fn param_def(&self, did: DefId) -> stable_mir::ty::ParamDef {
         stable_mir::ty::ParamDef(self.create_def_id(did))
     }
 
-    pub fn br_named_def(&mut self, did: DefId) -> stable_mir::ty::BrNamedDef {
+    pub fn br_named_def(&self, did: DefId) -> stable_mir::ty::BrNamedDef {
         stable_mir::ty::BrNamedDef(self.create_def_id(did))
     }
 
-    fn create_def_id(&mut self, did: DefId) -> stable_mir::DefId {
-        // FIXME: this becomes inefficient when we have too many ids
-        for (i, &d) in self.def_ids.iter().enumerate() {
-            if d == did {
-                return i;
-            }
-        }
-        let id = self.def_ids.len();
-        self.def_ids.push(did);
-        id
+    pub fn create_span(&self, span: rustc_span::Span) -> stable_mir::ty::Span {
+        stable_mir::ty::Span(self.spans.intern(span))
+    }
+
+    fn create_def_id(&self, did: DefId) -> stable_mir::DefId {
+        self.def_ids.intern(did)
     }
 }
 
@@ -110,12 +164,86 @@ pub fn crate_num(item: &stable_mir::Crate) -> CrateNum {
 }
 
 pub fn run(tcx: TyCtxt<'_>, f: impl FnOnce()) {
-    crate::stable_mir::run(Tables { tcx, def_ids: vec![], types: vec![] }, f);
+    crate::stable_mir::run(Tables::new(tcx), f);
+}
+
+/// Override the `mir_borrowck` query so that the input facts of the Polonius borrow checker are
+/// collected for every body, which makes them available through
+/// [`CrateItem::borrowck_facts`](stable_mir::CrateItem::borrowck_facts).
+///
+/// Bodies are borrow checked before stable MIR can be used, so this has to be called from the
+/// `override_queries` callback of the compiler configuration.
+pub fn provide_borrowck_facts(providers: &mut Providers) {
+    crate::rustc_smir::borrowck::provide(providers);
+}
+
+/// Write the stable MIR of every local item with a body to `w`.
+///
+/// This is the output of `-Z emit-stable-mir`. The format is not stable and only meant to be
+/// read by humans or diffed across compiler versions.
+pub fn write_smir_pretty(tcx: TyCtxt<'_>, w: &mut dyn io::Write) -> io::Result<()> {
+    use stable_mir::DefKind;
+
+    let mut result = Ok(());
+    run(tcx, || {
+        result = (|| {
+            let krate = stable_mir::local_crate();
+            writeln!(w, "// Stable MIR for crate `{}`. This output is unstable.", krate.name)?;
+            for item in stable_mir::local_items() {
+                match item.kind() {
+                    DefKind::Fn
+                    | DefKind::AssocFn
+                    | DefKind::Closure
+                    | DefKind::Generator
+                    | DefKind::Ctor(..)
+                    | DefKind::Const
+                    | DefKind::AssocConst
+                    | DefKind::Static { .. }
+                    | DefKind::AnonConst
+                    | DefKind::InlineConst => {
+                        writeln!(w, "{}: {:#?}", item.name(), item.body())?;
+                    }
+                    kind => writeln!(w, "{}: unsupported item kind {kind:?}", item.name())?,
+                }
+            }
+            Ok(())
+        })();
+    });
+    result
 }
 
 /// A type that provides internal information but that can still be used for debug purpose.
-pub type Opaque = impl Debug + ToString + Clone;
+///
+/// Opaque values are compared and hashed through their textual representation. This is only a
+/// best-effort equality: values that are printed the same way compare equal, even if they are
+/// different inside the compiler.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Opaque(String);
+
+impl Opaque {
+    /// The textual representation of this value.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Opaque {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Debug for Opaque {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
 
 pub(crate) fn opaque<T: Debug>(value: &T) -> Opaque {
-    format!("{value:?}")
+    Opaque(format!("{value:?}"))
+}
+
+/// Same as [`opaque`], but uses the user facing representation of the value.
+pub(crate) fn opaque_display<T: Display>(value: &T) -> Opaque {
+    Opaque(with_no_trimmed_paths!(value.to_string()))
 }
@@ -0,0 +1,150 @@
+//! Collecting the Polonius input facts of bodies while they're borrow checked.
+//!
+//! The body that is borrow checked is stolen once borrow checking is done, so the facts can't be
+//! computed on demand. Instead, the `mir_borrowck` query is overridden to compute the facts while
+//! borrow checking, and they're kept until they're asked for.
+//!
+//! Query providers only get a `TyCtxt`, so the facts are kept in a thread local. Every session
+//! runs its queries on a thread of its own, which is spawned by the driver that installs the
+//! override, so the facts only live as long as that session. They're also dropped whenever the
+//! override is installed, so a session never sees the facts of another one, even if it reuses a
+//! thread.
+
+use std::cell::RefCell;
+
+use crate::stable_mir::mir::borrowck::{BorrowckFacts, Point, PointKind};
+use rustc_borrowck::consumers::{self, ConsumerOptions, LocationTable, RichLocation};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::mir::BorrowCheckResult;
+use rustc_middle::query::Providers;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::def_id::LocalDefId;
+
+thread_local! {
+    /// The facts of the bodies borrow checked by the session running on this thread.
+    static BORROWCK_FACTS: RefCell<FxHashMap<LocalDefId, BorrowckFacts>> =
+        RefCell::new(FxHashMap::default());
+}
+
+pub(crate) fn provide(providers: &mut Providers) {
+    // This is called on the thread of the session, before any body is borrow checked, so facts
+    // left over from an earlier session on this thread can be dropped.
+    BORROWCK_FACTS.with(|facts| facts.borrow_mut().clear());
+    providers.mir_borrowck = mir_borrowck;
+}
+
+pub(super) fn borrowck_facts(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<BorrowckFacts> {
+    // Without the override, the facts weren't collected by this session.
+    if tcx.query_system.fns.local_providers.mir_borrowck as usize != mir_borrowck as usize {
+        return None;
+    }
+    BORROWCK_FACTS.with(|facts| facts.borrow().get(&def_id).cloned())
+}
+
+fn mir_borrowck(tcx: TyCtxt<'_>, def_id: LocalDefId) -> &BorrowCheckResult<'_> {
+    // Bodies that are injected by `#[custom_mir]` aren't borrow checked, which the regular query
+    // takes care of.
+    if tcx.mir_promoted(def_id).0.borrow().should_skip() {
+        let mut providers = Providers::default();
+        rustc_borrowck::provide(&mut providers);
+        return (providers.mir_borrowck)(tcx, def_id);
+    }
+
+    let (result, body) =
+        consumers::get_borrowck_result_with_facts(tcx, def_id, ConsumerOptions::PoloniusInputFacts);
+    if let (Some(input_facts), Some(location_table)) = (&body.input_facts, &body.location_table) {
+        let facts = stable_facts(input_facts, location_table);
+        BORROWCK_FACTS.with(|session_facts| session_facts.borrow_mut().insert(def_id, facts));
+    }
+    tcx.arena.alloc(result)
+}
+
+fn stable_facts(facts: &consumers::PoloniusInput, location_table: &LocationTable) -> BorrowckFacts {
+    let point = |index| {
+        let (location, kind) = match location_table.to_location(index) {
+            RichLocation::Start(location) => (location, PointKind::Start),
+            RichLocation::Mid(location) => (location, PointKind::Mid),
+        };
+        Point { block: location.block.as_usize(), statement: location.statement_index, kind }
+    };
+    BorrowckFacts {
+        loan_issued_at: facts
+            .loan_issued_at
+            .iter()
+            .map(|&(origin, loan, p)| (origin.as_usize(), loan.as_usize(), point(p)))
+            .collect(),
+        universal_region: facts.universal_region.iter().map(|origin| origin.as_usize()).collect(),
+        cfg_edge: facts.cfg_edge.iter().map(|&(from, to)| (point(from), point(to))).collect(),
+        loan_killed_at: facts
+            .loan_killed_at
+            .iter()
+            .map(|&(loan, p)| (loan.as_usize(), point(p)))
+            .collect(),
+        subset_base: facts
+            .subset_base
+            .iter()
+            .map(|&(sub, sup, p)| (sub.as_usize(), sup.as_usize(), point(p)))
+            .collect(),
+        loan_invalidated_at: facts
+            .loan_invalidated_at
+            .iter()
+            .map(|&(p, loan)| (point(p), loan.as_usize()))
+            .collect(),
+        var_used_at: facts.var_used_at.iter().map(|&(var, p)| (var.as_usize(), point(p))).collect(),
+        var_defined_at: facts
+            .var_defined_at
+            .iter()
+            .map(|&(var, p)| (var.as_usize(), point(p)))
+            .collect(),
+        var_dropped_at: facts
+            .var_dropped_at
+            .iter()
+            .map(|&(var, p)| (var.as_usize(), point(p)))
+            .collect(),
+        use_of_var_derefs_origin: facts
+            .use_of_var_derefs_origin
+            .iter()
+            .map(|&(var, origin)| (var.as_usize(), origin.as_usize()))
+            .collect(),
+        drop_of_var_derefs_origin: facts
+            .drop_of_var_derefs_origin
+            .iter()
+            .map(|&(var, origin)| (var.as_usize(), origin.as_usize()))
+            .collect(),
+        child_path: facts
+            .child_path
+            .iter()
+            .map(|&(child, parent)| (child.as_usize(), parent.as_usize()))
+            .collect(),
+        path_is_var: facts
+            .path_is_var
+            .iter()
+            .map(|&(path, var)| (path.as_usize(), var.as_usize()))
+            .collect(),
+        path_assigned_at_base: facts
+            .path_assigned_at_base
+            .iter()
+            .map(|&(path, p)| (path.as_usize(), point(p)))
+            .collect(),
+        path_moved_at_base: facts
+            .path_moved_at_base
+            .iter()
+            .map(|&(path, p)| (path.as_usize(), point(p)))
+            .collect(),
+        path_accessed_at_base: facts
+            .path_accessed_at_base
+            .iter()
+            .map(|&(path, p)| (path.as_usize(), point(p)))
+            .collect(),
+        known_placeholder_subset: facts
+            .known_placeholder_subset
+            .iter()
+            .map(|&(sub, sup)| (sub.as_usize(), sup.as_usize()))
+            .collect(),
+        placeholder: facts
+            .placeholder
+            .iter()
+            .map(|&(origin, loan)| (origin.as_usize(), loan.as_usize()))
+            .collect(),
+    }
+}
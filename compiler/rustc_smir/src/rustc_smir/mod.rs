@@ -7,17 +7,65 @@
 //!
 //! For now, we are developing everything inside `rustc`, thus, we keep this module private.
 
-use crate::rustc_internal::{self, opaque};
+use crate::rustc_internal::{self, opaque, opaque_display};
+use crate::stable_mir::mir::alloc::{AllocId, GlobalAlloc};
+use crate::stable_mir::mir::mono::InstanceDef;
+use crate::stable_mir::target::{Endian, MachineInfo, MachineSize};
 use crate::stable_mir::ty::{FloatTy, IntTy, Movability, RigidTy, TyKind, UintTy};
 use crate::stable_mir::{self, Context};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxIndexSet};
+use rustc_data_structures::sharded::{make_hash, Sharded, SHARDS};
+use rustc_data_structures::stable_hasher::{Hash64, HashStable, StableHasher};
+use rustc_data_structures::sync::{par_for_each_in, IntoDynSyncSend, Lock};
 use rustc_hir as hir;
+use rustc_hir::def::DefKind;
 use rustc_middle::mir;
+use rustc_middle::mir::interpret::{self, get_slice_bytes, ConstValue, Scalar};
+use rustc_middle::span_bug;
+use rustc_middle::ty::layout::fn_can_unwind;
+use rustc_middle::ty::print::with_no_trimmed_paths;
 use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_span::def_id::{CrateNum, DefId, LOCAL_CRATE};
-use rustc_target::abi::FieldIdx;
+use rustc_span::Symbol;
+use rustc_target::abi::{FieldIdx, Size};
+use std::hash::Hash;
 use tracing::debug;
 
+pub(crate) mod borrowck;
+mod thir;
+mod unsafety;
+
 impl<'tcx> Context for Tables<'tcx> {
+    fn smir_version(&self) -> stable_mir::CompilerVersion {
+        stable_mir::CompilerVersion {
+            smir_version: stable_mir::CRATE_VERSION.to_string(),
+            rustc_version: self.tcx.sess.cfg_version.to_string(),
+        }
+    }
+
+    fn compiler_options(&self) -> stable_mir::CompilerOptions {
+        use rustc_session::config::OptLevel;
+        use rustc_target::spec::PanicStrategy;
+
+        let sess = self.tcx.sess;
+        stable_mir::CompilerOptions {
+            opt_level: match sess.opts.optimize {
+                OptLevel::No => stable_mir::OptLevel::No,
+                OptLevel::Less => stable_mir::OptLevel::Less,
+                OptLevel::Default => stable_mir::OptLevel::Default,
+                OptLevel::Aggressive => stable_mir::OptLevel::Aggressive,
+                OptLevel::Size => stable_mir::OptLevel::Size,
+                OptLevel::SizeMin => stable_mir::OptLevel::SizeMin,
+            },
+            debug_assertions: sess.opts.debug_assertions,
+            overflow_checks: sess.overflow_checks(),
+            panic_strategy: match sess.panic_strategy() {
+                PanicStrategy::Unwind => stable_mir::PanicStrategy::Unwind,
+                PanicStrategy::Abort => stable_mir::PanicStrategy::Abort,
+            },
+        }
+    }
+
     fn local_crate(&self) -> stable_mir::Crate {
         smir_crate(self.tcx, LOCAL_CRATE)
     }
@@ -33,57 +81,1187 @@ fn find_crate(&self, name: &str) -> Option<stable_mir::Crate> {
         })
     }
 
-    fn all_local_items(&mut self) -> stable_mir::CrateItems {
-        self.tcx.mir_keys(()).iter().map(|item| self.crate_item(item.to_def_id())).collect()
+    fn find_item_by_path(&self, path: &str) -> Option<stable_mir::CrateItem> {
+        let tcx = self.tcx;
+        let segments: Vec<&str> = path.split("::").collect();
+        let (krate, rest) = segments.split_first()?;
+        let crate_roots: Vec<DefId> = [LOCAL_CRATE]
+            .iter()
+            .chain(tcx.crates(()).iter())
+            .filter(|crate_num| tcx.crate_name(**crate_num).as_str() == *krate)
+            .map(|crate_num| crate_num.as_def_id())
+            .collect();
+        let (mut candidates, rest) = if crate_roots.is_empty() {
+            (vec![LOCAL_CRATE.as_def_id()], &segments[..])
+        } else {
+            (crate_roots, rest)
+        };
+        for segment in rest {
+            candidates = candidates
+                .into_iter()
+                .flat_map(|def_id| item_children_by_name(tcx, def_id, segment))
+                .collect();
+        }
+        candidates.first().map(|def_id| self.crate_item(*def_id))
+    }
+
+    fn local_items_len(&self) -> usize {
+        self.tcx.mir_keys(()).len()
     }
-    fn entry_fn(&mut self) -> Option<stable_mir::CrateItem> {
+
+    fn local_item(&self, index: usize) -> stable_mir::CrateItem {
+        let def_id = self.tcx.mir_keys(())[index];
+        self.crate_item(def_id.to_def_id())
+    }
+
+    fn par_for_each_item(
+        &self,
+        items: stable_mir::CrateItems,
+        f: &(dyn Fn(stable_mir::CrateItem) + Sync + Send),
+    ) {
+        let f = IntoDynSyncSend(f);
+        par_for_each_in(items, |item| stable_mir::enter(self, || (f.0)(item)));
+    }
+
+    fn entry_fn(&self) -> Option<stable_mir::CrateItem> {
         Some(self.crate_item(self.tcx.entry_fn(())?.0))
     }
-    fn mir_body(&mut self, item: &stable_mir::CrateItem) -> stable_mir::mir::Body {
+    fn mir_body(&self, item: &stable_mir::CrateItem) -> stable_mir::mir::Body {
         let def_id = self.item_def_id(item);
-        let mir = self.tcx.optimized_mir(def_id);
-        stable_mir::mir::Body {
-            blocks: mir
-                .basic_blocks
+        if is_const_context(self.tcx.def_kind(def_id)) {
+            self.body(self.tcx.mir_for_ctfe(def_id))
+        } else {
+            self.body(self.tcx.optimized_mir(def_id))
+        }
+    }
+
+    fn has_body(&self, item: &stable_mir::CrateItem) -> bool {
+        let def_id = self.item_def_id(item);
+        if is_const_context(self.tcx.def_kind(def_id)) {
+            self.tcx.is_ctfe_mir_available(def_id)
+        } else {
+            self.tcx.is_mir_available(def_id)
+        }
+    }
+
+    fn crate_items(&self, krate: stable_mir::CrateNum) -> stable_mir::CrateItems {
+        let krate = CrateNum::from_usize(krate);
+        if krate == LOCAL_CRATE {
+            return self
+                .tcx
+                .mir_keys(())
                 .iter()
-                .map(|block| stable_mir::mir::BasicBlock {
-                    terminator: block.terminator().stable(self),
-                    statements: block
-                        .statements
-                        .iter()
-                        .map(|statement| statement.stable(self))
-                        .collect(),
-                })
-                .collect(),
-            locals: mir.local_decls.iter().map(|decl| self.intern_ty(decl.ty)).collect(),
+                .map(|def_id| self.crate_item(def_id.to_def_id()))
+                .collect();
         }
+        extern_crate_items(self.tcx, krate)
+            .into_iter()
+            .map(|def_id| self.crate_item(def_id))
+            .collect()
+    }
+
+    fn item_span(&self, item: &stable_mir::CrateItem) -> stable_mir::ty::Span {
+        let span = self.tcx.def_span(self.item_def_id(item));
+        self.create_span(span)
+    }
+
+    fn def_ty(&self, item: &stable_mir::CrateItem) -> stable_mir::ty::Ty {
+        let def_id = self.item_def_id(item);
+        self.intern_ty(self.tcx.type_of(def_id).instantiate_identity())
     }
 
-    fn rustc_tables(&mut self, f: &mut dyn FnMut(&mut Tables<'_>)) {
+    fn visibility(&self, item: &stable_mir::CrateItem) -> stable_mir::Visibility {
+        let mut def_id = self.item_def_id(item);
+        // Anonymous constants, e.g. array lengths, are only visible where they're used.
+        while matches!(self.tcx.def_kind(def_id), DefKind::AnonConst | DefKind::InlineConst) {
+            def_id = self.tcx.parent(def_id);
+        }
+        match self.tcx.visibility(def_id) {
+            ty::Visibility::Public => stable_mir::Visibility::Public,
+            ty::Visibility::Restricted(module) => {
+                let mut parent_module = self.tcx.parent(def_id);
+                while self.tcx.def_kind(parent_module) != DefKind::Mod {
+                    parent_module = self.tcx.parent(parent_module);
+                }
+                if module == parent_module {
+                    stable_mir::Visibility::Private
+                } else if module.is_crate_root() {
+                    stable_mir::Visibility::Restricted("crate".to_string())
+                } else {
+                    // Restricted visibilities always name a module of the local crate, but
+                    // `with_crate_prefix!` only prints `crate::` in the 2018 edition and later.
+                    let path = with_no_trimmed_paths!(self.tcx.def_path_str(module));
+                    stable_mir::Visibility::Restricted(format!("crate::{path}"))
+                }
+            }
+        }
+    }
+
+    fn param_bounds(
+        &self,
+        item: &stable_mir::CrateItem,
+        param_index: u32,
+    ) -> Vec<stable_mir::ty::ParamBound> {
+        use stable_mir::ty::ParamBound;
+
+        let is_param =
+            |ty: Ty<'tcx>| matches!(ty.kind(), ty::Param(param) if param.index == param_index);
+        let predicates =
+            self.tcx.predicates_of(self.item_def_id(item)).instantiate_identity(self.tcx);
+        predicates
+            .predicates
+            .iter()
+            .filter_map(|clause| match clause.kind().skip_binder() {
+                ty::ClauseKind::Trait(pred) if is_param(pred.self_ty()) => {
+                    Some(ParamBound::Trait(pred.trait_ref.stable(self)))
+                }
+                ty::ClauseKind::TypeOutlives(ty::OutlivesPredicate(ty, region)) if is_param(ty) => {
+                    Some(ParamBound::Outlives(opaque(&region)))
+                }
+                ty::ClauseKind::RegionOutlives(ty::OutlivesPredicate(param, region))
+                    if matches!(*param, ty::ReEarlyBound(param) if param.index == param_index) =>
+                {
+                    Some(ParamBound::Outlives(opaque(&region)))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn linkage_attrs(&self, item: &stable_mir::CrateItem) -> stable_mir::mir::mono::LinkageAttrs {
+        use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+        use rustc_middle::mir::mono::Linkage;
+        use stable_mir::mir::mono::{self, LinkageAttrs};
+
+        let def_id = self.item_def_id(item);
+        if !self.tcx.def_kind(def_id).has_codegen_attrs() {
+            return LinkageAttrs::default();
+        }
+        let attrs = self.tcx.codegen_fn_attrs(def_id);
+        LinkageAttrs {
+            no_mangle: attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE),
+            export_name: attrs.export_name.map(|name| name.to_string()),
+            link_section: attrs.link_section.map(|section| section.to_string()),
+            linkage: attrs.linkage.map(|linkage| match linkage {
+                Linkage::External => mono::Linkage::External,
+                Linkage::AvailableExternally => mono::Linkage::AvailableExternally,
+                Linkage::LinkOnceAny => mono::Linkage::LinkOnceAny,
+                Linkage::LinkOnceODR => mono::Linkage::LinkOnceODR,
+                Linkage::WeakAny => mono::Linkage::WeakAny,
+                Linkage::WeakODR => mono::Linkage::WeakODR,
+                Linkage::Appending => mono::Linkage::Appending,
+                Linkage::Internal => mono::Linkage::Internal,
+                Linkage::Private => mono::Linkage::Private,
+                Linkage::ExternalWeak => mono::Linkage::ExternalWeak,
+                Linkage::Common => mono::Linkage::Common,
+            }),
+        }
+    }
+
+    fn coverage_info(
+        &self,
+        item: &stable_mir::CrateItem,
+    ) -> Option<stable_mir::mir::coverage::CoverageInfo> {
+        use mir::coverage::{CoverageKind, ExpressionOperandId, Op};
+        use stable_mir::mir::coverage::{
+            CodeRegion, CoverageExpression, CoverageInfo, CoverageMapping, CoverageOp, CoverageTerm,
+        };
+
+        let def_id = self.item_def_id(item);
+        if !self.tcx.sess.instrument_coverage() || !self.tcx.is_mir_available(def_id) {
+            return None;
+        }
+        let body = self.tcx.optimized_mir(def_id);
+        let coverages: Vec<_> = body
+            .basic_blocks
+            .iter()
+            .flat_map(|block| &block.statements)
+            .filter_map(|statement| match &statement.kind {
+                mir::StatementKind::Coverage(coverage) => Some(&**coverage),
+                _ => None,
+            })
+            .collect();
+        if coverages.is_empty() {
+            return None;
+        }
+
+        // Operands don't say whether they refer to a counter or an expression, so find out
+        // which IDs belong to expressions first.
+        let expression_ids: FxHashSet<ExpressionOperandId> = coverages
+            .iter()
+            .filter_map(|coverage| match coverage.kind {
+                CoverageKind::Expression { id, .. } => Some(ExpressionOperandId::from(id)),
+                _ => None,
+            })
+            .collect();
+        let term = |id: ExpressionOperandId| {
+            if id == ExpressionOperandId::ZERO {
+                CoverageTerm::Zero
+            } else if expression_ids.contains(&id) {
+                CoverageTerm::Expression(id.as_u32())
+            } else {
+                CoverageTerm::Counter(id.as_u32())
+            }
+        };
+
+        let mut expressions = vec![];
+        let mut mappings = vec![];
+        for coverage in coverages {
+            if let CoverageKind::Expression { id, lhs, op, rhs } = coverage.kind {
+                expressions.push(CoverageExpression {
+                    id: id.as_u32(),
+                    lhs: term(lhs),
+                    op: match op {
+                        Op::Add => CoverageOp::Add,
+                        Op::Subtract => CoverageOp::Subtract,
+                    },
+                    rhs: term(rhs),
+                });
+            }
+            if let Some(region) = &coverage.code_region {
+                let term = match coverage.kind {
+                    CoverageKind::Unreachable => None,
+                    _ => Some(term(coverage.kind.as_operand_id())),
+                };
+                mappings.push(CoverageMapping {
+                    term,
+                    region: CodeRegion {
+                        file_name: region.file_name.to_string(),
+                        start_line: region.start_line,
+                        start_col: region.start_col,
+                        end_line: region.end_line,
+                        end_col: region.end_col,
+                    },
+                });
+            }
+        }
+
+        let info = self.tcx.coverageinfo(ty::InstanceDef::Item(def_id));
+        Some(CoverageInfo {
+            num_counters: info.num_counters,
+            num_expressions: info.num_expressions,
+            expressions,
+            mappings,
+        })
+    }
+
+    fn borrowck_facts(
+        &self,
+        item: &stable_mir::CrateItem,
+    ) -> Option<stable_mir::mir::borrowck::BorrowckFacts> {
+        borrowck::borrowck_facts(self.tcx, self.item_def_id(item).as_local()?)
+    }
+
+    fn thir_body(&self, item: &stable_mir::CrateItem) -> Option<stable_mir::thir::ThirBody> {
+        thir::thir_body(self, self.item_def_id(item).as_local()?)
+    }
+
+    fn unsafety(
+        &self,
+        item: &stable_mir::CrateItem,
+    ) -> Option<stable_mir::mir::unsafety::UnsafetyInfo> {
+        let def_id = self.item_def_id(item).as_local()?;
+        Some(unsafety::unsafety_info(self, def_id))
+    }
+
+    fn all_local_impls(&self) -> Vec<stable_mir::ty::ImplDef> {
+        self.tcx
+            .hir_crate_items(())
+            .definitions()
+            .filter(|def_id| matches!(self.tcx.def_kind(*def_id), DefKind::Impl { .. }))
+            .map(|def_id| self.impl_def(def_id.to_def_id()))
+            .collect()
+    }
+
+    fn collect_mono_items(&self) -> Vec<stable_mir::mir::mono::MonoItem> {
+        use rustc_middle::mir::mono::MonoItem;
+
+        // Items that are inlined into several codegen units appear in each of them.
+        let (_, codegen_units) = self.tcx.collect_and_partition_mono_items(());
+        let items: FxIndexSet<_> = codegen_units
+            .iter()
+            .flat_map(|cgu| cgu.items_in_deterministic_order(self.tcx))
+            .map(|(item, _)| item)
+            .collect();
+        items
+            .into_iter()
+            .map(|item| match item {
+                MonoItem::Fn(instance) => {
+                    stable_mir::mir::mono::MonoItem::Fn(self.intern_instance(instance))
+                }
+                MonoItem::Static(def_id) => {
+                    stable_mir::mir::mono::MonoItem::Static(self.static_def(def_id))
+                }
+                MonoItem::GlobalAsm(item_id) => {
+                    stable_mir::mir::mono::MonoItem::GlobalAsm(opaque(&item_id))
+                }
+            })
+            .collect()
+    }
+
+    fn adt_inherent_impls(&self, def: &stable_mir::ty::AdtDef) -> Vec<stable_mir::ty::ImplDef> {
+        let def_id = self.adt_def_id(def);
+        self.tcx.inherent_impls(def_id).iter().map(|impl_id| self.impl_def(*impl_id)).collect()
+    }
+
+    fn impl_self_ty(&self, def: &stable_mir::ty::ImplDef) -> stable_mir::ty::Ty {
+        let def_id = self.impl_def_id(def);
+        self.intern_ty(self.tcx.type_of(def_id).instantiate_identity())
+    }
+
+    fn impl_trait_ref(&self, def: &stable_mir::ty::ImplDef) -> Option<stable_mir::ty::TraitRef> {
+        let def_id = self.impl_def_id(def);
+        Some(self.tcx.impl_trait_ref(def_id)?.instantiate_identity().stable(self))
+    }
+
+    fn impl_items(&self, def: &stable_mir::ty::ImplDef) -> Vec<stable_mir::CrateItem> {
+        let def_id = self.impl_def_id(def);
+        self.tcx
+            .associated_items(def_id)
+            .in_definition_order()
+            .filter(|item| item.kind != ty::AssocKind::Type)
+            .map(|item| self.crate_item(item.def_id))
+            .collect()
+    }
+
+    fn closure_parent_item(&self, def: &stable_mir::ty::ClosureDef) -> stable_mir::CrateItem {
+        self.crate_item(self.tcx.typeck_root_def_id(self.closure_def_id(def)))
+    }
+
+    fn closure_captures(
+        &self,
+        def: &stable_mir::ty::ClosureDef,
+    ) -> Vec<stable_mir::ty::CapturedPlace> {
+        use stable_mir::ty::{CaptureBorrowKind, CaptureKind, CapturedPlace};
+
+        let Some(def_id) = self.closure_def_id(def).as_local() else {
+            return vec![];
+        };
+        self.tcx
+            .closure_captures(def_id)
+            .iter()
+            .map(|captured| CapturedPlace {
+                path: captured.to_string(self.tcx),
+                var_name: captured.var_ident.to_string(),
+                kind: match captured.info.capture_kind {
+                    ty::UpvarCapture::ByValue => CaptureKind::ByValue,
+                    ty::UpvarCapture::ByRef(kind) => CaptureKind::ByRef(match kind {
+                        ty::BorrowKind::ImmBorrow => CaptureBorrowKind::Shared,
+                        ty::BorrowKind::UniqueImmBorrow => CaptureBorrowKind::Unique,
+                        ty::BorrowKind::MutBorrow => CaptureBorrowKind::Mut,
+                    }),
+                },
+                ty: self.intern_ty(captured.place.ty()),
+                span: self.create_span(captured.get_path_span(self.tcx)),
+            })
+            .collect()
+    }
+
+    fn emit_diagnostic(
+        &self,
+        level: stable_mir::DiagnosticLevel,
+        message: &str,
+        span: stable_mir::ty::Span,
+    ) {
+        let span = self.spans.get(span.0);
+        let sess = self.tcx.sess;
+        match level {
+            stable_mir::DiagnosticLevel::Error => {
+                sess.span_err(span, message.to_string());
+            }
+            stable_mir::DiagnosticLevel::Warning => sess.span_warn(span, message.to_string()),
+            stable_mir::DiagnosticLevel::Note => {
+                sess.span_note_without_error(span, message.to_string())
+            }
+        }
+    }
+
+    fn rustc_tables(&self, f: &mut dyn FnMut(&Tables<'_>)) {
         f(self)
     }
 
-    fn ty_kind(&mut self, ty: crate::stable_mir::ty::Ty) -> TyKind {
-        let ty = self.types[ty.0];
-        ty.stable(self)
+    fn ty_kind(&self, ty: crate::stable_mir::ty::Ty) -> TyKind {
+        let shard = self.ty_kinds.get_shard_by_value(&ty.0);
+        if let Some(kind) = shard.lock().get(&ty.0) {
+            return kind.clone();
+        }
+        let kind = self.types.get(ty.0).stable(self);
+        shard.lock().insert(ty.0, kind.clone());
+        kind
+    }
+
+    fn ty_fn_sig(&self, ty: stable_mir::ty::Ty) -> Option<stable_mir::ty::PolyFnSig> {
+        let ty = self.types.get(ty.0);
+        let sig = match ty.kind() {
+            ty::FnDef(..) | ty::FnPtr(_) => ty.fn_sig(self.tcx),
+            ty::Closure(_, args) => args.as_closure().sig(),
+            _ => return None,
+        };
+        Some(sig.stable(self))
+    }
+
+    fn mk_ty(&self, kind: TyKind) -> stable_mir::ty::Ty {
+        self.intern_ty(self.internal_ty_kind(&kind))
+    }
+
+    fn def_path_str(&self, def_id: stable_mir::DefId) -> String {
+        with_no_trimmed_paths!(self.tcx.def_path_str(self.def_ids.get(def_id)))
+    }
+
+    fn def_kind(&self, def_id: stable_mir::DefId) -> stable_mir::DefKind {
+        self.tcx.def_kind(self.def_ids.get(def_id)).stable(self)
+    }
+
+    fn resolve_opaque_ty(&self, ty: stable_mir::ty::Ty) -> Option<stable_mir::ty::Ty> {
+        let ty::Alias(ty::Opaque, alias_ty) = self.types.get(ty.0).kind() else {
+            return None;
+        };
+        let hidden_ty = self.tcx.type_of(alias_ty.def_id).instantiate(self.tcx, alias_ty.args);
+        Some(self.intern_ty(hidden_ty))
+    }
+
+    fn normalize(
+        &self,
+        ty: stable_mir::ty::Ty,
+        args: &stable_mir::ty::GenericArgs,
+    ) -> stable_mir::ty::Ty {
+        let args = self.internal_args(args);
+        let ty = ty::EarlyBinder::bind(self.types.get(ty.0)).instantiate(self.tcx, args);
+        let ty = self.tcx.erase_regions(ty);
+        let param_env = ty::ParamEnv::reveal_all();
+        self.intern_ty(self.tcx.try_normalize_erasing_regions(param_env, ty).unwrap_or(ty))
+    }
+
+    fn ty_stable_hash(&self, ty: stable_mir::ty::Ty) -> u64 {
+        self.tcx.type_id_hash(self.types.get(ty.0)).truncate().as_u64()
+    }
+
+    fn item_stable_hash(&self, item: &stable_mir::CrateItem) -> u64 {
+        self.tcx.def_path_hash(self.item_def_id(item)).0.to_smaller_hash().as_u64()
+    }
+
+    fn const_stable_hash(&self, id: stable_mir::ty::ConstId) -> u64 {
+        let constant = self.consts.get(id.0).eval(self.tcx, ty::ParamEnv::reveal_all());
+        // Pointers are hashed by what they point to, not by their `AllocId`.
+        self.tcx.with_stable_hashing_context(|mut hcx| {
+            let mut hasher = StableHasher::new();
+            hcx.while_hashing_spans(false, |hcx| constant.hash_stable(hcx, &mut hasher));
+            hasher.finish::<Hash64>().as_u64()
+        })
+    }
+
+    fn adt_is_box(&self, def: &stable_mir::ty::AdtDef) -> bool {
+        self.tcx.adt_def(self.adt_def_id(def)).is_box()
+    }
+
+    fn array_subslice_ty(
+        &self,
+        array: stable_mir::ty::Ty,
+        from: u64,
+        to: u64,
+        from_end: bool,
+    ) -> stable_mir::ty::Ty {
+        let ty::Array(elem, len) = self.types.get(array.0).kind() else {
+            panic!("expected an array type, found `{:?}`", self.types.get(array.0));
+        };
+        let len = if from_end {
+            len.eval_target_usize(self.tcx, ty::ParamEnv::reveal_all()) - from - to
+        } else {
+            to - from
+        };
+        self.intern_ty(Ty::new_array(self.tcx, *elem, len))
+    }
+
+    fn adt_discriminants(&self, def: &stable_mir::ty::AdtDef) -> Vec<stable_mir::ty::Discr> {
+        let adt_def = self.tcx.adt_def(self.adt_def_id(def));
+        if !adt_def.is_enum() {
+            return vec![];
+        }
+        adt_def
+            .discriminants(self.tcx)
+            .map(|(_, discr)| stable_mir::ty::Discr {
+                val: discr.val,
+                ty: self.intern_ty(discr.ty),
+            })
+            .collect()
+    }
+
+    fn eval_const(&self, id: stable_mir::ty::ConstId) -> stable_mir::ty::Const {
+        let constant = self.consts.get(id.0);
+        self.intern_const(constant.eval(self.tcx, ty::ParamEnv::reveal_all()))
+    }
+
+    fn rebuild_const(
+        &self,
+        id: stable_mir::ty::ConstId,
+        ty: stable_mir::ty::Ty,
+        args: Option<&stable_mir::ty::GenericArgs>,
+    ) -> stable_mir::ty::Const {
+        let ty = self.types.get(ty.0);
+        let args = args.map(|args| self.internal_args(args));
+        let constant = match self.consts.get(id.0) {
+            mir::ConstantKind::Ty(c) => {
+                let kind = match (c.kind(), args) {
+                    (ty::ConstKind::Unevaluated(uv), Some(args)) => {
+                        ty::ConstKind::Unevaluated(ty::UnevaluatedConst { args, ..uv })
+                    }
+                    (kind, _) => kind,
+                };
+                mir::ConstantKind::Ty(ty::Const::new(self.tcx, kind, ty))
+            }
+            mir::ConstantKind::Unevaluated(uv, _) => mir::ConstantKind::Unevaluated(
+                mir::UnevaluatedConst { args: args.unwrap_or(uv.args), ..uv },
+                ty,
+            ),
+            mir::ConstantKind::Val(val, _) => mir::ConstantKind::Val(val, ty),
+        };
+        self.intern_const(constant)
+    }
+
+    fn const_slice_bytes(&self, id: stable_mir::ty::ConstId) -> Option<Vec<u8>> {
+        let constant = self.consts.get(id.0).eval(self.tcx, ty::ParamEnv::reveal_all());
+        let ty::Ref(_, pointee, _) = constant.ty().kind() else {
+            return None;
+        };
+        match pointee.kind() {
+            ty::Str => {}
+            ty::Slice(elem) if *elem == self.tcx.types.u8 => {}
+            _ => return None,
+        }
+        let value @ ConstValue::Slice { .. } = constant.try_to_value(self.tcx)? else {
+            return None;
+        };
+        Some(get_slice_bytes(&self.tcx, value).to_vec())
+    }
+
+    fn const_provenance(&self, id: stable_mir::ty::ConstId) -> Vec<(usize, AllocId)> {
+        let constant = self.consts.get(id.0);
+        match constant.try_to_value(self.tcx) {
+            Some(ConstValue::Scalar(Scalar::Ptr(ptr, _))) => {
+                vec![(0, self.intern_alloc_id(ptr.provenance))]
+            }
+            Some(ConstValue::ByRef { alloc, offset }) => {
+                // The allocation may be shared with other values, so only keep the pointers that
+                // are part of this one.
+                let size = self
+                    .tcx
+                    .layout_of(ty::ParamEnv::reveal_all().and(constant.ty()))
+                    .map_or(Size::ZERO, |layout| layout.size);
+                alloc
+                    .inner()
+                    .provenance()
+                    .ptrs()
+                    .iter()
+                    .filter(|(ptr_offset, _)| *ptr_offset >= offset && *ptr_offset < offset + size)
+                    .map(|(ptr_offset, id)| {
+                        ((*ptr_offset - offset).bytes_usize(), self.intern_alloc_id(*id))
+                    })
+                    .collect()
+            }
+            Some(
+                ConstValue::Scalar(Scalar::Int(_))
+                | ConstValue::Slice { .. }
+                | ConstValue::ZeroSized,
+            )
+            | None => vec![],
+        }
+    }
+
+    fn global_alloc(&self, id: AllocId) -> GlobalAlloc {
+        match self.tcx.global_alloc(self.alloc_ids.get(id.0)) {
+            interpret::GlobalAlloc::Function(instance) => {
+                GlobalAlloc::Function(self.intern_instance(instance))
+            }
+            interpret::GlobalAlloc::VTable(ty, trait_ref) => GlobalAlloc::VTable(
+                self.intern_ty(ty),
+                trait_ref.map(|trait_ref| trait_ref.stable(self)),
+            ),
+            interpret::GlobalAlloc::Static(def_id) => GlobalAlloc::Static(self.static_def(def_id)),
+            interpret::GlobalAlloc::Memory(alloc) => {
+                GlobalAlloc::Memory(alloc.inner().stable(self))
+            }
+        }
+    }
+
+    fn eval_static_initializer(
+        &self,
+        def: &stable_mir::ty::StaticDef,
+    ) -> Option<stable_mir::mir::alloc::Allocation> {
+        let def_id = self.def_ids.get(def.0);
+        if self.tcx.is_foreign_item(def_id) {
+            return None;
+        }
+        let alloc = self.tcx.eval_static_initializer(def_id).ok()?;
+        Some(alloc.inner().stable(self))
+    }
+
+    fn target_info(&self) -> MachineInfo {
+        let data_layout = &self.tcx.data_layout;
+        MachineInfo {
+            triple: self.tcx.sess.opts.target_triple.triple().to_string(),
+            endian: match data_layout.endian {
+                rustc_target::abi::Endian::Little => Endian::Little,
+                rustc_target::abi::Endian::Big => Endian::Big,
+            },
+            pointer_width: MachineSize::from_bits(data_layout.pointer_size.bits_usize()),
+        }
+    }
+
+    fn resolve_instance(
+        &self,
+        def: &stable_mir::ty::FnDef,
+        args: &stable_mir::ty::GenericArgs,
+    ) -> Option<stable_mir::mir::mono::Instance> {
+        let def_id = self.fn_def_id(def);
+        let args = self.internal_args(args);
+        match ty::Instance::resolve(self.tcx, ty::ParamEnv::reveal_all(), def_id, args) {
+            Ok(Some(instance)) => Some(self.intern_instance(instance)),
+            Ok(None) | Err(_) => None,
+        }
+    }
+
+    fn resolve_drop_in_place(&self, ty: stable_mir::ty::Ty) -> stable_mir::mir::mono::Instance {
+        let ty = self.types.get(ty.0);
+        self.intern_instance(ty::Instance::resolve_drop_in_place(self.tcx, ty))
+    }
+
+    fn resolve_for_fn_ptr(
+        &self,
+        def: &stable_mir::ty::FnDef,
+        args: &stable_mir::ty::GenericArgs,
+    ) -> Option<stable_mir::mir::mono::Instance> {
+        let def_id = self.fn_def_id(def);
+        let args = self.internal_args(args);
+        ty::Instance::resolve_for_fn_ptr(self.tcx, ty::ParamEnv::reveal_all(), def_id, args)
+            .map(|instance| self.intern_instance(instance))
+    }
+
+    fn resolve_closure(
+        &self,
+        def: &stable_mir::ty::ClosureDef,
+        args: &stable_mir::ty::GenericArgs,
+        kind: stable_mir::ty::ClosureKind,
+    ) -> Option<stable_mir::mir::mono::Instance> {
+        let def_id = self.closure_def_id(def);
+        let args = self.internal_args(args);
+        let kind = match kind {
+            stable_mir::ty::ClosureKind::Fn => ty::ClosureKind::Fn,
+            stable_mir::ty::ClosureKind::FnMut => ty::ClosureKind::FnMut,
+            stable_mir::ty::ClosureKind::FnOnce => ty::ClosureKind::FnOnce,
+        };
+        ty::Instance::resolve_closure(self.tcx, def_id, args, kind)
+            .map(|instance| self.intern_instance(instance))
+    }
+
+    fn instance_args(&self, def: InstanceDef) -> stable_mir::ty::GenericArgs {
+        self.instances.get(def.0).args.stable(self)
+    }
+
+    fn instance_ty(&self, def: InstanceDef) -> stable_mir::ty::Ty {
+        let instance = self.instances.get(def.0);
+        self.intern_ty(instance.ty(self.tcx, ty::ParamEnv::reveal_all()))
+    }
+
+    fn instance_mangled_name(&self, def: InstanceDef) -> String {
+        self.tcx.symbol_name(self.instances.get(def.0)).name.to_string()
+    }
+
+    fn instance_kind(&self, def: InstanceDef) -> stable_mir::mir::mono::InstanceKind {
+        use stable_mir::mir::mono::InstanceKind;
+
+        match self.instances.get(def.0).def {
+            ty::InstanceDef::Item(_) => InstanceKind::Item,
+            ty::InstanceDef::Intrinsic(_) => InstanceKind::Intrinsic,
+            ty::InstanceDef::Virtual(_, idx) => InstanceKind::Virtual { idx },
+            ty::InstanceDef::VTableShim(_) => InstanceKind::VTableShim,
+            ty::InstanceDef::ReifyShim(_) => InstanceKind::ReifyShim,
+            ty::InstanceDef::FnPtrShim(..) => InstanceKind::FnPtrShim,
+            ty::InstanceDef::ClosureOnceShim { .. } => InstanceKind::ClosureOnceShim,
+            ty::InstanceDef::ThreadLocalShim(_) => InstanceKind::ThreadLocalShim,
+            ty::InstanceDef::DropGlue(..) => InstanceKind::DropGlue,
+            ty::InstanceDef::CloneShim(..) => InstanceKind::CloneShim,
+            ty::InstanceDef::FnPtrAddrShim(..) => InstanceKind::FnPtrAddrShim,
+        }
+    }
+
+    fn instance_body(&self, def: InstanceDef) -> Option<stable_mir::mir::Body> {
+        let instance = self.instances.get(def.0);
+        match instance.def {
+            ty::InstanceDef::Item(def_id) if !self.tcx.is_mir_available(def_id) => return None,
+            ty::InstanceDef::Intrinsic(_) | ty::InstanceDef::Virtual(..) => return None,
+            _ => {}
+        }
+        let mir = instance.subst_mir_and_normalize_erasing_regions(
+            self.tcx,
+            ty::ParamEnv::reveal_all(),
+            ty::EarlyBinder::bind(self.tcx.instance_mir(instance.def).clone()),
+        );
+        Some(self.body(&mir))
+    }
+
+    fn instance_is_empty_shim(&self, def: InstanceDef) -> bool {
+        matches!(self.instances.get(def.0).def, ty::InstanceDef::DropGlue(_, None))
     }
 }
 
 pub struct Tables<'tcx> {
     pub tcx: TyCtxt<'tcx>,
-    pub def_ids: Vec<DefId>,
-    pub types: Vec<Ty<'tcx>>,
+    pub def_ids: Interner<DefId>,
+    pub types: Interner<Ty<'tcx>>,
+    pub spans: Interner<rustc_span::Span>,
+    pub instances: Interner<ty::Instance<'tcx>>,
+    pub consts: Interner<mir::ConstantKind<'tcx>>,
+    pub alloc_ids: Interner<interpret::AllocId>,
+    /// Cache of the stable representation of each type, indexed like `types`.
+    pub ty_kinds: Sharded<FxHashMap<usize, TyKind>>,
 }
 
 impl<'tcx> Tables<'tcx> {
-    fn intern_ty(&mut self, ty: Ty<'tcx>) -> stable_mir::ty::Ty {
-        if let Some(id) = self.types.iter().position(|&t| t == ty) {
-            return stable_mir::ty::Ty(id);
+    pub(crate) fn new(tcx: TyCtxt<'tcx>) -> Self {
+        Tables {
+            tcx,
+            def_ids: Interner::new(),
+            types: Interner::new(),
+            spans: Interner::new(),
+            instances: Interner::new(),
+            consts: Interner::new(),
+            alloc_ids: Interner::new(),
+            ty_kinds: Sharded::default(),
+        }
+    }
+
+    fn intern_ty(&self, ty: Ty<'tcx>) -> stable_mir::ty::Ty {
+        stable_mir::ty::Ty(self.types.intern(ty))
+    }
+
+    /// Convert a body, and validate the result if `-Z validate-stable-mir` is set.
+    fn body(&self, mir: &mir::Body<'tcx>) -> stable_mir::mir::Body {
+        use rustc_target::spec::abi::Abi;
+
+        let def_id = mir.source.def_id();
+        let abi = match self.tcx.def_kind(def_id) {
+            DefKind::Fn | DefKind::AssocFn => self.tcx.fn_sig(def_id).skip_binder().abi(),
+            _ => Abi::Rust,
+        };
+        let can_unwind = fn_can_unwind(self.tcx, Some(def_id), abi);
+        let body = stable_mir::mir::Body {
+            blocks: mir
+                .basic_blocks
+                .iter()
+                .map(|block| basic_block(block, can_unwind, self))
+                .collect(),
+            locals: mir.local_decls.iter().map(|decl| decl.stable(self)).collect(),
+            source_scopes: mir.source_scopes.iter().map(|scope| scope.stable(self)).collect(),
+        };
+        if self.tcx.sess.opts.unstable_opts.validate_stable_mir {
+            if let Err(errors) = body.validate() {
+                let errors: Vec<_> = errors.iter().map(|error| error.to_string()).collect();
+                span_bug!(
+                    mir.span,
+                    "invalid stable MIR for `{}`:\n{}",
+                    self.tcx.def_path_str(mir.source.def_id()),
+                    errors.join("\n")
+                );
+            }
+        }
+        body
+    }
+
+    fn intern_const(&self, constant: mir::ConstantKind<'tcx>) -> stable_mir::ty::Const {
+        use stable_mir::ty::{ConstantKind, UnevaluatedConst};
+        let kind = match constant {
+            mir::ConstantKind::Ty(c) => match c.kind() {
+                ty::ConstKind::Value(_) => ConstantKind::Value,
+                ty::ConstKind::Unevaluated(uv) => ConstantKind::Unevaluated(UnevaluatedConst {
+                    def: self.const_def(uv.def),
+                    args: uv.args.stable(self),
+                    promoted: None,
+                }),
+                ty::ConstKind::Param(param) => ConstantKind::Param(stable_mir::ty::ParamConst {
+                    index: param.index,
+                    name: param.name.to_string(),
+                }),
+                _ => ConstantKind::Other,
+            },
+            mir::ConstantKind::Unevaluated(uv, _) => ConstantKind::Unevaluated(UnevaluatedConst {
+                def: self.const_def(uv.def),
+                args: uv.args.stable(self),
+                promoted: uv.promoted.map(|promoted| promoted.as_u32()),
+            }),
+            mir::ConstantKind::Val(..) => ConstantKind::Value,
+        };
+        stable_mir::ty::Const {
+            kind,
+            ty: self.intern_ty(constant.ty()),
+            literal: opaque_display(&constant),
+            id: stable_mir::ty::ConstId(self.consts.intern(constant)),
+        }
+    }
+
+    /// Convert generic arguments back to their internal representation. Lifetimes are erased.
+    fn internal_args(&self, args: &stable_mir::ty::GenericArgs) -> ty::GenericArgsRef<'tcx> {
+        use stable_mir::ty::GenericArgKind;
+        self.tcx.mk_args_from_iter(args.0.iter().map(|arg| -> ty::GenericArg<'tcx> {
+            match arg {
+                GenericArgKind::Lifetime(_) => self.tcx.lifetimes.re_erased.into(),
+                GenericArgKind::Type(ty) => self.types.get(ty.0).into(),
+                GenericArgKind::Const(constant) => self.internal_ty_const(constant).into(),
+            }
+        }))
+    }
+
+    fn internal_ty_const(&self, constant: &stable_mir::ty::Const) -> ty::Const<'tcx> {
+        match self.consts.get(constant.id.0) {
+            mir::ConstantKind::Ty(c) => c,
+            other => panic!("expected a type system constant, found `{other:?}`"),
         }
-        let id = self.types.len();
-        self.types.push(ty);
-        stable_mir::ty::Ty(id)
     }
+
+    /// Convert a type back to its internal representation. Regions are erased.
+    fn internal_ty_kind(&self, kind: &TyKind) -> Ty<'tcx> {
+        let tcx = self.tcx;
+        let rigid_ty = match kind {
+            TyKind::RigidTy(rigid_ty) => rigid_ty,
+            TyKind::Alias(alias_kind, alias_ty) => {
+                let alias_kind = match alias_kind {
+                    stable_mir::ty::AliasKind::Projection => ty::Projection,
+                    stable_mir::ty::AliasKind::Inherent => ty::Inherent,
+                    stable_mir::ty::AliasKind::Opaque => ty::Opaque,
+                    stable_mir::ty::AliasKind::Weak => ty::Weak,
+                };
+                let def_id = self.def_ids.get(alias_ty.def_id.0);
+                let alias_ty = tcx.mk_alias_ty(def_id, self.internal_args(&alias_ty.args));
+                return Ty::new_alias(tcx, alias_kind, alias_ty);
+            }
+            TyKind::Param(param_ty) => {
+                return Ty::new_param(tcx, param_ty.index, Symbol::intern(&param_ty.name));
+            }
+        };
+        let internal_mutability = |mutability: &stable_mir::mir::Mutability| match mutability {
+            stable_mir::mir::Mutability::Not => mir::Mutability::Not,
+            stable_mir::mir::Mutability::Mut => mir::Mutability::Mut,
+        };
+        match rigid_ty {
+            RigidTy::Bool => tcx.types.bool,
+            RigidTy::Char => tcx.types.char,
+            RigidTy::Int(int_ty) => Ty::new_int(
+                tcx,
+                match int_ty {
+                    IntTy::Isize => ty::IntTy::Isize,
+                    IntTy::I8 => ty::IntTy::I8,
+                    IntTy::I16 => ty::IntTy::I16,
+                    IntTy::I32 => ty::IntTy::I32,
+                    IntTy::I64 => ty::IntTy::I64,
+                    IntTy::I128 => ty::IntTy::I128,
+                },
+            ),
+            RigidTy::Uint(uint_ty) => Ty::new_uint(
+                tcx,
+                match uint_ty {
+                    UintTy::Usize => ty::UintTy::Usize,
+                    UintTy::U8 => ty::UintTy::U8,
+                    UintTy::U16 => ty::UintTy::U16,
+                    UintTy::U32 => ty::UintTy::U32,
+                    UintTy::U64 => ty::UintTy::U64,
+                    UintTy::U128 => ty::UintTy::U128,
+                },
+            ),
+            RigidTy::Float(float_ty) => Ty::new_float(
+                tcx,
+                match float_ty {
+                    FloatTy::F32 => ty::FloatTy::F32,
+                    FloatTy::F64 => ty::FloatTy::F64,
+                },
+            ),
+            RigidTy::Adt(def, args) => {
+                Ty::new_adt(tcx, tcx.adt_def(self.adt_def_id(def)), self.internal_args(args))
+            }
+            RigidTy::Foreign(def) => Ty::new_foreign(tcx, self.def_ids.get(def.0)),
+            RigidTy::Str => tcx.types.str_,
+            RigidTy::Array(ty, len) => {
+                Ty::new_array_with_const_len(tcx, self.types.get(ty.0), self.internal_ty_const(len))
+            }
+            RigidTy::Slice(ty) => Ty::new_slice(tcx, self.types.get(ty.0)),
+            RigidTy::RawPtr(ty, mutability) => Ty::new_ptr(
+                tcx,
+                ty::TypeAndMut { ty: self.types.get(ty.0), mutbl: internal_mutability(mutability) },
+            ),
+            RigidTy::Ref(_, ty, mutability) => Ty::new_ref(
+                tcx,
+                tcx.lifetimes.re_erased,
+                ty::TypeAndMut { ty: self.types.get(ty.0), mutbl: internal_mutability(mutability) },
+            ),
+            RigidTy::FnDef(def, args) => {
+                Ty::new_fn_def(tcx, self.fn_def_id(def), self.internal_args(args))
+            }
+            RigidTy::FnPtr(sig) => Ty::new_fn_ptr(tcx, self.internal_poly_fn_sig(sig)),
+            RigidTy::Dynamic(predicates, _, dyn_kind) => Ty::new_dynamic(
+                tcx,
+                tcx.mk_poly_existential_predicates_from_iter(
+                    predicates.iter().map(|pred| self.internal_existential_predicate(pred)),
+                ),
+                tcx.lifetimes.re_erased,
+                match dyn_kind {
+                    stable_mir::ty::DynKind::Dyn => ty::Dyn,
+                    stable_mir::ty::DynKind::DynStar => ty::DynStar,
+                },
+            ),
+            RigidTy::Closure(def, args) => {
+                Ty::new_closure(tcx, self.closure_def_id(def), self.internal_args(args))
+            }
+            RigidTy::Generator(def, args, movability) => Ty::new_generator(
+                tcx,
+                self.def_ids.get(def.0),
+                self.internal_args(args),
+                match movability {
+                    Movability::Static => hir::Movability::Static,
+                    Movability::Movable => hir::Movability::Movable,
+                },
+            ),
+            RigidTy::GeneratorWitness(types) => Ty::new_generator_witness(
+                tcx,
+                ty::Binder::bind_with_vars(
+                    tcx.mk_type_list_from_iter(types.value.iter().map(|ty| self.types.get(ty.0))),
+                    self.internal_bound_vars(&types.bound_vars),
+                ),
+            ),
+            RigidTy::GeneratorWitnessMIR(def, args) => Ty::new_generator_witness_mir(
+                tcx,
+                self.def_ids.get(def.0),
+                self.internal_args(args),
+            ),
+            RigidTy::Never => tcx.types.never,
+            RigidTy::Tuple(tys) => {
+                Ty::new_tup_from_iter(tcx, tys.iter().map(|ty| self.types.get(ty.0)))
+            }
+        }
+    }
+
+    fn internal_existential_predicate(
+        &self,
+        pred: &stable_mir::ty::Binder<stable_mir::ty::ExistentialPredicate>,
+    ) -> ty::PolyExistentialPredicate<'tcx> {
+        use stable_mir::ty::{ExistentialPredicate, TermKind};
+
+        let value = match &pred.value {
+            ExistentialPredicate::Trait(trait_ref) => {
+                ty::ExistentialPredicate::Trait(ty::ExistentialTraitRef {
+                    def_id: self.def_ids.get(trait_ref.def_id.0),
+                    args: self.internal_args(&trait_ref.args),
+                })
+            }
+            ExistentialPredicate::Projection(projection) => {
+                ty::ExistentialPredicate::Projection(ty::ExistentialProjection {
+                    def_id: self.def_ids.get(projection.def_id.0),
+                    args: self.internal_args(&projection.args),
+                    term: match &projection.term {
+                        TermKind::Type(ty) => self.types.get(ty.0).into(),
+                        TermKind::Const(constant) => self.internal_ty_const(constant).into(),
+                    },
+                })
+            }
+            ExistentialPredicate::AutoTrait(def) => {
+                ty::ExistentialPredicate::AutoTrait(self.def_ids.get(def.0))
+            }
+        };
+        ty::Binder::bind_with_vars(value, self.internal_bound_vars(&pred.bound_vars))
+    }
+
+    fn internal_bound_vars(
+        &self,
+        bound_vars: &[stable_mir::ty::BoundVariableKind],
+    ) -> &'tcx ty::List<ty::BoundVariableKind> {
+        use stable_mir::ty::{BoundRegionKind, BoundTyKind, BoundVariableKind};
+
+        self.tcx.mk_bound_variable_kinds_from_iter(bound_vars.iter().map(
+            |bound_var| match bound_var {
+                BoundVariableKind::Ty(BoundTyKind::Anon) => {
+                    ty::BoundVariableKind::Ty(ty::BoundTyKind::Anon)
+                }
+                BoundVariableKind::Ty(BoundTyKind::Param(def, name)) => ty::BoundVariableKind::Ty(
+                    ty::BoundTyKind::Param(self.def_ids.get(def.0), Symbol::intern(name)),
+                ),
+                BoundVariableKind::Region(BoundRegionKind::BrAnon(span)) => {
+                    ty::BoundVariableKind::Region(ty::BoundRegionKind::BrAnon(
+                        span.map(|span| self.spans.get(span.0)),
+                    ))
+                }
+                BoundVariableKind::Region(BoundRegionKind::BrNamed(def, name)) => {
+                    ty::BoundVariableKind::Region(ty::BoundRegionKind::BrNamed(
+                        self.def_ids.get(def.0),
+                        Symbol::intern(name),
+                    ))
+                }
+                BoundVariableKind::Region(BoundRegionKind::BrEnv) => {
+                    ty::BoundVariableKind::Region(ty::BoundRegionKind::BrEnv)
+                }
+                BoundVariableKind::Const => ty::BoundVariableKind::Const,
+            },
+        ))
+    }
+
+    fn internal_poly_fn_sig(&self, sig: &stable_mir::ty::PolyFnSig) -> ty::PolyFnSig<'tcx> {
+        use rustc_target::spec::abi;
+        use stable_mir::ty::{Abi, Unsafety};
+
+        let tcx = self.tcx;
+        let bound_vars = self.internal_bound_vars(&sig.bound_vars);
+        let stable_mir::ty::FnSig { inputs_and_output, c_variadic, unsafety, abi } = &sig.value;
+        let (output, inputs) =
+            inputs_and_output.split_last().expect("a signature always has an output");
+        let fn_sig = tcx.mk_fn_sig(
+            inputs.iter().map(|ty| self.types.get(ty.0)),
+            self.types.get(output.0),
+            *c_variadic,
+            match unsafety {
+                Unsafety::Normal => hir::Unsafety::Normal,
+                Unsafety::Unsafe => hir::Unsafety::Unsafe,
+            },
+            match *abi {
+                Abi::Rust => abi::Abi::Rust,
+                Abi::C { unwind } => abi::Abi::C { unwind },
+                Abi::Cdecl { unwind } => abi::Abi::Cdecl { unwind },
+                Abi::Stdcall { unwind } => abi::Abi::Stdcall { unwind },
+                Abi::Fastcall { unwind } => abi::Abi::Fastcall { unwind },
+                Abi::Vectorcall { unwind } => abi::Abi::Vectorcall { unwind },
+                Abi::Thiscall { unwind } => abi::Abi::Thiscall { unwind },
+                Abi::Aapcs { unwind } => abi::Abi::Aapcs { unwind },
+                Abi::Win64 { unwind } => abi::Abi::Win64 { unwind },
+                Abi::SysV64 { unwind } => abi::Abi::SysV64 { unwind },
+                Abi::PtxKernel => abi::Abi::PtxKernel,
+                Abi::Msp430Interrupt => abi::Abi::Msp430Interrupt,
+                Abi::X86Interrupt => abi::Abi::X86Interrupt,
+                Abi::AmdGpuKernel => abi::Abi::AmdGpuKernel,
+                Abi::EfiApi => abi::Abi::EfiApi,
+                Abi::AvrInterrupt => abi::Abi::AvrInterrupt,
+                Abi::AvrNonBlockingInterrupt => abi::Abi::AvrNonBlockingInterrupt,
+                Abi::CCmseNonSecureCall => abi::Abi::CCmseNonSecureCall,
+                Abi::Wasm => abi::Abi::Wasm,
+                Abi::System { unwind } => abi::Abi::System { unwind },
+                Abi::RustIntrinsic => abi::Abi::RustIntrinsic,
+                Abi::RustCall => abi::Abi::RustCall,
+                Abi::PlatformIntrinsic => abi::Abi::PlatformIntrinsic,
+                Abi::Unadjusted => abi::Abi::Unadjusted,
+                Abi::RustCold => abi::Abi::RustCold,
+            },
+        );
+        ty::Binder::bind_with_vars(fn_sig, bound_vars)
+    }
+
+    fn intern_instance(&self, instance: ty::Instance<'tcx>) -> stable_mir::mir::mono::Instance {
+        let def = stable_mir::mir::mono::InstanceDef(self.instances.intern(instance));
+        stable_mir::mir::mono::Instance { def }
+    }
+
+    fn intern_alloc_id(&self, id: interpret::AllocId) -> AllocId {
+        AllocId(self.alloc_ids.intern(id))
+    }
+}
+
+/// Maps internal values to the indices used to refer to them in stable MIR.
+///
+/// The values are spread across independently locked shards, so multiple threads can convert
+/// bodies at the same time. An index encodes both the shard and the position within it, which
+/// keeps it valid as other threads keep interning.
+pub struct Interner<T> {
+    shards: Vec<Lock<FxIndexSet<T>>>,
+}
+
+impl<T: Copy + Eq + Hash> Interner<T> {
+    fn new() -> Self {
+        Interner { shards: (0..SHARDS).map(|_| Lock::new(FxIndexSet::default())).collect() }
+    }
+
+    /// Get the index of `value`, allocating a new one if this value hasn't been seen before.
+    pub fn intern(&self, value: T) -> usize {
+        let shard = make_hash(&value) as usize % SHARDS;
+        let (index, _) = self.shards[shard].lock().insert_full(value);
+        index * SHARDS + shard
+    }
+
+    /// Get the value behind an index returned by [`Interner::intern`].
+    pub fn get(&self, index: usize) -> T {
+        self.shards[index % SHARDS].lock()[index / SHARDS]
+    }
+}
+
+/// Find the items named `name` that are defined in the given module, enum or trait, or in the
+/// inherent impls of the given type.
+fn item_children_by_name(tcx: TyCtxt<'_>, def_id: DefId, name: &str) -> Vec<DefId> {
+    let mut children = vec![];
+    let def_kind = tcx.def_kind(def_id);
+    if matches!(def_kind, DefKind::Mod | DefKind::Enum | DefKind::Trait) {
+        let module_children = match def_id.as_local() {
+            Some(local_def_id) => tcx.module_children_local(local_def_id),
+            None => tcx.module_children(def_id),
+        };
+        children.extend(
+            module_children
+                .iter()
+                .filter(|child| child.ident.as_str() == name)
+                .filter_map(|child| child.res.opt_def_id()),
+        );
+    }
+    if matches!(def_kind, DefKind::Struct | DefKind::Enum | DefKind::Union) {
+        children.extend(
+            tcx.inherent_impls(def_id)
+                .iter()
+                .flat_map(|impl_def_id| tcx.associated_item_def_ids(*impl_def_id))
+                .filter(|assoc_def_id| tcx.item_name(**assoc_def_id).as_str() == name),
+        );
+    }
+    children
+}
+
+/// Find the functions, constants and statics of an external crate, by walking its modules,
+/// traits and the inherent impls of its types, and looking at its trait impls.
+fn extern_crate_items(tcx: TyCtxt<'_>, krate: CrateNum) -> FxIndexSet<DefId> {
+    let mut items = FxIndexSet::default();
+    let mut containers: Vec<DefId> = tcx.trait_impls_in_crate(krate).to_vec();
+    let mut modules = vec![krate.as_def_id()];
+    let mut visited = FxHashSet::default();
+    while let Some(module) = modules.pop() {
+        if !visited.insert(module) {
+            continue;
+        }
+        // Skip re-exports of items from other crates.
+        let children =
+            tcx.module_children(module).iter().filter_map(|child| child.res.opt_def_id());
+        for def_id in children.filter(|def_id| def_id.krate == krate) {
+            match tcx.def_kind(def_id) {
+                DefKind::Mod => modules.push(def_id),
+                DefKind::Fn | DefKind::Const | DefKind::Static(_) => {
+                    items.insert(def_id);
+                }
+                DefKind::Trait => containers.push(def_id),
+                DefKind::Struct | DefKind::Enum | DefKind::Union => {
+                    containers.extend(tcx.inherent_impls(def_id).iter().copied());
+                }
+                _ => {}
+            }
+        }
+    }
+    for container in containers.into_iter().filter(|def_id| def_id.krate == krate) {
+        items.extend(
+            tcx.associated_items(container)
+                .in_definition_order()
+                .filter_map(|item| (item.kind != ty::AssocKind::Type).then_some(item.def_id)),
+        );
+    }
+    items
+}
+
+/// Whether items of this kind are only evaluated at compile time, so that they only have MIR for
+/// const evaluation, and no optimized MIR.
+fn is_const_context(def_kind: DefKind) -> bool {
+    matches!(
+        def_kind,
+        DefKind::Const
+            | DefKind::AssocConst
+            | DefKind::Static(_)
+            | DefKind::AnonConst
+            | DefKind::InlineConst
+    )
 }
 
 /// Build a stable mir crate from a given crate number.
@@ -99,40 +1277,56 @@ pub(crate) trait Stable<'tcx> {
     /// The stable representation of the type implementing Stable.
     type T;
     /// Converts an object to the equivalent Stable MIR representation.
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T;
 }
 
 impl<'tcx> Stable<'tcx> for mir::Statement<'tcx> {
     type T = stable_mir::mir::Statement;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use rustc_middle::mir::StatementKind::*;
+        use stable_mir::mir::{NonDivergingIntrinsic, Statement};
         match &self.kind {
-            Assign(assign) => {
-                stable_mir::mir::Statement::Assign(assign.0.stable(tables), assign.1.stable(tables))
-            }
-            FakeRead(_) => todo!(),
-            SetDiscriminant { .. } => todo!(),
-            Deinit(_) => todo!(),
-            StorageLive(_) => todo!(),
-            StorageDead(_) => todo!(),
-            Retag(_, _) => todo!(),
-            PlaceMention(_) => todo!(),
-            AscribeUserType(_, _) => todo!(),
-            Coverage(_) => todo!(),
-            Intrinsic(_) => todo!(),
-            ConstEvalCounter => todo!(),
-            Nop => stable_mir::mir::Statement::Nop,
+            Assign(assign) => Statement::Assign(assign.0.stable(tables), assign.1.stable(tables)),
+            SetDiscriminant { place, variant_index } => Statement::SetDiscriminant {
+                place: place.stable(tables),
+                variant_index: variant_index.as_usize(),
+            },
+            Deinit(place) => Statement::Deinit(place.stable(tables)),
+            StorageLive(local) => Statement::StorageLive(local.as_usize()),
+            StorageDead(local) => Statement::StorageDead(local.as_usize()),
+            Retag(_, place) => Statement::Retag(place.stable(tables)),
+            Intrinsic(intrinsic) => Statement::Intrinsic(match &**intrinsic {
+                mir::NonDivergingIntrinsic::Assume(operand) => {
+                    NonDivergingIntrinsic::Assume(operand.stable(tables))
+                }
+                mir::NonDivergingIntrinsic::CopyNonOverlapping(copy) => {
+                    NonDivergingIntrinsic::CopyNonOverlapping {
+                        src: copy.src.stable(tables),
+                        dst: copy.dst.stable(tables),
+                        count: copy.count.stable(tables),
+                    }
+                }
+            }),
+            FakeRead(_)
+            | PlaceMention(_)
+            | AscribeUserType(_, _)
+            | Coverage(_)
+            | ConstEvalCounter
+            | Nop => Statement::Nop,
         }
     }
 }
 
 impl<'tcx> Stable<'tcx> for mir::Rvalue<'tcx> {
     type T = stable_mir::mir::Rvalue;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use mir::Rvalue::*;
         match self {
             Use(op) => stable_mir::mir::Rvalue::Use(op.stable(tables)),
-            Repeat(_, _) => todo!(),
+            Repeat(op, len) => stable_mir::mir::Rvalue::Repeat(
+                op.stable(tables),
+                tables.intern_const(mir::ConstantKind::Ty(*len)),
+            ),
             Ref(region, kind, place) => stable_mir::mir::Rvalue::Ref(
                 opaque(region),
                 kind.stable(tables),
@@ -145,7 +1339,11 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                 stable_mir::mir::Rvalue::AddressOf(mutability.stable(tables), place.stable(tables))
             }
             Len(place) => stable_mir::mir::Rvalue::Len(place.stable(tables)),
-            Cast(_, _, _) => todo!(),
+            Cast(cast_kind, op, ty) => stable_mir::mir::Rvalue::Cast(
+                cast_kind.stable(tables),
+                op.stable(tables),
+                tables.intern_ty(*ty),
+            ),
             BinaryOp(bin_op, ops) => stable_mir::mir::Rvalue::BinaryOp(
                 bin_op.stable(tables),
                 ops.0.stable(tables),
@@ -156,21 +1354,58 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                 ops.0.stable(tables),
                 ops.1.stable(tables),
             ),
-            NullaryOp(_, _) => todo!(),
+            NullaryOp(null_op, ty) => {
+                stable_mir::mir::Rvalue::NullaryOp(null_op.stable(tables), tables.intern_ty(*ty))
+            }
             UnaryOp(un_op, op) => {
                 stable_mir::mir::Rvalue::UnaryOp(un_op.stable(tables), op.stable(tables))
             }
             Discriminant(place) => stable_mir::mir::Rvalue::Discriminant(place.stable(tables)),
-            Aggregate(_, _) => todo!(),
-            ShallowInitBox(_, _) => todo!(),
+            Aggregate(agg_kind, operands) => stable_mir::mir::Rvalue::Aggregate(
+                agg_kind.stable(tables),
+                operands.iter().map(|op| op.stable(tables)).collect(),
+            ),
+            ShallowInitBox(op, ty) => {
+                stable_mir::mir::Rvalue::ShallowInitBox(op.stable(tables), tables.intern_ty(*ty))
+            }
             CopyForDeref(place) => stable_mir::mir::Rvalue::CopyForDeref(place.stable(tables)),
         }
     }
 }
 
+impl<'tcx> Stable<'tcx> for mir::AggregateKind<'tcx> {
+    type T = stable_mir::mir::AggregateKind;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        use stable_mir::mir::AggregateKind;
+        match self {
+            mir::AggregateKind::Array(ty) => AggregateKind::Array(tables.intern_ty(*ty)),
+            mir::AggregateKind::Tuple => AggregateKind::Tuple,
+            mir::AggregateKind::Adt(def_id, variant_index, args, _, active_field) => {
+                AggregateKind::Adt(
+                    tables.adt_def(*def_id),
+                    variant_index.as_usize(),
+                    args.stable(tables),
+                    active_field.map(|field| field.as_usize()),
+                )
+            }
+            mir::AggregateKind::Closure(def_id, args) => {
+                AggregateKind::Closure(tables.closure_def(*def_id), args.stable(tables))
+            }
+            mir::AggregateKind::Generator(def_id, args, movability) => AggregateKind::Generator(
+                tables.generator_def(*def_id),
+                args.stable(tables),
+                match movability {
+                    hir::Movability::Static => Movability::Static,
+                    hir::Movability::Movable => Movability::Movable,
+                },
+            ),
+        }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for mir::Mutability {
     type T = stable_mir::mir::Mutability;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, _: &Tables<'tcx>) -> Self::T {
         use mir::Mutability::*;
         match *self {
             Not => stable_mir::mir::Mutability::Not,
@@ -181,7 +1416,7 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for mir::BorrowKind {
     type T = stable_mir::mir::BorrowKind;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use mir::BorrowKind::*;
         match *self {
             Shared => stable_mir::mir::BorrowKind::Shared,
@@ -193,7 +1428,7 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for mir::MutBorrowKind {
     type T = stable_mir::mir::MutBorrowKind;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, _: &Tables<'tcx>) -> Self::T {
         use mir::MutBorrowKind::*;
         match *self {
             Default => stable_mir::mir::MutBorrowKind::Default,
@@ -205,7 +1440,7 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for mir::NullOp<'tcx> {
     type T = stable_mir::mir::NullOp;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use mir::NullOp::*;
         match self {
             SizeOf => stable_mir::mir::NullOp::SizeOf,
@@ -219,7 +1454,7 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for mir::CastKind {
     type T = stable_mir::mir::CastKind;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use mir::CastKind::*;
         match self {
             PointerExposeAddress => stable_mir::mir::CastKind::PointerExposeAddress,
@@ -239,7 +1474,7 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for ty::adjustment::PointerCoercion {
     type T = stable_mir::mir::PointerCoercion;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use ty::adjustment::PointerCoercion;
         match self {
             PointerCoercion::ReifyFnPointer => stable_mir::mir::PointerCoercion::ReifyFnPointer,
@@ -258,7 +1493,7 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for rustc_hir::Unsafety {
     type T = stable_mir::mir::Safety;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, _: &Tables<'tcx>) -> Self::T {
         match self {
             rustc_hir::Unsafety::Unsafe => stable_mir::mir::Safety::Unsafe,
             rustc_hir::Unsafety::Normal => stable_mir::mir::Safety::Normal,
@@ -266,51 +1501,132 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
     }
 }
 
+impl<'tcx> Stable<'tcx> for DefKind {
+    type T = stable_mir::DefKind;
+    fn stable(&self, _: &Tables<'tcx>) -> Self::T {
+        use rustc_hir::def::{CtorKind, CtorOf};
+        use stable_mir::DefKind as Kind;
+        match *self {
+            DefKind::Mod => Kind::Mod,
+            DefKind::Struct => Kind::Struct,
+            DefKind::Union => Kind::Union,
+            DefKind::Enum => Kind::Enum,
+            DefKind::Variant => Kind::Variant,
+            DefKind::Trait => Kind::Trait,
+            DefKind::TraitAlias => Kind::TraitAlias,
+            DefKind::TyAlias => Kind::TyAlias,
+            DefKind::ForeignTy => Kind::ForeignTy,
+            DefKind::AssocTy => Kind::AssocTy,
+            DefKind::TyParam => Kind::TyParam,
+            DefKind::Fn => Kind::Fn,
+            DefKind::Const => Kind::Const,
+            DefKind::ConstParam => Kind::ConstParam,
+            DefKind::Static(mutability) => Kind::Static { mutable: mutability.is_mut() },
+            DefKind::Ctor(of, kind) => Kind::Ctor(
+                match of {
+                    CtorOf::Struct => stable_mir::CtorOf::Struct,
+                    CtorOf::Variant => stable_mir::CtorOf::Variant,
+                },
+                match kind {
+                    CtorKind::Fn => stable_mir::CtorKind::Fn,
+                    CtorKind::Const => stable_mir::CtorKind::Const,
+                },
+            ),
+            DefKind::AssocFn => Kind::AssocFn,
+            DefKind::AssocConst => Kind::AssocConst,
+            DefKind::Macro(_) => Kind::Macro,
+            DefKind::ExternCrate => Kind::ExternCrate,
+            DefKind::Use => Kind::Use,
+            DefKind::ForeignMod => Kind::ForeignMod,
+            DefKind::AnonConst => Kind::AnonConst,
+            DefKind::InlineConst => Kind::InlineConst,
+            DefKind::OpaqueTy => Kind::OpaqueTy,
+            DefKind::Field => Kind::Field,
+            DefKind::LifetimeParam => Kind::LifetimeParam,
+            DefKind::GlobalAsm => Kind::GlobalAsm,
+            DefKind::Impl { of_trait } => Kind::Impl { of_trait },
+            DefKind::Closure => Kind::Closure,
+            DefKind::Generator => Kind::Generator,
+        }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for FieldIdx {
     type T = usize;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, _: &Tables<'tcx>) -> Self::T {
         self.as_usize()
     }
 }
 
 impl<'tcx> Stable<'tcx> for mir::Operand<'tcx> {
     type T = stable_mir::mir::Operand;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use mir::Operand::*;
         match self {
             Copy(place) => stable_mir::mir::Operand::Copy(place.stable(tables)),
             Move(place) => stable_mir::mir::Operand::Move(place.stable(tables)),
-            Constant(c) => stable_mir::mir::Operand::Constant(c.to_string()),
+            Constant(c) => stable_mir::mir::Operand::Constant(c.stable(tables)),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::Constant<'tcx> {
+    type T = stable_mir::mir::Constant;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        stable_mir::mir::Constant {
+            span: tables.create_span(self.span),
+            literal: tables.intern_const(self.literal),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::LocalDecl<'tcx> {
+    type T = stable_mir::mir::LocalDecl;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        stable_mir::mir::LocalDecl {
+            ty: tables.intern_ty(self.ty),
+            span: tables.create_span(self.source_info.span),
+            mutability: self.mutability.stable(tables),
         }
     }
 }
 
 impl<'tcx> Stable<'tcx> for mir::Place<'tcx> {
     type T = stable_mir::mir::Place;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         stable_mir::mir::Place {
             local: self.local.as_usize(),
-            projection: format!("{:?}", self.projection),
+            projection: self.projection.iter().map(|elem| elem.stable(tables)).collect(),
         }
     }
 }
 
-impl<'tcx> Stable<'tcx> for mir::UnwindAction {
-    type T = stable_mir::mir::UnwindAction;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
-        use rustc_middle::mir::UnwindAction;
+impl<'tcx> Stable<'tcx> for mir::PlaceElem<'tcx> {
+    type T = stable_mir::mir::ProjectionElem;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        use mir::ProjectionElem::*;
+        use stable_mir::mir::ProjectionElem;
         match self {
-            UnwindAction::Continue => stable_mir::mir::UnwindAction::Continue,
-            UnwindAction::Unreachable => stable_mir::mir::UnwindAction::Unreachable,
-            UnwindAction::Terminate => stable_mir::mir::UnwindAction::Terminate,
-            UnwindAction::Cleanup(bb) => stable_mir::mir::UnwindAction::Cleanup(bb.as_usize()),
+            Deref => ProjectionElem::Deref,
+            Field(idx, ty) => ProjectionElem::Field(idx.stable(tables), tables.intern_ty(*ty)),
+            Index(local) => ProjectionElem::Index(local.as_usize()),
+            ConstantIndex { offset, min_length, from_end } => ProjectionElem::ConstantIndex {
+                offset: *offset,
+                min_length: *min_length,
+                from_end: *from_end,
+            },
+            Subslice { from, to, from_end } => {
+                ProjectionElem::Subslice { from: *from, to: *to, from_end: *from_end }
+            }
+            Downcast(_, idx) => ProjectionElem::Downcast(idx.as_usize()),
+            OpaqueCast(ty) => ProjectionElem::OpaqueCast(tables.intern_ty(*ty)),
         }
     }
 }
 
 impl<'tcx> Stable<'tcx> for mir::AssertMessage<'tcx> {
     type T = stable_mir::mir::AssertMessage;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use rustc_middle::mir::AssertKind;
         match self {
             AssertKind::BoundsCheck { len, index } => stable_mir::mir::AssertMessage::BoundsCheck {
@@ -349,7 +1665,7 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for mir::BinOp {
     type T = stable_mir::mir::BinOp;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, _: &Tables<'tcx>) -> Self::T {
         use mir::BinOp;
         match self {
             BinOp::Add => stable_mir::mir::BinOp::Add,
@@ -380,7 +1696,7 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for mir::UnOp {
     type T = stable_mir::mir::UnOp;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, _: &Tables<'tcx>) -> Self::T {
         use mir::UnOp;
         match self {
             UnOp::Not => stable_mir::mir::UnOp::Not,
@@ -391,7 +1707,7 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for rustc_hir::GeneratorKind {
     type T = stable_mir::mir::GeneratorKind;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, _: &Tables<'tcx>) -> Self::T {
         use rustc_hir::{AsyncGeneratorKind, GeneratorKind};
         match self {
             GeneratorKind::Async(async_gen) => {
@@ -409,7 +1725,7 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for mir::InlineAsmOperand<'tcx> {
     type T = stable_mir::mir::InlineAsmOperand;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use rustc_middle::mir::InlineAsmOperand;
 
         let (in_value, out_place) = match self {
@@ -427,67 +1743,133 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
     }
 }
 
-impl<'tcx> Stable<'tcx> for mir::Terminator<'tcx> {
-    type T = stable_mir::mir::Terminator;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
-        use rustc_middle::mir::TerminatorKind::*;
-        use stable_mir::mir::Terminator;
-        match &self.kind {
-            Goto { target } => Terminator::Goto { target: target.as_usize() },
-            SwitchInt { discr, targets } => Terminator::SwitchInt {
-                discr: discr.stable(tables),
-                targets: targets
-                    .iter()
-                    .map(|(value, target)| stable_mir::mir::SwitchTarget {
-                        value,
-                        target: target.as_usize(),
-                    })
-                    .collect(),
-                otherwise: targets.otherwise().as_usize(),
-            },
-            Resume => Terminator::Resume,
-            Terminate => Terminator::Abort,
-            Return => Terminator::Return,
-            Unreachable => Terminator::Unreachable,
-            Drop { place, target, unwind, replace: _ } => Terminator::Drop {
-                place: place.stable(tables),
-                target: target.as_usize(),
-                unwind: unwind.stable(tables),
-            },
-            Call { func, args, destination, target, unwind, call_source: _, fn_span: _ } => {
-                Terminator::Call {
-                    func: func.stable(tables),
-                    args: args.iter().map(|arg| arg.stable(tables)).collect(),
-                    destination: destination.stable(tables),
-                    target: target.map(|t| t.as_usize()),
-                    unwind: unwind.stable(tables),
-                }
+impl<'tcx> Stable<'tcx> for mir::SwitchTargets {
+    type T = stable_mir::mir::SwitchTargets;
+    fn stable(&self, _: &Tables<'tcx>) -> Self::T {
+        stable_mir::mir::SwitchTargets::new(
+            self.iter().map(|(value, target)| (value, target.as_usize())),
+            self.otherwise().as_usize(),
+        )
+    }
+}
+
+/// Convert a basic block of a body. `can_unwind` is whether the function of the body is allowed
+/// to unwind, according to its ABI.
+fn basic_block<'tcx>(
+    block: &mir::BasicBlockData<'tcx>,
+    can_unwind: bool,
+    tables: &Tables<'tcx>,
+) -> stable_mir::mir::BasicBlock {
+    use rustc_middle::mir::TerminatorKind::*;
+    use stable_mir::mir::{Terminator, UnwindTerminateReason};
+
+    // MIR doesn't record why unwinding is terminated. Inside of cleanup blocks we are
+    // already unwinding, so this is due to a nested panic. Otherwise, this comes from a
+    // function whose ABI doesn't allow unwinding.
+    let reason = if block.is_cleanup {
+        UnwindTerminateReason::InCleanup
+    } else {
+        UnwindTerminateReason::Abi
+    };
+    // Blocks that abort are always cleanup blocks. In functions that can't unwind, they are
+    // mostly reached by unwinding out of an inlined callee, so the ABI is the reason.
+    let abort_reason =
+        if can_unwind { UnwindTerminateReason::InCleanup } else { UnwindTerminateReason::Abi };
+    let terminator = match &block.terminator().kind {
+        Goto { target } => Terminator::Goto { target: target.as_usize() },
+        SwitchInt { discr, targets } => {
+            Terminator::SwitchInt { discr: discr.stable(tables), targets: targets.stable(tables) }
+        }
+        Resume => Terminator::Resume,
+        Terminate => Terminator::Abort(abort_reason),
+        Return => Terminator::Return,
+        Unreachable => Terminator::Unreachable,
+        Drop { place, target, unwind, replace: _ } => Terminator::Drop {
+            place: place.stable(tables),
+            target: target.as_usize(),
+            unwind: unwind_action(unwind, reason),
+        },
+        Call { func, args, destination, target, unwind, call_source: _, fn_span: _ } => {
+            Terminator::Call {
+                func: func.stable(tables),
+                args: args.iter().map(|arg| arg.stable(tables)).collect(),
+                destination: destination.stable(tables),
+                target: target.map(|t| t.as_usize()),
+                unwind: unwind_action(unwind, reason),
             }
-            Assert { cond, expected, msg, target, unwind } => Terminator::Assert {
-                cond: cond.stable(tables),
-                expected: *expected,
-                msg: msg.stable(tables),
-                target: target.as_usize(),
-                unwind: unwind.stable(tables),
-            },
-            InlineAsm { template, operands, options, line_spans, destination, unwind } => {
-                Terminator::InlineAsm {
-                    template: format!("{:?}", template),
-                    operands: operands.iter().map(|operand| operand.stable(tables)).collect(),
-                    options: format!("{:?}", options),
-                    line_spans: format!("{:?}", line_spans),
-                    destination: destination.map(|d| d.as_usize()),
-                    unwind: unwind.stable(tables),
-                }
+        }
+        Assert { cond, expected, msg, target, unwind } => Terminator::Assert {
+            cond: cond.stable(tables),
+            expected: *expected,
+            msg: msg.stable(tables),
+            target: target.as_usize(),
+            unwind: unwind_action(unwind, reason),
+        },
+        InlineAsm { template, operands, options, line_spans, destination, unwind } => {
+            Terminator::InlineAsm {
+                template: format!("{:?}", template),
+                operands: operands.iter().map(|operand| operand.stable(tables)).collect(),
+                options: format!("{:?}", options),
+                line_spans: format!("{:?}", line_spans),
+                destination: destination.map(|d| d.as_usize()),
+                unwind: unwind_action(unwind, reason),
             }
-            Yield { .. } | GeneratorDrop | FalseEdge { .. } | FalseUnwind { .. } => unreachable!(),
         }
+        Yield { .. } | GeneratorDrop | FalseEdge { .. } | FalseUnwind { .. } => unreachable!(),
+    };
+    stable_mir::mir::BasicBlock {
+        statements: block.statements.iter().map(|statement| statement.stable(tables)).collect(),
+        terminator,
+        statement_sources: block
+            .statements
+            .iter()
+            .map(|statement| statement.source_info.stable(tables))
+            .collect(),
+        terminator_source: block.terminator().source_info.stable(tables),
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::SourceInfo {
+    type T = stable_mir::mir::SourceInfo;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        stable_mir::mir::SourceInfo {
+            span: tables.create_span(self.span),
+            scope: self.scope.as_usize(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::SourceScopeData<'tcx> {
+    type T = stable_mir::mir::SourceScopeData;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        stable_mir::mir::SourceScopeData {
+            span: tables.create_span(self.span),
+            parent_scope: self.parent_scope.map(|scope| scope.as_usize()),
+            inlined: self.inlined.map(|(instance, span)| {
+                (tables.intern_instance(instance), tables.create_span(span))
+            }),
+            inlined_parent_scope: self.inlined_parent_scope.map(|scope| scope.as_usize()),
+        }
+    }
+}
+
+/// Convert an unwind action, using `reason` as the reason for `UnwindAction::Terminate`.
+fn unwind_action(
+    unwind: &mir::UnwindAction,
+    reason: stable_mir::mir::UnwindTerminateReason,
+) -> stable_mir::mir::UnwindAction {
+    use rustc_middle::mir::UnwindAction;
+    match unwind {
+        UnwindAction::Continue => stable_mir::mir::UnwindAction::Continue,
+        UnwindAction::Unreachable => stable_mir::mir::UnwindAction::Unreachable,
+        UnwindAction::Terminate => stable_mir::mir::UnwindAction::Terminate(reason),
+        UnwindAction::Cleanup(bb) => stable_mir::mir::UnwindAction::Cleanup(bb.as_usize()),
     }
 }
 
 impl<'tcx> Stable<'tcx> for ty::GenericArgs<'tcx> {
     type T = stable_mir::ty::GenericArgs;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use stable_mir::ty::{GenericArgKind, GenericArgs};
 
         GenericArgs(
@@ -497,16 +1879,39 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                         GenericArgKind::Lifetime(opaque(&region))
                     }
                     ty::GenericArgKind::Type(ty) => GenericArgKind::Type(tables.intern_ty(ty)),
-                    ty::GenericArgKind::Const(const_) => GenericArgKind::Const(opaque(&const_)),
+                    ty::GenericArgKind::Const(const_) => {
+                        GenericArgKind::Const(tables.intern_const(mir::ConstantKind::Ty(const_)))
+                    }
                 })
                 .collect(),
         )
     }
 }
 
+impl<'tcx> Stable<'tcx> for ty::AliasKind {
+    type T = stable_mir::ty::AliasKind;
+    fn stable(&self, _: &Tables<'tcx>) -> Self::T {
+        use ty::AliasKind::*;
+        match self {
+            Projection => stable_mir::ty::AliasKind::Projection,
+            Inherent => stable_mir::ty::AliasKind::Inherent,
+            Opaque => stable_mir::ty::AliasKind::Opaque,
+            Weak => stable_mir::ty::AliasKind::Weak,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::AliasTy<'tcx> {
+    type T = stable_mir::ty::AliasTy;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        let ty::AliasTy { args, def_id, .. } = self;
+        stable_mir::ty::AliasTy { def_id: tables.alias_def(*def_id), args: args.stable(tables) }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for ty::PolyFnSig<'tcx> {
     type T = stable_mir::ty::PolyFnSig;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use stable_mir::ty::Binder;
 
         Binder {
@@ -522,7 +1927,7 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for ty::FnSig<'tcx> {
     type T = stable_mir::ty::FnSig;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use rustc_target::spec::abi;
         use stable_mir::ty::{Abi, FnSig, Unsafety};
 
@@ -568,9 +1973,103 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
     }
 }
 
+impl<'tcx> Stable<'tcx> for ty::TraitRef<'tcx> {
+    type T = stable_mir::ty::TraitRef;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        stable_mir::ty::TraitRef {
+            def_id: tables.trait_def(self.def_id),
+            args: self.args.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::PolyExistentialTraitRef<'tcx> {
+    type T = stable_mir::ty::Binder<stable_mir::ty::ExistentialTraitRef>;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        use stable_mir::ty::Binder;
+
+        Binder {
+            value: self.skip_binder().stable(tables),
+            bound_vars: self
+                .bound_vars()
+                .iter()
+                .map(|bound_var| bound_var.stable(tables))
+                .collect(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::PolyExistentialPredicate<'tcx> {
+    type T = stable_mir::ty::Binder<stable_mir::ty::ExistentialPredicate>;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        use stable_mir::ty::{Binder, ExistentialPredicate, ExistentialProjection, TermKind};
+
+        let value = match self.skip_binder() {
+            ty::ExistentialPredicate::Trait(trait_ref) => {
+                ExistentialPredicate::Trait(trait_ref.stable(tables))
+            }
+            ty::ExistentialPredicate::Projection(projection) => {
+                ExistentialPredicate::Projection(ExistentialProjection {
+                    def_id: tables.alias_def(projection.def_id),
+                    args: projection.args.stable(tables),
+                    term: match projection.term.unpack() {
+                        ty::TermKind::Ty(ty) => TermKind::Type(tables.intern_ty(ty)),
+                        ty::TermKind::Const(c) => {
+                            TermKind::Const(tables.intern_const(mir::ConstantKind::Ty(c)))
+                        }
+                    },
+                })
+            }
+            ty::ExistentialPredicate::AutoTrait(def_id) => {
+                ExistentialPredicate::AutoTrait(tables.trait_def(def_id))
+            }
+        };
+        Binder {
+            value,
+            bound_vars: self
+                .bound_vars()
+                .iter()
+                .map(|bound_var| bound_var.stable(tables))
+                .collect(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::ExistentialTraitRef<'tcx> {
+    type T = stable_mir::ty::ExistentialTraitRef;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        stable_mir::ty::ExistentialTraitRef {
+            def_id: tables.trait_def(self.def_id),
+            args: self.args.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for interpret::Allocation {
+    type T = stable_mir::mir::alloc::Allocation;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        let bytes = self.inspect_with_uninit_and_ptr_outside_interpreter(0..self.len());
+        stable_mir::mir::alloc::Allocation {
+            bytes: bytes
+                .iter()
+                .enumerate()
+                .map(|(i, byte)| self.init_mask().get(Size::from_bytes(i)).then_some(*byte))
+                .collect(),
+            provenance: self
+                .provenance()
+                .ptrs()
+                .iter()
+                .map(|(offset, id)| (offset.bytes_usize(), tables.intern_alloc_id(*id)))
+                .collect(),
+            align: self.align.bytes(),
+            mutability: self.mutability.stable(tables),
+        }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for ty::BoundVariableKind {
     type T = stable_mir::ty::BoundVariableKind;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         use stable_mir::ty::{BoundRegionKind, BoundTyKind, BoundVariableKind};
 
         match self {
@@ -585,7 +2084,7 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
             ty::BoundVariableKind::Region(bound_region_kind) => {
                 BoundVariableKind::Region(match bound_region_kind {
                     ty::BoundRegionKind::BrAnon(option_span) => {
-                        BoundRegionKind::BrAnon(option_span.map(|span| opaque(&span)))
+                        BoundRegionKind::BrAnon(option_span.map(|span| tables.create_span(span)))
                     }
                     ty::BoundRegionKind::BrNamed(def_id, symbol) => BoundRegionKind::BrNamed(
                         rustc_internal::br_named_def(*def_id),
@@ -601,7 +2100,7 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for Ty<'tcx> {
     type T = stable_mir::ty::TyKind;
-    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
         match self.kind() {
             ty::Bool => TyKind::RigidTy(RigidTy::Bool),
             ty::Char => TyKind::RigidTy(RigidTy::Char),
@@ -633,9 +2132,10 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                 TyKind::RigidTy(RigidTy::Foreign(rustc_internal::foreign_def(*def_id)))
             }
             ty::Str => TyKind::RigidTy(RigidTy::Str),
-            ty::Array(ty, constant) => {
-                TyKind::RigidTy(RigidTy::Array(tables.intern_ty(*ty), opaque(constant)))
-            }
+            ty::Array(ty, constant) => TyKind::RigidTy(RigidTy::Array(
+                tables.intern_ty(*ty),
+                tables.intern_const(mir::ConstantKind::Ty(*constant)),
+            )),
             ty::Slice(ty) => TyKind::RigidTy(RigidTy::Slice(tables.intern_ty(*ty))),
             ty::RawPtr(ty::TypeAndMut { ty, mutbl }) => {
                 TyKind::RigidTy(RigidTy::RawPtr(tables.intern_ty(*ty), mutbl.stable(tables)))
@@ -650,7 +2150,16 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                 generic_args.stable(tables),
             )),
             ty::FnPtr(poly_fn_sig) => TyKind::RigidTy(RigidTy::FnPtr(poly_fn_sig.stable(tables))),
-            ty::Dynamic(_, _, _) => todo!(),
+            ty::Dynamic(existential_predicates, region, dyn_kind) => {
+                TyKind::RigidTy(RigidTy::Dynamic(
+                    existential_predicates.iter().map(|pred| pred.stable(tables)).collect(),
+                    opaque(region),
+                    match dyn_kind {
+                        ty::Dyn => stable_mir::ty::DynKind::Dyn,
+                        ty::DynStar => stable_mir::ty::DynKind::DynStar,
+                    },
+                ))
+            }
             ty::Closure(def_id, generic_args) => TyKind::RigidTy(RigidTy::Closure(
                 rustc_internal::closure_def(*def_id),
                 generic_args.stable(tables),
@@ -663,18 +2172,35 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                     hir::Movability::Movable => Movability::Movable,
                 },
             )),
+            ty::GeneratorWitness(types) => {
+                TyKind::RigidTy(RigidTy::GeneratorWitness(stable_mir::ty::Binder {
+                    value: types.skip_binder().iter().map(|ty| tables.intern_ty(ty)).collect(),
+                    bound_vars: types
+                        .bound_vars()
+                        .iter()
+                        .map(|bound_var| bound_var.stable(tables))
+                        .collect(),
+                }))
+            }
+            ty::GeneratorWitnessMIR(def_id, generic_args) => {
+                TyKind::RigidTy(RigidTy::GeneratorWitnessMIR(
+                    rustc_internal::generator_def(*def_id),
+                    generic_args.stable(tables),
+                ))
+            }
             ty::Never => TyKind::RigidTy(RigidTy::Never),
             ty::Tuple(fields) => TyKind::RigidTy(RigidTy::Tuple(
                 fields.iter().map(|ty| tables.intern_ty(ty)).collect(),
             )),
-            ty::Alias(_, _) => todo!(),
-            ty::Param(_) => todo!(),
+            ty::Alias(alias_kind, alias_ty) => {
+                TyKind::Alias(alias_kind.stable(tables), alias_ty.stable(tables))
+            }
+            ty::Param(param_ty) => TyKind::Param(stable_mir::ty::ParamTy {
+                index: param_ty.index,
+                name: param_ty.name.to_string(),
+            }),
             ty::Bound(_, _) => todo!(),
-            ty::Placeholder(..)
-            | ty::GeneratorWitness(_)
-            | ty::GeneratorWitnessMIR(_, _)
-            | ty::Infer(_)
-            | ty::Error(_) => {
+            ty::Placeholder(..) | ty::Infer(_) | ty::Error(_) => {
                 unreachable!();
             }
         }
@@ -0,0 +1,269 @@
+//! Conversion of the THIR of bodies to their stable representation.
+
+use crate::rustc_internal::opaque;
+use crate::rustc_smir::{Stable, Tables};
+use crate::stable_mir::thir::{
+    Arm, BindingMode, Block, BlockSafety, Expr, ExprKind, FieldExpr, FieldPat, Guard, Param, Pat,
+    PatKind, Stmt, ThirBody,
+};
+use rustc_hir::RangeEnd;
+use rustc_middle::mir;
+use rustc_middle::thir;
+use rustc_span::def_id::LocalDefId;
+
+/// Convert the THIR of a body, or return `None` if it isn't available anymore because MIR was
+/// built from it already, or because the body has type errors.
+pub(super) fn thir_body(tables: &Tables<'_>, def_id: LocalDefId) -> Option<ThirBody> {
+    let tcx = tables.tcx;
+    tcx.hir().maybe_body_owned_by(def_id)?;
+    let (thir, value) = tcx.thir_body(def_id).ok()?;
+    if thir.is_stolen() {
+        return None;
+    }
+    let thir = thir.borrow();
+    Some(ThirBody {
+        exprs: thir.exprs.iter().map(|expr| expr.stable(tables)).collect(),
+        stmts: thir.stmts.iter().map(|stmt| stmt.stable(tables)).collect(),
+        blocks: thir.blocks.iter().map(|block| block.stable(tables)).collect(),
+        arms: thir.arms.iter().map(|arm| arm.stable(tables)).collect(),
+        params: thir.params.iter().map(|param| param.stable(tables)).collect(),
+        value: value.as_usize(),
+    })
+}
+
+impl<'tcx> Stable<'tcx> for thir::Param<'tcx> {
+    type T = Param;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        Param {
+            pat: self.pat.as_ref().map(|pat| pat.stable(tables)),
+            ty: tables.intern_ty(self.ty),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for thir::Expr<'tcx> {
+    type T = Expr;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        use thir::ExprKind::*;
+        let kind = match &self.kind {
+            Scope { value, .. } => ExprKind::Scope { value: value.as_usize() },
+            Box { value } => ExprKind::Box { value: value.as_usize() },
+            If { cond, then, else_opt, .. } => ExprKind::If {
+                cond: cond.as_usize(),
+                then: then.as_usize(),
+                else_opt: else_opt.map(|expr| expr.as_usize()),
+            },
+            Call { fun, args, from_hir_call, fn_span, .. } => ExprKind::Call {
+                fun: fun.as_usize(),
+                args: args.iter().map(|id| id.as_usize()).collect(),
+                from_hir_call: *from_hir_call,
+                fn_span: tables.create_span(*fn_span),
+            },
+            Deref { arg } => ExprKind::Deref { arg: arg.as_usize() },
+            Binary { op, lhs, rhs } => {
+                ExprKind::Binary { op: op.stable(tables), lhs: lhs.as_usize(), rhs: rhs.as_usize() }
+            }
+            LogicalOp { op, lhs, rhs } => ExprKind::LogicalOp {
+                op: match op {
+                    thir::LogicalOp::And => crate::stable_mir::thir::LogicalOp::And,
+                    thir::LogicalOp::Or => crate::stable_mir::thir::LogicalOp::Or,
+                },
+                lhs: lhs.as_usize(),
+                rhs: rhs.as_usize(),
+            },
+            Unary { op, arg } => ExprKind::Unary { op: op.stable(tables), arg: arg.as_usize() },
+            Cast { source } => ExprKind::Cast { source: source.as_usize() },
+            Use { source } => ExprKind::Use { source: source.as_usize() },
+            NeverToAny { source } => ExprKind::NeverToAny { source: source.as_usize() },
+            PointerCoercion { cast, source } => {
+                ExprKind::PointerCoercion { cast: cast.stable(tables), source: source.as_usize() }
+            }
+            Loop { body } => ExprKind::Loop { body: body.as_usize() },
+            Let { expr, pat } => ExprKind::Let { expr: expr.as_usize(), pat: pat.stable(tables) },
+            Match { scrutinee, arms } => ExprKind::Match {
+                scrutinee: scrutinee.as_usize(),
+                arms: arms.iter().map(|id| id.as_usize()).collect(),
+            },
+            Block { block } => ExprKind::Block { block: block.as_usize() },
+            Assign { lhs, rhs } => ExprKind::Assign { lhs: lhs.as_usize(), rhs: rhs.as_usize() },
+            AssignOp { op, lhs, rhs } => ExprKind::AssignOp {
+                op: op.stable(tables),
+                lhs: lhs.as_usize(),
+                rhs: rhs.as_usize(),
+            },
+            Field { lhs, variant_index, name } => ExprKind::Field {
+                lhs: lhs.as_usize(),
+                variant_index: variant_index.as_usize(),
+                field: name.as_usize(),
+            },
+            Index { lhs, index } => {
+                ExprKind::Index { lhs: lhs.as_usize(), index: index.as_usize() }
+            }
+            VarRef { id } => ExprKind::VarRef { var: id.0.local_id.as_usize() },
+            UpvarRef { closure_def_id, var_hir_id } => ExprKind::UpvarRef {
+                closure: tables.closure_def(*closure_def_id),
+                var: var_hir_id.0.local_id.as_usize(),
+            },
+            Borrow { borrow_kind, arg } => {
+                ExprKind::Borrow { kind: borrow_kind.stable(tables), arg: arg.as_usize() }
+            }
+            AddressOf { mutability, arg } => {
+                ExprKind::AddressOf { mutability: mutability.stable(tables), arg: arg.as_usize() }
+            }
+            Break { value, .. } => ExprKind::Break { value: value.map(|expr| expr.as_usize()) },
+            Continue { .. } => ExprKind::Continue,
+            Return { value } => ExprKind::Return { value: value.map(|expr| expr.as_usize()) },
+            Become { value } => ExprKind::Become { value: value.as_usize() },
+            ConstBlock { did, .. } => ExprKind::ConstBlock { item: tables.crate_item(*did) },
+            Repeat { value, count } => ExprKind::Repeat {
+                value: value.as_usize(),
+                count: tables.intern_const(mir::ConstantKind::Ty(*count)),
+            },
+            Array { fields } => {
+                ExprKind::Array { fields: fields.iter().map(|id| id.as_usize()).collect() }
+            }
+            Tuple { fields } => {
+                ExprKind::Tuple { fields: fields.iter().map(|id| id.as_usize()).collect() }
+            }
+            Adt(adt) => ExprKind::Adt {
+                adt: tables.adt_def(adt.adt_def.did()),
+                variant_index: adt.variant_index.as_usize(),
+                fields: adt
+                    .fields
+                    .iter()
+                    .map(|field| FieldExpr {
+                        field: field.name.as_usize(),
+                        expr: field.expr.as_usize(),
+                    })
+                    .collect(),
+                base: adt.base.as_ref().map(|base| base.base.as_usize()),
+            },
+            PlaceTypeAscription { source, .. } | ValueTypeAscription { source, .. } => {
+                ExprKind::TypeAscription { source: source.as_usize() }
+            }
+            Closure(closure) => ExprKind::Closure {
+                def: tables.closure_def(closure.closure_id.to_def_id()),
+                upvars: closure.upvars.iter().map(|id| id.as_usize()).collect(),
+            },
+            Literal { lit, neg } => ExprKind::Literal { value: opaque(&lit.node), neg: *neg },
+            NonHirLiteral { lit, .. } => ExprKind::Literal { value: opaque(lit), neg: false },
+            ZstLiteral { .. } => ExprKind::ZstLiteral,
+            NamedConst { def_id, .. } => ExprKind::NamedConst { item: tables.crate_item(*def_id) },
+            ConstParam { param, .. } => ExprKind::ConstParam(crate::stable_mir::ty::ParamConst {
+                index: param.index,
+                name: param.name.to_string(),
+            }),
+            StaticRef { def_id, .. } => ExprKind::StaticRef { def: tables.static_def(*def_id) },
+            ThreadLocalRef(def_id) => ExprKind::ThreadLocalRef { def: tables.static_def(*def_id) },
+            Yield { value } => ExprKind::Yield { value: value.as_usize() },
+            InlineAsm(_) | OffsetOf { .. } => ExprKind::Other(opaque(&self.kind)),
+        };
+        Expr { kind, ty: tables.intern_ty(self.ty), span: tables.create_span(self.span) }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for thir::Stmt<'tcx> {
+    type T = Stmt;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        match &self.kind {
+            thir::StmtKind::Expr { expr, .. } => Stmt::Expr { expr: expr.as_usize() },
+            thir::StmtKind::Let { pattern, initializer, else_block, span, .. } => Stmt::Let {
+                pattern: pattern.stable(tables),
+                initializer: initializer.map(|expr| expr.as_usize()),
+                else_block: else_block.map(|block| block.as_usize()),
+                span: tables.create_span(*span),
+            },
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for thir::Block {
+    type T = Block;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        Block {
+            stmts: self.stmts.iter().map(|id| id.as_usize()).collect(),
+            expr: self.expr.map(|expr| expr.as_usize()),
+            safety: match self.safety_mode {
+                thir::BlockSafety::Safe => BlockSafety::Safe,
+                thir::BlockSafety::BuiltinUnsafe => BlockSafety::BuiltinUnsafe,
+                thir::BlockSafety::ExplicitUnsafe(_) => BlockSafety::ExplicitUnsafe,
+            },
+            span: tables.create_span(self.span),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for thir::Arm<'tcx> {
+    type T = Arm;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        Arm {
+            pattern: self.pattern.stable(tables),
+            guard: self.guard.as_ref().map(|guard| match guard {
+                thir::Guard::If(expr) => Guard::If(expr.as_usize()),
+                thir::Guard::IfLet(pat, expr) => Guard::IfLet(pat.stable(tables), expr.as_usize()),
+            }),
+            body: self.body.as_usize(),
+            span: tables.create_span(self.span),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for thir::Pat<'tcx> {
+    type T = Pat;
+    fn stable(&self, tables: &Tables<'tcx>) -> Self::T {
+        use thir::PatKind::*;
+        let pats =
+            |pats: &[Box<thir::Pat<'tcx>>]| pats.iter().map(|pat| pat.stable(tables)).collect();
+        let subpatterns = |subpatterns: &[thir::FieldPat<'tcx>]| {
+            subpatterns
+                .iter()
+                .map(|field| FieldPat {
+                    field: field.field.as_usize(),
+                    pattern: field.pattern.stable(tables),
+                })
+                .collect()
+        };
+        let kind = match &self.kind {
+            Wild => PatKind::Wild,
+            // Only keep the pattern, with the type of the ascription.
+            AscribeUserType { subpattern, .. } => return subpattern.stable(tables),
+            Binding { mutability, name, mode, var, subpattern, .. } => PatKind::Binding {
+                name: name.to_string(),
+                var: var.0.local_id.as_usize(),
+                mutability: mutability.stable(tables),
+                mode: match mode {
+                    thir::BindingMode::ByValue => BindingMode::ByValue,
+                    thir::BindingMode::ByRef(kind) => BindingMode::ByRef(kind.stable(tables)),
+                },
+                subpattern: subpattern.as_ref().map(|pat| Box::new(pat.stable(tables))),
+            },
+            Variant { adt_def, variant_index, subpatterns: fields, .. } => PatKind::Variant {
+                adt: tables.adt_def(adt_def.did()),
+                variant_index: variant_index.as_usize(),
+                subpatterns: subpatterns(fields),
+            },
+            Leaf { subpatterns: fields } => PatKind::Leaf { subpatterns: subpatterns(fields) },
+            Deref { subpattern } => {
+                PatKind::Deref { subpattern: Box::new(subpattern.stable(tables)) }
+            }
+            Constant { value } => PatKind::Constant { value: opaque(value) },
+            Range(range) => PatKind::Range {
+                lo: opaque(&range.lo),
+                hi: opaque(&range.hi),
+                inclusive: matches!(range.end, RangeEnd::Included),
+            },
+            Slice { prefix, slice, suffix } => PatKind::Slice {
+                prefix: pats(prefix),
+                slice: slice.as_ref().map(|pat| Box::new(pat.stable(tables))),
+                suffix: pats(suffix),
+            },
+            Array { prefix, slice, suffix } => PatKind::Array {
+                prefix: pats(prefix),
+                slice: slice.as_ref().map(|pat| Box::new(pat.stable(tables))),
+                suffix: pats(suffix),
+            },
+            Or { pats: alternatives } => PatKind::Or { pats: pats(alternatives) },
+        };
+        Pat { kind, ty: tables.intern_ty(self.ty), span: tables.create_span(self.span) }
+    }
+}
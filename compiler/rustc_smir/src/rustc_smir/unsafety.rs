@@ -0,0 +1,175 @@
+//! Finding the operations of a body that require `unsafe`.
+//!
+//! The unsafety checker of the compiler only records the operations that aren't allowed where
+//! they appear, so the operations are collected again here, following the same rules.
+
+use crate::rustc_smir::Tables;
+use crate::stable_mir::mir::unsafety::{
+    UnsafeBlock, UnsafeOperation, UnsafeOperationKind, UnsafeSource, UnsafetyInfo,
+};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir as hir;
+use rustc_middle::mir::visit::{MutatingUseContext, PlaceContext, Visitor};
+use rustc_middle::mir::{self, Local, Location, ProjectionElem, Safety};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::def_id::{DefId, LocalDefId};
+
+pub(super) fn unsafety_info(tables: &Tables<'_>, def_id: LocalDefId) -> UnsafetyInfo {
+    let tcx = tables.tcx;
+    let body = tcx.optimized_mir(def_id);
+    let mut collector = UnsafeOperationCollector {
+        tcx,
+        body,
+        static_refs: static_refs(tcx, body),
+        source_info: mir::SourceInfo::outermost(body.span),
+        operations: vec![],
+    };
+    collector.visit_body(body);
+
+    // Closures and inline constants are checked together with the body they're defined in, so
+    // their operations may be allowed by an `unsafe` block of that body.
+    let is_typeck_child = tcx.is_typeck_child(def_id.to_def_id());
+    let block_span = |hir_id: hir::HirId| tables.create_span(tcx.hir().span(hir_id));
+    let operations = collector
+        .operations
+        .into_iter()
+        .filter_map(|(kind, source_info)| {
+            let source = match scope_safety(body, source_info.scope)? {
+                Safety::Safe if is_typeck_child => UnsafeSource::Inherited,
+                // Anything else outside of an unsafe context was introduced by the compiler.
+                Safety::Safe | Safety::BuiltinUnsafe => return None,
+                Safety::FnUnsafe => UnsafeSource::UnsafeFn,
+                Safety::ExplicitUnsafe(hir_id) => UnsafeSource::Block(block_span(hir_id)),
+            };
+            Some(UnsafeOperation { kind, span: tables.create_span(source_info.span), source })
+        })
+        .collect();
+
+    // Every `unsafe` block gets a scope of its own, even if it's empty.
+    let used_unsafe_blocks = &tcx.unsafety_check_result(def_id).used_unsafe_blocks;
+    let mut seen = FxHashSet::default();
+    let unsafe_blocks = body
+        .source_scopes
+        .indices()
+        .filter_map(|scope| match scope_safety(body, scope)? {
+            Safety::ExplicitUnsafe(hir_id) if seen.insert(hir_id) => Some(UnsafeBlock {
+                span: block_span(hir_id),
+                used: used_unsafe_blocks.contains(&hir_id),
+            }),
+            _ => None,
+        })
+        .collect();
+
+    UnsafetyInfo { operations, unsafe_blocks }
+}
+
+/// The locals that hold a pointer to a static, mapped to that static.
+///
+/// Statics are accessed through such a local, which the compiler introduces. Optimized MIR doesn't
+/// record what these locals are for anymore, so they are found through the constant pointer
+/// that is assigned to them.
+fn static_refs(tcx: TyCtxt<'_>, body: &mir::Body<'_>) -> FxHashMap<Local, DefId> {
+    body.basic_blocks
+        .iter()
+        .flat_map(|block| &block.statements)
+        .filter_map(|statement| {
+            let mir::StatementKind::Assign(assign) = &statement.kind else {
+                return None;
+            };
+            let (place, mir::Rvalue::Use(mir::Operand::Constant(constant))) = &**assign else {
+                return None;
+            };
+            if !body.local_decls[place.as_local()?].internal {
+                return None;
+            }
+            Some((place.local, constant.check_static_ptr(tcx)?))
+        })
+        .collect()
+}
+
+/// The safety of the code in `scope`, or `None` if the scope was inlined from another body.
+fn scope_safety(body: &mir::Body<'_>, scope: mir::SourceScope) -> Option<Safety> {
+    if scope.inlined_instance(&body.source_scopes).is_some() {
+        return None;
+    }
+    Some(body.source_scopes[scope].local_data.as_ref().assert_crate_local().safety)
+}
+
+struct UnsafeOperationCollector<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a mir::Body<'tcx>,
+    static_refs: FxHashMap<Local, DefId>,
+    source_info: mir::SourceInfo,
+    operations: Vec<(UnsafeOperationKind, mir::SourceInfo)>,
+}
+
+impl UnsafeOperationCollector<'_, '_> {
+    fn push(&mut self, kind: UnsafeOperationKind) {
+        self.operations.push((kind, self.source_info));
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for UnsafeOperationCollector<'_, 'tcx> {
+    fn visit_statement(&mut self, statement: &mir::Statement<'tcx>, location: Location) {
+        self.source_info = statement.source_info;
+        self.super_statement(statement, location);
+    }
+
+    fn visit_terminator(&mut self, terminator: &mir::Terminator<'tcx>, location: Location) {
+        self.source_info = terminator.source_info;
+        match &terminator.kind {
+            mir::TerminatorKind::Call { func, .. } => {
+                let func_ty = func.ty(self.body, self.tcx);
+                if func_ty.fn_sig(self.tcx).unsafety() == hir::Unsafety::Unsafe {
+                    self.push(UnsafeOperationKind::CallToUnsafeFunction);
+                }
+            }
+            mir::TerminatorKind::InlineAsm { .. } => {
+                self.push(UnsafeOperationKind::InlineAssembly);
+            }
+            _ => {}
+        }
+        self.super_terminator(terminator, location);
+    }
+
+    fn visit_place(&mut self, place: &mir::Place<'tcx>, context: PlaceContext, _: Location) {
+        let decl = &self.body.local_decls[place.local];
+        if decl.internal && place.projection.first() == Some(&ProjectionElem::Deref) {
+            // Statics are accessed through a pointer held by a local introduced by the compiler.
+            if let Some(&def_id) = self.static_refs.get(&place.local) {
+                if self.tcx.is_mutable_static(def_id) {
+                    self.push(UnsafeOperationKind::UseOfMutableStatic);
+                } else if self.tcx.is_foreign_item(def_id) {
+                    self.push(UnsafeOperationKind::UseOfExternStatic);
+                }
+            }
+            // Other internal locals hold pointers created by the compiler, e.g., the pointer
+            // of a `Box` that is dereferenced.
+            return;
+        }
+
+        for (base, elem) in place.iter_projections() {
+            if elem == ProjectionElem::Deref && base.ty(self.body, self.tcx).ty.is_unsafe_ptr() {
+                self.push(UnsafeOperationKind::DerefOfRawPointer);
+            }
+        }
+
+        // Assigning to a union field is safe, unless the field is behind a pointer.
+        let is_store = matches!(
+            context,
+            PlaceContext::MutatingUse(
+                MutatingUseContext::Store
+                    | MutatingUseContext::Drop
+                    | MutatingUseContext::AsmOutput
+            )
+        );
+        let mut saw_deref = false;
+        for (base, elem) in place.iter_projections().rev() {
+            if elem == ProjectionElem::Deref {
+                saw_deref = true;
+            } else if base.ty(self.body, self.tcx).ty.is_union() && (saw_deref || !is_store) {
+                self.push(UnsafeOperationKind::AccessToUnionField);
+            }
+        }
+    }
+}
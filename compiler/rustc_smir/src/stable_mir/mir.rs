@@ -1,3 +1,11 @@
+pub mod alloc;
 mod body;
+pub mod borrowck;
+pub mod coverage;
+mod fingerprint;
+pub mod mono;
+pub mod unsafety;
+mod validate;
 
 pub use body::*;
+pub use validate::ValidationError;
@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+use crate::stable_mir::mir::mono::Instance;
+use crate::stable_mir::mir::Mutability;
+use crate::stable_mir::ty::{Binder, ExistentialTraitRef, StaticDef, Ty};
+use crate::stable_mir::with;
+
+/// The identifier of a global allocation, such as the memory a pointer inside a constant points
+/// to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AllocId(pub(crate) usize);
+
+impl AllocId {
+    /// Retrieve what this allocation ID refers to.
+    pub fn global_alloc(&self) -> GlobalAlloc {
+        with(|cx| cx.global_alloc(*self))
+    }
+
+    /// Read the bytes in the given range of the memory this allocation ID refers to. For
+    /// statics, this is their initial value.
+    ///
+    /// Uninitialized bytes are `None`. Returns `None` if the range is out of bounds, or if the
+    /// allocation is not memory, e.g., because it's a function.
+    pub fn read_bytes(&self, range: Range<usize>) -> Option<Vec<Option<u8>>> {
+        let alloc = match self.global_alloc() {
+            GlobalAlloc::Memory(alloc) => alloc,
+            GlobalAlloc::Static(def) => def.eval_initializer()?,
+            GlobalAlloc::Function(_) | GlobalAlloc::VTable(..) => return None,
+        };
+        Some(alloc.bytes.get(range)?.to_vec())
+    }
+}
+
+/// What an [`AllocId`] refers to.
+#[derive(Clone, Debug)]
+pub enum GlobalAlloc {
+    /// The allocation is used as a function pointer.
+    Function(Instance),
+    /// The allocation is the vtable of the given type for the given trait. The trait is `None`
+    /// for vtables of trait objects without a principal trait, e.g., `dyn Send`.
+    VTable(Ty, Option<Binder<ExistentialTraitRef>>),
+    /// The allocation is a static that might not have been evaluated yet.
+    Static(StaticDef),
+    /// The allocation is plain memory, such as the data of a constant.
+    Memory(Allocation),
+}
+
+/// The memory of a global allocation.
+#[derive(Clone, Debug)]
+pub struct Allocation {
+    /// The contents of the allocation. Uninitialized bytes are `None`.
+    pub bytes: Vec<Option<u8>>,
+    /// The pointers stored in this allocation, given as the offset of each pointer together
+    /// with the allocation it points to. The bytes of a pointer contain its offset in that
+    /// allocation.
+    pub provenance: Vec<(usize, AllocId)>,
+    /// The alignment of the allocation, in bytes.
+    pub align: u64,
+    pub mutability: Mutability,
+}
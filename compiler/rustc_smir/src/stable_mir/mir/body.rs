@@ -1,16 +1,73 @@
-use crate::stable_mir::ty::Region;
-use crate::stable_mir::{self, ty::Ty};
+use crate::stable_mir::mir::mono::Instance;
+use crate::stable_mir::ty::{
+    AdtDef, ClosureDef, Const, FnDef, GeneratorDef, GenericArgs, Movability, Region, RigidTy, Span,
+    TyKind,
+};
+use crate::stable_mir::{self, ty::Ty, with};
 
 #[derive(Clone, Debug)]
 pub struct Body {
     pub blocks: Vec<BasicBlock>,
-    pub locals: Vec<Ty>,
+    pub locals: Vec<LocalDecl>,
+    /// The lexical scopes of the body, indexed by [`SourceScope`]. The first scope is the
+    /// outermost scope of the function.
+    pub source_scopes: Vec<SourceScopeData>,
+}
+
+impl Body {
+    /// The functions that were inlined to produce the code in the given scope, innermost first,
+    /// together with the span of the call that was inlined.
+    ///
+    /// This is empty if the scope belongs to the function of this body itself.
+    pub fn inlined_call_sites(&self, scope: SourceScope) -> Vec<(Instance, Span)> {
+        let mut call_sites = vec![];
+        let mut scope = Some(scope);
+        while let Some(current) = scope {
+            let data = &self.source_scopes[current];
+            call_sites.extend(data.inlined.clone());
+            scope = data.inlined_parent_scope;
+        }
+        call_sites
+    }
+}
+
+/// The index of a scope in [`Body::source_scopes`].
+pub type SourceScope = usize;
+
+/// A lexical scope in a body, such as a block or the body of a function that was inlined.
+#[derive(Clone, Debug)]
+pub struct SourceScopeData {
+    pub span: Span,
+    pub parent_scope: Option<SourceScope>,
+    /// If this scope is the outermost scope of a function that was inlined into this body, the
+    /// inlined function and the span of the call.
+    pub inlined: Option<(Instance, Span)>,
+    /// The innermost scope enclosing this one, excluding this one, that has `inlined` set.
+    pub inlined_parent_scope: Option<SourceScope>,
+}
+
+/// Where a statement or terminator comes from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SourceInfo {
+    pub span: Span,
+    pub scope: SourceScope,
+}
+
+#[derive(Clone, Debug)]
+pub struct LocalDecl {
+    pub ty: Ty,
+    pub span: Span,
+    pub mutability: Mutability,
 }
 
 #[derive(Clone, Debug)]
 pub struct BasicBlock {
     pub statements: Vec<Statement>,
     pub terminator: Terminator,
+    /// Where each statement comes from, indexed like `statements`.
+    pub statement_sources: Vec<SourceInfo>,
+    /// Where the terminator comes from.
+    pub terminator_source: SourceInfo,
 }
 
 #[derive(Clone, Debug)]
@@ -20,11 +77,10 @@ pub enum Terminator {
     },
     SwitchInt {
         discr: Operand,
-        targets: Vec<SwitchTarget>,
-        otherwise: usize,
+        targets: SwitchTargets,
     },
     Resume,
-    Abort,
+    Abort(UnwindTerminateReason),
     Return,
     Unreachable,
     Drop {
@@ -66,14 +122,23 @@ pub struct InlineAsmOperand {
     pub raw_rpr: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum UnwindAction {
     Continue,
     Unreachable,
-    Terminate,
+    Terminate(UnwindTerminateReason),
     Cleanup(usize),
 }
 
+/// The reason why unwinding is terminated by aborting the program.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum UnwindTerminateReason {
+    /// Unwinding is not possible given the ABI of the current function.
+    Abi,
+    /// A panic occurred while already unwinding, e.g. in a destructor during cleanup.
+    InCleanup,
+}
+
 #[derive(Clone, Debug)]
 pub enum AssertMessage {
     BoundsCheck { len: Operand, index: Operand },
@@ -86,7 +151,7 @@ pub enum AssertMessage {
     MisalignedPointerDereference { required: Operand, found: Operand },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum BinOp {
     Add,
     AddUnchecked,
@@ -112,31 +177,59 @@ pub enum BinOp {
     Offset,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum UnOp {
     Not,
     Neg,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum GeneratorKind {
     Async(AsyncGeneratorKind),
     Gen,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum AsyncGeneratorKind {
     Block,
     Closure,
     Fn,
 }
 
+/// A statement of a basic block.
+///
+/// Statements that only matter to borrow checking, const evaluation or coverage instrumentation
+/// don't change what the code does, so they are converted to [`Statement::Nop`].
 #[derive(Clone, Debug)]
 pub enum Statement {
     Assign(Place, Rvalue),
+    /// Writes the discriminant of the given variant to the place, leaving its fields as is.
+    SetDiscriminant {
+        place: Place,
+        variant_index: VariantIdx,
+    },
+    /// Makes the contents of the place uninitialized.
+    Deinit(Place),
+    /// Starts the live range of the storage of the local.
+    StorageLive(Local),
+    /// Ends the live range of the storage of the local.
+    StorageDead(Local),
+    /// Retags the references in the place, as inserted by `-Z mir-emit-retag`.
+    Retag(Place),
+    /// A call to an intrinsic that can't diverge.
+    Intrinsic(NonDivergingIntrinsic),
     Nop,
 }
 
+#[derive(Clone, Debug)]
+pub enum NonDivergingIntrinsic {
+    /// Lets the optimizer assume that the operand, a `bool`, is true.
+    Assume(Operand),
+    /// Copies `count` values of the pointee type of `src` to `dst`, like
+    /// [`std::ptr::copy_nonoverlapping`].
+    CopyNonOverlapping { src: Operand, dst: Operand, count: Operand },
+}
+
 // FIXME this is incomplete
 #[derive(Clone, Debug)]
 pub enum Rvalue {
@@ -146,6 +239,12 @@ pub enum Rvalue {
     /// `&raw v` or `addr_of!(v)`.
     AddressOf(Mutability, Place),
 
+    /// Creates an aggregate value, like a tuple or a struct.
+    ///
+    /// The operands are the values of the fields, in order. For enum variants, these are only the
+    /// fields of the given variant, and for unions, only the active field.
+    Aggregate(AggregateKind, Vec<Operand>),
+
     /// * `Offset` has the same semantics as [`offset`](pointer::offset), except that the second
     ///   parameter may be a `usize` as well.
     /// * The comparison operations accept `bool`s, `char`s, signed or unsigned integers, floats,
@@ -195,9 +294,15 @@ pub enum Rvalue {
     /// ill-formed for places of other types.
     Len(Place),
 
+    /// Computes a value that only depends on the type, like its size.
+    NullaryOp(NullOp, Ty),
+
     /// Creates a reference to the place.
     Ref(Region, BorrowKind, Place),
 
+    /// Creates an array where each element is the value of the operand, e.g. `[x; N]`.
+    Repeat(Operand, Const),
+
     /// Transmutes a `*mut u8` into shallow-initialized `Box<T>`.
     ///
     /// This is different from a normal transmute because dataflow analysis will treat the box as
@@ -229,28 +334,205 @@ pub enum Rvalue {
     Use(Operand),
 }
 
+#[derive(Clone, Debug)]
+pub enum AggregateKind {
+    /// An array, with the type of its elements.
+    Array(Ty),
+    Tuple,
+    /// A struct, enum variant or union. For unions, the index of the active field is given.
+    Adt(AdtDef, VariantIdx, GenericArgs, Option<FieldIdx>),
+    Closure(ClosureDef, GenericArgs),
+    Generator(GeneratorDef, GenericArgs, Movability),
+}
+
 #[derive(Clone, Debug)]
 pub enum Operand {
     Copy(Place),
     Move(Place),
-    Constant(String),
+    Constant(Constant),
+}
+
+impl Operand {
+    /// Compute the type of this operand.
+    ///
+    /// `locals` are the local declarations of the body this operand belongs to.
+    pub fn ty(&self, locals: &[LocalDecl]) -> Ty {
+        match self {
+            Operand::Copy(place) | Operand::Move(place) => place.ty(locals),
+            Operand::Constant(constant) => constant.literal.ty,
+        }
+    }
+
+    /// Determine the function that is called if this operand is the `func` of a
+    /// [`Terminator::Call`].
+    ///
+    /// `locals` are the local declarations of the body this operand belongs to.
+    pub fn callee(&self, locals: &[LocalDecl]) -> Callee {
+        let ty = self.ty(locals);
+        match ty.kind() {
+            TyKind::RigidTy(RigidTy::FnDef(def, args)) => Callee::Direct(def, args),
+            _ => Callee::Indirect(ty),
+        }
+    }
 }
 
+/// The function called by a [`Terminator::Call`].
 #[derive(Clone, Debug)]
-pub struct Place {
-    pub local: usize,
-    pub projection: String,
+pub enum Callee {
+    /// A call to a function item that is known statically, together with its generic
+    /// arguments. The arguments may still refer to generic parameters of the caller.
+    ///
+    /// Calls to trait methods refer to the method of the trait, use [`Instance`] to resolve
+    /// the implementation.
+    ///
+    /// [`Instance`]: crate::stable_mir::mir::mono::Instance
+    Direct(FnDef, GenericArgs),
+    /// A call through a value of the given type, such as a function pointer, or a closure or
+    /// trait object that is called through one of the `Fn` traits.
+    Indirect(Ty),
 }
 
-type FieldIdx = usize;
+#[derive(Clone, Debug)]
+pub struct Constant {
+    pub span: Span,
+    pub literal: Const,
+}
 
 #[derive(Clone, Debug)]
-pub struct SwitchTarget {
-    pub value: u128,
-    pub target: usize,
+pub struct Place {
+    pub local: Local,
+    pub projection: Vec<ProjectionElem>,
+}
+
+impl Place {
+    /// Compute the type of this place.
+    ///
+    /// `locals` are the local declarations of the body this place belongs to.
+    pub fn ty(&self, locals: &[LocalDecl]) -> Ty {
+        self.projection.iter().fold(locals[self.local].ty, |ty, elem| elem.ty(ty))
+    }
 }
 
 #[derive(Clone, Debug)]
+pub enum ProjectionElem {
+    /// Dereference a reference, raw pointer or `Box`.
+    Deref,
+
+    /// A field of a struct, union, tuple, closure or enum variant, together with the field type.
+    ///
+    /// For enums, this projection is preceded by a `Downcast` to the variant.
+    Field(FieldIdx, Ty),
+
+    /// Index into an array or slice using the value of a local.
+    Index(Local),
+
+    /// Index into an array or slice using a constant offset, as produced by slice patterns.
+    ///
+    /// If `from_end` is `true`, the element is `offset` elements from the end of the sequence.
+    /// `min_length` is the minimum length the sequence is known to have.
+    ConstantIndex { offset: u64, min_length: u64, from_end: bool },
+
+    /// A subslice `[from..to]` of an array or slice, as produced by slice patterns.
+    ///
+    /// If `from_end` is `true`, `to` counts from the end of the sequence instead.
+    Subslice { from: u64, to: u64, from_end: bool },
+
+    /// "Downcast" an enum to one of its variants, so its fields can be accessed.
+    Downcast(VariantIdx),
+
+    /// Like an explicit cast from an opaque type to its hidden type.
+    OpaqueCast(Ty),
+}
+
+impl ProjectionElem {
+    /// Compute the type obtained by applying this projection to a place of type `ty`.
+    pub fn ty(&self, ty: Ty) -> Ty {
+        match self {
+            ProjectionElem::Deref => match ty.builtin_deref(true) {
+                Some(pointee) => pointee.ty,
+                None => panic!("cannot dereference type `{ty:?}`"),
+            },
+            ProjectionElem::Field(_, field_ty) => *field_ty,
+            ProjectionElem::Index(_) | ProjectionElem::ConstantIndex { .. } => match ty.kind() {
+                TyKind::RigidTy(RigidTy::Array(elem, _) | RigidTy::Slice(elem)) => elem,
+                _ => panic!("cannot index into type `{ty:?}`"),
+            },
+            ProjectionElem::Subslice { from, to, from_end } => match ty.kind() {
+                TyKind::RigidTy(RigidTy::Slice(_)) => ty,
+                TyKind::RigidTy(RigidTy::Array(..)) => {
+                    with(|cx| cx.array_subslice_ty(ty, *from, *to, *from_end))
+                }
+                _ => panic!("cannot subslice type `{ty:?}`"),
+            },
+            ProjectionElem::Downcast(_) => ty,
+            ProjectionElem::OpaqueCast(cast_ty) => *cast_ty,
+        }
+    }
+}
+
+pub type Local = usize;
+
+pub type FieldIdx = usize;
+
+pub type VariantIdx = usize;
+
+/// The targets of a `SwitchInt` terminator.
+///
+/// Each branch jumps to its target block if the discriminant is equal to its value. If none
+/// of the branches match, execution continues in the `otherwise` block.
+#[derive(Clone, Debug, Hash)]
+pub struct SwitchTargets {
+    branches: Vec<(u128, usize)>,
+    otherwise: usize,
+}
+
+impl SwitchTargets {
+    /// Create switch targets from a list of `(value, target)` branches and a fallback target.
+    pub fn new(branches: impl IntoIterator<Item = (u128, usize)>, otherwise: usize) -> Self {
+        SwitchTargets { branches: branches.into_iter().collect(), otherwise }
+    }
+
+    /// Create switch targets that jump to `then` if the discriminant is `value`, and to
+    /// `else_` otherwise.
+    pub fn static_if(value: u128, then: usize, else_: usize) -> Self {
+        SwitchTargets { branches: vec![(value, then)], otherwise: else_ }
+    }
+
+    /// The `(value, target)` pairs of this switch, excluding the `otherwise` target.
+    pub fn branches(&self) -> impl Iterator<Item = (u128, usize)> + '_ {
+        self.branches.iter().copied()
+    }
+
+    /// The target to jump to if none of the branches match.
+    pub fn otherwise(&self) -> usize {
+        self.otherwise
+    }
+
+    /// The target to jump to if the discriminant is equal to `value`.
+    pub fn target_for_value(&self, value: u128) -> usize {
+        self.branches
+            .iter()
+            .find_map(|&(branch_value, target)| (branch_value == value).then_some(target))
+            .unwrap_or(self.otherwise)
+    }
+
+    /// All possible targets, including the `otherwise` target. This may contain duplicates.
+    pub fn all_targets(&self) -> Vec<usize> {
+        self.branches.iter().map(|&(_, target)| target).chain([self.otherwise]).collect()
+    }
+
+    /// The number of branches, excluding the `otherwise` target.
+    pub fn len(&self) -> usize {
+        self.branches.len()
+    }
+
+    /// Whether this switch only has the `otherwise` target.
+    pub fn is_empty(&self) -> bool {
+        self.branches.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Hash)]
 pub enum BorrowKind {
     /// Data must be immutable and is aliasable.
     Shared,
@@ -267,26 +549,26 @@ pub enum BorrowKind {
     },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum MutBorrowKind {
     Default,
     TwoPhaseBorrow,
     ClosureCapture,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum Mutability {
     Not,
     Mut,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum Safety {
     Unsafe,
     Normal,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum PointerCoercion {
     /// Go from a fn-item type to a fn-pointer type.
     ReifyFnPointer,
@@ -313,7 +595,7 @@ pub enum PointerCoercion {
     Unsize,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum CastKind {
     PointerExposeAddress,
     PointerFromExposedAddress,
@@ -328,7 +610,7 @@ pub enum CastKind {
     Transmute,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Hash)]
 pub enum NullOp {
     /// Returns the size of a value of that type.
     SizeOf,
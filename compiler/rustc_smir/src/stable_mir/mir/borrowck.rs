@@ -0,0 +1,60 @@
+//! The input facts of the Polonius borrow checker.
+//!
+//! The facts describe the body as it is borrow checked, before any optimizations, so the
+//! points of the facts don't necessarily correspond to the statements of
+//! [`CrateItem::body`](crate::stable_mir::CrateItem::body). Computing the facts has to be
+//! enabled with [`provide_borrowck_facts`](crate::rustc_internal::provide_borrowck_facts).
+
+use crate::stable_mir::mir::Local;
+
+/// A region variable of the borrow checker.
+pub type Origin = usize;
+
+/// The index of a borrow expression of the body.
+pub type Loan = usize;
+
+/// The index of a place that can be moved from, see `child_path` and `path_is_var`.
+pub type MovePath = usize;
+
+/// A point of the body as seen by the borrow checker.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Point {
+    pub block: usize,
+    /// The index of the statement in `block`, or the number of statements of the block for its
+    /// terminator.
+    pub statement: usize,
+    pub kind: PointKind,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PointKind {
+    /// The point just before the statement starts.
+    Start,
+    /// The point just before the statement takes effect, e.g., for an assignment `a = b`, the
+    /// point at which `b` has been evaluated but isn't written into `a` yet.
+    Mid,
+}
+
+/// The facts that are given to Polonius for a body. See the Polonius book for the meaning of
+/// each relation.
+#[derive(Clone, Debug, Default)]
+pub struct BorrowckFacts {
+    pub loan_issued_at: Vec<(Origin, Loan, Point)>,
+    pub universal_region: Vec<Origin>,
+    pub cfg_edge: Vec<(Point, Point)>,
+    pub loan_killed_at: Vec<(Loan, Point)>,
+    pub subset_base: Vec<(Origin, Origin, Point)>,
+    pub loan_invalidated_at: Vec<(Point, Loan)>,
+    pub var_used_at: Vec<(Local, Point)>,
+    pub var_defined_at: Vec<(Local, Point)>,
+    pub var_dropped_at: Vec<(Local, Point)>,
+    pub use_of_var_derefs_origin: Vec<(Local, Origin)>,
+    pub drop_of_var_derefs_origin: Vec<(Local, Origin)>,
+    pub child_path: Vec<(MovePath, MovePath)>,
+    pub path_is_var: Vec<(MovePath, Local)>,
+    pub path_assigned_at_base: Vec<(MovePath, Point)>,
+    pub path_moved_at_base: Vec<(MovePath, Point)>,
+    pub path_accessed_at_base: Vec<(MovePath, Point)>,
+    pub known_placeholder_subset: Vec<(Origin, Origin)>,
+    pub placeholder: Vec<(Origin, Loan)>,
+}
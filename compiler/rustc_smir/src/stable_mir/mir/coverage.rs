@@ -0,0 +1,58 @@
+//! The source code coverage instrumentation of functions, as inserted by `-C instrument-coverage`.
+
+/// The coverage instrumentation of a function.
+#[derive(Clone, Debug)]
+pub struct CoverageInfo {
+    /// The number of physical counters incremented by the function.
+    pub num_counters: u32,
+    /// The number of expressions used to compute counts from other counts.
+    pub num_expressions: u32,
+    /// The expressions used by `mappings`, or by other expressions.
+    pub expressions: Vec<CoverageExpression>,
+    /// The code regions whose execution counts are tracked, in no particular order.
+    pub mappings: Vec<CoverageMapping>,
+}
+
+/// An expression that computes a count from two other counts.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CoverageExpression {
+    pub id: u32,
+    pub lhs: CoverageTerm,
+    pub op: CoverageOp,
+    pub rhs: CoverageTerm,
+}
+
+/// A count, either read from a physical counter or computed by an expression.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CoverageTerm {
+    /// A count that is always zero.
+    Zero,
+    /// The count of the counter with the given ID.
+    Counter(u32),
+    /// The count computed by the expression with the given ID.
+    Expression(u32),
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CoverageOp {
+    Add,
+    Subtract,
+}
+
+/// A code region together with the count that tracks how often it runs.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CoverageMapping {
+    /// The count of the region, or `None` if the region is unreachable.
+    pub term: Option<CoverageTerm>,
+    pub region: CodeRegion,
+}
+
+/// A region of source code. Lines and columns are 1-based, and the end column is exclusive.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CodeRegion {
+    pub file_name: String,
+    pub start_line: u32,
+    pub start_col: u32,
+    pub end_line: u32,
+    pub end_col: u32,
+}
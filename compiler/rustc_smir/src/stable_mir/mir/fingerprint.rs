@@ -0,0 +1,342 @@
+use std::hash::Hash;
+
+use rustc_data_structures::stable_hasher::StableHasher;
+
+use crate::stable_mir::mir::{
+    AggregateKind, AssertMessage, Body, InlineAsmOperand, LocalDecl, NonDivergingIntrinsic,
+    Operand, Place, ProjectionElem, Rvalue, Statement, Terminator,
+};
+use crate::stable_mir::ty::{Const, ConstantKind, GenericArgKind, GenericArgs, Ty};
+use crate::stable_mir::{with, CrateItem};
+
+impl Body {
+    /// Compute a fingerprint of this body.
+    ///
+    /// The result only depends on the contents of the body, and not on the order in which types
+    /// and items were interned. Thus, fingerprints of the same function computed in different
+    /// compilation sessions of the same compiler can be compared to detect whether the function
+    /// has changed.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        self.locals.stable_hash(&mut hasher);
+        self.blocks.len().hash(&mut hasher);
+        for block in &self.blocks {
+            block.statements.stable_hash(&mut hasher);
+            block.terminator.stable_hash(&mut hasher);
+        }
+        hasher.finalize().0
+    }
+}
+
+/// Like [`Hash`], but types and items are hashed independently of their interning order.
+trait StableHash {
+    fn stable_hash(&self, hasher: &mut StableHasher);
+}
+
+impl<T: StableHash> StableHash for Vec<T> {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        self.len().hash(hasher);
+        for elem in self {
+            elem.stable_hash(hasher);
+        }
+    }
+}
+
+impl<T: StableHash> StableHash for Option<T> {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        self.is_some().hash(hasher);
+        if let Some(value) = self {
+            value.stable_hash(hasher);
+        }
+    }
+}
+
+impl StableHash for Ty {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        with(|cx| cx.ty_stable_hash(*self)).hash(hasher);
+    }
+}
+
+impl StableHash for Const {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        self.ty.stable_hash(hasher);
+        std::mem::discriminant(&self.kind).hash(hasher);
+        match &self.kind {
+            ConstantKind::Unevaluated(uv) => {
+                CrateItem(uv.def.0).stable_hash(hasher);
+                uv.args.stable_hash(hasher);
+                uv.promoted.hash(hasher);
+            }
+            ConstantKind::Param(param) => param.index.hash(hasher),
+            // The rendering of a value may contain `AllocId`s, which differ between sessions.
+            ConstantKind::Value | ConstantKind::Other => {
+                with(|cx| cx.const_stable_hash(self.id)).hash(hasher)
+            }
+        }
+    }
+}
+
+impl StableHash for GenericArgs {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        self.0.len().hash(hasher);
+        for arg in &self.0 {
+            std::mem::discriminant(arg).hash(hasher);
+            match arg {
+                GenericArgKind::Lifetime(_) => {}
+                GenericArgKind::Type(ty) => ty.stable_hash(hasher),
+                GenericArgKind::Const(constant) => constant.stable_hash(hasher),
+            }
+        }
+    }
+}
+
+impl StableHash for CrateItem {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        with(|cx| cx.item_stable_hash(self)).hash(hasher);
+    }
+}
+
+impl StableHash for LocalDecl {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        // Spans are left out on purpose, moving code around doesn't change its behavior.
+        self.ty.stable_hash(hasher);
+        self.mutability.hash(hasher);
+    }
+}
+
+impl StableHash for Place {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        self.local.hash(hasher);
+        self.projection.stable_hash(hasher);
+    }
+}
+
+impl StableHash for ProjectionElem {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        std::mem::discriminant(self).hash(hasher);
+        match self {
+            ProjectionElem::Deref => {}
+            ProjectionElem::Field(idx, ty) => {
+                idx.hash(hasher);
+                ty.stable_hash(hasher);
+            }
+            ProjectionElem::Index(local) => local.hash(hasher),
+            ProjectionElem::ConstantIndex { offset, min_length, from_end } => {
+                offset.hash(hasher);
+                min_length.hash(hasher);
+                from_end.hash(hasher);
+            }
+            ProjectionElem::Subslice { from, to, from_end } => {
+                from.hash(hasher);
+                to.hash(hasher);
+                from_end.hash(hasher);
+            }
+            ProjectionElem::Downcast(idx) => idx.hash(hasher),
+            ProjectionElem::OpaqueCast(ty) => ty.stable_hash(hasher),
+        }
+    }
+}
+
+impl StableHash for Operand {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        std::mem::discriminant(self).hash(hasher);
+        match self {
+            Operand::Copy(place) | Operand::Move(place) => place.stable_hash(hasher),
+            Operand::Constant(constant) => constant.literal.stable_hash(hasher),
+        }
+    }
+}
+
+impl StableHash for Statement {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        std::mem::discriminant(self).hash(hasher);
+        match self {
+            Statement::Assign(place, rvalue) => {
+                place.stable_hash(hasher);
+                rvalue.stable_hash(hasher);
+            }
+            Statement::SetDiscriminant { place, variant_index } => {
+                place.stable_hash(hasher);
+                variant_index.hash(hasher);
+            }
+            Statement::Deinit(place) | Statement::Retag(place) => place.stable_hash(hasher),
+            Statement::StorageLive(local) | Statement::StorageDead(local) => local.hash(hasher),
+            Statement::Intrinsic(intrinsic) => {
+                std::mem::discriminant(intrinsic).hash(hasher);
+                match intrinsic {
+                    NonDivergingIntrinsic::Assume(operand) => operand.stable_hash(hasher),
+                    NonDivergingIntrinsic::CopyNonOverlapping { src, dst, count } => {
+                        src.stable_hash(hasher);
+                        dst.stable_hash(hasher);
+                        count.stable_hash(hasher);
+                    }
+                }
+            }
+            Statement::Nop => {}
+        }
+    }
+}
+
+impl StableHash for Rvalue {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        std::mem::discriminant(self).hash(hasher);
+        match self {
+            Rvalue::AddressOf(mutability, place) => {
+                mutability.hash(hasher);
+                place.stable_hash(hasher);
+            }
+            Rvalue::Aggregate(kind, operands) => {
+                kind.stable_hash(hasher);
+                operands.stable_hash(hasher);
+            }
+            Rvalue::BinaryOp(bin_op, lhs, rhs) | Rvalue::CheckedBinaryOp(bin_op, lhs, rhs) => {
+                bin_op.hash(hasher);
+                lhs.stable_hash(hasher);
+                rhs.stable_hash(hasher);
+            }
+            Rvalue::Cast(kind, operand, ty) => {
+                kind.hash(hasher);
+                operand.stable_hash(hasher);
+                ty.stable_hash(hasher);
+            }
+            Rvalue::CopyForDeref(place) | Rvalue::Discriminant(place) | Rvalue::Len(place) => {
+                place.stable_hash(hasher);
+            }
+            Rvalue::NullaryOp(null_op, ty) => {
+                null_op.hash(hasher);
+                ty.stable_hash(hasher);
+            }
+            Rvalue::Ref(region, kind, place) => {
+                region.to_string().hash(hasher);
+                kind.hash(hasher);
+                place.stable_hash(hasher);
+            }
+            Rvalue::Repeat(operand, len) => {
+                operand.stable_hash(hasher);
+                len.stable_hash(hasher);
+            }
+            Rvalue::ShallowInitBox(operand, ty) => {
+                operand.stable_hash(hasher);
+                ty.stable_hash(hasher);
+            }
+            Rvalue::ThreadLocalRef(item) => item.stable_hash(hasher),
+            Rvalue::UnaryOp(un_op, operand) => {
+                un_op.hash(hasher);
+                operand.stable_hash(hasher);
+            }
+            Rvalue::Use(operand) => operand.stable_hash(hasher),
+        }
+    }
+}
+
+impl StableHash for AggregateKind {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        std::mem::discriminant(self).hash(hasher);
+        match self {
+            AggregateKind::Array(ty) => ty.stable_hash(hasher),
+            AggregateKind::Tuple => {}
+            AggregateKind::Adt(def, variant_index, args, active_field) => {
+                CrateItem(def.0).stable_hash(hasher);
+                variant_index.hash(hasher);
+                args.stable_hash(hasher);
+                active_field.hash(hasher);
+            }
+            AggregateKind::Closure(def, args) => {
+                CrateItem(def.0).stable_hash(hasher);
+                args.stable_hash(hasher);
+            }
+            AggregateKind::Generator(def, args, movability) => {
+                CrateItem(def.0).stable_hash(hasher);
+                args.stable_hash(hasher);
+                std::mem::discriminant(movability).hash(hasher);
+            }
+        }
+    }
+}
+
+impl StableHash for Terminator {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        std::mem::discriminant(self).hash(hasher);
+        match self {
+            Terminator::Goto { target } => target.hash(hasher),
+            Terminator::SwitchInt { discr, targets } => {
+                discr.stable_hash(hasher);
+                targets.hash(hasher);
+            }
+            Terminator::Resume
+            | Terminator::Return
+            | Terminator::Unreachable
+            | Terminator::GeneratorDrop => {}
+            Terminator::Abort(reason) => reason.hash(hasher),
+            Terminator::Drop { place, target, unwind } => {
+                place.stable_hash(hasher);
+                target.hash(hasher);
+                unwind.hash(hasher);
+            }
+            Terminator::Call { func, args, destination, target, unwind } => {
+                func.stable_hash(hasher);
+                args.stable_hash(hasher);
+                destination.stable_hash(hasher);
+                target.hash(hasher);
+                unwind.hash(hasher);
+            }
+            Terminator::Assert { cond, expected, msg, target, unwind } => {
+                cond.stable_hash(hasher);
+                expected.hash(hasher);
+                msg.stable_hash(hasher);
+                target.hash(hasher);
+                unwind.hash(hasher);
+            }
+            Terminator::InlineAsm {
+                template,
+                operands,
+                options,
+                line_spans,
+                destination,
+                unwind,
+            } => {
+                template.hash(hasher);
+                operands.stable_hash(hasher);
+                options.hash(hasher);
+                line_spans.hash(hasher);
+                destination.hash(hasher);
+                unwind.hash(hasher);
+            }
+        }
+    }
+}
+
+impl StableHash for InlineAsmOperand {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        self.in_value.stable_hash(hasher);
+        self.out_place.stable_hash(hasher);
+        self.raw_rpr.hash(hasher);
+    }
+}
+
+impl StableHash for AssertMessage {
+    fn stable_hash(&self, hasher: &mut StableHasher) {
+        std::mem::discriminant(self).hash(hasher);
+        match self {
+            AssertMessage::BoundsCheck { len, index } => {
+                len.stable_hash(hasher);
+                index.stable_hash(hasher);
+            }
+            AssertMessage::Overflow(bin_op, lhs, rhs) => {
+                bin_op.hash(hasher);
+                lhs.stable_hash(hasher);
+                rhs.stable_hash(hasher);
+            }
+            AssertMessage::OverflowNeg(op)
+            | AssertMessage::DivisionByZero(op)
+            | AssertMessage::RemainderByZero(op) => op.stable_hash(hasher),
+            AssertMessage::ResumedAfterReturn(kind) | AssertMessage::ResumedAfterPanic(kind) => {
+                kind.hash(hasher)
+            }
+            AssertMessage::MisalignedPointerDereference { required, found } => {
+                required.stable_hash(hasher);
+                found.stable_hash(hasher);
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::rustc_internal::Opaque;
+use crate::stable_mir::mir::Body;
+use crate::stable_mir::ty::{ClosureDef, ClosureKind, FnDef, GenericArgs, StaticDef, Ty};
+use crate::stable_mir::with;
+
+/// A function or a compiler generated shim, together with all of its generic arguments.
+///
+/// This is what actually runs when a function is called, which is not necessarily the body of a
+/// user-defined item, e.g., the drop glue of a type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instance {
+    /// An ID used to get the instance definition from the compiler.
+    pub(crate) def: InstanceDef,
+}
+
+/// The identifier of an instance in the current compilation session.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InstanceDef(pub(crate) usize);
+
+impl Instance {
+    /// Resolve the instance that runs when the given function is called with the given generic
+    /// arguments.
+    ///
+    /// For trait methods, this is the method of the implementation selected by the arguments.
+    /// Returns `None` if the function can't be resolved yet, because the generic arguments are
+    /// still too generic.
+    pub fn resolve(def: &FnDef, args: &GenericArgs) -> Option<Instance> {
+        with(|cx| cx.resolve_instance(def, args))
+    }
+
+    /// Resolve the drop glue of a type, i.e., the `drop_in_place::<T>` shim that runs when a
+    /// value of type `ty` is dropped.
+    pub fn resolve_drop_in_place(ty: Ty) -> Instance {
+        with(|cx| cx.resolve_drop_in_place(ty))
+    }
+
+    /// Resolve the instance that is called through a function pointer created from the given
+    /// function, e.g., by a [`ReifyFnPointer`] cast.
+    ///
+    /// This is not always the function itself, since some functions, such as virtual methods or
+    /// functions with `#[track_caller]`, are called through a reify shim. Returns `None` if the
+    /// function can't be resolved yet, because the generic arguments are still too generic.
+    ///
+    /// [`ReifyFnPointer`]: crate::stable_mir::mir::PointerCoercion::ReifyFnPointer
+    pub fn resolve_for_fn_ptr(def: &FnDef, args: &GenericArgs) -> Option<Instance> {
+        with(|cx| cx.resolve_for_fn_ptr(def, args))
+    }
+
+    /// Resolve the instance that runs when a closure is called through the trait given by `kind`.
+    ///
+    /// This is the closure body itself, unless a closure that implements `Fn` or `FnMut` is
+    /// called through `FnOnce`, in which case this is the shim that adapts the closure body to
+    /// take `self` by value. Returns `None` if the closure can't be resolved yet.
+    pub fn resolve_closure(
+        def: &ClosureDef,
+        args: &GenericArgs,
+        kind: ClosureKind,
+    ) -> Option<Instance> {
+        with(|cx| cx.resolve_closure(def, args, kind))
+    }
+
+    /// The generic arguments of this instance.
+    pub fn args(&self) -> GenericArgs {
+        with(|cx| cx.instance_args(self.def))
+    }
+
+    /// The type of this instance, which is a function definition type.
+    pub fn ty(&self) -> Ty {
+        with(|cx| cx.instance_ty(self.def))
+    }
+
+    /// The symbol name of this instance.
+    pub fn mangled_name(&self) -> String {
+        with(|cx| cx.instance_mangled_name(self.def))
+    }
+
+    /// What kind of function this instance is, e.g., a user-defined function or a shim.
+    pub fn kind(&self) -> InstanceKind {
+        with(|cx| cx.instance_kind(self.def))
+    }
+
+    /// The body of this instance, with its generic arguments instantiated.
+    ///
+    /// For shims, this is the body generated by the compiler, e.g., for drop glue the body of the
+    /// `drop_in_place::<T>` shim, which drops the fields or elements of `T` in order. Returns
+    /// `None` for intrinsics and virtual calls, which have no body, and for instances without a
+    /// body available, such as functions of other crates that aren't generic or inlinable.
+    pub fn body(&self) -> Option<Body> {
+        with(|cx| cx.instance_body(self.def))
+    }
+
+    /// Whether this instance is drop glue that does nothing, because the dropped type doesn't
+    /// need to be dropped.
+    pub fn is_empty_shim(&self) -> bool {
+        with(|cx| cx.instance_is_empty_shim(self.def))
+    }
+}
+
+/// What kind of function an [`Instance`] is.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InstanceKind {
+    /// A user-defined function, closure or generator.
+    Item,
+    /// An intrinsic, which is implemented by the compiler at its call sites and has no body.
+    Intrinsic,
+    /// A method called through a vtable, which has no body. The index is the position of the
+    /// method in the vtable.
+    Virtual { idx: usize },
+    /// A shim that takes the receiver of a method by pointer, so the method can be called on
+    /// unsized values through a vtable.
+    VTableShim,
+    /// A shim used instead of a function when it's reified to a function pointer, e.g., for
+    /// functions with `#[track_caller]`.
+    ReifyShim,
+    /// The implementation of an `Fn` trait method for a function pointer.
+    FnPtrShim,
+    /// The `FnOnce::call_once` adapter of a closure that implements `FnMut`.
+    ClosureOnceShim,
+    /// The accessor of a thread local exported from a dylib.
+    ThreadLocalShim,
+    /// The drop glue of a type, i.e., `drop_in_place::<T>`.
+    DropGlue,
+    /// The built-in `Clone::clone` implementation of a type, such as a closure or a tuple.
+    CloneShim,
+    /// The `FnPtr::addr` implementation of a function pointer type.
+    FnPtrAddrShim,
+}
+
+/// An item that is code generated, see [`collect_mono_items`].
+///
+/// [`collect_mono_items`]: crate::stable_mir::collect_mono_items
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MonoItem {
+    Fn(Instance),
+    Static(StaticDef),
+    /// A `global_asm!` block.
+    GlobalAsm(Opaque),
+}
+
+/// The attributes of an item that affect its symbol and how it's linked, e.g., `#[no_mangle]`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinkageAttrs {
+    /// Whether the item has `#[no_mangle]`, so its symbol is its name.
+    pub no_mangle: bool,
+    /// The symbol given with `#[export_name = "..."]`.
+    pub export_name: Option<String>,
+    /// The section given with `#[link_section = "..."]`.
+    pub link_section: Option<String>,
+    /// The linkage given with `#[linkage = "..."]`.
+    pub linkage: Option<Linkage>,
+}
+
+/// The linkage of a symbol, as understood by LLVM.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Linkage {
+    External,
+    AvailableExternally,
+    LinkOnceAny,
+    LinkOnceODR,
+    WeakAny,
+    WeakODR,
+    Appending,
+    Internal,
+    Private,
+    ExternalWeak,
+    Common,
+}
@@ -0,0 +1,57 @@
+//! The operations of a body that are only allowed in `unsafe` code.
+
+use crate::stable_mir::ty::Span;
+
+/// The unsafe operations of a body, and the `unsafe` blocks they are found in.
+#[derive(Clone, Debug)]
+pub struct UnsafetyInfo {
+    /// The operations that require `unsafe`, in the order they appear in the body.
+    pub operations: Vec<UnsafeOperation>,
+    /// The `unsafe` blocks of the body, including the ones that aren't needed.
+    pub unsafe_blocks: Vec<UnsafeBlock>,
+}
+
+/// An operation that is only allowed in `unsafe` code.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnsafeOperation {
+    pub kind: UnsafeOperationKind,
+    pub span: Span,
+    /// What allows the operation to be performed.
+    pub source: UnsafeSource,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum UnsafeOperationKind {
+    /// A call to an `unsafe fn`, or through an `unsafe` function pointer.
+    CallToUnsafeFunction,
+    /// An `asm!` block.
+    InlineAssembly,
+    /// The dereference of a raw pointer.
+    DerefOfRawPointer,
+    /// A read of, or a borrow of, a union field.
+    AccessToUnionField,
+    /// An access to a `static mut`.
+    UseOfMutableStatic,
+    /// An access to a static declared in an `extern` block.
+    UseOfExternStatic,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum UnsafeSource {
+    /// The operation is inside the `unsafe` block with the given span.
+    Block(Span),
+    /// The operation is directly in the body of an `unsafe fn`.
+    UnsafeFn,
+    /// The operation is in a closure or inline constant, and is allowed by an `unsafe` block or
+    /// function that the closure or constant is defined in.
+    Inherited,
+}
+
+/// An `unsafe { .. }` block.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnsafeBlock {
+    pub span: Span,
+    /// Whether the block contains an operation that requires it. Unneeded blocks are reported
+    /// by the `unused_unsafe` lint.
+    pub used: bool,
+}
@@ -0,0 +1,361 @@
+use std::fmt;
+
+use crate::stable_mir::mir::{
+    AssertMessage, BinOp, Body, NonDivergingIntrinsic, Operand, Place, ProjectionElem, Rvalue,
+    SourceScope, Statement, Terminator, UnwindAction,
+};
+use crate::stable_mir::ty::{RigidTy, Ty, TyKind, UintTy};
+
+/// An invariant that doesn't hold in a body, as found by [`Body::validate`].
+#[derive(Clone, Debug)]
+pub struct ValidationError {
+    /// The block in which the problem was found, if it isn't about the body as a whole.
+    pub block: Option<usize>,
+    /// The statement in which the problem was found. This is `None` for problems found in the
+    /// terminator of `block`.
+    pub statement: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.block, self.statement) {
+            (Some(block), Some(statement)) => write!(f, "bb{block}[{statement}]: ")?,
+            (Some(block), None) => write!(f, "bb{block}[terminator]: ")?,
+            (None, _) => {}
+        }
+        f.write_str(&self.message)
+    }
+}
+
+impl Body {
+    /// Check that this body is well-formed.
+    ///
+    /// This verifies that all jump targets are valid blocks, that all referenced locals and
+    /// scopes are declared, and that the types of assignments are consistent with their
+    /// right-hand side. Type checks are conservative: only types that can't be related through
+    /// subtyping, such as primitive types, are compared.
+    ///
+    /// Bodies converted by the compiler are always expected to be valid, so an error is a bug in
+    /// the conversion. Pass `-Z validate-stable-mir` to run this on every converted body.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut validator = Validator { body: self, errors: vec![], block: None, statement: None };
+        validator.validate();
+        if validator.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(validator.errors)
+        }
+    }
+}
+
+struct Validator<'a> {
+    body: &'a Body,
+    errors: Vec<ValidationError>,
+    block: Option<usize>,
+    statement: Option<usize>,
+}
+
+impl Validator<'_> {
+    fn error(&mut self, message: String) {
+        self.errors.push(ValidationError { block: self.block, statement: self.statement, message });
+    }
+
+    fn validate(&mut self) {
+        if self.body.locals.is_empty() {
+            self.error("body has no return place".to_string());
+        }
+        if self.body.blocks.is_empty() {
+            self.error("body has no basic blocks".to_string());
+        }
+        for (index, scope) in self.body.source_scopes.iter().enumerate() {
+            for parent in [scope.parent_scope, scope.inlined_parent_scope].into_iter().flatten() {
+                if parent >= index {
+                    self.error(format!(
+                        "scope {index} has parent scope {parent} that isn't before it"
+                    ));
+                }
+            }
+        }
+        for (index, block) in self.body.blocks.iter().enumerate() {
+            self.block = Some(index);
+            if block.statement_sources.len() != block.statements.len() {
+                self.error(format!(
+                    "block has {} statements, but {} source infos",
+                    block.statements.len(),
+                    block.statement_sources.len()
+                ));
+            }
+            for (index, statement) in block.statements.iter().enumerate() {
+                self.statement = Some(index);
+                if let Some(source) = block.statement_sources.get(index) {
+                    self.check_scope(source.scope);
+                }
+                self.check_statement(statement);
+            }
+            self.statement = None;
+            self.check_scope(block.terminator_source.scope);
+            self.check_terminator(&block.terminator);
+        }
+    }
+
+    fn check_scope(&mut self, scope: SourceScope) {
+        if scope >= self.body.source_scopes.len() {
+            self.error(format!("use of undeclared scope {scope}"));
+        }
+    }
+
+    fn check_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Assign(place, rvalue) => {
+                let num_errors = self.errors.len();
+                self.check_place(place);
+                self.check_rvalue(rvalue);
+                // Only compare types if the places are valid, since computing their types
+                // would fail otherwise.
+                if self.errors.len() == num_errors {
+                    self.check_assign_types(place, rvalue);
+                }
+            }
+            Statement::SetDiscriminant { place, .. }
+            | Statement::Deinit(place)
+            | Statement::Retag(place) => self.check_place(place),
+            Statement::StorageLive(local) | Statement::StorageDead(local) => {
+                self.check_local(*local)
+            }
+            Statement::Intrinsic(NonDivergingIntrinsic::Assume(operand)) => {
+                self.check_operand(operand)
+            }
+            Statement::Intrinsic(NonDivergingIntrinsic::CopyNonOverlapping { src, dst, count }) => {
+                self.check_operand(src);
+                self.check_operand(dst);
+                self.check_operand(count);
+            }
+            Statement::Nop => {}
+        }
+    }
+
+    fn check_terminator(&mut self, terminator: &Terminator) {
+        match terminator {
+            Terminator::Goto { target } => self.check_target(*target),
+            Terminator::SwitchInt { discr, targets } => {
+                self.check_operand(discr);
+                for target in targets.all_targets() {
+                    self.check_target(target);
+                }
+            }
+            Terminator::Resume
+            | Terminator::Abort(_)
+            | Terminator::Return
+            | Terminator::Unreachable
+            | Terminator::GeneratorDrop => {}
+            Terminator::Drop { place, target, unwind } => {
+                self.check_place(place);
+                self.check_target(*target);
+                self.check_unwind(unwind);
+            }
+            Terminator::Call { func, args, destination, target, unwind } => {
+                self.check_operand(func);
+                for arg in args {
+                    self.check_operand(arg);
+                }
+                self.check_place(destination);
+                if let Some(target) = target {
+                    self.check_target(*target);
+                }
+                self.check_unwind(unwind);
+            }
+            Terminator::Assert { cond, msg, target, unwind, .. } => {
+                self.check_operand(cond);
+                self.check_assert_message(msg);
+                self.check_target(*target);
+                self.check_unwind(unwind);
+            }
+            Terminator::InlineAsm { operands, destination, unwind, .. } => {
+                for operand in operands {
+                    if let Some(in_value) = &operand.in_value {
+                        self.check_operand(in_value);
+                    }
+                    if let Some(out_place) = &operand.out_place {
+                        self.check_place(out_place);
+                    }
+                }
+                if let Some(destination) = destination {
+                    self.check_target(*destination);
+                }
+                self.check_unwind(unwind);
+            }
+        }
+    }
+
+    fn check_assert_message(&mut self, msg: &AssertMessage) {
+        match msg {
+            AssertMessage::BoundsCheck { len, index } => {
+                self.check_operand(len);
+                self.check_operand(index);
+            }
+            AssertMessage::Overflow(_, lhs, rhs) => {
+                self.check_operand(lhs);
+                self.check_operand(rhs);
+            }
+            AssertMessage::OverflowNeg(operand)
+            | AssertMessage::DivisionByZero(operand)
+            | AssertMessage::RemainderByZero(operand) => self.check_operand(operand),
+            AssertMessage::ResumedAfterReturn(_) | AssertMessage::ResumedAfterPanic(_) => {}
+            AssertMessage::MisalignedPointerDereference { required, found } => {
+                self.check_operand(required);
+                self.check_operand(found);
+            }
+        }
+    }
+
+    fn check_target(&mut self, target: usize) {
+        if target >= self.body.blocks.len() {
+            self.error(format!("jump to out of range block bb{target}"));
+        }
+    }
+
+    fn check_unwind(&mut self, unwind: &UnwindAction) {
+        if let UnwindAction::Cleanup(target) = unwind {
+            self.check_target(*target);
+        }
+    }
+
+    fn check_rvalue(&mut self, rvalue: &Rvalue) {
+        match rvalue {
+            Rvalue::AddressOf(_, place)
+            | Rvalue::CopyForDeref(place)
+            | Rvalue::Discriminant(place)
+            | Rvalue::Len(place)
+            | Rvalue::Ref(_, _, place) => self.check_place(place),
+            Rvalue::BinaryOp(_, lhs, rhs) | Rvalue::CheckedBinaryOp(_, lhs, rhs) => {
+                self.check_operand(lhs);
+                self.check_operand(rhs);
+            }
+            Rvalue::Aggregate(_, operands) => {
+                for operand in operands {
+                    self.check_operand(operand);
+                }
+            }
+            Rvalue::Cast(_, operand, _)
+            | Rvalue::Repeat(operand, _)
+            | Rvalue::ShallowInitBox(operand, _)
+            | Rvalue::UnaryOp(_, operand)
+            | Rvalue::Use(operand) => self.check_operand(operand),
+            Rvalue::NullaryOp(..) | Rvalue::ThreadLocalRef(_) => {}
+        }
+    }
+
+    fn check_operand(&mut self, operand: &Operand) {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) => self.check_place(place),
+            Operand::Constant(_) => {}
+        }
+    }
+
+    fn check_place(&mut self, place: &Place) {
+        self.check_local(place.local);
+        for elem in &place.projection {
+            if let ProjectionElem::Index(local) = elem {
+                self.check_local(*local);
+            }
+        }
+    }
+
+    fn check_local(&mut self, local: usize) {
+        if local >= self.body.locals.len() {
+            self.error(format!("use of undeclared local _{local}"));
+        }
+    }
+
+    fn check_assign_types(&mut self, place: &Place, rvalue: &Rvalue) {
+        let place_ty = place.ty(&self.body.locals);
+        match rvalue {
+            Rvalue::Use(operand) => {
+                let operand_ty = self.operand_ty(operand);
+                if is_primitive(place_ty) || is_primitive(operand_ty) {
+                    self.expect_same_ty(place_ty, operand_ty);
+                }
+            }
+            Rvalue::UnaryOp(_, operand) => {
+                let operand_ty = self.operand_ty(operand);
+                if is_primitive(operand_ty) {
+                    self.expect_same_ty(place_ty, operand_ty);
+                }
+            }
+            Rvalue::BinaryOp(op, lhs, _) => {
+                if is_comparison(op) {
+                    self.expect_ty(place_ty, "bool", |kind| matches!(kind, RigidTy::Bool));
+                } else if !matches!(op, BinOp::Offset) {
+                    let lhs_ty = self.operand_ty(lhs);
+                    if is_primitive(lhs_ty) {
+                        self.expect_same_ty(place_ty, lhs_ty);
+                    }
+                }
+            }
+            Rvalue::CheckedBinaryOp(..) => {
+                self.expect_ty(place_ty, "a `(T, bool)` tuple", |kind| match kind {
+                    RigidTy::Tuple(tys) if tys.len() == 2 => {
+                        matches!(tys[1].kind(), TyKind::RigidTy(RigidTy::Bool))
+                    }
+                    _ => false,
+                });
+            }
+            Rvalue::Len(_) | Rvalue::NullaryOp(..) => self
+                .expect_ty(place_ty, "usize", |kind| matches!(kind, RigidTy::Uint(UintTy::Usize))),
+            Rvalue::Repeat(..) => {
+                self.expect_ty(place_ty, "an array", |kind| matches!(kind, RigidTy::Array(..)))
+            }
+            Rvalue::Ref(..) => {
+                self.expect_ty(place_ty, "a reference", |kind| matches!(kind, RigidTy::Ref(..)))
+            }
+            Rvalue::AddressOf(..) => self
+                .expect_ty(place_ty, "a raw pointer", |kind| matches!(kind, RigidTy::RawPtr(..))),
+            Rvalue::Discriminant(_) => self.expect_ty(place_ty, "an integer", |kind| {
+                matches!(kind, RigidTy::Int(_) | RigidTy::Uint(_))
+            }),
+            Rvalue::Aggregate(..)
+            | Rvalue::Cast(..)
+            | Rvalue::CopyForDeref(_)
+            | Rvalue::ShallowInitBox(..)
+            | Rvalue::ThreadLocalRef(_) => {}
+        }
+    }
+
+    fn operand_ty(&self, operand: &Operand) -> Ty {
+        operand.ty(&self.body.locals)
+    }
+
+    fn expect_same_ty(&mut self, place_ty: Ty, rvalue_ty: Ty) {
+        if place_ty != rvalue_ty {
+            self.error(format!(
+                "assignment of a value of type `{rvalue_ty}` to a place of type `{place_ty}`"
+            ));
+        }
+    }
+
+    fn expect_ty(&mut self, place_ty: Ty, expected: &str, is_expected: impl Fn(RigidTy) -> bool) {
+        let valid = match place_ty.kind() {
+            TyKind::RigidTy(kind) => is_expected(kind),
+            // Generic types may stand for anything.
+            TyKind::Alias(..) | TyKind::Param(_) => true,
+        };
+        if !valid {
+            self.error(format!("expected place of type {expected}, found `{place_ty}`"));
+        }
+    }
+}
+
+/// Whether `ty` is a type that is only equal to itself, even through subtyping.
+fn is_primitive(ty: Ty) -> bool {
+    matches!(
+        ty.kind(),
+        TyKind::RigidTy(
+            RigidTy::Bool | RigidTy::Char | RigidTy::Int(_) | RigidTy::Uint(_) | RigidTy::Float(_)
+        )
+    )
+}
+
+fn is_comparison(op: &BinOp) -> bool {
+    matches!(op, BinOp::Eq | BinOp::Lt | BinOp::Le | BinOp::Ne | BinOp::Ge | BinOp::Gt)
+}
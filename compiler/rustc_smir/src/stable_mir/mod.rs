@@ -15,14 +15,28 @@
 
 use crate::rustc_smir::Tables;
 
-use self::ty::{Ty, TyKind};
+use self::mir::alloc::{AllocId, Allocation, GlobalAlloc};
+use self::mir::mono::{Instance, InstanceDef, InstanceKind, LinkageAttrs, MonoItem};
+use self::target::MachineInfo;
+use self::ty::{
+    AdtDef, CapturedPlace, ClosureDef, ClosureKind, Const, ConstId, Discr, FnDef, GenericArgs,
+    ImplDef, ParamBound, PolyFnSig, Span, StaticDef, TraitRef, Ty, TyKind,
+};
 
 pub mod mir;
+pub mod target;
+pub mod thir;
 pub mod ty;
 
 /// Use String for now but we should replace it.
 pub type Symbol = String;
 
+/// The version of the stable MIR interface that this crate provides.
+///
+/// It is increased whenever the interface changes in a way that may break tools. Use
+/// [`check_compatibility`] to verify that the compiler a tool runs in implements this version.
+pub const CRATE_VERSION: &str = "0.1.0";
+
 /// The number that identifies a crate.
 pub type CrateNum = usize;
 
@@ -40,6 +54,18 @@ pub struct Crate {
     pub is_local: bool,
 }
 
+impl Crate {
+    /// The functions, constants and statics defined in this crate, including associated ones.
+    ///
+    /// For the local crate, these are the items returned by [`all_local_items`]. For other
+    /// crates, the items are found through the crate's modules, traits and the impls of its
+    /// types, so inherent impls of primitive types aren't included. Use
+    /// [`CrateItem::has_body`] to check whether the body of an item is available.
+    pub fn items(&self) -> CrateItems {
+        with(|cx| cx.crate_items(self.id))
+    }
+}
+
 /// Holds information about an item in the crate.
 /// For now, it only stores the item DefId. Use functions inside `rustc_internal` module to
 /// use this item.
@@ -47,9 +73,182 @@ pub struct Crate {
 pub struct CrateItem(pub(crate) DefId);
 
 impl CrateItem {
+    /// The MIR of this item. For constants and statics, this is the MIR that is used to evaluate
+    /// them at compile time.
     pub fn body(&self) -> mir::Body {
         with(|cx| cx.mir_body(self))
     }
+
+    /// Whether the body of this item is available, which is the case for all items of the local
+    /// crate, and for items of other crates that are generic or inlinable.
+    pub fn has_body(&self) -> bool {
+        with(|cx| cx.has_body(self))
+    }
+
+    /// The path of this item, e.g. `core::mem::swap`.
+    ///
+    /// Items of the local crate are printed without the crate name. The result can be given
+    /// back to [`find_item_by_path`].
+    pub fn name(&self) -> String {
+        with(|cx| cx.def_path_str(self.0))
+    }
+
+    /// The span of this item's definition.
+    pub fn span(&self) -> Span {
+        with(|cx| cx.item_span(self))
+    }
+
+    /// The type of this item, e.g. the function definition type for functions, or the type of
+    /// the value for statics and constants.
+    ///
+    /// Generic parameters of the item are left as is.
+    pub fn ty(&self) -> Ty {
+        with(|cx| cx.def_ty(self))
+    }
+
+    /// The coverage instrumentation of this item's body.
+    ///
+    /// Returns `None` if the crate isn't compiled with `-C instrument-coverage`, or if this
+    /// item isn't instrumented.
+    pub fn coverage_info(&self) -> Option<mir::coverage::CoverageInfo> {
+        with(|cx| cx.coverage_info(self))
+    }
+
+    /// The input facts of the Polonius borrow checker for this item's body.
+    ///
+    /// Returns `None` unless the facts were collected while the body was borrow checked, see
+    /// [`provide_borrowck_facts`](crate::rustc_internal::provide_borrowck_facts).
+    pub fn borrowck_facts(&self) -> Option<mir::borrowck::BorrowckFacts> {
+        with(|cx| cx.borrowck_facts(self))
+    }
+
+    /// The THIR of this item's body, which keeps the expressions, patterns and blocks of the
+    /// source that MIR is built from.
+    ///
+    /// This is best-effort: the THIR of a body is discarded once MIR is built from it, so this
+    /// returns `None` if it's called after analysis. Run stable MIR from the `after_expansion`
+    /// callback of the driver to use it. It also returns `None` for items of other crates, items
+    /// without a body, and bodies with type errors.
+    pub fn thir_body(&self) -> Option<thir::ThirBody> {
+        with(|cx| cx.thir_body(self))
+    }
+
+    /// The operations of this item's body that require `unsafe`, and the `unsafe` blocks of
+    /// the body.
+    ///
+    /// Returns `None` for items of other crates. Operations that are introduced by the compiler,
+    /// such as the ones in the desugaring of `async` blocks, aren't included.
+    pub fn unsafety(&self) -> Option<mir::unsafety::UnsafetyInfo> {
+        with(|cx| cx.unsafety(self))
+    }
+
+    /// The visibility of this item, as declared in the source.
+    ///
+    /// Items without a visibility of their own, such as closures and the items of trait impls,
+    /// have the visibility of the item they belong to.
+    pub fn visibility(&self) -> Visibility {
+        with(|cx| cx.visibility(self))
+    }
+
+    /// What kind of definition this item is, e.g. a function or an associated constant.
+    pub fn kind(&self) -> DefKind {
+        with(|cx| cx.def_kind(self.0))
+    }
+
+    /// The bounds that the generic parameter at the given index has to satisfy, including
+    /// implicit `Sized` bounds.
+    ///
+    /// The index counts the generic parameters of the parents of this item first, e.g., the
+    /// parameters of the impl an associated function is defined in. Bounds that don't constrain
+    /// the parameter directly, such as `Vec<T>: Clone`, aren't included.
+    pub fn param_bounds(&self, param_index: u32) -> Vec<ParamBound> {
+        with(|cx| cx.param_bounds(self, param_index))
+    }
+
+    /// The attributes of this item that affect its symbol name and section.
+    ///
+    /// This is empty for items that aren't code generated, such as constants.
+    pub fn linkage_attrs(&self) -> LinkageAttrs {
+        with(|cx| cx.linkage_attrs(self))
+    }
+}
+
+/// The kind of a definition.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DefKind {
+    Mod,
+    Struct,
+    Union,
+    Enum,
+    Variant,
+    Trait,
+    TraitAlias,
+    TyAlias,
+    ForeignTy,
+    AssocTy,
+    TyParam,
+    Fn,
+    Const,
+    ConstParam,
+    Static {
+        mutable: bool,
+    },
+    /// The constructor function or constant of a tuple or unit struct or variant.
+    Ctor(CtorOf, CtorKind),
+    AssocFn,
+    AssocConst,
+    Macro,
+    ExternCrate,
+    Use,
+    ForeignMod,
+    /// A constant expression, such as an array length or a const generic argument.
+    AnonConst,
+    /// An inline `const { .. }` block.
+    InlineConst,
+    OpaqueTy,
+    Field,
+    LifetimeParam,
+    GlobalAsm,
+    Impl {
+        of_trait: bool,
+    },
+    Closure,
+    Generator,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CtorOf {
+    Struct,
+    Variant,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CtorKind {
+    /// The constructor of a tuple struct or variant, which is a function.
+    Fn,
+    /// The constructor of a unit struct or variant, which is a constant.
+    Const,
+}
+
+/// Where an item can be accessed from.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Visibility {
+    /// The item is visible everywhere, e.g. `pub fn`.
+    Public,
+    /// The item is only visible in the given module and its descendants, e.g. `pub(crate) fn`.
+    /// The module path is prefixed with the crate it belongs to, e.g. `crate::foo` or
+    /// `std::io`.
+    Restricted(String),
+    /// The item is only visible in the module where it's defined and its descendants.
+    Private,
+}
+
+/// The severity of a diagnostic emitted by a tool.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DiagnosticLevel {
+    Error,
+    Warning,
+    Note,
 }
 
 /// Return the function where execution starts if the current
@@ -59,6 +258,70 @@ pub fn entry_fn() -> Option<CrateItem> {
     with(|cx| cx.entry_fn())
 }
 
+/// The versions of the compiler a tool is running in.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CompilerVersion {
+    /// The version of the stable MIR interface implemented by the compiler.
+    pub smir_version: String,
+    /// The version of the compiler, e.g. `1.73.0-nightly`.
+    pub rustc_version: String,
+}
+
+/// Check that the compiler implements the version of stable MIR this crate was built with.
+///
+/// Returns the versions of the compiler if they don't match, so tools can report an error
+/// instead of failing in unexpected ways.
+pub fn check_compatibility() -> Result<(), CompilerVersion> {
+    let version = with(|cx| cx.smir_version());
+    if version.smir_version == CRATE_VERSION {
+        Ok(())
+    } else {
+        Err(version)
+    }
+}
+
+/// The options of the current compilation that affect the semantics of the code.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CompilerOptions {
+    pub opt_level: OptLevel,
+    /// Whether `debug_assert!` and `cfg(debug_assertions)` are enabled.
+    pub debug_assertions: bool,
+    /// Whether arithmetic overflow panics instead of wrapping.
+    pub overflow_checks: bool,
+    /// What happens when a panic occurs.
+    pub panic_strategy: PanicStrategy,
+}
+
+/// The optimization level, as given with `-C opt-level`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum OptLevel {
+    /// `-C opt-level=0`.
+    No,
+    /// `-C opt-level=1`.
+    Less,
+    /// `-C opt-level=2`.
+    Default,
+    /// `-C opt-level=3`.
+    Aggressive,
+    /// `-C opt-level=s`.
+    Size,
+    /// `-C opt-level=z`.
+    SizeMin,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PanicStrategy {
+    /// The stack is unwound, running destructors, and the panic can be caught.
+    Unwind,
+    /// The process is aborted.
+    Abort,
+}
+
+/// Get the options of the current compilation, e.g., whether overflow checks are enabled.
+pub fn compiler_options() -> CompilerOptions {
+    with(|cx| cx.compiler_options())
+}
+
 /// Access to the local crate.
 pub fn local_crate() -> Crate {
     with(|cx| cx.local_crate())
@@ -75,15 +338,161 @@ pub fn external_crates() -> Vec<Crate> {
 }
 
 /// Retrieve all items in the local crate that have a MIR associated with them.
+///
+/// Prefer [`local_items`] when only some of the items are needed.
 pub fn all_local_items() -> CrateItems {
-    with(|cx| cx.all_local_items())
+    local_items().collect()
+}
+
+/// Iterate over the items in the local crate that have a MIR associated with them.
+///
+/// Items are only looked up as the iterator advances, so filtering them or stopping early
+/// avoids doing work for items that aren't needed.
+pub fn local_items() -> ItemsIter {
+    ItemsIter { next: 0, len: with(|cx| cx.local_items_len()) }
+}
+
+/// An iterator over the items of the local crate, see [`local_items`].
+#[derive(Clone, Debug)]
+pub struct ItemsIter {
+    next: usize,
+    len: usize,
+}
+
+impl Iterator for ItemsIter {
+    type Item = CrateItem;
+
+    fn next(&mut self) -> Option<CrateItem> {
+        if self.next == self.len {
+            return None;
+        }
+        let item = with(|cx| cx.local_item(self.next));
+        self.next += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.next;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<CrateItem> {
+        self.next = self.len.min(self.next.saturating_add(n));
+        self.next()
+    }
+}
+
+impl ExactSizeIterator for ItemsIter {}
+
+/// Call `f` on each of the given items, possibly from multiple threads.
+///
+/// The items are processed in parallel if the compiler was built with support for it and more
+/// than one thread was requested with `-Z threads`. Otherwise, they're processed in order.
+/// Stable MIR can be used from `f` as usual, e.g. to convert the body of each item.
+pub fn par_for_each_item(items: CrateItems, f: impl Fn(CrateItem) + Sync + Send) {
+    with(|cx| cx.par_for_each_item(items, &f))
+}
+
+/// Retrieve all impl blocks in the local crate, both inherent and of traits.
+pub fn all_local_impls() -> Vec<ImplDef> {
+    with(|cx| cx.all_local_impls())
+}
+
+/// Collect the items that are code generated for the local crate, i.e., every function, shim
+/// and static reachable from the crate's roots, with all generic arguments instantiated.
+///
+/// This includes drop glue, the methods of vtables and closures, which are easy to miss when
+/// following calls manually. Which roots are used depends on the crate type, e.g., only `main`
+/// for binaries that don't export anything. Each item is returned once.
+pub fn collect_mono_items() -> Vec<MonoItem> {
+    with(|cx| cx.collect_mono_items())
+}
+
+/// Find an item given its path, e.g. `core::mem::swap` or `std::vec::Vec::new`.
+///
+/// The path may start with the name of any crate in the crate graph. Otherwise, it is resolved
+/// relative to the root of the local crate. Re-exports and inherent associated items are
+/// followed as well.
+pub fn find_item_by_path(path: &str) -> Option<CrateItem> {
+    with(|cx| cx.find_item_by_path(path))
+}
+
+/// Emit a diagnostic pointing at the given span.
+///
+/// The diagnostic goes through the compiler's own diagnostic machinery, so it is rendered the
+/// same way as any compiler diagnostic, including in JSON output. Emitting an error causes the
+/// compilation to fail.
+pub fn emit_diagnostic(level: DiagnosticLevel, message: &str, span: Span) {
+    with(|cx| cx.emit_diagnostic(level, message, span))
 }
 
 pub trait Context {
-    fn entry_fn(&mut self) -> Option<CrateItem>;
-    /// Retrieve all items of the local crate that have a MIR associated with them.
-    fn all_local_items(&mut self) -> CrateItems;
-    fn mir_body(&mut self, item: &CrateItem) -> mir::Body;
+    /// Get the version of stable MIR implemented by the compiler and of the compiler itself.
+    fn smir_version(&self) -> CompilerVersion;
+    /// Get the options of the current compilation.
+    fn compiler_options(&self) -> CompilerOptions;
+    fn entry_fn(&self) -> Option<CrateItem>;
+    /// The number of items in the local crate that have a MIR associated with them.
+    fn local_items_len(&self) -> usize;
+    /// The item at the given position among the items counted by `local_items_len`.
+    fn local_item(&self, index: usize) -> CrateItem;
+    /// Call a function on each item, possibly in parallel.
+    fn par_for_each_item(&self, items: CrateItems, f: &(dyn Fn(CrateItem) + Sync + Send));
+    fn mir_body(&self, item: &CrateItem) -> mir::Body;
+    /// Whether the MIR of an item is available.
+    fn has_body(&self, item: &CrateItem) -> bool;
+    /// Retrieve the functions, constants and statics of a crate.
+    fn crate_items(&self, krate: CrateNum) -> CrateItems;
+    /// Get the span of an item's definition.
+    fn item_span(&self, item: &CrateItem) -> Span;
+
+    /// Get the type of an item, with its own generic parameters.
+    fn def_ty(&self, item: &CrateItem) -> Ty;
+
+    /// Get the coverage instrumentation of an item's body.
+    fn coverage_info(&self, item: &CrateItem) -> Option<mir::coverage::CoverageInfo>;
+
+    /// Get the borrow checker facts of an item's body, if they were collected.
+    fn borrowck_facts(&self, item: &CrateItem) -> Option<mir::borrowck::BorrowckFacts>;
+
+    /// Get the THIR of an item's body, if it's still available.
+    fn thir_body(&self, item: &CrateItem) -> Option<thir::ThirBody>;
+
+    /// Get the unsafe operations and `unsafe` blocks of an item's body.
+    fn unsafety(&self, item: &CrateItem) -> Option<mir::unsafety::UnsafetyInfo>;
+
+    /// Get the visibility of an item.
+    fn visibility(&self, item: &CrateItem) -> Visibility;
+
+    /// Get the bounds of a generic parameter of an item.
+    fn param_bounds(&self, item: &CrateItem, param_index: u32) -> Vec<ParamBound>;
+
+    /// Get the attributes of an item that affect how it's linked.
+    fn linkage_attrs(&self, item: &CrateItem) -> LinkageAttrs;
+
+    /// Retrieve all impl blocks in the local crate.
+    fn all_local_impls(&self) -> Vec<ImplDef>;
+
+    /// Collect the items that are code generated for the local crate.
+    fn collect_mono_items(&self) -> Vec<MonoItem>;
+
+    /// Get the inherent impls of an ADT.
+    fn adt_inherent_impls(&self, def: &AdtDef) -> Vec<ImplDef>;
+
+    /// Get the self type of an impl.
+    fn impl_self_ty(&self, def: &ImplDef) -> Ty;
+
+    /// Get the trait implemented by an impl, if any.
+    fn impl_trait_ref(&self, def: &ImplDef) -> Option<TraitRef>;
+
+    /// Get the associated functions and constants of an impl.
+    fn impl_items(&self, def: &ImplDef) -> Vec<CrateItem>;
+
+    /// Get the item whose body defines a closure.
+    fn closure_parent_item(&self, def: &ClosureDef) -> CrateItem;
+
+    /// Get the places captured by a closure.
+    fn closure_captures(&self, def: &ClosureDef) -> Vec<CapturedPlace>;
     /// Get information about the local crate.
     fn local_crate(&self) -> Crate;
     /// Retrieve a list of all external crates.
@@ -92,36 +501,137 @@ pub trait Context {
     /// Find a crate with the given name.
     fn find_crate(&self, name: &str) -> Option<Crate>;
 
+    /// Find an item given its path.
+    fn find_item_by_path(&self, path: &str) -> Option<CrateItem>;
+
     /// Obtain the representation of a type.
-    fn ty_kind(&mut self, ty: Ty) -> TyKind;
+    fn ty_kind(&self, ty: Ty) -> TyKind;
+
+    /// Get the signature of a function definition, function pointer or closure type.
+    fn ty_fn_sig(&self, ty: Ty) -> Option<PolyFnSig>;
+
+    /// Create a type from its kind, erasing regions.
+    fn mk_ty(&self, kind: TyKind) -> Ty;
+
+    /// Get the path of a definition, e.g. `std::vec::Vec`.
+    fn def_path_str(&self, def_id: DefId) -> String;
+
+    /// Get the kind of a definition.
+    fn def_kind(&self, def_id: DefId) -> DefKind;
+
+    /// Resolve the hidden type of an opaque type. Returns `None` for any other type.
+    fn resolve_opaque_ty(&self, ty: Ty) -> Option<Ty>;
+
+    /// Instantiate the generic parameters of a type with `args`, and normalize it.
+    fn normalize(&self, ty: Ty, args: &GenericArgs) -> Ty;
+
+    /// Compute a hash of a type that doesn't depend on the current compilation session.
+    fn ty_stable_hash(&self, ty: Ty) -> u64;
+
+    /// Compute a hash of an item that doesn't depend on the current compilation session.
+    fn item_stable_hash(&self, item: &CrateItem) -> u64;
+
+    /// Compute a hash of the type and evaluated value of a constant that doesn't depend on the
+    /// current compilation session.
+    fn const_stable_hash(&self, id: ConstId) -> u64;
+
+    /// Whether the given ADT is `Box`.
+    fn adt_is_box(&self, def: &AdtDef) -> bool;
+
+    /// Compute the type of a subslice of an array, as done by [`mir::ProjectionElem::Subslice`].
+    fn array_subslice_ty(&self, array: Ty, from: u64, to: u64, from_end: bool) -> Ty;
+
+    /// Retrieve the discriminant of every variant of an enum.
+    fn adt_discriminants(&self, def: &AdtDef) -> Vec<Discr>;
+
+    /// Emit a diagnostic through the compiler's diagnostic handler.
+    fn emit_diagnostic(&self, level: DiagnosticLevel, message: &str, span: Span);
+
+    /// Evaluate a constant, or return it unchanged if it can't be evaluated.
+    fn eval_const(&self, id: ConstId) -> Const;
+
+    /// Rebuild a constant with a new type and, if it's unevaluated, new generic arguments.
+    /// Regions are erased.
+    fn rebuild_const(&self, id: ConstId, ty: Ty, args: Option<&GenericArgs>) -> Const;
+
+    /// Read the bytes of a `&[u8]` or `&str` constant.
+    fn const_slice_bytes(&self, id: ConstId) -> Option<Vec<u8>>;
+
+    /// The pointers stored in the value of a constant.
+    fn const_provenance(&self, id: ConstId) -> Vec<(usize, AllocId)>;
+
+    /// Retrieve what an allocation ID refers to.
+    fn global_alloc(&self, id: AllocId) -> GlobalAlloc;
+
+    /// Evaluate the initial value of a static.
+    fn eval_static_initializer(&self, def: &StaticDef) -> Option<Allocation>;
+
+    /// Get the information of the compilation target.
+    fn target_info(&self) -> MachineInfo;
+
+    /// Resolve the instance that runs when a function is called.
+    fn resolve_instance(&self, def: &FnDef, args: &GenericArgs) -> Option<Instance>;
+
+    /// Resolve the drop glue of a type.
+    fn resolve_drop_in_place(&self, ty: Ty) -> Instance;
+
+    /// Resolve the instance used when a function is reified to a function pointer.
+    fn resolve_for_fn_ptr(&self, def: &FnDef, args: &GenericArgs) -> Option<Instance>;
+
+    /// Resolve the instance that runs when a closure is called through the given trait.
+    fn resolve_closure(
+        &self,
+        def: &ClosureDef,
+        args: &GenericArgs,
+        kind: ClosureKind,
+    ) -> Option<Instance>;
+
+    /// Get the generic arguments of an instance.
+    fn instance_args(&self, def: InstanceDef) -> GenericArgs;
+
+    /// Get the type of an instance.
+    fn instance_ty(&self, def: InstanceDef) -> Ty;
+
+    /// Get the symbol name of an instance.
+    fn instance_mangled_name(&self, def: InstanceDef) -> String;
+
+    /// Get the kind of an instance.
+    fn instance_kind(&self, def: InstanceDef) -> InstanceKind;
+
+    /// Get the body of an instance, with its generic arguments instantiated.
+    fn instance_body(&self, def: InstanceDef) -> Option<mir::Body>;
+
+    /// Whether an instance is drop glue for a type that doesn't need to be dropped.
+    fn instance_is_empty_shim(&self, def: InstanceDef) -> bool;
 
     /// HACK: Until we have fully stable consumers, we need an escape hatch
     /// to get `DefId`s out of `CrateItem`s.
-    fn rustc_tables(&mut self, f: &mut dyn FnMut(&mut Tables<'_>));
+    fn rustc_tables(&self, f: &mut dyn FnMut(&Tables<'_>));
 }
 
 // A thread local variable that stores a pointer to the tables mapping between TyCtxt
 // datastructures and stable MIR datastructures
-scoped_thread_local! (static TLV: Cell<*mut ()>);
+scoped_thread_local! (static TLV: Cell<*const ()>);
 
-pub fn run(mut context: impl Context, f: impl FnOnce()) {
+pub fn run(context: impl Context, f: impl FnOnce()) {
     assert!(!TLV.is_set());
-    fn g<'a>(mut context: &mut (dyn Context + 'a), f: impl FnOnce()) {
-        let ptr: *mut () = &mut context as *mut &mut _ as _;
-        TLV.set(&Cell::new(ptr), || {
-            f();
-        });
-    }
-    g(&mut context, f);
+    enter(&context, f);
+}
+
+/// Make `context` the current context of this thread while `f` runs.
+pub(crate) fn enter<'a>(context: &(dyn Context + 'a), f: impl FnOnce()) {
+    let ptr: *const () = &context as *const &_ as _;
+    TLV.set(&Cell::new(ptr), || {
+        f();
+    });
 }
 
 /// Loads the current context and calls a function with it.
-/// Do not nest these, as that will ICE.
-pub(crate) fn with<R>(f: impl FnOnce(&mut dyn Context) -> R) -> R {
+pub(crate) fn with<R>(f: impl FnOnce(&dyn Context) -> R) -> R {
     assert!(TLV.is_set());
     TLV.with(|tlv| {
         let ptr = tlv.get();
         assert!(!ptr.is_null());
-        f(unsafe { *(ptr as *mut &mut dyn Context) })
+        f(unsafe { *(ptr as *const &dyn Context) })
     })
 }
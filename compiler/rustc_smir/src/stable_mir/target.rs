@@ -0,0 +1,48 @@
+//! Information about the target machine that code is being compiled for.
+
+use crate::stable_mir::with;
+
+/// The properties of the target machine that affect how values are laid out in memory.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MachineInfo {
+    /// The target triple, e.g. `x86_64-unknown-linux-gnu`.
+    pub triple: String,
+    pub endian: Endian,
+    /// The size of a pointer, which is also the size of `usize` and `isize`.
+    pub pointer_width: MachineSize,
+}
+
+impl MachineInfo {
+    /// Get the information of the target of the current compilation.
+    pub fn target() -> MachineInfo {
+        with(|cx| cx.target_info())
+    }
+}
+
+/// The byte order of the target machine.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+/// The size of a value on the target machine.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct MachineSize {
+    num_bits: usize,
+}
+
+impl MachineSize {
+    pub fn from_bits(num_bits: usize) -> MachineSize {
+        MachineSize { num_bits }
+    }
+
+    pub fn bits(&self) -> usize {
+        self.num_bits
+    }
+
+    /// The size in bytes, rounded up.
+    pub fn bytes(&self) -> usize {
+        (self.num_bits + 7) / 8
+    }
+}
@@ -0,0 +1,332 @@
+//! A best-effort view of the typed high-level IR (THIR) of bodies.
+//!
+//! The THIR is the representation of a body that MIR is built from. Unlike MIR, it keeps the
+//! expression structure of the source, with types, method calls resolved to function calls, and
+//! implicit coercions made explicit. Parts that don't have a structured representation yet are
+//! kept as [`Opaque`] values.
+//!
+//! Expressions, statements, blocks and match arms are stored in the vectors of [`ThirBody`], and
+//! refer to each other through their index in those vectors.
+
+use crate::rustc_internal::Opaque;
+use crate::stable_mir::mir::{BinOp, BorrowKind, Mutability, PointerCoercion, UnOp};
+use crate::stable_mir::ty::{AdtDef, ClosureDef, Const, ParamConst, Span, StaticDef, Ty};
+use crate::stable_mir::{CrateItem, Symbol};
+
+pub type ExprId = usize;
+pub type StmtId = usize;
+pub type BlockId = usize;
+pub type ArmId = usize;
+
+/// Identifies a local variable. The ID of a variable is only unique among the bodies of the
+/// item it is declared in, which includes the closures of the item.
+pub type VarId = usize;
+
+#[derive(Clone, Debug)]
+pub struct ThirBody {
+    pub exprs: Vec<Expr>,
+    pub stmts: Vec<Stmt>,
+    pub blocks: Vec<Block>,
+    pub arms: Vec<Arm>,
+    /// The parameters of the function, including the implicit environment parameter of closures.
+    /// This is empty for constants and statics.
+    pub params: Vec<Param>,
+    /// The expression that computes the value of the body.
+    pub value: ExprId,
+}
+
+#[derive(Clone, Debug)]
+pub struct Param {
+    /// The pattern that binds the parameter, or `None` for implicit parameters.
+    pub pat: Option<Pat>,
+    pub ty: Ty,
+}
+
+#[derive(Clone, Debug)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub ty: Ty,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub enum ExprKind {
+    /// Marks the scope of an expression of the source, see the THIR documentation of the compiler.
+    Scope {
+        value: ExprId,
+    },
+    Box {
+        value: ExprId,
+    },
+    If {
+        cond: ExprId,
+        then: ExprId,
+        else_opt: Option<ExprId>,
+    },
+    /// A function call. Method calls and overloaded operators are represented as calls too,
+    /// in which case `from_hir_call` is `false` for overloaded operators.
+    Call {
+        fun: ExprId,
+        args: Vec<ExprId>,
+        from_hir_call: bool,
+        fn_span: Span,
+    },
+    /// A dereference of a reference or raw pointer. Overloaded dereferences are calls.
+    Deref {
+        arg: ExprId,
+    },
+    /// A binary operation on primitive types. Overloaded operators are calls.
+    Binary {
+        op: BinOp,
+        lhs: ExprId,
+        rhs: ExprId,
+    },
+    LogicalOp {
+        op: LogicalOp,
+        lhs: ExprId,
+        rhs: ExprId,
+    },
+    /// A unary operation on primitive types. Overloaded operators are calls.
+    Unary {
+        op: UnOp,
+        arg: ExprId,
+    },
+    /// A cast to the type of this expression.
+    Cast {
+        source: ExprId,
+    },
+    /// The use of a place as a value.
+    Use {
+        source: ExprId,
+    },
+    NeverToAny {
+        source: ExprId,
+    },
+    PointerCoercion {
+        cast: PointerCoercion,
+        source: ExprId,
+    },
+    Loop {
+        body: ExprId,
+    },
+    /// A `let` expression in the condition of an `if` or in a match guard.
+    Let {
+        expr: ExprId,
+        pat: Pat,
+    },
+    Match {
+        scrutinee: ExprId,
+        arms: Vec<ArmId>,
+    },
+    Block {
+        block: BlockId,
+    },
+    Assign {
+        lhs: ExprId,
+        rhs: ExprId,
+    },
+    AssignOp {
+        op: BinOp,
+        lhs: ExprId,
+        rhs: ExprId,
+    },
+    Field {
+        lhs: ExprId,
+        variant_index: usize,
+        field: usize,
+    },
+    Index {
+        lhs: ExprId,
+        index: ExprId,
+    },
+    VarRef {
+        var: VarId,
+    },
+    /// A variable that a closure or generator captures.
+    UpvarRef {
+        closure: ClosureDef,
+        var: VarId,
+    },
+    Borrow {
+        kind: BorrowKind,
+        arg: ExprId,
+    },
+    AddressOf {
+        mutability: Mutability,
+        arg: ExprId,
+    },
+    Break {
+        value: Option<ExprId>,
+    },
+    Continue,
+    Return {
+        value: Option<ExprId>,
+    },
+    Become {
+        value: ExprId,
+    },
+    /// An inline `const { .. }` block.
+    ConstBlock {
+        item: CrateItem,
+    },
+    Repeat {
+        value: ExprId,
+        count: Const,
+    },
+    Array {
+        fields: Vec<ExprId>,
+    },
+    Tuple {
+        fields: Vec<ExprId>,
+    },
+    Adt {
+        adt: AdtDef,
+        variant_index: usize,
+        fields: Vec<FieldExpr>,
+        base: Option<ExprId>,
+    },
+    TypeAscription {
+        source: ExprId,
+    },
+    Closure {
+        def: ClosureDef,
+        upvars: Vec<ExprId>,
+    },
+    /// A literal of the source, or a value introduced by the compiler.
+    Literal {
+        value: Opaque,
+        neg: bool,
+    },
+    /// The value of a zero-sized type, such as a function item.
+    ZstLiteral,
+    NamedConst {
+        item: CrateItem,
+    },
+    ConstParam(ParamConst),
+    StaticRef {
+        def: StaticDef,
+    },
+    ThreadLocalRef {
+        def: StaticDef,
+    },
+    Yield {
+        value: ExprId,
+    },
+    /// An expression without a structured representation yet, such as inline assembly.
+    Other(Opaque),
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LogicalOp {
+    And,
+    Or,
+}
+
+#[derive(Clone, Debug)]
+pub struct FieldExpr {
+    pub field: usize,
+    pub expr: ExprId,
+}
+
+#[derive(Clone, Debug)]
+pub enum Stmt {
+    Expr { expr: ExprId },
+    Let { pattern: Pat, initializer: Option<ExprId>, else_block: Option<BlockId>, span: Span },
+}
+
+#[derive(Clone, Debug)]
+pub struct Block {
+    pub stmts: Vec<StmtId>,
+    /// The trailing expression of the block.
+    pub expr: Option<ExprId>,
+    pub safety: BlockSafety,
+    pub span: Span,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BlockSafety {
+    Safe,
+    /// A block that is unsafe because of code introduced by the compiler.
+    BuiltinUnsafe,
+    ExplicitUnsafe,
+}
+
+#[derive(Clone, Debug)]
+pub struct Arm {
+    pub pattern: Pat,
+    pub guard: Option<Guard>,
+    pub body: ExprId,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub enum Guard {
+    If(ExprId),
+    IfLet(Pat, ExprId),
+}
+
+#[derive(Clone, Debug)]
+pub struct Pat {
+    pub kind: PatKind,
+    pub ty: Ty,
+    pub span: Span,
+}
+
+/// The kind of a pattern. Type ascriptions are left out, and only their pattern is kept.
+#[derive(Clone, Debug)]
+pub enum PatKind {
+    Wild,
+    Binding {
+        name: Symbol,
+        var: VarId,
+        mutability: Mutability,
+        mode: BindingMode,
+        subpattern: Option<Box<Pat>>,
+    },
+    /// A pattern of a variant of an enum.
+    Variant {
+        adt: AdtDef,
+        variant_index: usize,
+        subpatterns: Vec<FieldPat>,
+    },
+    /// A pattern of a struct, union or tuple.
+    Leaf {
+        subpatterns: Vec<FieldPat>,
+    },
+    Deref {
+        subpattern: Box<Pat>,
+    },
+    Constant {
+        value: Opaque,
+    },
+    Range {
+        lo: Opaque,
+        hi: Opaque,
+        inclusive: bool,
+    },
+    Slice {
+        prefix: Vec<Pat>,
+        slice: Option<Box<Pat>>,
+        suffix: Vec<Pat>,
+    },
+    Array {
+        prefix: Vec<Pat>,
+        slice: Option<Box<Pat>>,
+        suffix: Vec<Pat>,
+    },
+    Or {
+        pats: Vec<Pat>,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct FieldPat {
+    pub field: usize,
+    pub pattern: Pat,
+}
+
+#[derive(Clone, Debug)]
+pub enum BindingMode {
+    ByValue,
+    ByRef(BorrowKind),
+}
@@ -1,22 +1,223 @@
-use super::{mir::Mutability, with, DefId};
+use super::{
+    mir::alloc::{AllocId, Allocation},
+    mir::Mutability,
+    with, CrateItem, DefId, DefKind,
+};
 use crate::rustc_internal::Opaque;
 
-#[derive(Copy, Clone, Debug)]
+mod pretty;
+pub mod visit;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Ty(pub usize);
 
 impl Ty {
+    /// Get the kind of this type. Kinds are cached, so calling this repeatedly is cheap.
     pub fn kind(&self) -> TyKind {
         with(|context| context.ty_kind(*self))
     }
+
+    /// Whether this is the unit type `()`.
+    pub fn is_unit(&self) -> bool {
+        matches!(self.kind(), TyKind::RigidTy(RigidTy::Tuple(tys)) if tys.is_empty())
+    }
+
+    /// Whether this is a reference, e.g. `&T` or `&mut T`.
+    pub fn is_ref(&self) -> bool {
+        matches!(self.kind(), TyKind::RigidTy(RigidTy::Ref(..)))
+    }
+
+    /// Whether this is a raw pointer, e.g. `*const T` or `*mut T`.
+    pub fn is_raw_ptr(&self) -> bool {
+        matches!(self.kind(), TyKind::RigidTy(RigidTy::RawPtr(..)))
+    }
+
+    /// Whether this is `Box<T>`.
+    pub fn is_box(&self) -> bool {
+        matches!(self.kind(), TyKind::RigidTy(RigidTy::Adt(def, _)) if def.is_box())
+    }
+
+    /// The type obtained by dereferencing a value of this type with the built-in `*` operator,
+    /// i.e., without going through the `Deref` trait.
+    ///
+    /// References and `Box` can be dereferenced. Raw pointers can only be dereferenced if
+    /// `explicit` is true, since they are never dereferenced implicitly.
+    pub fn builtin_deref(&self, explicit: bool) -> Option<TypeAndMut> {
+        match self.kind() {
+            TyKind::RigidTy(RigidTy::Ref(_, ty, mutability)) => Some(TypeAndMut { ty, mutability }),
+            TyKind::RigidTy(RigidTy::RawPtr(ty, mutability)) if explicit => {
+                Some(TypeAndMut { ty, mutability })
+            }
+            TyKind::RigidTy(RigidTy::Adt(def, args)) if def.is_box() => match args.0.first() {
+                Some(GenericArgKind::Type(ty)) => {
+                    Some(TypeAndMut { ty: *ty, mutability: Mutability::Not })
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Strip all the references from this type, e.g. `&&mut T` becomes `T`.
+    pub fn peel_refs(&self) -> Ty {
+        let mut ty = *self;
+        while let TyKind::RigidTy(RigidTy::Ref(_, pointee, _)) = ty.kind() {
+            ty = pointee;
+        }
+        ty
+    }
+
+    /// The signature of a function definition, function pointer or closure type.
+    ///
+    /// Returns `None` for any other type.
+    pub fn fn_sig(&self) -> Option<PolyFnSig> {
+        with(|context| context.ty_fn_sig(*self))
+    }
+
+    /// Resolve the hidden type behind an opaque type, such as an `impl Trait` or an async fn
+    /// return type.
+    ///
+    /// Only one level of opaque type is revealed, so the hidden type may itself be opaque.
+    /// Returns `None` if this is not an opaque type.
+    pub fn resolve_opaque(&self) -> Option<Ty> {
+        with(|context| context.resolve_opaque_ty(*self))
+    }
+
+    /// Replace the generic parameters of this type with `args`, and normalize the associated
+    /// types it refers to, erasing all regions.
+    ///
+    /// This is how the declared types of an item, e.g. the signature of a callee, are composed
+    /// with the arguments of a use of the item, e.g. a call site. `args` has to provide all
+    /// generic parameters of the item. Associated types that can't be normalized, such as the
+    /// ones that still depend on generic parameters, are left as is.
+    pub fn normalize(&self, args: &GenericArgs) -> Ty {
+        with(|context| context.normalize(*self, args))
+    }
+
+    /// Create a type from its kind.
+    ///
+    /// Regions can't be converted back to the compiler's representation, so they are erased.
+    pub fn from_kind(kind: TyKind) -> Ty {
+        with(|context| context.mk_ty(kind))
+    }
 }
 
-type Const = Opaque;
 pub(crate) type Region = Opaque;
-type Span = Opaque;
+
+/// A constant, e.g., the length of an array, a const generic argument or a constant operand.
+#[derive(Clone, Debug)]
+pub struct Const {
+    /// The kind of the constant.
+    pub kind: ConstantKind,
+    /// The type of the constant.
+    pub ty: Ty,
+    /// The constant as rendered by the compiler.
+    pub(crate) literal: Opaque,
+    /// An ID used to get the constant from the compiler.
+    pub(crate) id: ConstId,
+}
+
+impl Const {
+    /// Evaluate this constant.
+    ///
+    /// The constant is returned as is if it can't be evaluated, e.g., because it depends on
+    /// generic parameters.
+    pub fn eval(&self) -> Const {
+        with(|cx| cx.eval_const(self.id))
+    }
+
+    /// Get the contents of a string constant, such as a string literal.
+    ///
+    /// Returns `None` if this is not a `&str` constant, or if it can't be evaluated.
+    pub fn try_as_str(&self) -> Option<String> {
+        let TyKind::RigidTy(RigidTy::Ref(_, pointee, _)) = self.ty.kind() else {
+            return None;
+        };
+        if !matches!(pointee.kind(), TyKind::RigidTy(RigidTy::Str)) {
+            return None;
+        }
+        String::from_utf8(self.try_as_bytes()?).ok()
+    }
+
+    /// Get the contents of a `&[u8]` or `&str` constant, such as a byte string literal.
+    ///
+    /// Returns `None` if this is not such a constant, or if it can't be evaluated.
+    pub fn try_as_bytes(&self) -> Option<Vec<u8>> {
+        with(|cx| cx.const_slice_bytes(self.id))
+    }
+
+    /// The pointers stored in the value of this constant, given as the offset of each pointer
+    /// together with the allocation it points to.
+    ///
+    /// Use [`AllocId::global_alloc`] to follow these pointers. This is empty if the constant
+    /// hasn't been evaluated.
+    pub fn provenance(&self) -> Vec<(usize, AllocId)> {
+        with(|cx| cx.const_provenance(self.id))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ConstantKind {
+    /// A constant that was already evaluated.
+    Value,
+    /// A constant that still has to be evaluated, such as a use of a const item.
+    Unevaluated(UnevaluatedConst),
+    /// A const generic parameter, e.g., `N` in `fn foo<const N: usize>()`.
+    Param(ParamConst),
+    /// Any other constant, e.g., an expression that depends on generic parameters.
+    Other,
+}
+
+/// A const generic parameter, e.g., `N` in `fn foo<const N: usize>()`.
+#[derive(Clone, Debug)]
+pub struct ParamConst {
+    /// The position of the parameter in the generics of the item that declares it, including
+    /// the generics of its parents.
+    pub index: u32,
+    pub name: String,
+}
+
+/// A use of a const item or of a promoted constant that hasn't been evaluated.
+#[derive(Clone, Debug)]
+pub struct UnevaluatedConst {
+    /// The item that defines the constant. For promoteds, this is the item they were promoted
+    /// from.
+    pub def: ConstDef,
+    pub args: GenericArgs,
+    /// The index of the promoted constant in the body of `def`, if any.
+    pub promoted: Option<Promoted>,
+}
+
+pub type Promoted = u32;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ConstId(pub(crate) usize);
+
+/// A reference to a source code location.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Span(pub(crate) usize);
 
 #[derive(Clone, Debug)]
 pub enum TyKind {
     RigidTy(RigidTy),
+    Alias(AliasKind, AliasTy),
+    Param(ParamTy),
+}
+
+/// A type, together with the mutability it can be accessed with.
+#[derive(Clone, Debug)]
+pub struct TypeAndMut {
+    pub ty: Ty,
+    pub mutability: Mutability,
+}
+
+/// A generic type parameter, e.g., `T` in `fn foo<T>()`.
+#[derive(Clone, Debug)]
+pub struct ParamTy {
+    /// The position of the parameter in the generics of the item that declares it, including
+    /// the generics of its parents.
+    pub index: u32,
+    pub name: String,
 }
 
 #[derive(Clone, Debug)]
@@ -35,8 +236,16 @@ pub enum RigidTy {
     Ref(Region, Ty, Mutability),
     FnDef(FnDef, GenericArgs),
     FnPtr(PolyFnSig),
+    /// A trait object, e.g., `dyn Iterator<Item = u8> + Send`.
+    Dynamic(Vec<Binder<ExistentialPredicate>>, Region, DynKind),
     Closure(ClosureDef, GenericArgs),
     Generator(GeneratorDef, GenericArgs, Movability),
+    /// The types of the values that a generator may keep alive across suspension points, with
+    /// the regions between them bound.
+    GeneratorWitness(Binder<Vec<Ty>>),
+    /// The types of the values that the given generator keeps alive across suspension points,
+    /// as computed from its MIR with `-Z drop-tracking-mir`.
+    GeneratorWitnessMIR(GeneratorDef, GenericArgs),
     Never,
     Tuple(Vec<Ty>),
 }
@@ -73,18 +282,102 @@ pub enum Movability {
     Movable,
 }
 
+/// The trait through which a closure is called.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClosureKind {
+    Fn,
+    FnMut,
+    FnOnce,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct ForeignDef(pub(crate) DefId);
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct FnDef(pub(crate) DefId);
 
+impl FnDef {
+    /// What kind of definition this function is, e.g. an associated function or the
+    /// constructor of a tuple struct.
+    pub fn kind(&self) -> DefKind {
+        with(|cx| cx.def_kind(self.0))
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct ClosureDef(pub(crate) DefId);
 
+impl ClosureDef {
+    /// The item whose body defines this closure. For closures defined inside other closures,
+    /// this is the item that contains the outermost closure.
+    pub fn parent_item(&self) -> CrateItem {
+        with(|cx| cx.closure_parent_item(self))
+    }
+
+    /// The places captured by this closure, in the order of its upvars.
+    ///
+    /// This is empty for closures defined in other crates.
+    pub fn captures(&self) -> Vec<CapturedPlace> {
+        with(|cx| cx.closure_captures(self))
+    }
+}
+
+/// A place captured by a closure, such as a variable or a field of a variable.
+#[derive(Clone, Debug)]
+pub struct CapturedPlace {
+    /// The captured place as written in the source, e.g., `x`, `x.field` or `*x`.
+    pub path: String,
+    /// The name of the captured variable, e.g., `x` for `x.field`.
+    pub var_name: String,
+    pub kind: CaptureKind,
+    /// The type of the captured place. For places captured by reference, this is the type of
+    /// the referenced value.
+    pub ty: Ty,
+    /// The use of the place that caused it to be captured.
+    pub span: Span,
+}
+
+/// How a closure captures a place.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CaptureKind {
+    /// The place is moved or copied into the closure.
+    ByValue,
+    /// The closure stores a reference to the place.
+    ByRef(CaptureBorrowKind),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CaptureBorrowKind {
+    /// A shared reference, e.g., for a closure that reads `x`.
+    Shared,
+    /// A reference that is immutable but unique, used when the closure mutates the referent
+    /// of a mutable reference it captures, e.g., `*x += 1` for `x: &mut i32`.
+    Unique,
+    /// A mutable reference, e.g., for a closure that assigns to `x`.
+    Mut,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct GeneratorDef(pub(crate) DefId);
 
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConstDef(pub(crate) DefId);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StaticDef(pub(crate) DefId);
+
+impl StaticDef {
+    /// Evaluate the initial value of this static.
+    ///
+    /// Returns `None` if the static is defined in an `extern` block, or if its evaluation fails.
+    pub fn eval_initializer(&self) -> Option<Allocation> {
+        with(|cx| cx.eval_static_initializer(self))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TraitDef(pub(crate) DefId);
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct ParamDef(pub(crate) DefId);
 
@@ -94,6 +387,59 @@ pub enum Movability {
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct AdtDef(pub(crate) DefId);
 
+impl AdtDef {
+    /// Whether this is the `Box` type.
+    pub fn is_box(&self) -> bool {
+        with(|cx| cx.adt_is_box(self))
+    }
+
+    /// Retrieve the discriminant of each variant of this ADT, in declaration order.
+    ///
+    /// Both explicit (`A = 10`) and implicit discriminants are resolved. This returns an empty
+    /// list if the ADT is not an enum.
+    pub fn discriminants(&self) -> Vec<Discr> {
+        with(|cx| cx.adt_discriminants(self))
+    }
+
+    /// The inherent impls of this type, i.e., its `impl Type { .. }` blocks, from any crate.
+    pub fn inherent_impls(&self) -> Vec<ImplDef> {
+        with(|cx| cx.adt_inherent_impls(self))
+    }
+}
+
+/// An impl block, either inherent or of a trait.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ImplDef(pub(crate) DefId);
+
+impl ImplDef {
+    /// The type this impl is for. Generic parameters of the impl are left as is.
+    pub fn self_ty(&self) -> Ty {
+        with(|cx| cx.impl_self_ty(self))
+    }
+
+    /// The trait implemented by this impl, or `None` for inherent impls.
+    pub fn trait_ref(&self) -> Option<TraitRef> {
+        with(|cx| cx.impl_trait_ref(self))
+    }
+
+    /// The associated functions and constants defined in this impl, in definition order.
+    pub fn items(&self) -> Vec<CrateItem> {
+        with(|cx| cx.impl_items(self))
+    }
+}
+
+/// The discriminant of an enum variant.
+#[derive(Clone, Debug)]
+pub struct Discr {
+    /// Bit representation of the discriminant (e.g., `-128i8` is `0xFF_u128`).
+    pub val: u128,
+    /// The integer type of the discriminant.
+    pub ty: Ty,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AliasDef(pub(crate) DefId);
+
 #[derive(Clone, Debug)]
 pub struct GenericArgs(pub Vec<GenericArgKind>);
 
@@ -104,6 +450,94 @@ pub enum GenericArgKind {
     Const(Const),
 }
 
+#[derive(Clone, Debug)]
+pub enum AliasKind {
+    Projection,
+    Inherent,
+    Opaque,
+    Weak,
+}
+
+#[derive(Clone, Debug)]
+pub struct AliasTy {
+    pub def_id: AliasDef,
+    pub args: GenericArgs,
+}
+
+/// A trait together with its generic arguments, e.g., `u8: Into<u32>`.
+///
+/// The first generic argument is the self type.
+#[derive(Clone, Debug)]
+pub struct TraitRef {
+    pub def_id: TraitDef,
+    pub args: GenericArgs,
+}
+
+impl TraitRef {
+    /// The type the trait is implemented for.
+    pub fn self_ty(&self) -> Ty {
+        let Some(GenericArgKind::Type(ty)) = self.args.0.first() else {
+            panic!("the self type of a trait reference should be its first generic argument")
+        };
+        *ty
+    }
+}
+
+/// A requirement on a generic parameter, see [`CrateItem::param_bounds`].
+///
+/// [`CrateItem::param_bounds`]: crate::stable_mir::CrateItem::param_bounds
+#[derive(Clone, Debug)]
+pub enum ParamBound {
+    /// The parameter has to implement a trait, e.g., `T: Clone`. The self type of the trait
+    /// reference is the parameter.
+    Trait(TraitRef),
+    /// The parameter has to outlive a region, e.g., `T: 'a` or `'a: 'b`.
+    Outlives(Region),
+}
+
+/// A trait reference with the self type erased, e.g., the `Iterator<Item = u8>` in
+/// `dyn Iterator<Item = u8>`.
+#[derive(Clone, Debug)]
+pub struct ExistentialTraitRef {
+    pub def_id: TraitDef,
+    pub args: GenericArgs,
+}
+
+/// One of the bounds of a trait object type.
+#[derive(Clone, Debug)]
+pub enum ExistentialPredicate {
+    /// The principal trait of the object, e.g., `Iterator` in `dyn Iterator<Item = u8>`.
+    Trait(ExistentialTraitRef),
+    /// An associated type binding, e.g., `Item = u8` in `dyn Iterator<Item = u8>`.
+    Projection(ExistentialProjection),
+    /// An auto trait, e.g., `Send` in `dyn Debug + Send`.
+    AutoTrait(TraitDef),
+}
+
+/// An associated type binding of a trait object type, with the self type erased.
+#[derive(Clone, Debug)]
+pub struct ExistentialProjection {
+    /// The associated item that is bound.
+    pub def_id: AliasDef,
+    pub args: GenericArgs,
+    pub term: TermKind,
+}
+
+/// The value an associated item is bound to.
+#[derive(Clone, Debug)]
+pub enum TermKind {
+    Type(Ty),
+    Const(Const),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DynKind {
+    /// An unsized `dyn Trait` object.
+    Dyn,
+    /// A sized `dyn* Trait` object.
+    DynStar,
+}
+
 pub type PolyFnSig = Binder<FnSig>;
 
 #[derive(Clone, Debug)]
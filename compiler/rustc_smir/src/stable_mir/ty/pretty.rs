@@ -0,0 +1,263 @@
+//! Human readable rendering of types, e.g. `std::vec::Vec<std::option::Option<&u32>>`.
+//!
+//! Items are printed using their full definition path, and lifetimes are omitted.
+
+use std::fmt::{self, Display, Formatter};
+
+use super::{
+    Abi, AliasTy, Binder, Const, DynKind, ExistentialPredicate, FloatTy, FnSig, GenericArgKind,
+    GenericArgs, IntTy, RigidTy, TermKind, Ty, TyKind, UintTy, Unsafety,
+};
+use crate::stable_mir::mir::Mutability;
+use crate::stable_mir::{with, DefId};
+
+fn def_path(def_id: DefId) -> String {
+    with(|cx| cx.def_path_str(def_id))
+}
+
+impl Display for Ty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.kind().fmt(f)
+    }
+}
+
+impl Display for TyKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TyKind::RigidTy(rigid_ty) => rigid_ty.fmt(f),
+            TyKind::Alias(_, AliasTy { def_id, args }) => {
+                write!(f, "{}{}", def_path(def_id.0), args)
+            }
+            TyKind::Param(param_ty) => write!(f, "{}", param_ty.name),
+        }
+    }
+}
+
+impl Display for RigidTy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RigidTy::Bool => write!(f, "bool"),
+            RigidTy::Char => write!(f, "char"),
+            RigidTy::Int(int_ty) => int_ty.fmt(f),
+            RigidTy::Uint(uint_ty) => uint_ty.fmt(f),
+            RigidTy::Float(float_ty) => float_ty.fmt(f),
+            RigidTy::Adt(def, args) => write!(f, "{}{}", def_path(def.0), args),
+            RigidTy::Foreign(def) => write!(f, "{}", def_path(def.0)),
+            RigidTy::Str => write!(f, "str"),
+            RigidTy::Array(ty, len) => write!(f, "[{ty}; {len}]"),
+            RigidTy::Slice(ty) => write!(f, "[{ty}]"),
+            RigidTy::RawPtr(ty, Mutability::Not) => write!(f, "*const {ty}"),
+            RigidTy::RawPtr(ty, Mutability::Mut) => write!(f, "*mut {ty}"),
+            RigidTy::Ref(_, ty, Mutability::Not) => write!(f, "&{ty}"),
+            RigidTy::Ref(_, ty, Mutability::Mut) => write!(f, "&mut {ty}"),
+            RigidTy::FnDef(def, args) => write!(f, "fn {}{}", def_path(def.0), args),
+            RigidTy::FnPtr(sig) => sig.value.fmt(f),
+            RigidTy::Dynamic(predicates, _, dyn_kind) => fmt_dynamic(predicates, *dyn_kind, f),
+            RigidTy::Closure(def, _) => write!(f, "{{closure@{}}}", def_path(def.0)),
+            RigidTy::Generator(def, _, _) => write!(f, "{{generator@{}}}", def_path(def.0)),
+            RigidTy::GeneratorWitness(types) => {
+                write!(f, "{{generator witness}}(")?;
+                for (idx, ty) in types.value.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    ty.fmt(f)?;
+                }
+                write!(f, ")")
+            }
+            RigidTy::GeneratorWitnessMIR(def, _) => {
+                write!(f, "{{generator witness@{}}}", def_path(def.0))
+            }
+            RigidTy::Never => write!(f, "!"),
+            RigidTy::Tuple(fields) => {
+                write!(f, "(")?;
+                for (idx, field) in fields.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    field.fmt(f)?;
+                }
+                if fields.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Renders a trait object type, e.g. `dyn std::iter::Iterator<Item = u8> + std::marker::Send`.
+/// Associated type bindings are rendered among the arguments of the principal trait.
+fn fmt_dynamic(
+    predicates: &[Binder<ExistentialPredicate>],
+    dyn_kind: DynKind,
+    f: &mut Formatter<'_>,
+) -> fmt::Result {
+    let mut bounds = Vec::with_capacity(predicates.len());
+    for pred in predicates {
+        match &pred.value {
+            ExistentialPredicate::Trait(trait_ref) => {
+                let mut args: Vec<String> = trait_ref
+                    .args
+                    .0
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        GenericArgKind::Lifetime(_) => None,
+                        GenericArgKind::Type(ty) => Some(ty.to_string()),
+                        GenericArgKind::Const(ct) => Some(ct.to_string()),
+                    })
+                    .collect();
+                args.extend(predicates.iter().filter_map(|pred| {
+                    let ExistentialPredicate::Projection(projection) = &pred.value else {
+                        return None;
+                    };
+                    let path = def_path(projection.def_id.0);
+                    let name = path.rsplit("::").next().unwrap_or_default();
+                    Some(match &projection.term {
+                        TermKind::Type(ty) => format!("{name} = {ty}"),
+                        TermKind::Const(ct) => format!("{name} = {ct}"),
+                    })
+                }));
+                let path = def_path(trait_ref.def_id.0);
+                if args.is_empty() {
+                    bounds.push(path);
+                } else {
+                    bounds.push(format!("{path}<{}>", args.join(", ")));
+                }
+            }
+            ExistentialPredicate::Projection(_) => {}
+            ExistentialPredicate::AutoTrait(def) => bounds.push(def_path(def.0)),
+        }
+    }
+    let prefix = match dyn_kind {
+        DynKind::Dyn => "dyn",
+        DynKind::DynStar => "dyn*",
+    };
+    write!(f, "{prefix} {}", bounds.join(" + "))
+}
+
+impl Display for GenericArgs {
+    /// Renders the type and const arguments between angle brackets, e.g. `<u8, 4>`.
+    /// Nothing is rendered if there are no such arguments.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for arg in &self.0 {
+            match arg {
+                GenericArgKind::Lifetime(_) => continue,
+                GenericArgKind::Type(ty) => {
+                    write!(f, "{}{}", if first { "<" } else { ", " }, ty)?;
+                }
+                GenericArgKind::Const(ct) => {
+                    write!(f, "{}{}", if first { "<" } else { ", " }, ct)?;
+                }
+            }
+            first = false;
+        }
+        if !first {
+            write!(f, ">")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Const {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.literal)
+    }
+}
+
+impl Display for FnSig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.unsafety == Unsafety::Unsafe {
+            write!(f, "unsafe ")?;
+        }
+        if self.abi != Abi::Rust {
+            write!(f, "extern \"{}\" ", self.abi)?;
+        }
+        write!(f, "fn(")?;
+        let (output, inputs) = self.inputs_and_output.split_last().unwrap();
+        for (idx, input) in inputs.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            input.fmt(f)?;
+        }
+        if self.c_variadic {
+            write!(f, "{}...", if inputs.is_empty() { "" } else { ", " })?;
+        }
+        write!(f, ")")?;
+        match output.kind() {
+            TyKind::RigidTy(RigidTy::Tuple(fields)) if fields.is_empty() => Ok(()),
+            _ => write!(f, " -> {output}"),
+        }
+    }
+}
+
+impl Display for IntTy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            IntTy::Isize => "isize",
+            IntTy::I8 => "i8",
+            IntTy::I16 => "i16",
+            IntTy::I32 => "i32",
+            IntTy::I64 => "i64",
+            IntTy::I128 => "i128",
+        })
+    }
+}
+
+impl Display for UintTy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            UintTy::Usize => "usize",
+            UintTy::U8 => "u8",
+            UintTy::U16 => "u16",
+            UintTy::U32 => "u32",
+            UintTy::U64 => "u64",
+            UintTy::U128 => "u128",
+        })
+    }
+}
+
+impl Display for FloatTy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FloatTy::F32 => "f32",
+            FloatTy::F64 => "f64",
+        })
+    }
+}
+
+impl Display for Abi {
+    /// Renders the ABI the same way it is written in source code, e.g. `C-unwind`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (name, unwind) = match *self {
+            Abi::Rust => ("Rust", false),
+            Abi::C { unwind } => ("C", unwind),
+            Abi::Cdecl { unwind } => ("cdecl", unwind),
+            Abi::Stdcall { unwind } => ("stdcall", unwind),
+            Abi::Fastcall { unwind } => ("fastcall", unwind),
+            Abi::Vectorcall { unwind } => ("vectorcall", unwind),
+            Abi::Thiscall { unwind } => ("thiscall", unwind),
+            Abi::Aapcs { unwind } => ("aapcs", unwind),
+            Abi::Win64 { unwind } => ("win64", unwind),
+            Abi::SysV64 { unwind } => ("sysv64", unwind),
+            Abi::PtxKernel => ("ptx-kernel", false),
+            Abi::Msp430Interrupt => ("msp430-interrupt", false),
+            Abi::X86Interrupt => ("x86-interrupt", false),
+            Abi::AmdGpuKernel => ("amdgpu-kernel", false),
+            Abi::EfiApi => ("efiapi", false),
+            Abi::AvrInterrupt => ("avr-interrupt", false),
+            Abi::AvrNonBlockingInterrupt => ("avr-non-blocking-interrupt", false),
+            Abi::CCmseNonSecureCall => ("C-cmse-nonsecure-call", false),
+            Abi::Wasm => ("wasm", false),
+            Abi::System { unwind } => ("system", unwind),
+            Abi::RustIntrinsic => ("rust-intrinsic", false),
+            Abi::RustCall => ("rust-call", false),
+            Abi::PlatformIntrinsic => ("platform-intrinsic", false),
+            Abi::Unadjusted => ("unadjusted", false),
+            Abi::RustCold => ("rust-cold", false),
+        };
+        write!(f, "{name}{}", if unwind { "-unwind" } else { "" })
+    }
+}
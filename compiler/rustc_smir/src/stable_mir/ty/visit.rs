@@ -0,0 +1,332 @@
+//! Traits to recursively walk and rewrite the types nested in a value.
+//!
+//! [`TyVisitor`] is used to inspect types, e.g., to collect all ADTs referenced by a signature,
+//! while [`TyFolder`] is used to rebuild them, e.g., to substitute generic parameters.
+
+use std::ops::ControlFlow;
+
+use super::{
+    AliasTy, Binder, Const, ConstantKind, ExistentialPredicate, ExistentialProjection, FnSig,
+    GenericArgKind, GenericArgs, RigidTy, TermKind, Ty, TyKind,
+};
+use crate::stable_mir::with;
+
+/// Walks over the types and constants nested in a value.
+///
+/// The default implementation of each method walks into its argument. An implementation that
+/// overrides a method should call `super_visit` on the argument to keep walking into it.
+pub trait TyVisitor: Sized {
+    type Break;
+
+    fn visit_ty(&mut self, ty: &Ty) -> ControlFlow<Self::Break> {
+        ty.super_visit(self)
+    }
+
+    fn visit_const(&mut self, constant: &Const) -> ControlFlow<Self::Break> {
+        constant.super_visit(self)
+    }
+}
+
+/// A value that can be walked by a [`TyVisitor`].
+pub trait Visitable {
+    fn visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        self.super_visit(visitor)
+    }
+
+    /// Visit the values nested in `self`.
+    fn super_visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break>;
+}
+
+/// Rewrites the types and constants nested in a value.
+///
+/// The default implementation of each method rebuilds its argument from the folded nested
+/// values. Rebuilding goes through the compiler's representation, which regions can't be
+/// converted back to, so every type or constant that is rebuilt has its regions erased, even if
+/// none of its nested values changed. E.g., folding `&'a T` with a folder that substitutes `T`
+/// by `u8` results in `&'erased u8`.
+pub trait TyFolder: Sized {
+    type Break;
+
+    fn fold_ty(&mut self, ty: &Ty) -> ControlFlow<Self::Break, Ty> {
+        ty.super_fold(self)
+    }
+
+    /// By default, the type of the constant and the generic arguments of an unevaluated constant
+    /// are folded. Const generic parameters, i.e. [`ConstantKind::Param`], are kept as is, so
+    /// a folder that substitutes generic parameters has to override this method.
+    fn fold_const(&mut self, constant: &Const) -> ControlFlow<Self::Break, Const> {
+        constant.super_fold(self)
+    }
+}
+
+/// A value that can be rewritten by a [`TyFolder`].
+pub trait Foldable: Sized + Clone {
+    fn fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        self.super_fold(folder)
+    }
+
+    /// Rebuild `self` from its folded nested values.
+    fn super_fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self>;
+}
+
+impl Visitable for Ty {
+    fn visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        visitor.visit_ty(self)
+    }
+
+    fn super_visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        self.kind().visit(visitor)
+    }
+}
+
+impl Foldable for Ty {
+    fn fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        folder.fold_ty(self)
+    }
+
+    fn super_fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        ControlFlow::Continue(Ty::from_kind(self.kind().fold(folder)?))
+    }
+}
+
+impl Visitable for Const {
+    fn visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        visitor.visit_const(self)
+    }
+
+    fn super_visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        self.ty.visit(visitor)?;
+        match &self.kind {
+            ConstantKind::Unevaluated(uv) => uv.args.visit(visitor),
+            ConstantKind::Value | ConstantKind::Param(_) | ConstantKind::Other => {
+                ControlFlow::Continue(())
+            }
+        }
+    }
+}
+
+impl Foldable for Const {
+    fn fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        folder.fold_const(self)
+    }
+
+    /// Only the type and the arguments of unevaluated constants are folded. Anything nested in
+    /// [`ConstantKind::Other`] constants is kept as is.
+    fn super_fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        let ty = self.ty.fold(folder)?;
+        let args = match &self.kind {
+            ConstantKind::Unevaluated(uv) => Some(uv.args.fold(folder)?),
+            ConstantKind::Value | ConstantKind::Param(_) | ConstantKind::Other => None,
+        };
+        ControlFlow::Continue(with(|cx| cx.rebuild_const(self.id, ty, args.as_ref())))
+    }
+}
+
+impl Visitable for TyKind {
+    fn super_visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        match self {
+            TyKind::RigidTy(rigid_ty) => rigid_ty.visit(visitor),
+            TyKind::Alias(_, alias_ty) => alias_ty.args.visit(visitor),
+            TyKind::Param(_) => ControlFlow::Continue(()),
+        }
+    }
+}
+
+impl Foldable for TyKind {
+    fn super_fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        ControlFlow::Continue(match self {
+            TyKind::RigidTy(rigid_ty) => TyKind::RigidTy(rigid_ty.fold(folder)?),
+            TyKind::Alias(kind, alias_ty) => TyKind::Alias(
+                kind.clone(),
+                AliasTy { def_id: alias_ty.def_id.clone(), args: alias_ty.args.fold(folder)? },
+            ),
+            TyKind::Param(param_ty) => TyKind::Param(param_ty.clone()),
+        })
+    }
+}
+
+impl Visitable for RigidTy {
+    fn super_visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        match self {
+            RigidTy::Bool
+            | RigidTy::Char
+            | RigidTy::Int(_)
+            | RigidTy::Uint(_)
+            | RigidTy::Float(_)
+            | RigidTy::Foreign(_)
+            | RigidTy::Str
+            | RigidTy::Never => ControlFlow::Continue(()),
+            RigidTy::Adt(_, args)
+            | RigidTy::FnDef(_, args)
+            | RigidTy::Closure(_, args)
+            | RigidTy::Generator(_, args, _)
+            | RigidTy::GeneratorWitnessMIR(_, args) => args.visit(visitor),
+            RigidTy::GeneratorWitness(types) => types.visit(visitor),
+            RigidTy::Array(ty, len) => {
+                ty.visit(visitor)?;
+                len.visit(visitor)
+            }
+            RigidTy::Slice(ty) | RigidTy::RawPtr(ty, _) | RigidTy::Ref(_, ty, _) => {
+                ty.visit(visitor)
+            }
+            RigidTy::FnPtr(sig) => sig.visit(visitor),
+            RigidTy::Dynamic(predicates, _, _) => predicates.visit(visitor),
+            RigidTy::Tuple(tys) => tys.visit(visitor),
+        }
+    }
+}
+
+impl Foldable for RigidTy {
+    fn super_fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        ControlFlow::Continue(match self {
+            RigidTy::Bool
+            | RigidTy::Char
+            | RigidTy::Int(_)
+            | RigidTy::Uint(_)
+            | RigidTy::Float(_)
+            | RigidTy::Foreign(_)
+            | RigidTy::Str
+            | RigidTy::Never => self.clone(),
+            RigidTy::Adt(def, args) => RigidTy::Adt(def.clone(), args.fold(folder)?),
+            RigidTy::FnDef(def, args) => RigidTy::FnDef(def.clone(), args.fold(folder)?),
+            RigidTy::Closure(def, args) => RigidTy::Closure(def.clone(), args.fold(folder)?),
+            RigidTy::Generator(def, args, movability) => {
+                RigidTy::Generator(def.clone(), args.fold(folder)?, *movability)
+            }
+            RigidTy::GeneratorWitness(types) => RigidTy::GeneratorWitness(types.fold(folder)?),
+            RigidTy::GeneratorWitnessMIR(def, args) => {
+                RigidTy::GeneratorWitnessMIR(def.clone(), args.fold(folder)?)
+            }
+            RigidTy::Array(ty, len) => RigidTy::Array(ty.fold(folder)?, len.fold(folder)?),
+            RigidTy::Slice(ty) => RigidTy::Slice(ty.fold(folder)?),
+            RigidTy::RawPtr(ty, mutability) => {
+                RigidTy::RawPtr(ty.fold(folder)?, mutability.clone())
+            }
+            RigidTy::Ref(region, ty, mutability) => {
+                RigidTy::Ref(region.clone(), ty.fold(folder)?, mutability.clone())
+            }
+            RigidTy::FnPtr(sig) => RigidTy::FnPtr(sig.fold(folder)?),
+            RigidTy::Dynamic(predicates, region, dyn_kind) => {
+                RigidTy::Dynamic(predicates.fold(folder)?, region.clone(), *dyn_kind)
+            }
+            RigidTy::Tuple(tys) => RigidTy::Tuple(tys.fold(folder)?),
+        })
+    }
+}
+
+impl Visitable for GenericArgs {
+    fn super_visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        for arg in &self.0 {
+            match arg {
+                GenericArgKind::Lifetime(_) => {}
+                GenericArgKind::Type(ty) => ty.visit(visitor)?,
+                GenericArgKind::Const(constant) => constant.visit(visitor)?,
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl Foldable for GenericArgs {
+    fn super_fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        let mut args = Vec::with_capacity(self.0.len());
+        for arg in &self.0 {
+            args.push(match arg {
+                GenericArgKind::Lifetime(region) => GenericArgKind::Lifetime(region.clone()),
+                GenericArgKind::Type(ty) => GenericArgKind::Type(ty.fold(folder)?),
+                GenericArgKind::Const(constant) => GenericArgKind::Const(constant.fold(folder)?),
+            });
+        }
+        ControlFlow::Continue(GenericArgs(args))
+    }
+}
+
+impl Visitable for FnSig {
+    fn super_visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        self.inputs_and_output.visit(visitor)
+    }
+}
+
+impl Foldable for FnSig {
+    fn super_fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        ControlFlow::Continue(FnSig {
+            inputs_and_output: self.inputs_and_output.fold(folder)?,
+            c_variadic: self.c_variadic,
+            unsafety: self.unsafety.clone(),
+            abi: self.abi.clone(),
+        })
+    }
+}
+
+impl Visitable for ExistentialPredicate {
+    fn super_visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        match self {
+            ExistentialPredicate::Trait(trait_ref) => trait_ref.args.visit(visitor),
+            ExistentialPredicate::Projection(projection) => {
+                projection.args.visit(visitor)?;
+                match &projection.term {
+                    TermKind::Type(ty) => ty.visit(visitor),
+                    TermKind::Const(constant) => constant.visit(visitor),
+                }
+            }
+            ExistentialPredicate::AutoTrait(_) => ControlFlow::Continue(()),
+        }
+    }
+}
+
+impl Foldable for ExistentialPredicate {
+    fn super_fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        ControlFlow::Continue(match self {
+            ExistentialPredicate::Trait(trait_ref) => {
+                let mut trait_ref = trait_ref.clone();
+                trait_ref.args = trait_ref.args.fold(folder)?;
+                ExistentialPredicate::Trait(trait_ref)
+            }
+            ExistentialPredicate::Projection(projection) => {
+                ExistentialPredicate::Projection(ExistentialProjection {
+                    def_id: projection.def_id.clone(),
+                    args: projection.args.fold(folder)?,
+                    term: match &projection.term {
+                        TermKind::Type(ty) => TermKind::Type(ty.fold(folder)?),
+                        TermKind::Const(constant) => TermKind::Const(constant.fold(folder)?),
+                    },
+                })
+            }
+            ExistentialPredicate::AutoTrait(def) => ExistentialPredicate::AutoTrait(def.clone()),
+        })
+    }
+}
+
+impl<T: Visitable> Visitable for Binder<T> {
+    fn super_visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        self.value.visit(visitor)
+    }
+}
+
+impl<T: Foldable> Foldable for Binder<T> {
+    fn super_fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        ControlFlow::Continue(Binder {
+            value: self.value.fold(folder)?,
+            bound_vars: self.bound_vars.clone(),
+        })
+    }
+}
+
+impl<T: Visitable> Visitable for Vec<T> {
+    fn super_visit<V: TyVisitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        for elem in self {
+            elem.visit(visitor)?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl<T: Foldable> Foldable for Vec<T> {
+    fn super_fold<F: TyFolder>(&self, folder: &mut F) -> ControlFlow<F::Break, Self> {
+        let mut folded = Vec::with_capacity(self.len());
+        for elem in self {
+            folded.push(elem.fold(folder)?);
+        }
+        ControlFlow::Continue(folded)
+    }
+}
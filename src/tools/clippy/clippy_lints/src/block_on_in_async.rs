@@ -0,0 +1,101 @@
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::sugg::Sugg;
+use clippy_utils::{def_path_def_ids, fn_def_id};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::Applicability;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind, GeneratorKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls to `block_on` functions, like `futures::executor::block_on` or tokio's
+    /// `Runtime::block_on`, directly inside an `async fn` or an `async` block.
+    ///
+    /// Other functions that block on a future can be added with the `block-on-functions`
+    /// configuration option, e.g., `block-on-functions = ["my_runtime::block_on"]`.
+    ///
+    /// ### Why is this bad?
+    /// `block_on` blocks the thread until the future completes. Inside an async context, that
+    /// thread is one of the executor's, so other tasks can't run on it in the meantime. If the
+    /// future waits for one of them, this deadlocks, and some executors, like tokio, panic
+    /// instead. The future can be awaited.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// async fn run() -> u32 {
+    ///     futures::executor::block_on(load())
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// async fn run() -> u32 {
+    ///     load().await
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub BLOCK_ON_IN_ASYNC,
+    suspicious,
+    "blocking on a future inside an async context"
+}
+
+const DEFAULT_BLOCK_ON_FNS: &[&str] = &[
+    "async_std::task::block_on",
+    "futures::executor::block_on",
+    "futures_executor::block_on",
+    "tokio::runtime::Handle::block_on",
+    "tokio::runtime::Runtime::block_on",
+];
+
+#[derive(Debug)]
+pub struct BlockOnInAsync {
+    conf_block_on_fns: Vec<String>,
+    block_on_fns: FxHashSet<DefId>,
+}
+
+impl BlockOnInAsync {
+    pub fn new(conf_block_on_fns: Vec<String>) -> Self {
+        Self {
+            conf_block_on_fns,
+            block_on_fns: FxHashSet::default(),
+        }
+    }
+}
+
+impl_lint_pass!(BlockOnInAsync => [BLOCK_ON_IN_ASYNC]);
+
+impl<'tcx> LateLintPass<'tcx> for BlockOnInAsync {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        let conf_block_on_fns = self.conf_block_on_fns.iter().map(String::as_str);
+        for path in DEFAULT_BLOCK_ON_FNS.iter().copied().chain(conf_block_on_fns) {
+            let segs: Vec<_> = path.split("::").collect();
+            self.block_on_fns.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        let (ExprKind::Call(_, [fut]) | ExprKind::MethodCall(_, _, [fut], _)) = expr.kind else {
+            return;
+        };
+        // Only the innermost body counts, a closure in an async block may be called elsewhere.
+        if let Some(body_id) = cx.enclosing_body
+            && let Some(GeneratorKind::Async(_)) = cx.tcx.hir().body(body_id).generator_kind
+            && let Some(block_on_fn) = fn_def_id(cx, expr).filter(|id| self.block_on_fns.contains(id))
+            && !in_external_macro(cx.sess(), expr.span)
+        {
+            let mut app = Applicability::MaybeIncorrect;
+            let fut = Sugg::hir_with_context(cx, fut, expr.span.ctxt(), "..", &mut app).maybe_par();
+            span_lint_and_sugg(
+                cx,
+                BLOCK_ON_IN_ASYNC,
+                expr.span,
+                &format!("calling `{}` inside an async context", cx.tcx.def_path_str(block_on_fn)),
+                "await the future instead",
+                format!("{fut}.await"),
+                app,
+            );
+        }
+    }
+}
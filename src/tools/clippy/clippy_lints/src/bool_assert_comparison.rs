@@ -116,6 +116,7 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
         }
 
         let macro_name = macro_name.as_str();
+        #[allow(clippy::unchecked_char_boundary)]
         let non_eq_mac = &macro_name[..macro_name.len() - 3];
         span_lint_and_then(
             cx,
@@ -26,6 +26,7 @@ pub(super) fn check(
     // has parens on the outside, they are no longer needed.
     let mut applicability = Applicability::MachineApplicable;
     let opt = snippet_opt(cx, cast_op.span);
+    #[allow(clippy::unchecked_char_boundary)]
     let sugg = opt.as_ref().map_or_else(
         || {
             applicability = Applicability::HasPlaceholders;
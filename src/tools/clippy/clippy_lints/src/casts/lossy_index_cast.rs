@@ -0,0 +1,122 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::visitors::for_each_expr_with_closures;
+use clippy_utils::{get_enclosing_block, get_parent_node, higher, path_to_local_id};
+use core::ops::ControlFlow;
+use rustc_hir::{BinOpKind, Expr, ExprKind, HirId, Node, PatKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, Ty, UintTy};
+
+use super::{utils, LOSSY_INDEX_CAST};
+
+/// Arithmetic methods, which are followed to the use of their result.
+const ARITHMETIC_METHODS: [&str; 6] = [
+    "wrapping_add",
+    "wrapping_sub",
+    "saturating_add",
+    "saturating_sub",
+    "min",
+    "max",
+];
+
+/// Methods that take an index or an offset.
+const INDEX_METHODS: [&str; 11] = [
+    "get",
+    "get_mut",
+    "get_unchecked",
+    "get_unchecked_mut",
+    "add",
+    "sub",
+    "offset",
+    "swap",
+    "remove",
+    "insert",
+    "split_at",
+];
+
+pub(super) fn check(cx: &LateContext<'_>, expr: &Expr<'_>, cast_from: Ty<'_>, cast_to: Ty<'_>) {
+    let ptr_bits = cx.tcx.data_layout.pointer_size.bits();
+    if matches!(cast_from.kind(), ty::Uint(UintTy::Usize))
+        && matches!(cast_to.kind(), ty::Int(_) | ty::Uint(_))
+        && utils::int_ty_to_nbits(cast_to, cx.tcx) < ptr_bits
+        && is_used_as_index(cx, expr.hir_id)
+    {
+        span_lint_and_help(
+            cx,
+            LOSSY_INDEX_CAST,
+            expr.span,
+            &format!(
+                "casting `usize` to `{cast_to}` may truncate the value on targets with {ptr_bits}-bit wide pointers"
+            ),
+            None,
+            &format!(
+                "the value is used as an index afterwards, consider keeping it as `usize`, or converting it with \
+                `{cast_to}::try_from`"
+            ),
+        );
+    }
+}
+
+/// Checks whether the value of the expression `id` is used as an index or an offset, possibly
+/// after some arithmetic and casts.
+fn is_used_as_index(cx: &LateContext<'_>, mut id: HirId) -> bool {
+    loop {
+        match get_parent_node(cx.tcx, id) {
+            Some(Node::Expr(parent)) => match parent.kind {
+                ExprKind::Binary(op, ..)
+                    if matches!(
+                        op.node,
+                        BinOpKind::Add
+                            | BinOpKind::Sub
+                            | BinOpKind::Mul
+                            | BinOpKind::Div
+                            | BinOpKind::Rem
+                            | BinOpKind::BitAnd
+                            | BinOpKind::Shl
+                            | BinOpKind::Shr
+                    ) =>
+                {
+                    id = parent.hir_id;
+                },
+                ExprKind::Cast(..) => id = parent.hir_id,
+                ExprKind::Index(_, index) => return index.hir_id == id,
+                ExprKind::MethodCall(path, recv, ..) => {
+                    let name = path.ident.as_str();
+                    if ARITHMETIC_METHODS.contains(&name) {
+                        id = parent.hir_id;
+                    } else {
+                        return recv.hir_id != id && INDEX_METHODS.contains(&name);
+                    }
+                },
+                _ => return false,
+            },
+            // A bound of a range, e.g., `x[start..]`.
+            Some(Node::ExprField(field)) => {
+                if let Some(Node::Expr(range)) = get_parent_node(cx.tcx, field.hir_id)
+                    && higher::Range::hir(range).is_some()
+                {
+                    id = range.hir_id;
+                } else {
+                    return false;
+                }
+            },
+            Some(Node::Local(local)) => {
+                return if local.init.map_or(false, |init| init.hir_id == id)
+                    && let PatKind::Binding(_, binding, _, None) = local.pat.kind
+                    && let Some(block) = get_enclosing_block(cx, local.hir_id)
+                {
+                    for_each_expr_with_closures(cx, block, |e| {
+                        if path_to_local_id(e, binding) && is_used_as_index(cx, e.hir_id) {
+                            ControlFlow::Break(())
+                        } else {
+                            ControlFlow::Continue(())
+                        }
+                    })
+                    .is_some()
+                } else {
+                    false
+                };
+            },
+            _ => return false,
+        }
+    }
+}
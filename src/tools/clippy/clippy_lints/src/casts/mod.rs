@@ -16,6 +16,7 @@
 mod fn_to_numeric_cast;
 mod fn_to_numeric_cast_any;
 mod fn_to_numeric_cast_with_truncation;
+mod lossy_index_cast;
 mod ptr_as_ptr;
 mod ptr_cast_constness;
 mod unnecessary_cast;
@@ -657,6 +658,35 @@
     "casting a known floating-point NaN into an integer"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for casts from `usize` to a smaller integer type, e.g., `v.len() as u32`, when the
+    /// result is used as an index or an offset afterwards. Only casts to types that are smaller
+    /// than pointers on the compilation target are linted.
+    ///
+    /// ### Why is this bad?
+    /// Values that don't fit into the smaller type are silently truncated, so a different element
+    /// is accessed for large collections. This often goes unnoticed, since such collections are rare
+    /// in tests.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # let v = vec![1];
+    /// let last = v.len() as u32 - 1;
+    /// let x = v[last as usize];
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let v = vec![1];
+    /// let last = v.len() - 1;
+    /// let x = v[last];
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub LOSSY_INDEX_CAST,
+    pedantic,
+    "casting `usize` to a smaller integer type that is used as an index afterwards"
+}
+
 pub struct Casts {
     msrv: Msrv,
 }
@@ -691,6 +721,7 @@ pub fn new(msrv: Msrv) -> Self {
     CAST_SLICE_FROM_RAW_PARTS,
     AS_PTR_CAST_MUT,
     CAST_NAN_TO_INT,
+    LOSSY_INDEX_CAST,
 ]);
 
 impl<'tcx> LateLintPass<'tcx> for Casts {
@@ -730,6 +761,7 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                     cast_sign_loss::check(cx, expr, cast_expr, cast_from, cast_to);
                     cast_abs_to_unsigned::check(cx, expr, cast_expr, cast_from, cast_to, &self.msrv);
                     cast_nan_to_int::check(cx, expr, cast_expr, cast_from, cast_to);
+                    lossy_index_cast::check(cx, expr, cast_from, cast_to);
                 }
                 cast_lossless::check(cx, expr, cast_expr, cast_from, cast_to, &self.msrv);
                 cast_enum_constructor::check(cx, expr, cast_expr, cast_from);
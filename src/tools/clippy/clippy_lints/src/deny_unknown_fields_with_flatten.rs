@@ -0,0 +1,77 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use rustc_ast::ast::{Attribute, Item, ItemKind};
+use rustc_lint::{EarlyContext, EarlyLintPass};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for structs with the `#[serde(deny_unknown_fields)]` attribute that have a field
+    /// with the `#[serde(flatten)]` attribute.
+    ///
+    /// ### Why is this bad?
+    /// Serde doesn't support this combination. The fields of the flattened value are treated as
+    /// unknown fields of the struct, so deserializing it fails.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// #[derive(Deserialize)]
+    /// #[serde(deny_unknown_fields)]
+    /// struct Request {
+    ///     id: u32,
+    ///     #[serde(flatten)]
+    ///     options: Options,
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// #[derive(Deserialize)]
+    /// struct Request {
+    ///     id: u32,
+    ///     #[serde(flatten)]
+    ///     options: Options,
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub DENY_UNKNOWN_FIELDS_WITH_FLATTEN,
+    suspicious,
+    "using `#[serde(flatten)]` in a struct with `#[serde(deny_unknown_fields)]`"
+}
+declare_lint_pass!(DenyUnknownFieldsWithFlatten => [DENY_UNKNOWN_FIELDS_WITH_FLATTEN]);
+
+impl EarlyLintPass for DenyUnknownFieldsWithFlatten {
+    fn check_item(&mut self, cx: &EarlyContext<'_>, item: &Item) {
+        let ItemKind::Struct(ref data, _) = item.kind else {
+            return;
+        };
+        let Some(deny_span) = find_serde_attr(&item.attrs, "deny_unknown_fields") else {
+            return;
+        };
+
+        for field in data.fields() {
+            if let Some(flatten_span) = find_serde_attr(&field.attrs, "flatten") {
+                span_lint_and_then(
+                    cx,
+                    DENY_UNKNOWN_FIELDS_WITH_FLATTEN,
+                    flatten_span,
+                    "`#[serde(flatten)]` is not supported in a struct with `#[serde(deny_unknown_fields)]`",
+                    |diag| {
+                        diag.span_note(deny_span, "unknown fields are denied here");
+                        diag.help("see https://serde.rs/container-attrs.html#deny_unknown_fields");
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Finds the span of the word `name` in a `#[serde(..)]` attribute.
+fn find_serde_attr(attrs: &[Attribute], name: &str) -> Option<Span> {
+    attrs
+        .iter()
+        .filter(|attr| attr.ident().is_some_and(|ident| ident.name.as_str() == "serde"))
+        .filter_map(Attribute::meta_item_list)
+        .flatten()
+        .find(|item| item.is_word() && item.ident().is_some_and(|ident| ident.name.as_str() == name))
+        .map(|item| item.span())
+}
@@ -0,0 +1,102 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::{def_path_def_ids, fn_def_id};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{ExprKind, Stmt, StmtKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for statements which spawn a task, e.g., with `tokio::spawn` or
+    /// `async_std::task::spawn`, and drop the `JoinHandle` right away.
+    ///
+    /// Other functions that spawn tasks or threads and return a handle can be added with the
+    /// `spawn-functions` configuration option, e.g., `spawn-functions = ["my_runtime::spawn"]`.
+    ///
+    /// ### Why is this bad?
+    /// Without the handle, nothing can wait for the task to finish, and if it panics or returns
+    /// an error, it's silently lost. Tasks which are meant to run detached can be made explicit
+    /// with `let _ = tokio::spawn(..)`, which isn't linted.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// tokio::spawn(async move {
+    ///     save(state).await.unwrap();
+    /// });
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// let handle = tokio::spawn(async move {
+    ///     save(state).await.unwrap();
+    /// });
+    /// handle.await?;
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub DETACHED_TASK,
+    pedantic,
+    "spawning a task and dropping its `JoinHandle` right away"
+}
+
+const DEFAULT_SPAWN_FNS: &[&str] = &[
+    "async_std::task::spawn",
+    "async_std::task::spawn_local",
+    "tokio::spawn",
+    "tokio::task::spawn",
+    "tokio::task::spawn_blocking",
+    "tokio::task::spawn_local",
+];
+
+#[derive(Debug)]
+pub struct DetachedTask {
+    conf_spawn_fns: Vec<String>,
+    spawn_fns: FxHashSet<DefId>,
+}
+
+impl DetachedTask {
+    pub fn new(conf_spawn_fns: Vec<String>) -> Self {
+        Self {
+            conf_spawn_fns,
+            spawn_fns: FxHashSet::default(),
+        }
+    }
+}
+
+impl_lint_pass!(DetachedTask => [DETACHED_TASK]);
+
+impl<'tcx> LateLintPass<'tcx> for DetachedTask {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        let conf_spawn_fns = self.conf_spawn_fns.iter().map(String::as_str);
+        for path in DEFAULT_SPAWN_FNS.iter().copied().chain(conf_spawn_fns) {
+            let segs: Vec<_> = path.split("::").collect();
+            self.spawn_fns.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
+    fn check_stmt(&mut self, cx: &LateContext<'tcx>, stmt: &'tcx Stmt<'tcx>) {
+        if let StmtKind::Semi(expr) = stmt.kind
+            && let Some(spawn_fn) = fn_def_id(cx, expr).filter(|id| self.spawn_fns.contains(id))
+            && !cx.typeck_results().expr_ty(expr).is_unit()
+            && !in_external_macro(cx.sess(), stmt.span)
+        {
+            let span = match expr.kind {
+                ExprKind::Call(func, _) => func.span,
+                ExprKind::MethodCall(path, ..) => path.ident.span,
+                _ => return,
+            };
+            span_lint_and_help(
+                cx,
+                DETACHED_TASK,
+                span,
+                &format!(
+                    "the handle returned by `{}` is dropped right away",
+                    cx.tcx.def_path_str(spawn_fn)
+                ),
+                None,
+                "if the task panics or fails, nothing will notice, consider keeping the handle and awaiting or \
+                joining it, or use `let _ = ..` if the task is meant to run detached",
+            );
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{get_parent_expr, path_to_local_id, SpanlessEq};
+use core::ops::ControlFlow;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::{Block, Expr, ExprKind, HirId, MatchSource, PatKind, StmtKind, UnOp};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::sym;
+use rustc_span::symbol::Ident;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `Mutex::lock` calls on a `Mutex` while the guard of an earlier lock of the same
+    /// `Mutex` is still bound to a local in the same block.
+    ///
+    /// ### Why is this bad?
+    /// The second call waits for the guard to be dropped, which never happens, so the thread
+    /// deadlocks.
+    ///
+    /// ### Known problems
+    /// Only guards that are bound with `let`, and `Mutex`es that are named by a local, a static or
+    /// a field of them, are checked. A guard is assumed to be dropped by the first statement that
+    /// moves it, even if it's only moved in one branch.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// let mut items = self.items.lock().unwrap();
+    /// items.push(item);
+    /// let len = self.items.lock().unwrap().len();
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// let mut items = self.items.lock().unwrap();
+    /// items.push(item);
+    /// let len = items.len();
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub DOUBLE_LOCK,
+    suspicious,
+    "locking a `Mutex` while a guard of it is still alive"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `RefCell::borrow_mut` calls on a `RefCell` while a guard of an earlier borrow of
+    /// the same `RefCell` is still bound to a local in the same block, and for `RefCell::borrow`
+    /// calls while a guard of an earlier `borrow_mut` is.
+    ///
+    /// ### Why is this bad?
+    /// A `RefCell` can't be borrowed mutably while it's borrowed, so the second call panics.
+    ///
+    /// ### Known problems
+    /// Only guards that are bound with `let`, and `RefCell`s that are named by a local, a static or
+    /// a field of them, are checked. A guard is assumed to be dropped by the first statement that
+    /// moves it, even if it's only moved in one branch.
+    ///
+    /// ### Example
+    /// ```rust,should_panic
+    /// # use std::cell::RefCell;
+    /// let names = RefCell::new(vec!["a"]);
+    /// let all = names.borrow();
+    /// names.borrow_mut().push("b");
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::cell::RefCell;
+    /// let names = RefCell::new(vec!["a"]);
+    /// names.borrow_mut().push("b");
+    /// let all = names.borrow();
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub OVERLAPPING_BORROW,
+    suspicious,
+    "mutably borrowing a `RefCell` while a guard of another borrow of it is still alive"
+}
+declare_lint_pass!(DoubleLock => [DOUBLE_LOCK, OVERLAPPING_BORROW]);
+
+/// The kind of guard that a call returns.
+#[derive(Clone, Copy)]
+enum Guard {
+    Lock,
+    Borrow,
+    BorrowMut,
+}
+
+impl Guard {
+    /// Checks whether a guard of this kind can't be alive at the same time as one of the kind
+    /// `other`, for the same value.
+    fn conflicts_with(self, other: Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::Lock, Self::Lock)
+                | (Self::Borrow, Self::BorrowMut)
+                | (Self::BorrowMut, Self::Borrow | Self::BorrowMut)
+        )
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for DoubleLock {
+    fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
+        if in_external_macro(cx.sess(), block.span) {
+            return;
+        }
+        for (i, stmt) in block.stmts.iter().enumerate() {
+            if let StmtKind::Local(local) = stmt.kind
+                && let PatKind::Binding(_, id, name, None) = local.pat.kind
+                && let Some(init) = local.init
+                && let Some((guard, value)) = acquire(cx, peel_unwraps(init))
+                && is_place(cx, value)
+            {
+                let rest = block.stmts[i + 1..]
+                    .iter()
+                    .filter_map(|stmt| match stmt.kind {
+                        StmtKind::Expr(e) | StmtKind::Semi(e) => Some(e),
+                        StmtKind::Local(local) => local.init,
+                        StmtKind::Item(_) => None,
+                    })
+                    .chain(block.expr);
+                check_guard(cx, id, name, guard, peel_unwraps(init), value, rest);
+            }
+        }
+    }
+}
+
+/// Lints the first call in `rest`, the expressions that follow the binding of the guard `id`,
+/// which acquires a guard of `value` that conflicts with it, unless the guard is moved before.
+fn check_guard<'tcx>(
+    cx: &LateContext<'tcx>,
+    id: HirId,
+    name: Ident,
+    guard: Guard,
+    first: &Expr<'_>,
+    value: &Expr<'_>,
+    rest: impl Iterator<Item = &'tcx Expr<'tcx>>,
+) {
+    for e in rest {
+        if moves_local(cx, e, id) {
+            return;
+        }
+        let second = for_each_expr(e, |e| match acquire(cx, e) {
+            Some((other, other_value))
+                if guard.conflicts_with(other)
+                    && SpanlessEq::new(cx).eq_expr(other_value.peel_borrows(), value.peel_borrows()) =>
+            {
+                ControlFlow::Break((e, other))
+            },
+            _ => ControlFlow::Continue(()),
+        });
+        let Some((second, other)) = second else {
+            continue;
+        };
+        let (lint, msg, acquired, consequence) = match (guard, other) {
+            (Guard::Lock, _) => (
+                DOUBLE_LOCK,
+                "locking a `Mutex` while the guard of an earlier lock of it is still alive",
+                "lock",
+                "deadlocks",
+            ),
+            (_, Guard::BorrowMut) => (
+                OVERLAPPING_BORROW,
+                "mutably borrowing a `RefCell` while an earlier borrow of it is still alive",
+                "borrow",
+                "panics",
+            ),
+            _ => (
+                OVERLAPPING_BORROW,
+                "borrowing a `RefCell` while an earlier mutable borrow of it is still alive",
+                "borrow",
+                "panics",
+            ),
+        };
+        span_lint_and_then(cx, lint, second.span, msg, |diag| {
+            diag.span_note(
+                first.span,
+                format!("the guard of this {acquired} is held by `{name}` until the end of the block"),
+            );
+            diag.help(format!(
+                "this {consequence}, consider using `{name}`, or dropping it first with `drop({name})`"
+            ));
+        });
+        return;
+    }
+}
+
+/// If `e` is a call to `Mutex::lock`, `RefCell::borrow` or `RefCell::borrow_mut`, gets the kind of
+/// the guard it returns, and the `Mutex` or `RefCell`.
+fn acquire<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> Option<(Guard, &'tcx Expr<'tcx>)> {
+    let ExprKind::MethodCall(path, recv, [], _) = e.kind else {
+        return None;
+    };
+    let (guard, ty) = match path.ident.as_str() {
+        "lock" => (Guard::Lock, sym::Mutex),
+        "borrow" => (Guard::Borrow, sym::RefCell),
+        "borrow_mut" => (Guard::BorrowMut, sym::RefCell),
+        _ => return None,
+    };
+    is_type_diagnostic_item(cx, cx.typeck_results().expr_ty_adjusted(recv).peel_refs(), ty).then_some((guard, recv))
+}
+
+/// Removes the calls and `?`s that get a guard out of the result of `lock`, e.g., `unwrap`.
+fn peel_unwraps<'tcx>(mut e: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    loop {
+        e = match e.kind {
+            ExprKind::MethodCall(path, recv, ..)
+                if matches!(path.ident.as_str(), "unwrap" | "expect" | "unwrap_or_else" | "map_err") =>
+            {
+                recv
+            },
+            ExprKind::Match(scrutinee, _, MatchSource::TryDesugar) => match scrutinee.kind {
+                ExprKind::Call(_, [inner]) => inner,
+                _ => return e,
+            },
+            _ => return e,
+        };
+    }
+}
+
+/// Checks whether `e` names the same value wherever it's evaluated, i.e., it's a local or a
+/// static, or a field of one.
+fn is_place(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    match e.kind {
+        ExprKind::Path(ref qpath) => matches!(
+            cx.qpath_res(qpath, e.hir_id),
+            Res::Local(_) | Res::Def(DefKind::Static(_), _)
+        ),
+        ExprKind::Field(base, _) | ExprKind::Unary(UnOp::Deref, base) | ExprKind::AddrOf(_, _, base) => {
+            is_place(cx, base)
+        },
+        _ => false,
+    }
+}
+
+/// Checks whether `e` moves the local `id` anywhere, e.g., with `drop(id)`.
+fn moves_local<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>, id: HirId) -> bool {
+    for_each_expr(e, |e| {
+        if !path_to_local_id(e, id) {
+            return ControlFlow::Continue(());
+        }
+        let moved = get_parent_expr(cx, e).map_or(true, |parent| match parent.kind {
+            ExprKind::Call(_, args) | ExprKind::MethodCall(_, _, args, _) => {
+                args.iter().any(|arg| arg.hir_id == e.hir_id)
+            },
+            ExprKind::Assign(_, rhs, _) => rhs.hir_id == e.hir_id,
+            ExprKind::Ret(_) | ExprKind::Break(..) | ExprKind::Struct(..) | ExprKind::Tup(_) | ExprKind::Array(_) => {
+                true
+            },
+            _ => false,
+        });
+        if moved {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_some()
+}
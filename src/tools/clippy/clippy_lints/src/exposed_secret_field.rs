@@ -0,0 +1,173 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::{is_type_diagnostic_item, is_type_lang_item};
+use clippy_utils::{match_def_path, paths};
+use rustc_ast::ast::Attribute;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{FieldDef, Item, ItemKind, LangItem};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for fields of structs which derive `Debug` or `serde::Serialize`, that look like
+    /// secrets, e.g., `password` or `api_key`. Only fields with a string or a byte buffer type, or an
+    /// `Option` or reference of one, are checked, and fields which serde skips, or serializes with
+    /// a custom function, are left alone.
+    ///
+    /// The field names which are treated as secrets can be configured with `secret-field-names`.
+    ///
+    /// ### Why is this bad?
+    /// The secret ends up in plain text wherever the value is logged, printed or serialized, e.g.,
+    /// in log files or API responses.
+    ///
+    /// ### Example
+    /// ```rust
+    /// #[derive(Debug)]
+    /// struct Login {
+    ///     user: String,
+    ///     password: String,
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::fmt;
+    /// struct Login {
+    ///     user: String,
+    ///     password: String,
+    /// }
+    ///
+    /// impl fmt::Debug for Login {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.debug_struct("Login")
+    ///             .field("user", &self.user)
+    ///             .field("password", &"<redacted>")
+    ///             .finish()
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub EXPOSED_SECRET_FIELD,
+    restriction,
+    "deriving `Debug` or `Serialize` for a struct with a field that looks like a secret"
+}
+
+pub struct ExposedSecretField {
+    secret_names: Vec<String>,
+}
+
+impl ExposedSecretField {
+    pub fn new(secret_names: Vec<String>) -> Self {
+        Self { secret_names }
+    }
+}
+
+impl_lint_pass!(ExposedSecretField => [EXPOSED_SECRET_FIELD]);
+
+/// The derived traits which expose the fields of a struct.
+#[derive(Clone, Copy)]
+enum Exposure {
+    Debug,
+    Serialize,
+}
+
+impl Exposure {
+    fn of_trait(cx: &LateContext<'_>, trait_id: DefId) -> Option<Self> {
+        if cx.tcx.is_diagnostic_item(sym::Debug, trait_id) {
+            Some(Self::Debug)
+        } else if match_def_path(cx, trait_id, &paths::SERDE_SERIALIZE) {
+            Some(Self::Serialize)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for ExposedSecretField {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if let ItemKind::Impl(impl_) = item.kind
+            && let Some(trait_id) = impl_.of_trait.and_then(|trait_ref| trait_ref.trait_def_id())
+            && cx.tcx.has_attr(item.owner_id, sym::automatically_derived)
+            && let Some(exposure) = Exposure::of_trait(cx, trait_id)
+            && let ty::Adt(adt, _) = cx.tcx.type_of(item.owner_id).instantiate_identity().kind()
+            && let Some(local_id) = adt.did().as_local()
+            && let ItemKind::Struct(data, _) = cx.tcx.hir().expect_item(local_id).kind
+        {
+            for field in data.fields() {
+                if is_secret_name(&self.secret_names, field.ident.as_str())
+                    && is_plain_text(cx, cx.tcx.type_of(field.def_id).instantiate_identity())
+                    && !in_external_macro(cx.sess(), field.span)
+                {
+                    check_field(cx, field, exposure);
+                }
+            }
+        }
+    }
+}
+
+fn check_field(cx: &LateContext<'_>, field: &FieldDef<'_>, exposure: Exposure) {
+    let name = field.ident;
+    match exposure {
+        Exposure::Debug => span_lint_and_help(
+            cx,
+            EXPOSED_SECRET_FIELD,
+            field.span,
+            &format!("field `{name}` looks like a secret, but is included in the derived `Debug` output"),
+            None,
+            "consider implementing `Debug` manually and leaving it out, or wrapping it in a type which redacts it",
+        ),
+        Exposure::Serialize if !is_skipped_by_serde(cx.tcx.hir().attrs(field.hir_id)) => span_lint_and_help(
+            cx,
+            EXPOSED_SECRET_FIELD,
+            field.span,
+            &format!("field `{name}` looks like a secret, but is serialized by the derived `Serialize` implementation"),
+            None,
+            "consider skipping it with `#[serde(skip_serializing)]`, or wrapping it in a type which redacts it",
+        ),
+        Exposure::Serialize => {},
+    }
+}
+
+/// Checks whether one of the `_`-separated parts of `name`, or a run of them, is one of
+/// `secret_names`, e.g., `db_password` for `password`.
+pub(crate) fn is_secret_name(secret_names: &[String], name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    let parts: Vec<&str> = name.split('_').collect();
+    secret_names.iter().any(|secret| {
+        let secret = secret.to_ascii_lowercase();
+        let secret_parts: Vec<&str> = secret.split('_').collect();
+        parts.windows(secret_parts.len()).any(|window| window == secret_parts)
+    })
+}
+
+/// Checks whether `ty` holds its contents as plain text or bytes, e.g., `String`, `Vec<u8>` or
+/// `Option<&str>`. Other types might redact their contents.
+pub(crate) fn is_plain_text<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
+    let ty = ty.peel_refs();
+    match ty.kind() {
+        ty::Str => true,
+        ty::Array(elem, _) | ty::Slice(elem) => *elem == cx.tcx.types.u8,
+        ty::Adt(_, args) if is_type_diagnostic_item(cx, ty, sym::Option) => is_plain_text(cx, args.type_at(0)),
+        ty::Adt(_, args) if is_type_diagnostic_item(cx, ty, sym::Vec) => args.type_at(0) == cx.tcx.types.u8,
+        _ => is_type_lang_item(cx, ty, LangItem::String),
+    }
+}
+
+/// Checks whether serde skips the field, or serializes it with a custom function.
+fn is_skipped_by_serde(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.ident().is_some_and(|ident| ident.name.as_str() == "serde"))
+        .filter_map(Attribute::meta_item_list)
+        .flatten()
+        .any(|item| {
+            item.ident().is_some_and(|ident| {
+                matches!(
+                    ident.name.as_str(),
+                    "skip" | "skip_serializing" | "serialize_with" | "with"
+                )
+            })
+        })
+}
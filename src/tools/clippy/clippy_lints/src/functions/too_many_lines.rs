@@ -29,6 +29,7 @@ pub(super) fn check_fn(
     let mut in_comment = false;
     let mut code_in_line;
 
+    #[allow(clippy::unchecked_char_boundary)]
     let function_lines = if matches!(body.value.kind, hir::ExprKind::Block(..))
         && code_snippet.as_bytes().first().copied() == Some(b'{')
         && code_snippet.as_bytes().last().copied() == Some(b'}')
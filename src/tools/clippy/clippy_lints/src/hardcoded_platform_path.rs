@@ -0,0 +1,178 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::source::snippet_opt;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::{def_path_def_ids, fn_def_id};
+use rustc_ast::LitKind;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{BorrowKind, Expr, ExprKind, QPath};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for string literals with paths which only exist on some platforms or machines,
+    /// like `"/tmp/.."`, `"/home/.."` or `"C:\\.."`, which are passed to functions like
+    /// `File::open`, `Path::new` or `std::fs::read`.
+    ///
+    /// Projects which only target one platform can allow `/tmp` and Windows paths with the
+    /// `allow-platform-specific-paths` configuration. Paths into home directories are still
+    /// linted then.
+    ///
+    /// ### Why is this bad?
+    /// The program fails on other platforms, or on other machines. The temporary directory can
+    /// be found with `std::env::temp_dir()`, and other directories can be looked up at runtime,
+    /// e.g., with the `dirs` crate, or be made configurable.
+    ///
+    /// ### Example
+    /// ```rust,no_run
+    /// let log = std::fs::File::create("/tmp/app.log");
+    /// ```
+    /// Use instead:
+    /// ```rust,no_run
+    /// let log = std::fs::File::create(std::env::temp_dir().join("app.log"));
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub HARDCODED_PLATFORM_PATH,
+    pedantic,
+    "using a hard-coded path which only exists on some platforms"
+}
+
+/// Functions which take a path, checked together with `PathBuf::from`.
+pub(crate) const PATH_FNS: &[&str] = &[
+    "std::fs::File::create",
+    "std::fs::File::open",
+    "std::fs::OpenOptions::open",
+    "std::fs::copy",
+    "std::fs::create_dir",
+    "std::fs::create_dir_all",
+    "std::fs::metadata",
+    "std::fs::read",
+    "std::fs::read_dir",
+    "std::fs::read_to_string",
+    "std::fs::remove_dir",
+    "std::fs::remove_dir_all",
+    "std::fs::remove_file",
+    "std::fs::rename",
+    "std::fs::write",
+    "std::path::Path::new",
+];
+
+pub struct HardcodedPlatformPath {
+    allow_platform_specific_paths: bool,
+    path_fns: FxHashSet<DefId>,
+}
+
+impl HardcodedPlatformPath {
+    pub fn new(allow_platform_specific_paths: bool) -> Self {
+        Self {
+            allow_platform_specific_paths,
+            path_fns: FxHashSet::default(),
+        }
+    }
+}
+
+impl_lint_pass!(HardcodedPlatformPath => [HARDCODED_PLATFORM_PATH]);
+
+impl<'tcx> LateLintPass<'tcx> for HardcodedPlatformPath {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for path in PATH_FNS {
+            let segs: Vec<_> = path.split("::").collect();
+            self.path_fns.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        let Some(args) = path_fn_args(cx, &self.path_fns, expr) else {
+            return;
+        };
+        for arg in args {
+            let arg = match arg.kind {
+                ExprKind::AddrOf(BorrowKind::Ref, _, inner) => inner,
+                _ => arg,
+            };
+            if let ExprKind::Lit(lit) = arg.kind
+                && let LitKind::Str(path, _) = lit.node
+                && !arg.span.from_expansion()
+                // `env!` expands to a literal with the span of its call.
+                && snippet_opt(cx, arg.span).map_or(false, |s| s.trim_start_matches(['r', '#']).starts_with('"'))
+                && let Some(kind) = PathKind::of(path.as_str())
+                && !(self.allow_platform_specific_paths && kind != PathKind::Home)
+            {
+                let (msg, help) = match kind {
+                    PathKind::Temp => (
+                        "hard-coded path into `/tmp`",
+                        "`/tmp` doesn't exist on all platforms, consider using `std::env::temp_dir()` instead",
+                    ),
+                    PathKind::Home => (
+                        "hard-coded path into a home directory",
+                        "this path only exists on some machines, consider looking up the home directory at runtime, \
+                        e.g., with the `dirs` crate, or making the path configurable",
+                    ),
+                    PathKind::Windows => (
+                        "hard-coded Windows path",
+                        "this path only exists on Windows, consider making it configurable, or relative to a \
+                        directory which is looked up at runtime",
+                    ),
+                };
+                span_lint_and_help(cx, HARDCODED_PLATFORM_PATH, arg.span, msg, None, help);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PathKind {
+    /// `/tmp/..`
+    Temp,
+    /// `/home/..` or `/Users/..`
+    Home,
+    /// `C:\..`
+    Windows,
+}
+
+impl PathKind {
+    fn of(path: &str) -> Option<Self> {
+        let is_in = |dir: &str| {
+            path.strip_prefix(dir)
+                .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+        };
+        if is_in("/tmp") {
+            Some(Self::Temp)
+        } else if is_in("/home") || is_in("/Users") {
+            Some(Self::Home)
+        } else if let [drive, b':', b'\\' | b'/', ..] = path.as_bytes()
+            && drive.is_ascii_alphabetic()
+        {
+            Some(Self::Windows)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the arguments of `expr` if it's a call to one of `path_fns`, which should be resolved
+/// from [`PATH_FNS`], or to `PathBuf::from`.
+pub(crate) fn path_fn_args<'tcx>(
+    cx: &LateContext<'tcx>,
+    path_fns: &FxHashSet<DefId>,
+    expr: &'tcx Expr<'tcx>,
+) -> Option<&'tcx [Expr<'tcx>]> {
+    match expr.kind {
+        ExprKind::Call(_, args) | ExprKind::MethodCall(_, _, args, _)
+            if fn_def_id(cx, expr).map_or(false, |id| path_fns.contains(&id)) =>
+        {
+            Some(args)
+        },
+        // `PathBuf::from("..")`
+        ExprKind::Call(func, args @ [_])
+            if let ExprKind::Path(QPath::TypeRelative(_, segment)) = func.kind
+                && segment.ident.name == sym::from
+                && is_type_diagnostic_item(cx, cx.typeck_results().expr_ty(expr), sym::PathBuf) =>
+        {
+            Some(args)
+        },
+        _ => None,
+    }
+}
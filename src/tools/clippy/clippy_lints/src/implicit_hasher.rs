@@ -76,6 +76,7 @@ fn suggestion(
         ) {
             let generics_snip = snippet(cx, generics_span, "");
             // trim `<` `>`
+            #[allow(clippy::unchecked_char_boundary)]
             let generics_snip = if generics_snip.is_empty() {
                 ""
             } else {
@@ -1,14 +1,18 @@
 use clippy_utils::diagnostics::{span_lint_and_sugg, span_lint_and_then};
 use clippy_utils::ty::implements_trait;
-use clippy_utils::{get_parent_node, is_res_lang_ctor, last_path_segment, path_res};
+use clippy_utils::visitors::for_each_expr_with_closures;
+use clippy_utils::{get_parent_expr, get_parent_node, is_res_lang_ctor, last_path_segment, path_res, path_to_local};
+use core::ops::ControlFlow;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::Applicability;
 use rustc_hir::def::Res;
-use rustc_hir::{Expr, ExprKind, ImplItem, ImplItemKind, ItemKind, LangItem, Node, UnOp};
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Body, Expr, ExprKind, ImplItem, ImplItemKind, ItemKind, LangItem, Node, PatKind, UnOp};
 use rustc_lint::{LateContext, LateLintPass};
-use rustc_middle::ty::EarlyBinder;
+use rustc_middle::ty::{self, AdtDef, EarlyBinder, Ty};
 use rustc_session::{declare_lint_pass, declare_tool_lint};
 use rustc_span::sym;
-use rustc_span::symbol::kw;
+use rustc_span::symbol::{kw, Symbol};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -106,7 +110,71 @@
     correctness,
     "manual implementation of `PartialOrd` when `Ord` is already implemented"
 }
-declare_lint_pass!(IncorrectImpls => [INCORRECT_CLONE_IMPL_ON_COPY_TYPE, INCORRECT_PARTIAL_ORD_IMPL_ON_ORD_TYPE]);
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for manual implementations of `Hash` which hash fields that the manual implementation
+    /// of `PartialEq` for the same type doesn't compare.
+    ///
+    /// ### Why is this bad?
+    /// Values that are equal must have the same hash, which is what `HashMap` and `HashSet` rely
+    /// on. If they can differ in a field that is hashed, they can end up with different hashes, and
+    /// looking them up fails, or they're stored twice.
+    ///
+    /// ### Limitations
+    /// Will not lint if either implementation uses `self` for anything other than accessing its
+    /// fields, e.g., calling one of its methods.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::hash::{Hash, Hasher};
+    /// struct User {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// impl PartialEq for User {
+    ///     fn eq(&self, other: &Self) -> bool {
+    ///         self.id == other.id
+    ///     }
+    /// }
+    ///
+    /// impl Hash for User {
+    ///     fn hash<H: Hasher>(&self, state: &mut H) {
+    ///         self.id.hash(state);
+    ///         self.name.hash(state);
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::hash::{Hash, Hasher};
+    /// struct User {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// impl PartialEq for User {
+    ///     fn eq(&self, other: &Self) -> bool {
+    ///         self.id == other.id
+    ///     }
+    /// }
+    ///
+    /// impl Hash for User {
+    ///     fn hash<H: Hasher>(&self, state: &mut H) {
+    ///         self.id.hash(state);
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub INCONSISTENT_HASH_AND_PARTIAL_EQ,
+    suspicious,
+    "manual implementation of `Hash` which hashes fields that `PartialEq` doesn't compare"
+}
+declare_lint_pass!(IncorrectImpls => [
+    INCORRECT_CLONE_IMPL_ON_COPY_TYPE,
+    INCORRECT_PARTIAL_ORD_IMPL_ON_ORD_TYPE,
+    INCONSISTENT_HASH_AND_PARTIAL_EQ
+]);
 
 impl LateLintPass<'_> for IncorrectImpls {
     #[expect(clippy::too_many_lines)]
@@ -235,9 +303,133 @@ fn check_impl_item(&mut self, cx: &LateContext<'_>, impl_item: &ImplItem<'_>) {
                             suggs,
                             Applicability::Unspecified,
                         );
+
+                        if let ty::Adt(adt, _) = trait_impl.self_ty().kind()
+                            && adt.is_struct()
+                            && let Some(partial_ord_fields) = used_fields(cx, body, body.params.len())
+                            && let Some((ord_impl, ord_fields)) =
+                                manual_impl_fields(cx, *ord_def_id, trait_impl.self_ty(), sym::cmp)
+                        {
+                            let only_ord = fields_not_in(*adt, &ord_fields, &partial_ord_fields);
+                            let only_partial_ord = fields_not_in(*adt, &partial_ord_fields, &ord_fields);
+                            if only_ord.is_empty() && only_partial_ord.is_empty() {
+                                return;
+                            }
+                            diag.span_note(
+                                cx.tcx.def_span(ord_impl),
+                                "`Ord` is implemented here, and compares different fields",
+                            );
+                            if !only_ord.is_empty() {
+                                diag.note(format!("only `cmp` compares {only_ord}"));
+                            }
+                            if !only_partial_ord.is_empty() {
+                                diag.note(format!("only `partial_cmp` compares {only_partial_ord}"));
+                            }
+                        }
                     }
                 );
             }
         }
+
+        if cx.tcx.is_diagnostic_item(sym::Hash, trait_impl.def_id)
+            && impl_item.ident.name == sym::hash
+            && let ty::Adt(adt, _) = trait_impl.self_ty().kind()
+            && adt.is_struct()
+            && let Some(hash_fields) = used_fields(cx, body, 1)
+            && let Some(eq_trait) = cx.tcx.lang_items().eq_trait()
+            && let Some((eq_impl, eq_fields)) = manual_impl_fields(cx, eq_trait, trait_impl.self_ty(), sym::eq)
+        {
+            let unequal = fields_not_in(*adt, &hash_fields, &eq_fields);
+            if !unequal.is_empty() {
+                span_lint_and_then(
+                    cx,
+                    INCONSISTENT_HASH_AND_PARTIAL_EQ,
+                    item.span,
+                    "this `Hash` implementation hashes fields which the `PartialEq` implementation doesn't compare",
+                    |diag| {
+                        diag.span_note(cx.tcx.def_span(eq_impl), "`PartialEq` implemented here");
+                        diag.help(format!(
+                            "values that are equal must have the same hash, consider not hashing {unequal}"
+                        ));
+                    },
+                );
+            }
+        }
     }
 }
+
+/// Gets the fields of `Self` that `method` of the manual implementation of `trait_id` for `ty`
+/// uses, e.g., the ones that `eq` compares for `PartialEq`, along with the `DefId` of the
+/// implementation. Only implementations where all type parameters of the trait are `Self` count.
+fn manual_impl_fields<'tcx>(
+    cx: &LateContext<'tcx>,
+    trait_id: DefId,
+    ty: Ty<'tcx>,
+    method: Symbol,
+) -> Option<(DefId, FxHashSet<Symbol>)> {
+    let mut found = None;
+    cx.tcx.for_each_relevant_impl(trait_id, ty, |impl_id| {
+        let args = cx
+            .tcx
+            .impl_trait_ref(impl_id)
+            .map(|trait_ref| trait_ref.skip_binder().args);
+        if found.is_none()
+            && impl_id.is_local()
+            && !cx.tcx.is_automatically_derived(impl_id)
+            && args.map_or(false, |args| args.types().all(|arg| arg == args.type_at(0)))
+        {
+            found = Some(impl_id);
+        }
+    });
+    let impl_id = found?;
+    let method = cx
+        .tcx
+        .associated_items(impl_id)
+        .filter_by_name_unhygienic(method)
+        .next()?
+        .def_id
+        .as_local()?;
+    let body = cx.tcx.hir().body(cx.tcx.hir().maybe_body_owned_by(method)?);
+    Some((impl_id, used_fields(cx, body, body.params.len())?))
+}
+
+/// Lists the fields of `adt` which are in `fields` but not in `other`, in the order they're
+/// declared, e.g., "`a`, `b`".
+fn fields_not_in(adt: AdtDef<'_>, fields: &FxHashSet<Symbol>, other: &FxHashSet<Symbol>) -> String {
+    adt.all_fields()
+        .filter(|field| fields.contains(&field.name) && !other.contains(&field.name))
+        .map(|field| format!("`{}`", field.name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Gets the names of the fields that the first `params` parameters of `body` are used to access,
+/// e.g., `a` for `self.a`. Returns `None` if any of them is used for anything else.
+fn used_fields<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Body<'tcx>, params: usize) -> Option<FxHashSet<Symbol>> {
+    let mut ids = FxHashSet::default();
+    for param in body.params.iter().take(params) {
+        match param.pat.kind {
+            PatKind::Binding(_, id, ..) => {
+                ids.insert(id);
+            },
+            PatKind::Wild => {},
+            _ => return None,
+        }
+    }
+    let mut fields = FxHashSet::default();
+    for_each_expr_with_closures(cx, body.value, |e| {
+        if let Some(id) = path_to_local(e)
+            && ids.contains(&id)
+        {
+            match get_parent_expr(cx, e).map(|parent| parent.kind) {
+                Some(ExprKind::Field(_, ident)) => {
+                    fields.insert(ident.name);
+                },
+                _ => return ControlFlow::Break(()),
+            }
+        }
+        ControlFlow::Continue(())
+    })
+    .is_none()
+    .then_some(fields)
+}
@@ -0,0 +1,165 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet;
+use clippy_utils::ty::approx_ty_size;
+use clippy_utils::visitors::for_each_expr_with_closures;
+use clippy_utils::{get_enclosing_block, get_parent_expr, is_trait_method, path_to_local_id};
+use core::ops::ControlFlow;
+use rustc_hir::{
+    BindingAnnotation, Block, BorrowKind, CaptureBy, Expr, ExprKind, HirId, Local, Mutability, Node, PatKind,
+};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::adjustment::{Adjust, AutoBorrow, AutoBorrowMutability, OverloadedDeref};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for large values which are cloned into a local that is then only borrowed by a
+    /// `move` closure, e.g., `let config = self.config.clone(); move || run(&config)`.
+    ///
+    /// The size above which values are linted can be configured with
+    /// `clone-into-closure-size-threshold`.
+    ///
+    /// ### Why is this bad?
+    /// Copying a large value takes time, and the closure doesn't need its own copy if it only
+    /// reads it. The closure can borrow the original instead, or, if it must be `'static`, share
+    /// it with an `Arc`.
+    ///
+    /// ### Known problems
+    /// The size is the size of the value itself, so memory on the heap, e.g., the elements of a
+    /// `Vec`, isn't counted.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # fn print_all(_: &[u64; 128]) {}
+    /// let table = [0u64; 128];
+    /// let copy = table.clone();
+    /// let print = move || print_all(&copy);
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # fn print_all(_: &[u64; 128]) {}
+    /// let table = [0u64; 128];
+    /// let print = || print_all(&table);
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub LARGE_CLONE_INTO_CLOSURE,
+    pedantic,
+    "cloning a large value into a closure which only borrows it"
+}
+
+pub struct LargeCloneIntoClosure {
+    size_threshold: u64,
+}
+
+impl LargeCloneIntoClosure {
+    pub fn new(size_threshold: u64) -> Self {
+        Self { size_threshold }
+    }
+}
+
+impl_lint_pass!(LargeCloneIntoClosure => [LARGE_CLONE_INTO_CLOSURE]);
+
+impl<'tcx> LateLintPass<'tcx> for LargeCloneIntoClosure {
+    fn check_local(&mut self, cx: &LateContext<'tcx>, local: &'tcx Local<'tcx>) {
+        if let PatKind::Binding(BindingAnnotation::NONE, id, name, None) = local.pat.kind
+            && let Some(init) = local.init
+            && let ExprKind::MethodCall(_, cloned, [], _) = init.kind
+            && is_trait_method(cx, init, sym::Clone)
+            && !in_external_macro(cx.sess(), local.span)
+        {
+            let size = approx_ty_size(cx, cx.typeck_results().expr_ty(init));
+            if size < self.size_threshold {
+                return;
+            }
+            if let Some(block) = get_enclosing_block(cx, local.hir_id)
+                && let Some(closure) = find_borrowing_closure(cx, block, id)
+            {
+                span_lint_and_then(
+                    cx,
+                    LARGE_CLONE_INTO_CLOSURE,
+                    init.span,
+                    &format!("cloning a value of {size} bytes into a closure which only borrows it"),
+                    |diag| {
+                        diag.span_note(closure.span, format!("`{name}` is only borrowed in this closure"));
+                        diag.help(format!(
+                            "consider borrowing `{}` in the closure instead, or sharing it with an `Arc` if the \
+                            closure must be `'static`",
+                            snippet(cx, cloned.span, "..")
+                        ));
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Finds the `move` closure which the local `id` is used in, if it's only used in one, and only
+/// borrowed immutably there.
+fn find_borrowing_closure<'tcx>(
+    cx: &LateContext<'tcx>,
+    block: &'tcx Block<'tcx>,
+    id: HirId,
+) -> Option<&'tcx Expr<'tcx>> {
+    let mut closure: Option<&Expr<'_>> = None;
+    let used_elsewhere = for_each_expr_with_closures(cx, block, |e| {
+        if path_to_local_id(e, id) {
+            match enclosing_move_closure(cx, block, e) {
+                Some(enclosing)
+                    if is_borrowed(cx, e) && closure.map_or(true, |closure| closure.hir_id == enclosing.hir_id) =>
+                {
+                    closure = Some(enclosing);
+                },
+                _ => return ControlFlow::Break(()),
+            }
+        }
+        ControlFlow::Continue(())
+    })
+    .is_some();
+    if used_elsewhere {
+        None
+    } else {
+        closure
+    }
+}
+
+/// Finds the `move` closure in `block` which `e` is in.
+fn enclosing_move_closure<'tcx>(cx: &LateContext<'tcx>, block: &Block<'_>, e: &Expr<'_>) -> Option<&'tcx Expr<'tcx>> {
+    cx.tcx
+        .hir()
+        .parent_iter(e.hir_id)
+        .take_while(|&(id, _)| id != block.hir_id)
+        .find_map(|(_, node)| {
+            if let Node::Expr(e) = node
+                && let ExprKind::Closure(closure) = e.kind
+                && closure.capture_clause == CaptureBy::Value
+            {
+                Some(e)
+            } else {
+                None
+            }
+        })
+}
+
+/// Checks whether `e` is borrowed immutably, e.g., `&x`, `x.len()` or `x.trim()`.
+fn is_borrowed(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    if let Some(parent) = get_parent_expr(cx, e)
+        && let ExprKind::AddrOf(BorrowKind::Ref, Mutability::Not, _) = parent.kind
+    {
+        return true;
+    }
+    matches!(
+        cx.typeck_results()
+            .expr_adjustments(e)
+            .first()
+            .map(|adjust| &adjust.kind),
+        Some(
+            Adjust::Borrow(AutoBorrow::Ref(_, AutoBorrowMutability::Not))
+                | Adjust::Deref(Some(OverloadedDeref {
+                    mutbl: Mutability::Not,
+                    ..
+                }))
+        )
+    )
+}
@@ -76,6 +76,7 @@
 mod async_yields_async;
 mod attrs;
 mod await_holding_invalid;
+mod block_on_in_async;
 mod blocks_in_if_conditions;
 mod bool_assert_comparison;
 mod bool_to_int_with_if;
@@ -99,15 +100,18 @@
 mod default_instead_of_iter_empty;
 mod default_numeric_fallback;
 mod default_union_representation;
+mod deny_unknown_fields_with_flatten;
 mod dereference;
 mod derivable_impls;
 mod derive;
+mod detached_task;
 mod disallowed_macros;
 mod disallowed_methods;
 mod disallowed_names;
 mod disallowed_script_idents;
 mod disallowed_types;
 mod doc;
+mod double_lock;
 mod double_parens;
 mod drop_forget_ref;
 mod duplicate_mod;
@@ -127,6 +131,7 @@
 mod exhaustive_items;
 mod exit;
 mod explicit_write;
+mod exposed_secret_field;
 mod extra_unused_type_parameters;
 mod fallible_impl_from;
 mod float_literal;
@@ -141,6 +146,7 @@
 mod from_str_radix_10;
 mod functions;
 mod future_not_send;
+mod hardcoded_platform_path;
 mod if_let_mutex;
 mod if_not_else;
 mod if_then_some_else_none;
@@ -163,6 +169,7 @@
 mod items_after_statements;
 mod items_after_test_module;
 mod iter_not_returning_iterator;
+mod large_clone_into_closure;
 mod large_const_arrays;
 mod large_enum_variant;
 mod large_futures;
@@ -184,10 +191,12 @@
 mod manual_bits;
 mod manual_clamp;
 mod manual_float_methods;
+mod manual_from_bytes;
 mod manual_is_ascii_check;
 mod manual_let_else;
 mod manual_main_separator_str;
 mod manual_non_exhaustive;
+mod manual_path_join;
 mod manual_range_patterns;
 mod manual_rem_euclid;
 mod manual_retain;
@@ -227,7 +236,10 @@
 mod needless_else;
 mod needless_for_each;
 mod needless_if;
+mod needless_interior_mutability;
+mod needless_join_or_select;
 mod needless_late_init;
+mod needless_lazy_init;
 mod needless_parens_on_range_literals;
 mod needless_pass_by_ref_mut;
 mod needless_pass_by_value;
@@ -238,9 +250,11 @@
 mod new_without_default;
 mod no_effect;
 mod no_mangle_with_rust_abi;
+mod non_constant_time_comparison;
 mod non_copy_const;
 mod non_expressive_names;
 mod non_octal_unix_permissions;
+mod non_send_capture_in_spawn;
 mod non_send_fields_in_send_ty;
 mod nonstandard_macro_braces;
 mod octal_escapes;
@@ -249,6 +263,8 @@
 mod option_env_unwrap;
 mod option_if_let_else;
 mod overflow_check_conditional;
+mod panic_in_drop;
+mod panic_in_extern_c_fn;
 mod panic_in_result_fn;
 mod panic_unimplemented;
 mod partial_pub_fields;
@@ -266,7 +282,9 @@
 mod ranges;
 mod raw_strings;
 mod rc_clone_in_vec_init;
+mod rc_cycle;
 mod read_zero_byte_vec;
+mod recompute_in_loop;
 mod redundant_async_block;
 mod redundant_clone;
 mod redundant_closure_call;
@@ -296,7 +314,9 @@
 mod size_of_in_element_count;
 mod size_of_ref;
 mod slow_vector_initialization;
+mod static_mut;
 mod std_instead_of_core;
+mod str_as_ptr_to_c_fn;
 mod strings;
 mod strlen_on_c_strings;
 mod suspicious_doc_comments;
@@ -305,15 +325,19 @@
 mod suspicious_xor_used_as_pow;
 mod swap;
 mod swap_ptr_to_ref;
+mod system_time_elapsed;
 mod tabs_in_doc_comments;
 mod temporary_assignment;
 mod tests_outside_test_module;
+mod thread_spawn_in_loop;
 mod to_digit_is_some;
 mod trailing_empty_array;
 mod trait_bounds;
 mod transmute;
 mod tuple_array_conversions;
 mod types;
+mod unbuffered_file_io;
+mod unchecked_char_boundary;
 mod undocumented_unsafe_blocks;
 mod unicode;
 mod uninit_vec;
@@ -341,6 +365,7 @@
 mod vec;
 mod vec_init_then_push;
 mod visibility;
+mod weak_key_randomness;
 mod wildcard_imports;
 mod write;
 mod zero_div_zero;
@@ -932,10 +957,12 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     });
     let accept_comment_above_statement = conf.accept_comment_above_statement;
     let accept_comment_above_attributes = conf.accept_comment_above_attributes;
+    let require_safety_comment_per_unsafe_call = conf.require_safety_comment_per_unsafe_call;
     store.register_late_pass(move |_| {
         Box::new(undocumented_unsafe_blocks::UndocumentedUnsafeBlocks::new(
             accept_comment_above_statement,
             accept_comment_above_attributes,
+            require_safety_comment_per_unsafe_call,
         ))
     });
     let allow_mixed_uninlined = conf.allow_mixed_uninlined_format_args;
@@ -978,7 +1005,13 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(move |_| Box::new(manual_rem_euclid::ManualRemEuclid::new(msrv())));
     store.register_late_pass(move |_| Box::new(manual_retain::ManualRetain::new(msrv())));
     let verbose_bit_mask_threshold = conf.verbose_bit_mask_threshold;
-    store.register_late_pass(move |_| Box::new(operators::Operators::new(verbose_bit_mask_threshold)));
+    let allow_rounding_division = conf.allow_rounding_division;
+    store.register_late_pass(move |_| {
+        Box::new(operators::Operators::new(
+            verbose_bit_mask_threshold,
+            allow_rounding_division,
+        ))
+    });
     store.register_late_pass(|_| Box::<std_instead_of_core::StdReexports>::default());
     store.register_late_pass(move |_| Box::new(instant_subtraction::InstantSubtraction::new(msrv())));
     store.register_late_pass(|_| Box::new(partialeq_to_none::PartialeqToNone));
@@ -1078,6 +1111,67 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_early_pass(|| Box::new(visibility::Visibility));
     store.register_late_pass(move |_| Box::new(tuple_array_conversions::TupleArrayConversions { msrv: msrv() }));
     store.register_late_pass(|_| Box::new(manual_float_methods::ManualFloatMethods));
+    store.register_late_pass(|_| Box::new(unbuffered_file_io::UnbufferedFileIo));
+    let expensive_constructors = conf.expensive_constructors.clone();
+    store.register_late_pass(move |_| {
+        Box::new(recompute_in_loop::RecomputeInLoop::new(expensive_constructors.clone()))
+    });
+    let check_fns_called_from_drop = conf.check_fns_called_from_drop;
+    store.register_late_pass(move |_| Box::new(panic_in_drop::PanicInDrop::new(check_fns_called_from_drop)));
+    store.register_late_pass(|_| Box::new(panic_in_extern_c_fn::PanicInExternCFn));
+    let spawn_functions = conf.spawn_functions.clone();
+    store.register_late_pass(move |_| {
+        Box::new(non_send_capture_in_spawn::NonSendCaptureInSpawn::new(spawn_functions.clone()))
+    });
+    store.register_early_pass(|| Box::new(deny_unknown_fields_with_flatten::DenyUnknownFieldsWithFlatten));
+    store.register_late_pass(|_| Box::<needless_interior_mutability::NeedlessInteriorMutability>::default());
+    store.register_late_pass(|_| Box::<rc_cycle::RcCycle>::default());
+    store.register_late_pass(|_| Box::new(unchecked_char_boundary::UncheckedCharBoundary));
+    store.register_late_pass(|_| Box::new(thread_spawn_in_loop::ThreadSpawnInLoop));
+    store.register_late_pass(|_| Box::new(double_lock::DoubleLock));
+    store.register_late_pass(|_| Box::new(system_time_elapsed::SystemTimeElapsed));
+    let secret_field_names = conf.secret_field_names.clone();
+    store.register_late_pass(move |_| {
+        Box::new(exposed_secret_field::ExposedSecretField::new(
+            secret_field_names.clone(),
+        ))
+    });
+    let secret_field_names = conf.secret_field_names.clone();
+    store.register_late_pass(move |_| {
+        Box::new(non_constant_time_comparison::NonConstantTimeComparison::new(
+            secret_field_names.clone(),
+        ))
+    });
+    let secret_field_names = conf.secret_field_names.clone();
+    let key_constructors = conf.key_constructors.clone();
+    store.register_late_pass(move |_| {
+        Box::new(weak_key_randomness::WeakKeyRandomness::new(
+            secret_field_names.clone(),
+            key_constructors.clone(),
+        ))
+    });
+    store.register_late_pass(|_| Box::new(manual_from_bytes::ManualFromBytes));
+    let clone_into_closure_size_threshold = conf.clone_into_closure_size_threshold;
+    store.register_late_pass(move |_| {
+        Box::new(large_clone_into_closure::LargeCloneIntoClosure::new(
+            clone_into_closure_size_threshold,
+        ))
+    });
+    store.register_late_pass(|_| Box::new(needless_lazy_init::NeedlessLazyInit));
+    store.register_late_pass(|_| Box::new(static_mut::StaticMut));
+    store.register_late_pass(|_| Box::new(str_as_ptr_to_c_fn::StrAsPtrToCFn));
+    let spawn_functions = conf.spawn_functions.clone();
+    store.register_late_pass(move |_| Box::new(detached_task::DetachedTask::new(spawn_functions.clone())));
+    let block_on_functions = conf.block_on_functions.clone();
+    store.register_late_pass(move |_| Box::new(block_on_in_async::BlockOnInAsync::new(block_on_functions.clone())));
+    store.register_late_pass(|_| Box::new(needless_join_or_select::NeedlessJoinOrSelect));
+    let allow_platform_specific_paths = conf.allow_platform_specific_paths;
+    store.register_late_pass(move |_| {
+        Box::new(hardcoded_platform_path::HardcodedPlatformPath::new(
+            allow_platform_specific_paths,
+        ))
+    });
+    store.register_late_pass(|_| Box::<manual_path_join::ManualPathJoin>::default());
     // add lints here, do not remove this comment, it's used in `new_lint`
 }
 
@@ -126,6 +126,7 @@ fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx TraitItem<'_>
     }
 }
 
+#[allow(clippy::linear_contains_in_loop)]
 fn check_fn_inner<'tcx>(
     cx: &LateContext<'tcx>,
     sig: &'tcx FnSig<'_>,
@@ -235,6 +236,7 @@ fn check_fn_inner<'tcx>(
     }
 }
 
+#[allow(clippy::linear_contains_in_loop)]
 fn elision_suggestions(
     cx: &LateContext<'_>,
     generics: &Generics<'_>,
@@ -331,6 +331,7 @@ fn check_for_mistyped_suffix(
         let mut split = part.rsplit('_');
         let last_group = split.next().expect("At least one group");
         if split.next().is_some() && mistyped_suffixes.contains(&last_group) {
+            #[allow(clippy::unchecked_char_boundary)]
             let main_part = &part[..part.len() - last_group.len()];
             let missing_char;
             if is_float {
@@ -523,6 +524,7 @@ fn do_lint(digits: &str) -> Result<(), WarningType> {
         } else {
             // Lint for Literals with a hex-representation of 4 digits or more
             let f = &digits[0..1]; // first digit
+            #[allow(clippy::unchecked_char_boundary)]
             let m = &digits[1..digits.len() - 1]; // middle digits, except last
             let s = &digits[1..]; // suffix
 
@@ -0,0 +1,206 @@
+use super::BUSY_WAIT_LOOP;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{fn_def_id, get_parent_expr, is_res_lang_ctor};
+use core::ops::ControlFlow;
+use rustc_hir::{Block, Expr, ExprKind, LangItem, MatchSource, Pat, PatKind, StmtKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+use rustc_span::{sym, Span, Symbol};
+
+/// Functions and methods that block the thread, or that make the loop cooperate with others.
+const BLOCKING_CALLS: [&str; 13] = [
+    "sleep",
+    "yield_now",
+    "spin_loop",
+    "park",
+    "park_timeout",
+    "recv",
+    "recv_timeout",
+    "wait",
+    "wait_timeout",
+    "wait_while",
+    "join",
+    "lock",
+    "select",
+];
+
+/// Checks `loop { .. }` and `while cond { .. }` loops. `cond` is `None` for the former.
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    block: &'tcx Block<'tcx>,
+    cond: Option<&'tcx Expr<'tcx>>,
+    span: Span,
+) {
+    if cond.is_some_and(|cond| is_blocking(cx, cond)) || block_exprs(block).any(|e| is_blocking(cx, e)) {
+        return;
+    }
+    // Loops which do more than polling, e.g., `loop { poll_events(); render(); }`, are left alone.
+    let mut polls = cond.into_iter().chain(block_exprs(block)).map(|e| find_poll(cx, e));
+    let Some(Some(first)) = polls.next() else {
+        return;
+    };
+    if polls.any(|poll| poll.is_none()) {
+        return;
+    }
+    // `while flag.load(..) {}` is linted by `missing_spin_loop`.
+    if cond.is_some()
+        && block.stmts.is_empty()
+        && block.expr.is_none()
+        && atomic_load_ty(cx, first) == Some(sym::AtomicBool)
+    {
+        return;
+    }
+    if block_exprs(block).any(|e| {
+        for_each_expr(e, |e| {
+            if is_poll(cx, e) && exits_on_failure(cx, e) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .is_some()
+    }) {
+        return;
+    }
+
+    span_lint_and_then(
+        cx,
+        BUSY_WAIT_LOOP,
+        span,
+        "this loop busy-waits by polling without ever blocking",
+        |diag| {
+            diag.span_note(first.span, "polled here");
+            match first.kind {
+                ExprKind::MethodCall(path, ..) if path.ident.as_str() != "load" => {
+                    diag.help(format!(
+                        "consider blocking with `{}` instead",
+                        path.ident.as_str().trim_start_matches("try_")
+                    ));
+                },
+                _ => {
+                    diag.help(
+                        "consider waiting for a notification, e.g., with a `Condvar` or `std::thread::park`, \
+                        or sleeping in the loop",
+                    );
+                },
+            }
+        },
+    );
+}
+
+/// Gets the expressions of the statements of `block`, and its trailing expression.
+fn block_exprs<'tcx>(block: &'tcx Block<'tcx>) -> impl Iterator<Item = &'tcx Expr<'tcx>> {
+    block
+        .stmts
+        .iter()
+        .filter_map(|stmt| match stmt.kind {
+            StmtKind::Expr(e) | StmtKind::Semi(e) => Some(e),
+            StmtKind::Local(local) => local.init,
+            StmtKind::Item(_) => None,
+        })
+        .chain(block.expr)
+}
+
+/// Checks whether `e` blocks, sleeps, yields or awaits anywhere.
+fn is_blocking<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> bool {
+    for_each_expr(e, |e| {
+        let name = match e.kind {
+            ExprKind::Yield(..) => return ControlFlow::Break(()),
+            ExprKind::MethodCall(path, ..) => path.ident.name,
+            ExprKind::Call(..) => match fn_def_id(cx, e) {
+                Some(id) => cx.tcx.item_name(id),
+                None => return ControlFlow::Continue(()),
+            },
+            _ => return ControlFlow::Continue(()),
+        };
+        if BLOCKING_CALLS.contains(&name.as_str()) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_some()
+}
+
+/// Finds the first call in `e` that polls for a message, a lock or the value of an atomic.
+fn find_poll<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> Option<&'tcx Expr<'tcx>> {
+    for_each_expr(e, |e| {
+        if is_poll(cx, e) {
+            ControlFlow::Break(e)
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+}
+
+fn is_poll(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    match e.kind {
+        ExprKind::MethodCall(path, ..) => {
+            matches!(path.ident.as_str(), "try_recv" | "try_lock" | "try_read" | "try_write")
+                || atomic_load_ty(cx, e).is_some()
+        },
+        _ => false,
+    }
+}
+
+/// If `e` loads the value of an atomic, gets the name of the atomic type, e.g., `AtomicBool`.
+fn atomic_load_ty(cx: &LateContext<'_>, e: &Expr<'_>) -> Option<Symbol> {
+    if let ExprKind::MethodCall(path, recv, ..) = e.kind
+        && path.ident.name == sym::load
+        && let ty::Adt(def, _) = cx.typeck_results().expr_ty_adjusted(recv).peel_refs().kind()
+    {
+        cx.tcx
+            .get_diagnostic_name(def.did())
+            .filter(|name| name.as_str().starts_with("Atomic"))
+    } else {
+        None
+    }
+}
+
+/// Checks whether the loop is left when `poll` fails, e.g., in
+/// `loop { match rx.try_recv() { Ok(x) => handle(x), Err(_) => break } }`, which drains a channel.
+fn exits_on_failure<'tcx>(cx: &LateContext<'tcx>, poll: &'tcx Expr<'tcx>) -> bool {
+    let mut child = poll;
+    while let Some(parent) = get_parent_expr(cx, child) {
+        match parent.kind {
+            ExprKind::Let(local) if local.init.hir_id == child.hir_id => {
+                let Some(ExprKind::If(_, then, els)) = get_parent_expr(cx, parent).map(|e| e.kind) else {
+                    return false;
+                };
+                return if is_success_pat(cx, local.pat) {
+                    els.is_some_and(exits)
+                } else {
+                    exits(then)
+                };
+            },
+            ExprKind::Match(scrutinee, arms, MatchSource::Normal) if scrutinee.hir_id == child.hir_id => {
+                return arms.iter().any(|arm| !is_success_pat(cx, arm.pat) && exits(arm.body));
+            },
+            ExprKind::Loop(..) | ExprKind::Closure(..) => return false,
+            _ => child = parent,
+        }
+    }
+    false
+}
+
+/// Checks whether `pat` only matches successful polls, i.e., `Ok(..)` or `Some(..)`.
+fn is_success_pat(cx: &LateContext<'_>, pat: &Pat<'_>) -> bool {
+    if let PatKind::TupleStruct(ref qpath, ..) = pat.kind {
+        let res = cx.qpath_res(qpath, pat.hir_id);
+        is_res_lang_ctor(cx, res, LangItem::ResultOk) || is_res_lang_ctor(cx, res, LangItem::OptionSome)
+    } else {
+        false
+    }
+}
+
+fn exits(e: &Expr<'_>) -> bool {
+    for_each_expr(e, |e| {
+        if matches!(e.kind, ExprKind::Break(..) | ExprKind::Ret(..)) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_some()
+}
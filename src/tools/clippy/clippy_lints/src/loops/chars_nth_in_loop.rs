@@ -0,0 +1,85 @@
+use super::CHARS_NTH_IN_LOOP;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{higher, is_integer_const, path_to_local, path_to_local_id, SpanlessEq};
+use core::ops::ControlFlow;
+use rustc_hir::{BinOpKind, Expr, ExprKind, HirId, Pat, PatKind};
+use rustc_lint::LateContext;
+
+/// Checks `for i in 0..s.len() { .. }` loops.
+pub(super) fn check_for_loop<'tcx>(
+    cx: &LateContext<'tcx>,
+    pat: &'tcx Pat<'_>,
+    arg: &'tcx Expr<'_>,
+    body: &'tcx Expr<'_>,
+) {
+    if let PatKind::Binding(_, counter, ..) = pat.kind
+        && let Some(range) = higher::Range::hir(arg)
+        && let Some(end) = range.end
+    {
+        check(cx, counter, end, body);
+    }
+}
+
+/// Checks `while i < s.len() { ..; i += 1; }` loops.
+pub(super) fn check_while_loop<'tcx>(cx: &LateContext<'tcx>, cond: &'tcx Expr<'_>, body: &'tcx Expr<'_>) {
+    if let ExprKind::Binary(op, lhs, end) = cond.kind
+        && matches!(op.node, BinOpKind::Lt | BinOpKind::Le)
+        && let Some(counter) = path_to_local(lhs)
+        && is_incremented(cx, counter, body)
+    {
+        check(cx, counter, end, body);
+    }
+}
+
+fn check<'tcx>(cx: &LateContext<'tcx>, counter: HirId, end: &'tcx Expr<'_>, body: &'tcx Expr<'_>) {
+    for_each_expr(body, |e| {
+        if let ExprKind::MethodCall(path, chars, [index], _) = e.kind
+            && path.ident.as_str() == "nth"
+            && path_to_local_id(index, counter)
+            && let ExprKind::MethodCall(chars_path, s, [], _) = chars.kind
+            && chars_path.ident.as_str() == "chars"
+            && cx.typeck_results().expr_ty_adjusted(s).peel_refs().is_str()
+            && !e.span.from_expansion()
+        {
+            let s_snip = snippet(cx, s.span, "..");
+            span_lint_and_then(
+                cx,
+                CHARS_NTH_IN_LOOP,
+                e.span,
+                "getting a char with `chars().nth()` in a loop",
+                |diag| {
+                    if let ExprKind::MethodCall(len_path, len_recv, [], _) = end.kind
+                        && len_path.ident.as_str() == "len"
+                        && SpanlessEq::new(cx).eq_expr(len_recv, s)
+                    {
+                        diag.span_note(
+                            end.span,
+                            "this is the length in bytes, which is more than the number of chars if the string isn't \
+                            ASCII",
+                        );
+                    }
+                    diag.help(format!(
+                        "`nth` goes through all the chars before the one it returns, consider iterating over \
+                        `{s_snip}.chars()`, or `{s_snip}.char_indices()` if the index is needed"
+                    ));
+                },
+            );
+        }
+        ControlFlow::<()>::Continue(())
+    });
+}
+
+/// Checks whether the local `counter` is incremented by one in `body`, e.g., `i += 1`.
+fn is_incremented(cx: &LateContext<'_>, counter: HirId, body: &Expr<'_>) -> bool {
+    for_each_expr(body, |e| match e.kind {
+        ExprKind::AssignOp(op, place, amount)
+            if op.node == BinOpKind::Add && path_to_local_id(place, counter) && is_integer_const(cx, amount, 1) =>
+        {
+            ControlFlow::Break(())
+        },
+        _ => ControlFlow::Continue(()),
+    })
+    .is_some()
+}
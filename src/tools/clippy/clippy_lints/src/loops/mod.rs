@@ -1,3 +1,5 @@
+mod busy_wait_loop;
+mod chars_nth_in_loop;
 mod empty_loop;
 mod explicit_counter_loop;
 mod explicit_into_iter_loop;
@@ -13,6 +15,7 @@
 mod needless_range_loop;
 mod never_loop;
 mod same_item_push;
+mod sequential_await_in_loop;
 mod single_element_loop;
 mod utils;
 mod while_immutable_condition;
@@ -607,6 +610,44 @@
     "checking for emptiness of a `Vec` in the loop condition and popping an element in the body"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for loops that poll a channel, a lock or an atomic, e.g., with `try_recv`, `try_lock`
+    /// or `load`, and do nothing else, without ever sleeping, yielding or awaiting.
+    ///
+    /// ### Why is this bad?
+    /// The loop keeps a CPU core busy while it waits, and takes it away from the thread it's
+    /// waiting for. Blocking until the value is ready, e.g., with `recv` or `lock`, lets the thread
+    /// sleep instead.
+    ///
+    /// ### Known problems
+    /// Loops that also do other work, or that are left when polling fails, aren't linted. Calls to
+    /// functions which block internally aren't recognized.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::sync::mpsc::Receiver;
+    /// fn wait_for_message(rx: &Receiver<String>) -> String {
+    ///     loop {
+    ///         if let Ok(message) = rx.try_recv() {
+    ///             break message;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::sync::mpsc::Receiver;
+    /// fn wait_for_message(rx: &Receiver<String>) -> String {
+    ///     rx.recv().unwrap()
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub BUSY_WAIT_LOOP,
+    pedantic,
+    "loops that poll without ever blocking, sleeping or yielding"
+}
+
 pub struct Loops {
     msrv: Msrv,
 }
@@ -615,6 +656,68 @@ pub fn new(msrv: Msrv) -> Self {
         Self { msrv }
     }
 }
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `s.chars().nth(i)` calls in loops which count `i` up, e.g., `for i in 0..s.len()`
+    /// or `while i < s.len() { ..; i += 1; }`.
+    ///
+    /// ### Why is this bad?
+    /// `nth` goes through all the chars before the one it returns, so the loop takes quadratic
+    /// time. Also, `s.len()` is the length in bytes, so such loops often go past the last char of
+    /// a string that isn't ASCII.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # let s = "hello";
+    /// for i in 0..s.len() {
+    ///     let c = s.chars().nth(i).unwrap();
+    ///     println!("{i}: {c}");
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let s = "hello";
+    /// for (i, c) in s.chars().enumerate() {
+    ///     println!("{i}: {c}");
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub CHARS_NTH_IN_LOOP,
+    perf,
+    "getting each char of a string with `chars().nth(i)` in a loop"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `for` loops which await one future in each iteration, where the future doesn't
+    /// depend on what the previous iterations did, e.g., `for id in ids { fetch(id).await; }`.
+    ///
+    /// ### Why is this bad?
+    /// Each future only starts after the previous one finished, so the loop takes as long as all
+    /// the futures together. If they're independent, they can run concurrently instead.
+    ///
+    /// ### Known problems
+    /// Running the futures concurrently changes the order of their side effects, and may start
+    /// more requests at once than a server accepts, in which case `buffer_unordered` can limit
+    /// them. Dependencies through shared state, e.g., a `Mutex`, aren't detected.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// let mut pages = Vec::new();
+    /// for url in urls {
+    ///     pages.push(client.get(url).await);
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// let pages = futures::future::join_all(urls.into_iter().map(|url| client.get(url))).await;
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub SEQUENTIAL_AWAIT_IN_LOOP,
+    pedantic,
+    "awaiting independent futures one at a time in a loop"
+}
+
 impl_lint_pass!(Loops => [
     MANUAL_MEMCPY,
     MANUAL_FLATTEN,
@@ -634,7 +737,10 @@ pub fn new(msrv: Msrv) -> Self {
     SINGLE_ELEMENT_LOOP,
     MISSING_SPIN_LOOP,
     MANUAL_FIND,
-    MANUAL_WHILE_LET_SOME
+    MANUAL_WHILE_LET_SOME,
+    BUSY_WAIT_LOOP,
+    CHARS_NTH_IN_LOOP,
+    SEQUENTIAL_AWAIT_IN_LOOP
 ]);
 
 impl<'tcx> LateLintPass<'tcx> for Loops {
@@ -677,6 +783,7 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
             // also check for empty `loop {}` statements, skipping those in #[panic_handler]
             empty_loop::check(cx, expr, block);
             while_let_loop::check(cx, expr, block);
+            busy_wait_loop::check(cx, block, None, expr.span);
         }
 
         while_let_on_iterator::check(cx, expr);
@@ -685,6 +792,10 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
             while_immutable_condition::check(cx, condition, body);
             missing_spin_loop::check(cx, condition, body);
             manual_while_let_some::check(cx, condition, body, span);
+            chars_nth_in_loop::check_while_loop(cx, condition, body);
+            if let ExprKind::Block(block, _) = body.kind {
+                busy_wait_loop::check(cx, block, Some(condition), expr.span);
+            }
         }
     }
 
@@ -713,6 +824,8 @@ fn check_for_loop<'tcx>(
         same_item_push::check(cx, pat, arg, body, expr);
         manual_flatten::check(cx, pat, arg, body, span);
         manual_find::check(cx, pat, arg, body, span, expr);
+        chars_nth_in_loop::check_for_loop(cx, pat, arg, body);
+        sequential_await_in_loop::check(cx, pat, body);
     }
 
     fn check_for_loop_arg(&self, cx: &LateContext<'_>, _: &Pat<'_>, arg: &Expr<'_>) {
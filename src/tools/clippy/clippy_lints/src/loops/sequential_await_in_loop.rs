@@ -0,0 +1,111 @@
+use super::SEQUENTIAL_AWAIT_IN_LOOP;
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::path_to_local;
+use clippy_utils::usage::mutated_variables;
+use clippy_utils::visitors::{for_each_expr, Descend};
+use core::ops::ControlFlow;
+use rustc_hir::{Expr, ExprKind, HirId, HirIdSet, MatchSource, Node, Pat};
+use rustc_lint::LateContext;
+use rustc_span::DesugaringKind;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, pat: &'tcx Pat<'_>, body: &'tcx Expr<'_>) {
+    let mut awaits = Vec::new();
+    if collect_awaits(body, &mut awaits).is_some() {
+        return;
+    }
+    let [(await_expr, fut)] = awaits[..] else {
+        return;
+    };
+    if fut.span.from_expansion() || is_conditional(cx, await_expr, body) {
+        return;
+    }
+
+    // Locals from outside the loop which the loop changes can carry results from one iteration to
+    // the next, so neither the future nor the locals declared in the loop may use them.
+    let Some(mutated) = mutated_variables(body, cx) else {
+        return;
+    };
+    let mut loop_bindings = HirIdSet::default();
+    pat.each_binding_or_first(&mut |_, id, _, _| {
+        loop_bindings.insert(id);
+    });
+    let is_changed_by_loop = |id: HirId| {
+        mutated.contains(&id)
+            && !loop_bindings.contains(&id)
+            && !cx.tcx.hir().parent_iter(id).any(|(parent, _)| parent == body.hir_id)
+    };
+    let depends_on_previous_iteration = for_each_expr(body, |e| {
+        // `.await` resumes with the task context, which is assigned to a local on every resumption.
+        if !e.span.is_desugaring(DesugaringKind::Await)
+            && let Some(id) = path_to_local(e)
+            && is_changed_by_loop(id)
+            && (e.hir_id == fut.hir_id
+                || cx
+                    .tcx
+                    .hir()
+                    .parent_iter(e.hir_id)
+                    .take_while(|&(id, _)| id != body.hir_id)
+                    .any(|(id, node)| id == fut.hir_id || matches!(node, Node::Local(_))))
+        {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_some();
+    if depends_on_previous_iteration {
+        return;
+    }
+
+    span_lint_and_help(
+        cx,
+        SEQUENTIAL_AWAIT_IN_LOOP,
+        await_expr.span,
+        "awaiting futures one at a time in a loop",
+        None,
+        "if the futures don't depend on each other, consider running them concurrently, e.g., with \
+        `futures::future::join_all`, `FuturesUnordered`, or `StreamExt::buffer_unordered` to limit how many run \
+        at once",
+    );
+}
+
+/// Collects the `.await` expressions in `e`, together with the awaited futures. Breaks if there's
+/// control flow which could depend on the result of a future, like `break`, `?` or nested loops.
+fn collect_awaits<'tcx>(e: &'tcx Expr<'tcx>, awaits: &mut Vec<(&'tcx Expr<'tcx>, &'tcx Expr<'tcx>)>) -> Option<()> {
+    for_each_expr(e, |e| match e.kind {
+        ExprKind::Match(scrutinee, _, MatchSource::AwaitDesugar) => {
+            // `IntoFuture::into_future(fut)`
+            let fut = match scrutinee.kind {
+                ExprKind::Call(_, [fut]) => fut,
+                _ => scrutinee,
+            };
+            awaits.push((e, fut));
+            match collect_awaits(fut, awaits) {
+                Some(()) => ControlFlow::Break(()),
+                None => ControlFlow::Continue(Descend::No),
+            }
+        },
+        ExprKind::Break(..) | ExprKind::Continue(_) | ExprKind::Ret(_) | ExprKind::Loop(..) | ExprKind::Yield(..) => {
+            ControlFlow::Break(())
+        },
+        _ => ControlFlow::Continue(Descend::Yes),
+    })
+}
+
+/// Checks whether the `.await` only runs in some iterations, e.g., because it's in an `if`.
+fn is_conditional(cx: &LateContext<'_>, await_expr: &Expr<'_>, body: &Expr<'_>) -> bool {
+    cx.tcx
+        .hir()
+        .parent_iter(await_expr.hir_id)
+        .take_while(|&(id, _)| id != body.hir_id)
+        .any(|(_, node)| {
+            matches!(
+                node,
+                Node::Arm(_)
+                    | Node::Expr(Expr {
+                        kind: ExprKind::If(..) | ExprKind::Match(..),
+                        ..
+                    })
+            )
+        })
+}
@@ -140,6 +140,7 @@ fn check_ty(&mut self, cx: &LateContext<'_>, ty: &hir::Ty<'_>) {
             self.push_unique_macro_pat_ty(cx, ty.span);
         }
     }
+    #[allow(clippy::linear_contains_in_loop)]
     fn check_crate_post(&mut self, cx: &LateContext<'_>) {
         let mut used = FxHashMap::default();
         let mut check_dup = vec![];
@@ -76,6 +76,7 @@ fn check_fn(
                             if let Some(ret_pos) = position_before_rarrow(&header_snip);
                             if let Some((ret_sugg, ret_snip)) = suggested_ret(cx, output);
                             then {
+                                #[allow(clippy::unchecked_char_boundary)]
                                 let header_snip = if vis_snip.is_empty() {
                                     format!("async {}", &header_snip[..ret_pos])
                                 } else {
@@ -0,0 +1,369 @@
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_sugg};
+use clippy_utils::sugg::Sugg;
+use clippy_utils::{
+    fn_def_id, get_enclosing_block, get_parent_expr, higher, is_diag_trait_item, path_to_local, SpanlessEq,
+};
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind, HirId, PatKind, StmtKind, UnOp};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for integers which are assembled from bytes by shifting and combining them, e.g.,
+    /// `(b[0] as u32) << 8 | b[1] as u32`, or by a loop which does the same for each byte.
+    ///
+    /// ### Why is this bad?
+    /// `from_be_bytes` and `from_le_bytes` say which byte order is meant, and are harder to get
+    /// wrong, e.g., by mixing up a shift amount.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # let bytes = [0u8; 4];
+    /// let n = (bytes[0] as u32) << 24 | (bytes[1] as u32) << 16 | (bytes[2] as u32) << 8 | bytes[3] as u32;
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let bytes = [0u8; 4];
+    /// let n = u32::from_be_bytes(bytes);
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub MANUAL_FROM_BYTES,
+    complexity,
+    "assembling an integer from bytes by hand instead of using `from_be_bytes` or `from_le_bytes`"
+}
+declare_lint_pass!(ManualFromBytes => [MANUAL_FROM_BYTES]);
+
+impl<'tcx> LateLintPass<'tcx> for ManualFromBytes {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let Some(for_loop) = higher::ForLoop::hir(expr) {
+            if !for_loop.span.from_expansion() {
+                check_loop(cx, expr, &for_loop);
+            }
+        } else if let ExprKind::Binary(op, ..) = expr.kind
+            && !expr.span.from_expansion()
+            && matches!(op.node, BinOpKind::BitOr | BinOpKind::Add)
+            // Only the whole chain is checked, not the parts of it.
+            && !get_parent_expr(cx, expr).is_some_and(|parent| {
+                matches!(parent.kind, ExprKind::Binary(parent_op, ..) if parent_op.node == op.node)
+            })
+        {
+            check_chain(cx, expr, op.node);
+        }
+    }
+}
+
+/// A byte of a slice or an array, shifted into place, e.g., `(b[1] as u32) << 8`.
+struct ShiftedByte<'tcx> {
+    bytes: &'tcx Expr<'tcx>,
+    index: u128,
+    shift: u128,
+}
+
+/// Checks `a | b | ..` and `a + b + ..`, where each operand is a shifted byte.
+fn check_chain<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>, op: BinOpKind) {
+    let mut parts = Vec::new();
+    if collect_shifted_bytes(cx, expr, op, &mut parts).is_none() {
+        return;
+    }
+    let Some(first) = parts.first() else {
+        return;
+    };
+    if !parts
+        .iter()
+        .all(|part| SpanlessEq::new(cx).eq_expr(part.bytes, first.bytes))
+    {
+        return;
+    }
+    // Each byte is used once, and the bytes are next to each other.
+    let start = parts.iter().map(|part| part.index).min().unwrap_or_default();
+    let len = parts.len() as u128;
+    let mut indices: Vec<_> = parts.iter().map(|part| part.index - start).collect();
+    indices.sort_unstable();
+    if !indices.iter().copied().eq(0..len) {
+        return;
+    }
+    let order = if parts
+        .iter()
+        .all(|part| part.shift == 8 * (start + len - 1 - part.index))
+    {
+        "be"
+    } else if parts.iter().all(|part| part.shift == 8 * (part.index - start)) {
+        "le"
+    } else {
+        return;
+    };
+    let ty = cx.typeck_results().expr_ty(expr);
+    let Some(from_ty) = bytes_int_ty(ty, parts.len()) else {
+        return;
+    };
+
+    let mut app = Applicability::MaybeIncorrect;
+    let bytes = first.bytes;
+    let bytes_sugg = Sugg::hir_with_applicability(cx, bytes, "..", &mut app);
+    let bytes_ty = cx.typeck_results().expr_ty(bytes);
+    let arg = match bytes_ty.kind() {
+        _ if start == 0 && array_len(cx, bytes_ty) == Some(len) => bytes_sugg.to_string(),
+        ty::Ref(_, inner, _) if start == 0 && array_len(cx, *inner) == Some(len) => bytes_sugg.deref().to_string(),
+        _ => format!(
+            "{}[{start}..{}].try_into().unwrap()",
+            bytes_sugg.maybe_par(),
+            start + len
+        ),
+    };
+    let from_bytes = format!("{from_ty}::from_{order}_bytes({arg})");
+    let sugg = if from_ty == ty.to_string() {
+        from_bytes
+    } else {
+        format!("{ty}::from({from_bytes})")
+    };
+    span_lint_and_sugg(
+        cx,
+        MANUAL_FROM_BYTES,
+        expr.span,
+        "manually assembling an integer from bytes",
+        &format!("consider using `{from_ty}::from_{order}_bytes`"),
+        sugg,
+        app,
+    );
+}
+
+/// Collects the operands of the `op` chain `e`, if they're all shifted bytes.
+fn collect_shifted_bytes<'tcx>(
+    cx: &LateContext<'tcx>,
+    e: &'tcx Expr<'tcx>,
+    op: BinOpKind,
+    parts: &mut Vec<ShiftedByte<'tcx>>,
+) -> Option<()> {
+    match e.kind {
+        ExprKind::Binary(bin_op, lhs, rhs) if bin_op.node == op => {
+            collect_shifted_bytes(cx, lhs, op, parts)?;
+            collect_shifted_bytes(cx, rhs, op, parts)
+        },
+        _ => {
+            let (widened, shift) = match e.kind {
+                ExprKind::Binary(bin_op, lhs, rhs) if bin_op.node == BinOpKind::Shl => (lhs, const_int(cx, rhs)?),
+                _ => (e, 0),
+            };
+            let ExprKind::Index(bytes, index) = widened_byte(cx, widened)?.kind else {
+                return None;
+            };
+            parts.push(ShiftedByte {
+                bytes,
+                index: const_int(cx, index)?,
+                shift,
+            });
+            Some(())
+        },
+    }
+}
+
+/// Checks `for b in bytes { acc = acc << 8 | b as u32 }`, where `acc` starts at zero.
+fn check_loop<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>, for_loop: &higher::ForLoop<'tcx>) {
+    let pat = match for_loop.pat.kind {
+        PatKind::Ref(pat, _) => pat,
+        _ => for_loop.pat,
+    };
+    let PatKind::Binding(_, byte_id, ..) = pat.kind else {
+        return;
+    };
+    let ExprKind::Block(body, _) = for_loop.body.kind else {
+        return;
+    };
+    let Some(stmts) = body
+        .stmts
+        .iter()
+        .map(|stmt| match stmt.kind {
+            StmtKind::Expr(e) | StmtKind::Semi(e) => Some(e),
+            _ => None,
+        })
+        .chain(body.expr.map(Some))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return;
+    };
+    let acc = match stmts[..] {
+        [step] => shift_and_combine(cx, step, byte_id),
+        [shift, combine] => shift_then_combine(cx, shift, combine, byte_id),
+        _ => None,
+    };
+    let Some(acc) = acc else {
+        return;
+    };
+    let Some(acc_id) = path_to_local(acc) else {
+        return;
+    };
+    let (bytes, reversed) = peel_iter_calls(for_loop.arg);
+    let Some(len) = bytes_len(cx, bytes) else {
+        return;
+    };
+    let ty = cx.typeck_results().expr_ty(acc);
+    if let Ok(len) = usize::try_from(len)
+        && let Some(from_ty) = bytes_int_ty(ty, len)
+        && starts_at_zero(cx, expr, acc_id)
+    {
+        let order = if reversed { "le" } else { "be" };
+        span_lint_and_help(
+            cx,
+            MANUAL_FROM_BYTES,
+            for_loop.span,
+            "manually assembling an integer from bytes",
+            None,
+            &format!("consider using `{from_ty}::from_{order}_bytes`"),
+        );
+    }
+}
+
+/// Checks `acc = acc << 8 | b as u32`, and gets `acc`.
+fn shift_and_combine<'tcx>(cx: &LateContext<'tcx>, step: &'tcx Expr<'tcx>, byte_id: HirId) -> Option<&'tcx Expr<'tcx>> {
+    let ExprKind::Assign(acc, value, _) = step.kind else {
+        return None;
+    };
+    let ExprKind::Binary(op, lhs, rhs) = value.kind else {
+        return None;
+    };
+    let (shifted, byte) = match lhs.kind {
+        ExprKind::Binary(shl, ..) if shl.node == BinOpKind::Shl => (lhs, rhs),
+        _ => (rhs, lhs),
+    };
+    (matches!(op.node, BinOpKind::BitOr | BinOpKind::Add)
+        && is_shifted_by_a_byte(cx, shifted, acc)
+        && is_loop_byte(cx, byte, byte_id))
+    .then_some(acc)
+}
+
+/// Checks `acc <<= 8; acc |= b as u32;`, and gets `acc`.
+fn shift_then_combine<'tcx>(
+    cx: &LateContext<'tcx>,
+    shift: &'tcx Expr<'tcx>,
+    combine: &'tcx Expr<'tcx>,
+    byte_id: HirId,
+) -> Option<&'tcx Expr<'tcx>> {
+    if let ExprKind::AssignOp(shl, acc, amount) = shift.kind
+        && let ExprKind::AssignOp(op, combined, byte) = combine.kind
+        && shl.node == BinOpKind::Shl
+        && const_int(cx, amount) == Some(8)
+        && matches!(op.node, BinOpKind::BitOr | BinOpKind::Add)
+        && SpanlessEq::new(cx).eq_expr(acc, combined)
+        && is_loop_byte(cx, byte, byte_id)
+    {
+        Some(acc)
+    } else {
+        None
+    }
+}
+
+/// Checks whether `e` is `acc << 8`.
+fn is_shifted_by_a_byte(cx: &LateContext<'_>, e: &Expr<'_>, acc: &Expr<'_>) -> bool {
+    matches!(e.kind, ExprKind::Binary(op, lhs, rhs)
+        if op.node == BinOpKind::Shl
+            && SpanlessEq::new(cx).eq_expr(lhs, acc)
+            && const_int(cx, rhs) == Some(8))
+}
+
+/// Checks whether `e` is the byte bound by the loop, widened, e.g., `*b as u32`.
+fn is_loop_byte<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>, byte_id: HirId) -> bool {
+    let Some(byte) = widened_byte(cx, e) else {
+        return false;
+    };
+    let byte = match byte.kind {
+        ExprKind::Unary(UnOp::Deref, inner) => inner,
+        _ => byte,
+    };
+    path_to_local(byte) == Some(byte_id)
+}
+
+/// Checks whether the local `acc` is declared with an initial value of zero right before the
+/// loop `expr`.
+fn starts_at_zero(cx: &LateContext<'_>, expr: &Expr<'_>, acc: HirId) -> bool {
+    let Some(block) = get_enclosing_block(cx, expr.hir_id) else {
+        return false;
+    };
+    let Some(pos) = block.stmts.iter().position(|stmt| match stmt.kind {
+        StmtKind::Expr(e) | StmtKind::Semi(e) => e.hir_id == expr.hir_id,
+        _ => false,
+    }) else {
+        return false;
+    };
+    pos > 0
+        && matches!(block.stmts[pos - 1].kind, StmtKind::Local(local)
+            if local.pat.hir_id == acc && local.init.is_some_and(|init| const_int(cx, init) == Some(0)))
+}
+
+/// If `e` widens a `u8`, e.g., `b as u32` or `u32::from(b)`, gets the byte.
+fn widened_byte<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> Option<&'tcx Expr<'tcx>> {
+    let byte = match e.kind {
+        ExprKind::Cast(byte, _) => byte,
+        ExprKind::Call(_, [byte]) if fn_def_id(cx, e).is_some_and(|id| is_diag_trait_item(cx, id, sym::From)) => byte,
+        _ => return None,
+    };
+    (*cx.typeck_results().expr_ty(byte).kind() == ty::Uint(ty::UintTy::U8)).then_some(byte)
+}
+
+/// Removes `iter()`, `into_iter()`, `copied()` and `rev()` calls from the iterated expression, and
+/// checks whether the bytes are iterated in reverse.
+fn peel_iter_calls<'tcx>(mut e: &'tcx Expr<'tcx>) -> (&'tcx Expr<'tcx>, bool) {
+    let mut reversed = false;
+    while let ExprKind::MethodCall(path, recv, [], _) = e.kind {
+        match path.ident.as_str() {
+            "iter" | "into_iter" | "copied" | "cloned" => {},
+            "rev" => reversed = !reversed,
+            _ => break,
+        }
+        e = recv;
+    }
+    (e, reversed)
+}
+
+/// Gets the number of bytes in `e`, which is an array, or a slice of a constant range.
+fn bytes_len(cx: &LateContext<'_>, e: &Expr<'_>) -> Option<u128> {
+    let e = match e.kind {
+        ExprKind::AddrOf(_, _, inner) => inner,
+        _ => e,
+    };
+    if let ExprKind::Index(_, index) = e.kind
+        && let Some(range) = higher::Range::hir(index)
+        && let Some(end) = range.end
+    {
+        let start = range.start.map_or(Some(0), |start| const_int(cx, start))?;
+        return const_int(cx, end)?.checked_sub(start);
+    }
+    array_len(cx, cx.typeck_results().expr_ty(e).peel_refs())
+}
+
+fn array_len<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> Option<u128> {
+    match ty.kind() {
+        ty::Array(elem, len) if *elem == cx.tcx.types.u8 => {
+            len.try_eval_target_usize(cx.tcx, cx.param_env).map(u128::from)
+        },
+        _ => None,
+    }
+}
+
+/// Gets the integer type which has exactly `len` bytes, and which can be converted to `ty`
+/// without loss, e.g., `u16` for 2 bytes in a `u32`.
+fn bytes_int_ty(ty: Ty<'_>, len: usize) -> Option<String> {
+    let (bits, signed) = match *ty.kind() {
+        ty::Int(ity) => (ity.bit_width()?, true),
+        ty::Uint(uty) => (uty.bit_width()?, false),
+        _ => return None,
+    };
+    let len_bits = 8 * len as u64;
+    if !matches!(len, 2 | 4 | 8 | 16) || len_bits > bits {
+        None
+    } else if signed && len_bits == bits {
+        Some(format!("i{len_bits}"))
+    } else {
+        Some(format!("u{len_bits}"))
+    }
+}
+
+fn const_int(cx: &LateContext<'_>, e: &Expr<'_>) -> Option<u128> {
+    match constant(cx, cx.typeck_results(), e)? {
+        Constant::Int(value) => Some(value),
+        _ => None,
+    }
+}
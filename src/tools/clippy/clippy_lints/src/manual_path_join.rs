@@ -0,0 +1,223 @@
+use crate::hardcoded_platform_path::{path_fn_args, PATH_FNS};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::macros::{find_format_arg_expr, find_format_args, root_macro_call_first_node};
+use clippy_utils::source::snippet_with_context;
+use clippy_utils::ty::is_type_lang_item;
+use clippy_utils::{def_path_def_ids, expr_or_init};
+use rustc_ast::{FormatArgsPiece, FormatOptions, FormatTrait, LitKind};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::Applicability;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{BinOpKind, BorrowKind, Expr, ExprKind, HirId, LangItem};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Span, SyntaxContext};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for paths which are built with a hard-coded `/` separator, like
+    /// `format!("{}/{}", dir, file)` or `dir + "/" + file`, and then passed to functions like
+    /// `Path::new`, `File::open` or `std::fs::read`, either directly or through a local.
+    ///
+    /// ### Why is this bad?
+    /// The separator isn't `/` on all platforms. On Windows, the result mixes separators, and it
+    /// breaks if a part is a verbatim path like `\\?\C:\data`. It also doesn't handle parts which
+    /// already end with a separator, or are absolute. `Path::join` handles all of these.
+    ///
+    /// ### Known problems
+    /// Only parts of type `str` or `String` are checked.
+    ///
+    /// ### Example
+    /// ```rust,no_run
+    /// # let dir = "data";
+    /// let path = format!("{}/{}", dir, "config.toml");
+    /// let config = std::fs::read_to_string(path);
+    /// ```
+    /// Use instead:
+    /// ```rust,no_run
+    /// # use std::path::Path;
+    /// # let dir = "data";
+    /// let path = Path::new(dir).join("config.toml");
+    /// let config = std::fs::read_to_string(path);
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub MANUAL_PATH_JOIN,
+    pedantic,
+    "building a path with a hard-coded `/` separator instead of using `Path::join`"
+}
+
+#[derive(Default)]
+pub struct ManualPathJoin {
+    path_fns: FxHashSet<DefId>,
+    /// The paths which were already linted, as they can be used by several calls.
+    linted: FxHashSet<HirId>,
+}
+
+impl_lint_pass!(ManualPathJoin => [MANUAL_PATH_JOIN]);
+
+impl<'tcx> LateLintPass<'tcx> for ManualPathJoin {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for path in PATH_FNS {
+            let segs: Vec<_> = path.split("::").collect();
+            self.path_fns.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        let Some(args) = path_fn_args(cx, &self.path_fns, expr) else {
+            return;
+        };
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+        for arg in args {
+            let arg = match arg.kind {
+                ExprKind::AddrOf(BorrowKind::Ref, _, inner) => inner,
+                _ => arg,
+            };
+            let path = expr_or_init(cx, arg);
+            if self.linted.contains(&path.hir_id) {
+                continue;
+            }
+            let mut app = Applicability::MaybeIncorrect;
+            let Some((span, parts)) = format_parts(cx, path, &mut app).or_else(|| add_parts(cx, path, &mut app)) else {
+                continue;
+            };
+            self.linted.insert(path.hir_id);
+
+            let mut sugg = format!("Path::new({})", parts[0]);
+            for part in &parts[1..] {
+                sugg.push_str(&format!(".join({part})"));
+            }
+            span_lint_and_then(
+                cx,
+                MANUAL_PATH_JOIN,
+                span,
+                "building a path with a hard-coded `/` separator",
+                |diag| {
+                    if path.hir_id != arg.hir_id {
+                        diag.span_note(arg.span, "the path is used here");
+                    }
+                    diag.help(format!("consider using `Path::join` instead: `{sugg}`"));
+                },
+            );
+        }
+    }
+}
+
+/// Checks whether `e` is `format!("{}/{}", ..)` with any number of parts, and returns the span of
+/// the `format!` call and the snippets of the parts, with the first one usable for `Path::new`.
+fn format_parts(cx: &LateContext<'_>, e: &Expr<'_>, app: &mut Applicability) -> Option<(Span, Vec<String>)> {
+    let macro_call = root_macro_call_first_node(cx, e)?;
+    // `format!` is an external macro itself, so only the span of its call is checked.
+    if !cx.tcx.is_diagnostic_item(sym::format_macro, macro_call.def_id) || in_external_macro(cx.sess(), macro_call.span)
+    {
+        return None;
+    }
+    let ctxt = macro_call.span.ctxt();
+    let mut parts = None;
+    find_format_args(cx, e, macro_call.expn, |format_args| {
+        if format_args.template.len() < 3 {
+            return;
+        }
+        let all_args = format_args.arguments.all_args();
+        let mut snippets = Vec::new();
+        for (i, piece) in format_args.template.iter().enumerate() {
+            match piece {
+                FormatArgsPiece::Literal(lit) if i % 2 == 1 && lit.as_str() == "/" => {},
+                FormatArgsPiece::Placeholder(placeholder)
+                    if i % 2 == 0
+                        && placeholder.format_trait == FormatTrait::Display
+                        && placeholder.format_options == FormatOptions::default()
+                        && let Ok(index) = placeholder.argument.index
+                        && let Some(arg) = all_args.get(index)
+                        && let Ok(arg) = find_format_arg_expr(e, arg)
+                        && let Some(snippet) = part_snippet(cx, arg, ctxt, snippets.is_empty(), app) =>
+                {
+                    snippets.push(snippet);
+                },
+                _ => return,
+            }
+        }
+        // The template has to end with a part, not with a separator.
+        if format_args.template.len() % 2 == 1 {
+            parts = Some((macro_call.span, snippets));
+        }
+    });
+    parts
+}
+
+/// Checks whether `e` is `dir + "/" + file` with any number of parts, and returns the span of the
+/// expression and the snippets of the parts, with the first one usable for `Path::new`.
+fn add_parts<'tcx>(
+    cx: &LateContext<'tcx>,
+    e: &'tcx Expr<'tcx>,
+    app: &mut Applicability,
+) -> Option<(Span, Vec<String>)> {
+    if e.span.from_expansion() {
+        return None;
+    }
+    let mut operands = Vec::new();
+    collect_add_operands(e, &mut operands);
+    if operands.len() < 3 || operands.len() % 2 == 0 {
+        return None;
+    }
+    let mut snippets = Vec::new();
+    for (i, operand) in operands.iter().enumerate() {
+        if i % 2 == 1 {
+            if let ExprKind::Lit(lit) = operand.kind
+                && let LitKind::Str(sep, _) = lit.node
+                && sep.as_str() == "/"
+            {
+                continue;
+            }
+            return None;
+        }
+        // `dir.to_owned() + "/" + file`
+        let operand = match operand.kind {
+            ExprKind::MethodCall(path, recv, [], _)
+                if i == 0 && matches!(path.ident.as_str(), "to_owned" | "to_string" | "clone") =>
+            {
+                recv
+            },
+            _ => operand,
+        };
+        snippets.push(part_snippet(cx, operand, e.span.ctxt(), i == 0, app)?);
+    }
+    Some((e.span, snippets))
+}
+
+fn collect_add_operands<'tcx>(e: &'tcx Expr<'tcx>, operands: &mut Vec<&'tcx Expr<'tcx>>) {
+    if let ExprKind::Binary(op, lhs, rhs) = e.kind
+        && op.node == BinOpKind::Add
+    {
+        collect_add_operands(lhs, operands);
+        operands.push(rhs);
+    } else {
+        operands.push(e);
+    }
+}
+
+/// Gets the snippet of a part of the path, if it's a `str` or a `String`. `Path::new` only takes
+/// references, so `first` adds a `&` to owned parts.
+fn part_snippet(
+    cx: &LateContext<'_>,
+    part: &Expr<'_>,
+    ctxt: SyntaxContext,
+    first: bool,
+    app: &mut Applicability,
+) -> Option<String> {
+    let ty = cx.typeck_results().expr_ty(part);
+    let peeled = ty.peel_refs();
+    if !peeled.is_str() && !is_type_lang_item(cx, peeled, LangItem::String) {
+        return None;
+    }
+    let (snippet, _) = snippet_with_context(cx, part.span, ctxt, "..", app);
+    if first && !matches!(ty.kind(), ty::Ref(..)) {
+        Some(format!("&{snippet}"))
+    } else {
+        Some(snippet.into_owned())
+    }
+}
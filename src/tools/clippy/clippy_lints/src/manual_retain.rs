@@ -37,6 +37,7 @@
     /// let mut vec = vec![0, 1, 2];
     /// vec = vec.iter().filter(|&x| x % 2 == 0).copied().collect();
     /// vec = vec.into_iter().filter(|x| x % 2 == 0).collect();
+    /// vec = vec.iter().cloned().filter(|x| x % 2 == 0).collect();
     /// ```
     /// Use instead:
     /// ```rust
@@ -74,6 +75,7 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'_>) {
         {
             check_into_iter(cx, parent_expr, left_expr, target_expr, &self.msrv);
             check_iter(cx, parent_expr, left_expr, target_expr, &self.msrv);
+            check_cloned_iter(cx, parent_expr, left_expr, target_expr, &self.msrv);
             check_to_owned(cx, parent_expr, left_expr, target_expr, &self.msrv);
         }
     }
@@ -96,7 +98,7 @@ fn check_into_iter(
         && Some(into_iter_def_id) == cx.tcx.lang_items().into_iter_fn()
         && match_acceptable_type(cx, left_expr, msrv)
         && SpanlessEq::new(cx).eq_expr(left_expr, struct_expr) {
-        suggest(cx, parent_expr, left_expr, target_expr);
+        suggest(cx, parent_expr, left_expr, target_expr, false);
     }
 }
 
@@ -119,7 +121,30 @@ fn check_iter(
         && match_acceptable_def_path(cx, iter_expr_def_id)
         && match_acceptable_type(cx, left_expr, msrv)
         && SpanlessEq::new(cx).eq_expr(left_expr, struct_expr) {
-        suggest(cx, parent_expr, left_expr, filter_expr);
+        suggest(cx, parent_expr, left_expr, filter_expr, true);
+    }
+}
+
+fn check_cloned_iter(
+    cx: &LateContext<'_>,
+    parent_expr: &hir::Expr<'_>,
+    left_expr: &hir::Expr<'_>,
+    target_expr: &hir::Expr<'_>,
+    msrv: &Msrv,
+) {
+    if let hir::ExprKind::MethodCall(_, copied_expr, [_], _) = &target_expr.kind
+        && let Some(filter_def_id) = cx.typeck_results().type_dependent_def_id(target_expr.hir_id)
+        && match_def_path(cx, filter_def_id, &paths::CORE_ITER_FILTER)
+        && let hir::ExprKind::MethodCall(_, iter_expr, [], _) = &copied_expr.kind
+        && let Some(copied_def_id) = cx.typeck_results().type_dependent_def_id(copied_expr.hir_id)
+        && (match_def_path(cx, copied_def_id, &paths::CORE_ITER_COPIED)
+            || match_def_path(cx, copied_def_id, &paths::CORE_ITER_CLONED))
+        && let hir::ExprKind::MethodCall(_, struct_expr, [], _) = &iter_expr.kind
+        && let Some(iter_expr_def_id) = cx.typeck_results().type_dependent_def_id(iter_expr.hir_id)
+        && match_acceptable_def_path(cx, iter_expr_def_id)
+        && match_acceptable_type(cx, left_expr, msrv)
+        && SpanlessEq::new(cx).eq_expr(left_expr, struct_expr) {
+        suggest(cx, parent_expr, left_expr, target_expr, false);
     }
 }
 
@@ -143,30 +168,58 @@ fn check_to_owned(
         && let ty = cx.typeck_results().expr_ty(str_expr).peel_refs()
         && is_type_lang_item(cx, ty, hir::LangItem::String)
         && SpanlessEq::new(cx).eq_expr(left_expr, str_expr) {
-        suggest(cx, parent_expr, left_expr, filter_expr);
+        suggest(cx, parent_expr, left_expr, filter_expr, true);
     }
 }
 
-fn suggest(cx: &LateContext<'_>, parent_expr: &hir::Expr<'_>, left_expr: &hir::Expr<'_>, filter_expr: &hir::Expr<'_>) {
+/// `ref_item` is `true` when the closure passed to `filter` gets one reference more than the
+/// closure passed to `retain` would, e.g., `&&T` instead of `&T`. A `&` pattern is then removed
+/// from the parameter, otherwise it's kept as it is.
+fn suggest(
+    cx: &LateContext<'_>,
+    parent_expr: &hir::Expr<'_>,
+    left_expr: &hir::Expr<'_>,
+    filter_expr: &hir::Expr<'_>,
+    ref_item: bool,
+) {
     if let hir::ExprKind::MethodCall(_, _, [closure], _) = filter_expr.kind
         && let hir::ExprKind::Closure(&hir::Closure { body, ..}) = closure.kind
         && let filter_body = cx.tcx.hir().body(body)
         && let [filter_params] = filter_body.params
-        && let Some(sugg) = match filter_params.pat.kind {
+        && let Some((sugg, applicability)) = match filter_params.pat.kind {
             hir::PatKind::Binding(_, _, filter_param_ident, None) => {
-                Some(format!("{}.retain(|{filter_param_ident}| {})", snippet(cx, left_expr.span, ".."), snippet(cx, filter_body.value.span, "..")))
+                // The body is written for the parameter with the additional reference, which
+                // might not compile anymore without it.
+                let applicability = if ref_item {
+                    Applicability::MaybeIncorrect
+                } else {
+                    Applicability::MachineApplicable
+                };
+                Some((
+                    format!("{}.retain(|{filter_param_ident}| {})", snippet(cx, left_expr.span, ".."), snippet(cx, filter_body.value.span, "..")),
+                    applicability,
+                ))
             },
             hir::PatKind::Tuple([key_pat, value_pat], _) => {
-                make_sugg(cx, key_pat, value_pat, left_expr, filter_body)
+                make_sugg(cx, key_pat, value_pat, left_expr, filter_body).map(|sugg| (sugg, Applicability::MachineApplicable))
             },
-            hir::PatKind::Ref(pat, _) => {
+            hir::PatKind::Ref(pat, _) if ref_item => {
                 match pat.kind {
                     hir::PatKind::Binding(_, _, filter_param_ident, None) => {
-                        Some(format!("{}.retain(|{filter_param_ident}| {})", snippet(cx, left_expr.span, ".."), snippet(cx, filter_body.value.span, "..")))
+                        Some((
+                            format!("{}.retain(|{filter_param_ident}| {})", snippet(cx, left_expr.span, ".."), snippet(cx, filter_body.value.span, "..")),
+                            Applicability::MachineApplicable,
+                        ))
                     },
                     _ => None
                 }
             },
+            hir::PatKind::Ref(..) => {
+                Some((
+                    format!("{}.retain(|{}| {})", snippet(cx, left_expr.span, ".."), snippet(cx, filter_params.pat.span, ".."), snippet(cx, filter_body.value.span, "..")),
+                    Applicability::MachineApplicable,
+                ))
+            },
             _ => None
         } {
         span_lint_and_sugg(
@@ -176,7 +229,7 @@ fn suggest(cx: &LateContext<'_>, parent_expr: &hir::Expr<'_>, left_expr: &hir::E
             "this expression can be written more simply using `.retain()`",
             "consider calling `.retain()` instead",
             sugg,
-            Applicability::MachineApplicable
+            applicability,
         );
     }
 }
@@ -0,0 +1,82 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::macros::{find_format_args, root_macro_call_first_node};
+use clippy_utils::ty::match_type;
+use clippy_utils::{last_path_segment, path_to_local, paths};
+use rustc_ast as ast;
+use rustc_hir::{Expr, ExprKind, Node};
+use rustc_lint::LateContext;
+use rustc_span::{sym, Symbol};
+
+use super::FORMAT_IN_SHELL_COMMAND;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, name: Symbol, recv: &'tcx Expr<'_>, args: &'tcx [Expr<'_>]) {
+    if name.as_str() == "arg"
+        && let [arg] = args
+        && let arg = arg.peel_borrows()
+        && let Some(macro_call) = root_macro_call_first_node(cx, arg)
+        && cx.tcx.is_diagnostic_item(sym::format_macro, macro_call.def_id)
+        && let ExprKind::MethodCall(path, command, [flag], _) = recv.kind
+        && path.ident.as_str() == "arg"
+        && let ExprKind::Lit(flag) = flag.kind
+        && let ast::LitKind::Str(flag, _) = flag.node
+        && let Some(program) = command_program(cx, command)
+        && let Some(shell) = program.rsplit(['/', '\\']).next()
+        && is_shell_with_script_flag(shell, flag.as_str())
+    {
+        let mut has_data = false;
+        find_format_args(cx, arg, macro_call.expn, |format_args| {
+            has_data = format_args
+                .arguments
+                .all_args()
+                .iter()
+                .any(|arg| !matches!(arg.expr.kind, ast::ExprKind::Lit(_)));
+        });
+        if has_data {
+            span_lint_and_help(
+                cx,
+                FORMAT_IN_SHELL_COMMAND,
+                macro_call.span,
+                &format!("formatted string is run as a script by `{shell} {flag}`"),
+                None,
+                "consider running the program without a shell, or passing the data as separate arguments",
+            );
+        }
+    }
+}
+
+fn is_shell_with_script_flag(shell: &str, flag: &str) -> bool {
+    match shell {
+        "sh" | "bash" | "dash" | "ksh" | "zsh" => flag == "-c",
+        "cmd" | "cmd.exe" => flag.eq_ignore_ascii_case("/c"),
+        _ => false,
+    }
+}
+
+/// Finds the program of the `Command` that `expr` evaluates to, if it's created with a string
+/// literal. Builder methods are skipped, and locals are followed to their initializer.
+fn command_program<'tcx>(cx: &LateContext<'tcx>, mut expr: &'tcx Expr<'tcx>) -> Option<String> {
+    loop {
+        match expr.kind {
+            ExprKind::MethodCall(_, recv, ..) => expr = recv,
+            ExprKind::Call(callee, [program]) => {
+                return if let ExprKind::Path(ref qpath) = callee.kind
+                    && last_path_segment(qpath).ident.name == sym::new
+                    && match_type(cx, cx.typeck_results().expr_ty(expr), &paths::STD_PROCESS_COMMAND)
+                    && let ExprKind::Lit(program) = program.kind
+                    && let ast::LitKind::Str(program, _) = program.node
+                {
+                    Some(program.to_string())
+                } else {
+                    None
+                };
+            },
+            ExprKind::Path(_) => {
+                let Node::Local(local) = cx.tcx.hir().get_parent(path_to_local(expr)?) else {
+                    return None;
+                };
+                expr = local.init?;
+            },
+            _ => return None,
+        }
+    }
+}
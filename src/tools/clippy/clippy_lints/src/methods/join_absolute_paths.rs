@@ -0,0 +1,51 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::is_diag_item_method;
+use rustc_ast::ast::LitKind;
+use rustc_errors::Applicability;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_span::symbol::sym;
+
+use super::JOIN_ABSOLUTE_PATHS;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, join_arg: &'tcx Expr<'_>) {
+    if let Some(method_id) = cx.typeck_results().type_dependent_def_id(expr.hir_id)
+        && is_diag_item_method(cx, method_id, sym::Path)
+        && let ExprKind::Lit(lit) = join_arg.kind
+        && let LitKind::Str(path, _) = lit.node
+    {
+        let path = path.as_str();
+        if path.starts_with(['/', '\\']) {
+            span_lint_and_then(
+                cx,
+                JOIN_ABSOLUTE_PATHS,
+                join_arg.span,
+                "argument to `Path::join` starts with a path separator",
+                |diag| {
+                    diag.note("joining a rooted path replaces the path it is joined to");
+                    diag.span_suggestion(
+                        join_arg.span,
+                        "if this is unintentional, try removing the leading separator",
+                        format!("{:?}", path.trim_start_matches(['/', '\\'])),
+                        Applicability::MaybeIncorrect,
+                    );
+                },
+            );
+        } else if has_drive_prefix(path) {
+            span_lint_and_then(
+                cx,
+                JOIN_ABSOLUTE_PATHS,
+                join_arg.span,
+                "argument to `Path::join` starts with a drive prefix",
+                |diag| {
+                    diag.note("on Windows, joining a path with a drive prefix replaces the path it is joined to");
+                },
+            );
+        }
+    }
+}
+
+/// Checks whether `path` starts with a Windows drive prefix, e.g. `C:\`.
+fn has_drive_prefix(path: &str) -> bool {
+    matches!(path.as_bytes(), [drive, b':', b'/' | b'\\', ..] if drive.is_ascii_alphabetic())
+}
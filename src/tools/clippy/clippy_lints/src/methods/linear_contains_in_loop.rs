@@ -0,0 +1,52 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::get_enclosing_loop_or_multi_call_closure;
+use clippy_utils::source::snippet;
+use clippy_utils::ty::{implements_trait, is_type_diagnostic_item};
+use clippy_utils::usage::is_potentially_mutated;
+use rustc_hir::{Expr, ExprKind, LoopSource};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+use rustc_span::sym;
+
+use super::utils::outer_local;
+use super::LINEAR_CONTAINS_IN_LOOP;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, recv: &'tcx Expr<'_>) {
+    let recv_ty = cx.typeck_results().expr_ty(recv).peel_refs();
+    let elem_ty = match recv_ty.kind() {
+        ty::Slice(elem_ty) => *elem_ty,
+        ty::Adt(_, args) if is_type_diagnostic_item(cx, recv_ty, sym::Vec) => args.type_at(0),
+        _ => return,
+    };
+    let set = if let Some(hash) = cx.tcx.get_diagnostic_item(sym::Hash)
+        && implements_trait(cx, elem_ty, hash, &[])
+    {
+        "HashSet"
+    } else if let Some(ord) = cx.tcx.get_diagnostic_item(sym::Ord)
+        && implements_trait(cx, elem_ty, ord, &[])
+    {
+        "BTreeSet"
+    } else {
+        return;
+    };
+    if let Some(enclosing_loop) = get_enclosing_loop_or_multi_call_closure(cx, expr)
+        && matches!(
+            enclosing_loop.kind,
+            ExprKind::Loop(_, _, LoopSource::ForLoop, _) | ExprKind::Closure(_)
+        )
+        && let Some(local) = outer_local(cx, recv, enclosing_loop)
+        && !is_potentially_mutated(local, enclosing_loop, cx)
+    {
+        span_lint_and_help(
+            cx,
+            LINEAR_CONTAINS_IN_LOOP,
+            expr.span,
+            "searching a `Vec` or a slice with `contains` in a loop",
+            None,
+            &format!(
+                "`contains` checks each element, consider collecting `{}` into a `{set}` before the loop",
+                snippet(cx, recv.span, "..")
+            ),
+        );
+    }
+}
@@ -70,6 +70,7 @@ pub(super) fn check(
             let mut app = Applicability::MachineApplicable;
             let count_snip = snippet_with_context(cx, take_arg.span, ctxt, "..", &mut app).0;
 
+            #[allow(clippy::unchecked_char_boundary)]
             let val_str = match repeat_kind {
                 RepeatKind::Char(_) if repeat_arg.span.ctxt() != ctxt => return,
                 RepeatKind::Char('\'') => r#""'""#.into(),
@@ -26,6 +26,7 @@
 mod filter_next;
 mod flat_map_identity;
 mod flat_map_option;
+mod format_in_shell_command;
 mod from_iter_instead_of_collect;
 mod get_first;
 mod get_last_with_len;
@@ -46,6 +47,8 @@
 mod iter_skip_next;
 mod iter_with_drain;
 mod iterator_step_by_zero;
+mod join_absolute_paths;
+mod linear_contains_in_loop;
 mod manual_next_back;
 mod manual_ok_or;
 mod manual_saturating_arithmetic;
@@ -82,6 +85,7 @@
 mod single_char_pattern;
 mod single_char_push_string;
 mod skip_while_next;
+mod split_collect_index;
 mod stable_sort_primitive;
 mod str_splitn;
 mod string_extend_chars;
@@ -104,9 +108,11 @@
 mod unwrap_used;
 mod useless_asref;
 mod utils;
+mod vec_front_ops_in_loop;
 mod vec_resize_to_zero;
 mod verbose_file_reads;
 mod wrong_self_convention;
+mod zip_length_mismatch;
 mod zst_offset;
 
 use bind_instead_of_map::BindInsteadOfMap;
@@ -3378,6 +3384,185 @@
     "calling `Stdin::read_line`, then trying to parse it without first trimming"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls to `Path::join` with a string literal that starts with a path separator
+    /// or a Windows drive prefix.
+    ///
+    /// ### Why is this bad?
+    /// Joining an absolute path replaces the path it's joined to, which is rarely intended. When
+    /// the base path is meant to restrict where files are read from or written to, this can lead
+    /// to path traversal.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::path::Path;
+    /// let config = Path::new("/home/user").join("/.config");
+    /// assert_eq!(config, Path::new("/.config"));
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::path::Path;
+    /// let config = Path::new("/home/user").join(".config");
+    /// assert_eq!(config, Path::new("/home/user/.config"));
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub JOIN_ABSOLUTE_PATHS,
+    suspicious,
+    "calling `Path::join` with an absolute path, which replaces the base path"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `std::process::Command`s that run a script with `sh -c` (or another shell) or
+    /// `cmd /C`, where the script is built with `format!` from data that isn't a literal.
+    ///
+    /// ### Why is this bad?
+    /// The data is interpreted by the shell, so quotes, semicolons and other special characters in
+    /// it can run arbitrary commands. This is a command injection vulnerability when the data comes
+    /// from users.
+    ///
+    /// ### Example
+    /// ```rust,no_run
+    /// # use std::process::Command;
+    /// # let file = "";
+    /// Command::new("sh").arg("-c").arg(format!("wc -l {file}")).status();
+    /// ```
+    /// Use instead:
+    /// ```rust,no_run
+    /// # use std::process::Command;
+    /// # let file = "";
+    /// Command::new("wc").arg("-l").arg(file).status();
+    /// // or, if a shell is needed, pass the data as an argument of the script
+    /// Command::new("sh").arg("-c").arg(r#"wc -l "$1""#).arg("sh").arg(file).status();
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub FORMAT_IN_SHELL_COMMAND,
+    pedantic,
+    "running a script built with `format!` in a shell"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `zip` calls on two arrays, or iterators over them, that have different lengths.
+    ///
+    /// ### Why is this bad?
+    /// `zip` stops at the end of the shorter iterator, so the last elements of the longer one are
+    /// silently ignored. This is likely a mistake, e.g., an element that was forgotten in one of the
+    /// arrays.
+    ///
+    /// ### Example
+    /// ```rust
+    /// let names = ["a", "b", "c"];
+    /// let values = [1, 2];
+    /// for (name, value) in names.iter().zip(values) {
+    ///     println!("{name}: {value}");
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// let names = ["a", "b", "c"];
+    /// let values = [1, 2];
+    /// for (name, value) in names.iter().take(2).zip(values) {
+    ///     println!("{name}: {value}");
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub ZIP_LENGTH_MISMATCH,
+    suspicious,
+    "zipping arrays of different lengths"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for indexing into a `Vec` that the items of `str::split` or a similar method are
+    /// collected into, e.g., `s.split(',').collect::<Vec<_>>()[1]`.
+    ///
+    /// ### Why is this bad?
+    /// Indexing panics if the string has fewer parts than expected, which often depends on the
+    /// input. The `Vec` is also allocated needlessly, since `nth` can get the item directly.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # let line = "key=value";
+    /// let value = line.splitn(2, '=').collect::<Vec<_>>()[1];
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let line = "key=value";
+    /// let Some(value) = line.splitn(2, '=').nth(1) else {
+    ///     return;
+    /// };
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub SPLIT_COLLECT_INDEX,
+    suspicious,
+    "indexing into the collected items of `split`"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `vec.remove(0)` and `vec.insert(0, x)` in loops, where `vec` is declared
+    /// outside of the loop.
+    ///
+    /// ### Why is this bad?
+    /// Removing or inserting at the start of a `Vec` moves all of its other elements, so doing it
+    /// once per iteration takes quadratic time. A `VecDeque` can do both in constant time.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # fn process(_: u32) {}
+    /// let mut queue = vec![1, 2, 3];
+    /// while !queue.is_empty() {
+    ///     process(queue.remove(0));
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::collections::VecDeque;
+    /// # fn process(_: u32) {}
+    /// let mut queue = VecDeque::from([1, 2, 3]);
+    /// while let Some(item) = queue.pop_front() {
+    ///     process(item);
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub VEC_FRONT_OPS_IN_LOOP,
+    perf,
+    "removing or inserting at the start of a `Vec` in a loop"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls to `contains` on a `Vec` or a slice inside of a `for` loop or an
+    /// iterator closure, where the searched collection doesn't change in the loop.
+    ///
+    /// ### Why is this bad?
+    /// `contains` compares the value with each element, so searching a collection of `m`
+    /// elements for each of `n` items takes `n * m` comparisons. Collecting the elements into a
+    /// `HashSet` or a `BTreeSet` once makes each search take constant or logarithmic time.
+    ///
+    /// ### Known problems
+    /// Building the set isn't free, so this is only faster if the collections aren't tiny.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # let (items, banned) = (vec![1, 2, 3], vec![2]);
+    /// let allowed: Vec<_> = items.iter().filter(|item| !banned.contains(item)).collect();
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::collections::HashSet;
+    /// # let (items, banned) = (vec![1, 2, 3], vec![2]);
+    /// let banned: HashSet<_> = banned.into_iter().collect();
+    /// let allowed: Vec<_> = items.iter().filter(|item| !banned.contains(item)).collect();
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub LINEAR_CONTAINS_IN_LOOP,
+    pedantic,
+    "searching a `Vec` or a slice with `contains` in a loop"
+}
+
 pub struct Methods {
     avoid_breaking_exported_api: bool,
     msrv: Msrv,
@@ -3512,6 +3697,12 @@ pub fn new(
     UNNECESSARY_LITERAL_UNWRAP,
     DRAIN_COLLECT,
     MANUAL_TRY_FOLD,
+    JOIN_ABSOLUTE_PATHS,
+    FORMAT_IN_SHELL_COMMAND,
+    ZIP_LENGTH_MISMATCH,
+    SPLIT_COLLECT_INDEX,
+    VEC_FRONT_OPS_IN_LOOP,
+    LINEAR_CONTAINS_IN_LOOP,
 ]);
 
 /// Extracts a method call name, args, and `Span` of the method name.
@@ -3550,6 +3741,8 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'_>) {
                 into_iter_on_ref::check(cx, expr, method_span, method_call.ident.name, receiver);
                 single_char_pattern::check(cx, expr, method_call.ident.name, receiver, args);
                 unnecessary_to_owned::check(cx, expr, method_call.ident.name, receiver, args, &self.msrv);
+                // `format!` arguments come from an expansion, which `method_call` skips.
+                format_in_shell_command::check(cx, method_call.ident.name, receiver, args);
             },
             hir::ExprKind::Binary(op, lhs, rhs) if op.node == hir::BinOpKind::Eq || op.node == hir::BinOpKind::Ne => {
                 let mut info = BinaryExprInfo {
@@ -3719,7 +3912,7 @@ fn check_methods<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                 },
                 ("arg", [arg]) => {
                     suspicious_command_arg_space::check(cx, recv, arg, span);
-                }
+                },
                 ("as_deref" | "as_deref_mut", []) => {
                     needless_option_as_deref::check(cx, expr, recv, name);
                 },
@@ -3744,9 +3937,17 @@ fn check_methods<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                         Some(("drain", recv, args, ..)) => {
                             drain_collect::check(cx, args, expr, recv);
                         }
+                        Some((
+                            name @ ("split" | "splitn" | "rsplit" | "rsplitn" | "split_whitespace"),
+                            split_recv,
+                            ..,
+                        )) => {
+                            split_collect_index::check(cx, expr, name, split_recv);
+                        },
                         _ => {},
                     }
                 },
+                ("contains", [_]) => linear_contains_in_loop::check(cx, expr, recv),
                 ("count", []) if is_trait_method(cx, expr, sym::Iterator) => match method_call(recv) {
                     Some(("cloned", recv2, [], _, _)) => iter_overeager_cloned::check(cx, expr, recv, recv2, true, false),
                     Some((name2 @ ("into_iter" | "iter" | "iter_mut"), recv2, [], _, _)) => {
@@ -3821,6 +4022,9 @@ fn check_methods<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                 ("hash", [arg]) => {
                     unit_hash::check(cx, expr, recv, arg);
                 },
+                ("insert", [index, _]) | ("remove", [index]) => {
+                    vec_front_ops_in_loop::check(cx, expr, recv, index, name);
+                },
                 ("is_file", []) => filetype_is_file::check(cx, expr, recv),
                 ("is_digit", [radix]) => is_digit_ascii_radix::check(cx, expr, recv, radix, &self.msrv),
                 ("is_none", []) => check_is_some_is_none(cx, expr, recv, false),
@@ -3832,6 +4036,7 @@ fn check_methods<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                     if let Some(("collect", _, _, span, _)) = method_call(recv) {
                         unnecessary_join::check(cx, expr, recv, join_arg, span);
                     }
+                    join_absolute_paths::check(cx, expr, join_arg);
                 },
                 ("last", []) | ("skip", [_]) => {
                     if let Some((name2, recv2, args2, _span2, _)) = method_call(recv) {
@@ -4039,6 +4244,7 @@ fn check_methods<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                     {
                         range_zip_with_len::check(cx, expr, iter_recv, arg);
                     }
+                    zip_length_mismatch::check(cx, expr, recv, arg);
                 },
                 _ => {},
             }
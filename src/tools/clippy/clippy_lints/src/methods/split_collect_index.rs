@@ -0,0 +1,37 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::source::snippet;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::{get_parent_expr, is_integer_const};
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, UintTy};
+use rustc_span::sym;
+
+use super::SPLIT_COLLECT_INDEX;
+
+pub(super) fn check(cx: &LateContext<'_>, expr: &Expr<'_>, split_name: &str, split_recv: &Expr<'_>) {
+    if let Some(parent) = get_parent_expr(cx, expr)
+        && let ExprKind::Index(base, index) = parent.kind
+        && base.hir_id == expr.hir_id
+        && matches!(cx.typeck_results().expr_ty(index).kind(), ty::Uint(UintTy::Usize))
+        && is_type_diagnostic_item(cx, cx.typeck_results().expr_ty(expr), sym::Vec)
+        && cx.typeck_results().expr_ty_adjusted(split_recv).peel_refs().is_str()
+    {
+        let method = if is_integer_const(cx, index, 0) {
+            "next()".to_owned()
+        } else {
+            format!("nth({})", snippet(cx, index.span, ".."))
+        };
+        span_lint_and_help(
+            cx,
+            SPLIT_COLLECT_INDEX,
+            parent.span,
+            &format!("indexing into the items of `{split_name}` after collecting them into a `Vec`"),
+            None,
+            &format!(
+                "this panics if there are too few items, consider calling `.{method}` on the iterator instead and \
+                handling the `None` case"
+            ),
+        );
+    }
+}
@@ -1,12 +1,12 @@
 use clippy_utils::source::snippet_with_applicability;
 use clippy_utils::ty::is_type_diagnostic_item;
-use clippy_utils::{get_parent_expr, path_to_local_id, usage};
+use clippy_utils::{get_parent_expr, path_to_local, path_to_local_id, usage};
 use if_chain::if_chain;
 use rustc_ast::ast;
 use rustc_errors::Applicability;
 use rustc_hir as hir;
 use rustc_hir::intravisit::{walk_expr, Visitor};
-use rustc_hir::{BorrowKind, Expr, ExprKind, HirId, Mutability, Pat};
+use rustc_hir::{BorrowKind, Expr, ExprKind, HirId, Mutability, Pat, UnOp};
 use rustc_lint::LateContext;
 use rustc_middle::hir::nested_filter;
 use rustc_middle::ty::{self, Ty};
@@ -62,6 +62,7 @@ pub(super) fn get_hint_if_single_char_arg(
         if string.chars().count() == 1;
         then {
             let snip = snippet_with_applicability(cx, arg.span, string, applicability);
+            #[allow(clippy::unchecked_char_boundary)]
             let ch = if let ast::StrStyle::Raw(nhash) = style {
                 let nhash = nhash as usize;
                 // for raw string: r##"a"##
@@ -84,6 +85,23 @@ pub(super) fn get_hint_if_single_char_arg(
     }
 }
 
+/// Gets the local which `e` is, or is a field of, if it's declared outside of `enclosing_loop`,
+/// i.e., if it's carried over between iterations of the loop.
+pub(super) fn outer_local(cx: &LateContext<'_>, mut e: &Expr<'_>, enclosing_loop: &Expr<'_>) -> Option<HirId> {
+    loop {
+        e = match e.kind {
+            ExprKind::Field(base, _) | ExprKind::Unary(UnOp::Deref, base) | ExprKind::AddrOf(_, _, base) => base,
+            _ => break,
+        };
+    }
+    path_to_local(e).filter(|&id| {
+        !cx.tcx
+            .hir()
+            .parent_id_iter(id)
+            .any(|parent| parent == enclosing_loop.hir_id)
+    })
+}
+
 /// The core logic of `check_for_loop_iter` in `unnecessary_iter_cloned.rs`, this function wraps a
 /// use of `CloneOrCopyVisitor`.
 pub(super) fn clone_or_copy_needed<'tcx>(
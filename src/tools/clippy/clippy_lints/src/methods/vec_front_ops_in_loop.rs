@@ -0,0 +1,62 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::{get_enclosing_loop_or_multi_call_closure, is_integer_const};
+use rustc_hir::{Expr, ExprKind, Node, StmtKind};
+use rustc_lint::LateContext;
+use rustc_span::sym;
+
+use super::utils::outer_local;
+use super::VEC_FRONT_OPS_IN_LOOP;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    recv: &'tcx Expr<'_>,
+    index: &'tcx Expr<'_>,
+    name: &str,
+) {
+    if is_integer_const(cx, index, 0)
+        && is_type_diagnostic_item(cx, cx.typeck_results().expr_ty(recv).peel_refs(), sym::Vec)
+        && let Some(enclosing_loop) = get_enclosing_loop_or_multi_call_closure(cx, expr)
+        && outer_local(cx, recv, enclosing_loop).is_some()
+        && !is_followed_by_exit(cx, expr)
+    {
+        let (msg, help) = if name == "remove" {
+            (
+                "removing the first element of a `Vec` in a loop",
+                "consider using a `VecDeque` and `pop_front`, or `drain` if all the elements are removed in order",
+            )
+        } else {
+            (
+                "inserting an element at the start of a `Vec` in a loop",
+                "consider using a `VecDeque` and `push_front`, or pushing to the end and reversing the `Vec` \
+                afterwards",
+            )
+        };
+        span_lint_and_help(
+            cx,
+            VEC_FRONT_OPS_IN_LOOP,
+            expr.span,
+            msg,
+            None,
+            &format!("{help}, as each `{name}` moves all the other elements"),
+        );
+    }
+}
+
+/// Checks whether `expr` is a statement in a block which ends with a `break` or a `return`, so
+/// it only runs once.
+fn is_followed_by_exit(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    let mut parents = cx.tcx.hir().parent_iter(expr.hir_id);
+    if let Some((_, Node::Stmt(_))) = parents.next()
+        && let Some((_, Node::Block(block))) = parents.next()
+    {
+        let last = block.expr.or_else(|| match block.stmts.last()?.kind {
+            StmtKind::Expr(e) | StmtKind::Semi(e) => Some(e),
+            _ => None,
+        });
+        last.is_some_and(|last| matches!(last.kind, ExprKind::Break(..) | ExprKind::Ret(_)))
+    } else {
+        false
+    }
+}
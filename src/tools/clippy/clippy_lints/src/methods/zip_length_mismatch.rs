@@ -0,0 +1,55 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::is_trait_method;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+use rustc_span::sym;
+
+use super::ZIP_LENGTH_MISMATCH;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, recv: &'tcx Expr<'_>, arg: &'tcx Expr<'_>) {
+    if is_trait_method(cx, expr, sym::Iterator)
+        && let Some(recv_len) = fixed_len(cx, recv)
+        && let Some(arg_len) = fixed_len(cx, arg)
+        && recv_len != arg_len
+    {
+        let ignored = recv_len.abs_diff(arg_len);
+        span_lint_and_help(
+            cx,
+            ZIP_LENGTH_MISMATCH,
+            expr.span,
+            &format!("zipping an iterator of {recv_len} elements with one of {arg_len} elements"),
+            None,
+            &format!(
+                "the last {} of the longer one {} ignored, if that's intended, consider calling `.take({})` on it",
+                if ignored == 1 {
+                    "element".to_owned()
+                } else {
+                    format!("{ignored} elements")
+                },
+                if ignored == 1 { "is" } else { "are" },
+                recv_len.min(arg_len),
+            ),
+        );
+    }
+}
+
+/// Gets the number of items of `e` if it's an array, or an iterator over one that yields all of
+/// its elements.
+fn fixed_len(cx: &LateContext<'_>, e: &Expr<'_>) -> Option<u64> {
+    match e.kind {
+        ExprKind::MethodCall(path, recv, [], _)
+            if matches!(
+                path.ident.as_str(),
+                "iter" | "iter_mut" | "into_iter" | "copied" | "cloned"
+            ) =>
+        {
+            fixed_len(cx, recv)
+        },
+        ExprKind::AddrOf(_, _, e) => fixed_len(cx, e),
+        _ => match cx.typeck_results().expr_ty(e).peel_refs().kind() {
+            ty::Array(_, len) => len.try_eval_target_usize(cx.tcx, cx.param_env),
+            _ => None,
+        },
+    }
+}
@@ -0,0 +1,324 @@
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_hir_and_then};
+use clippy_utils::ty::implements_trait;
+use clippy_utils::visitors::{for_each_expr_with_closures, is_local_used};
+use clippy_utils::{
+    fn_def_id, get_enclosing_block, get_parent_expr, get_parent_node, is_trait_method, path_to_local_id,
+};
+use core::ops::ControlFlow;
+use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::{
+    BindingAnnotation, BorrowKind, ByRef, Expr, ExprKind, Item, ItemKind, Local, Mutability, Node, Pat, PatKind, UnOp,
+    VariantData,
+};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::adjustment::{Adjust, AutoBorrow, AutoBorrowMutability, OverloadedDeref};
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for locals and private fields of type `Arc<Mutex<T>>`, `Arc<RwLock<T>>` or
+    /// `Rc<RefCell<T>>` whose content is only ever read.
+    ///
+    /// ### Why is this bad?
+    /// The cell is only needed to mutate the shared value. Without it, the value can be read
+    /// without taking a lock or checking for borrows at runtime.
+    ///
+    /// ### Known problems
+    /// Only a few ways to use the value are understood. Any other use, e.g., passing it to a
+    /// function, is assumed to mutate it.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::sync::{Arc, Mutex};
+    /// let names = Arc::new(Mutex::new(vec!["a", "b"]));
+    /// let names2 = Arc::clone(&names);
+    /// std::thread::spawn(move || println!("{}", names2.lock().unwrap().len()));
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::sync::Arc;
+    /// let names = Arc::new(vec!["a", "b"]);
+    /// let names2 = Arc::clone(&names);
+    /// std::thread::spawn(move || println!("{}", names2.len()));
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub NEEDLESS_INTERIOR_MUTABILITY,
+    pedantic,
+    "an `Arc<Mutex<T>>` or `Rc<RefCell<T>>` whose content is never mutated"
+}
+
+#[derive(Default)]
+pub struct NeedlessInteriorMutability {
+    /// The private fields that are checked, with the names of their pointer and cell types.
+    fields: FxIndexMap<LocalDefId, (Symbol, Symbol)>,
+    used_fields: FxHashSet<LocalDefId>,
+    /// Fields that might be mutated, or that might share their value with something else.
+    mutated_fields: FxHashSet<LocalDefId>,
+}
+
+impl_lint_pass!(NeedlessInteriorMutability => [NEEDLESS_INTERIOR_MUTABILITY]);
+
+impl<'tcx> LateLintPass<'tcx> for NeedlessInteriorMutability {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        // The fields of tuple structs are initialized through calls of the constructor, which
+        // aren't checked.
+        if let ItemKind::Struct(VariantData::Struct(fields, _), _) = item.kind
+            && !in_external_macro(cx.sess(), item.span)
+        {
+            for field in fields
+                .iter()
+                .filter(|field| !cx.tcx.visibility(field.def_id).is_public())
+            {
+                if let Some(names) = shared_cell(cx, cx.tcx.type_of(field.def_id).instantiate_identity()) {
+                    self.fields.insert(field.def_id, names);
+                }
+            }
+        }
+    }
+
+    fn check_local(&mut self, cx: &LateContext<'tcx>, local: &'tcx Local<'tcx>) {
+        if let Some(init) = local.init
+            && !in_external_macro(cx.sess(), local.span)
+            && let PatKind::Binding(_, id, _, None) = local.pat.kind
+            && let Some((ptr, cell)) = shared_cell(cx, cx.typeck_results().pat_ty(local.pat))
+            && is_new_value(cx, init)
+            && let Some(block) = get_enclosing_block(cx, local.hir_id)
+            && is_local_used(cx, block, id)
+            && is_read_only_binding(cx, local, is_read_only_use)
+        {
+            span_lint_and_help(
+                cx,
+                NEEDLESS_INTERIOR_MUTABILITY,
+                local.pat.span,
+                &format!("the content of this `{ptr}<{cell}<_>>` is never mutated"),
+                None,
+                &format!("consider removing the `{cell}` and using an `{ptr}<_>`"),
+            );
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        match expr.kind {
+            ExprKind::Field(base, _) => {
+                if let ty::Adt(adt, _) = cx.typeck_results().expr_ty_adjusted(base).peel_refs().kind()
+                    && adt.is_struct()
+                    && let def = &adt.non_enum_variant().fields[cx.typeck_results().field_index(expr.hir_id)]
+                    && let Some(def_id) = def.did.as_local()
+                    && shared_cell(cx, cx.typeck_results().expr_ty(expr)).is_some()
+                {
+                    self.used_fields.insert(def_id);
+                    if !is_read_only_use(cx, expr) {
+                        self.mutated_fields.insert(def_id);
+                    }
+                }
+            },
+            ExprKind::Struct(_, fields, _) => {
+                if let ty::Adt(adt, _) = cx.typeck_results().expr_ty(expr).kind()
+                    && adt.is_struct()
+                {
+                    for field in fields {
+                        let def = &adt.non_enum_variant().fields[cx.typeck_results().field_index(field.hir_id)];
+                        if let Some(def_id) = def.did.as_local()
+                            && !is_new_value(cx, field.expr)
+                        {
+                            self.mutated_fields.insert(def_id);
+                        }
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn check_pat(&mut self, cx: &LateContext<'tcx>, pat: &'tcx Pat<'tcx>) {
+        // Bindings of fields aren't followed.
+        if let PatKind::Struct(_, fields, _) = pat.kind
+            && let ty::Adt(adt, _) = cx.typeck_results().pat_ty(pat).kind()
+            && adt.is_struct()
+        {
+            for field in fields {
+                let def = &adt.non_enum_variant().fields[cx.typeck_results().field_index(field.hir_id)];
+                if let Some(def_id) = def.did.as_local() {
+                    self.mutated_fields.insert(def_id);
+                }
+            }
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        for (&field, &(ptr, cell)) in &self.fields {
+            if self.used_fields.contains(&field) && !self.mutated_fields.contains(&field) {
+                span_lint_hir_and_then(
+                    cx,
+                    NEEDLESS_INTERIOR_MUTABILITY,
+                    cx.tcx.hir().local_def_id_to_hir_id(field),
+                    cx.tcx.def_span(field),
+                    &format!("the content of this `{ptr}<{cell}<_>>` is never mutated"),
+                    |diag| {
+                        diag.help(format!("consider removing the `{cell}` and using an `{ptr}<_>`"));
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Gets the names of the pointer and the cell type if `ty` is one of the checked types, and the
+/// value could be shared without the cell.
+fn shared_cell<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> Option<(Symbol, Symbol)> {
+    if let ty::Adt(ptr, args) = ty.kind()
+        && let Some(ptr_name @ (sym::Arc | sym::Rc)) = cx.tcx.get_diagnostic_name(ptr.did())
+        && let ty::Adt(cell, cell_args) = args.type_at(0).kind()
+        && let Some(cell_name @ (sym::Mutex | sym::RwLock | sym::RefCell)) = cx.tcx.get_diagnostic_name(cell.did())
+        && let inner = cell_args.type_at(0)
+        && inner.is_freeze(cx.tcx, cx.param_env)
+        && (ptr_name == sym::Rc
+            || [sym::Send, sym::Sync].into_iter().all(|name| {
+                cx.tcx
+                    .get_diagnostic_item(name)
+                    .map_or(false, |id| implements_trait(cx, inner, id, &[]))
+            }))
+    {
+        Some((ptr_name, cell_name))
+    } else {
+        None
+    }
+}
+
+/// Checks whether `e` creates a new value, e.g., `Arc::new(Mutex::new(0))`.
+fn is_new_value(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    if let ExprKind::Call(..) = e.kind
+        && let Some(id) = fn_def_id(cx, e)
+        && cx.tcx.item_name(id) == sym::new
+        && let Some(impl_id) = cx.tcx.impl_of_method(id)
+        && let ty::Adt(adt, _) = cx.tcx.type_of(impl_id).instantiate_identity().kind()
+    {
+        matches!(cx.tcx.get_diagnostic_name(adt.did()), Some(sym::Arc | sym::Rc))
+    } else {
+        false
+    }
+}
+
+/// Checks whether all uses of the local bound by `local` pass `check`.
+fn is_read_only_binding<'tcx>(
+    cx: &LateContext<'tcx>,
+    local: &'tcx Local<'tcx>,
+    check: fn(&LateContext<'tcx>, &'tcx Expr<'tcx>) -> bool,
+) -> bool {
+    if let PatKind::Binding(BindingAnnotation(ByRef::No, _), id, _, None) = local.pat.kind
+        && let Some(block) = get_enclosing_block(cx, local.hir_id)
+    {
+        for_each_expr_with_closures(cx, block, |e| {
+            if path_to_local_id(e, id) && !check(cx, e) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .is_none()
+    } else {
+        false
+    }
+}
+
+/// Checks whether `e`, which is one of the checked types, is only used to read the content of
+/// the cell. Clones of the value are followed.
+fn is_read_only_use<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> bool {
+    match get_parent_node(cx.tcx, e.hir_id) {
+        Some(Node::Expr(parent)) => match parent.kind {
+            ExprKind::MethodCall(path, recv, [], _) if recv.hir_id == e.hir_id => {
+                if is_trait_method(cx, parent, sym::Clone) {
+                    is_read_only_use(cx, parent)
+                } else {
+                    is_read_only_cell_access(cx, parent, path.ident.name)
+                }
+            },
+            // `Arc::clone(&e)`
+            ExprKind::AddrOf(BorrowKind::Ref, Mutability::Not, _) => {
+                if let Some(call) = get_parent_expr(cx, parent)
+                    && let ExprKind::Call(_, [arg]) = call.kind
+                    && arg.hir_id == parent.hir_id
+                    && let Some(id) = fn_def_id(cx, call)
+                    && cx
+                        .tcx
+                        .trait_of_item(id)
+                        .map_or(false, |id| cx.tcx.is_diagnostic_item(sym::Clone, id))
+                {
+                    is_read_only_use(cx, call)
+                } else {
+                    false
+                }
+            },
+            _ => false,
+        },
+        Some(Node::Local(local)) => is_read_only_binding(cx, local, is_read_only_use),
+        _ => false,
+    }
+}
+
+/// Checks whether the call of the method `name` of the cell only reads its content.
+fn is_read_only_cell_access<'tcx>(cx: &LateContext<'tcx>, call: &'tcx Expr<'tcx>, name: Symbol) -> bool {
+    let Some(id) = cx.typeck_results().type_dependent_def_id(call.hir_id) else {
+        return false;
+    };
+    let Some(impl_id) = cx.tcx.impl_of_method(id) else {
+        return false;
+    };
+    let ty::Adt(adt, _) = cx.tcx.type_of(impl_id).instantiate_identity().kind() else {
+        return false;
+    };
+    match (cx.tcx.get_diagnostic_name(adt.did()), name.as_str()) {
+        (Some(sym::RwLock), "read" | "try_read") | (Some(sym::RefCell), "borrow" | "try_borrow") => true,
+        (Some(sym::Mutex), "lock" | "try_lock")
+        | (Some(sym::RwLock), "write" | "try_write")
+        | (Some(sym::RefCell), "borrow_mut" | "try_borrow_mut") => is_read_only_guard(cx, call),
+        _ => false,
+    }
+}
+
+/// Checks whether the guard returned by `e` is never used to mutate the content of the cell.
+fn is_read_only_guard<'tcx>(cx: &LateContext<'tcx>, mut e: &'tcx Expr<'tcx>) -> bool {
+    while let Some(parent) = get_parent_expr(cx, e)
+        && let ExprKind::MethodCall(path, recv, _, _) = parent.kind
+        && recv.hir_id == e.hir_id
+        && matches!(path.ident.as_str(), "unwrap" | "expect")
+    {
+        e = parent;
+    }
+    is_read_only_guard_use(cx, e)
+}
+
+fn is_read_only_guard_use<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> bool {
+    // Any mutable use of the guard, e.g., `*guard += 1` or `guard.push(1)`, needs a mutable borrow
+    // of it.
+    let mutably_borrowed = cx.typeck_results().expr_adjustments(e).iter().any(|adjust| {
+        matches!(
+            adjust.kind,
+            Adjust::Deref(Some(OverloadedDeref {
+                mutbl: Mutability::Mut,
+                ..
+            })) | Adjust::Borrow(AutoBorrow::Ref(_, AutoBorrowMutability::Mut { .. }))
+        )
+    });
+    if mutably_borrowed {
+        return false;
+    }
+    match get_parent_node(cx.tcx, e.hir_id) {
+        Some(Node::Expr(parent)) => match parent.kind {
+            ExprKind::Unary(UnOp::Deref, _)
+            | ExprKind::AddrOf(BorrowKind::Ref, Mutability::Not, _)
+            | ExprKind::Field(..) => true,
+            ExprKind::Index(base, ..) | ExprKind::MethodCall(_, base, ..) => base.hir_id == e.hir_id,
+            ExprKind::Call(_, [_]) => {
+                fn_def_id(cx, parent).map_or(false, |id| cx.tcx.is_diagnostic_item(sym::mem_drop, id))
+            },
+            _ => false,
+        },
+        Some(Node::Local(local)) => is_read_only_binding(cx, local, is_read_only_guard_use),
+        _ => false,
+    }
+}
@@ -0,0 +1,114 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::macros::root_macro_call_first_node;
+use clippy_utils::source::snippet_opt;
+use rustc_hir::Expr;
+use rustc_lexer::{tokenize, TokenKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `join!` and `try_join!` from `futures` or `tokio` with a single future, and for
+    /// `select!` with a single branch.
+    ///
+    /// ### Why is this bad?
+    /// These macros are for running several futures concurrently. With only one future, they
+    /// just add overhead and make the code harder to read, as awaiting the future does the same.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// let (config,) = futures::join!(load_config());
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// let config = load_config().await;
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub NEEDLESS_JOIN_OR_SELECT,
+    complexity,
+    "using `join!` with a single future, or `select!` with a single branch"
+}
+declare_lint_pass!(NeedlessJoinOrSelect => [NEEDLESS_JOIN_OR_SELECT]);
+
+impl<'tcx> LateLintPass<'tcx> for NeedlessJoinOrSelect {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        let Some(macro_call) = root_macro_call_first_node(cx, expr) else {
+            return;
+        };
+        if !matches!(
+            cx.tcx.crate_name(macro_call.def_id.krate).as_str(),
+            "futures" | "futures_util" | "tokio"
+        ) {
+            return;
+        }
+        let name = cx.tcx.item_name(macro_call.def_id);
+        let is_select = match name.as_str() {
+            "join" | "try_join" => false,
+            "select" | "select_biased" => true,
+            _ => return,
+        };
+        let Some(args) = snippet_opt(cx, macro_call.span).as_deref().and_then(count_macro_args) else {
+            return;
+        };
+        if is_select && args.arrows == 1 {
+            span_lint_and_help(
+                cx,
+                NEEDLESS_JOIN_OR_SELECT,
+                macro_call.span,
+                &format!("`{name}!` with a single branch"),
+                None,
+                "consider awaiting the future of the branch with `.await` instead",
+            );
+        } else if !is_select && args.args == 1 {
+            span_lint_and_help(
+                cx,
+                NEEDLESS_JOIN_OR_SELECT,
+                macro_call.span,
+                &format!("`{name}!` with a single future"),
+                None,
+                "consider awaiting the future with `.await` instead",
+            );
+        }
+    }
+}
+
+struct MacroArgs {
+    /// The number of comma separated arguments.
+    args: usize,
+    /// The number of `=>`, i.e., the number of branches of a `select!`.
+    arrows: usize,
+}
+
+/// Counts the arguments of a macro call like `join!(a, b)`, given its source.
+fn count_macro_args(src: &str) -> Option<MacroArgs> {
+    // Skip the path and the `!`, and get the tokens between the delimiters.
+    let src = src[src.find('!')? + 1..].trim();
+    let tokens = src.get(1..src.len().checked_sub(1)?)?;
+
+    let mut args = MacroArgs { args: 0, arrows: 0 };
+    let mut depth = 0usize;
+    let mut in_arg = false;
+    let mut prev_is_eq = false;
+    for token in tokenize(tokens) {
+        match token.kind {
+            TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. } => continue,
+            TokenKind::OpenParen | TokenKind::OpenBrace | TokenKind::OpenBracket => depth += 1,
+            TokenKind::CloseParen | TokenKind::CloseBrace | TokenKind::CloseBracket => {
+                depth = depth.saturating_sub(1);
+            },
+            TokenKind::Comma if depth == 0 => {
+                in_arg = false;
+                prev_is_eq = false;
+                continue;
+            },
+            TokenKind::Gt if depth == 0 && prev_is_eq => args.arrows += 1,
+            _ => {},
+        }
+        if !in_arg {
+            in_arg = true;
+            args.args += 1;
+        }
+        prev_is_eq = depth == 0 && token.kind == TokenKind::Eq;
+    }
+    Some(args)
+}
@@ -0,0 +1,118 @@
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_then};
+use clippy_utils::macros::macro_backtrace;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::visitors::is_const_evaluatable;
+use clippy_utils::{get_parent_node, match_def_path, paths, qpath_generic_tys};
+use rustc_errors::Applicability;
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Body, Expr, ExprKind, FnDecl, ItemKind, Node, TyKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::def_id::LocalDefId;
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for statics which are initialized lazily, with `once_cell::sync::Lazy`,
+    /// `std::sync::LazyLock` or `lazy_static!`, although their initializer can be evaluated at
+    /// compile time, e.g., because it only calls `const fn`s like `Vec::new` or `Mutex::new`.
+    ///
+    /// ### Why is this bad?
+    /// A plain `static` or `const` is simpler, and it doesn't need to check whether it's
+    /// initialized each time it's used.
+    ///
+    /// ### Known problems
+    /// The value has to be dereferenced explicitly where it's used as a `Lazy`, e.g., with `*`.
+    /// These places aren't changed by the suggestion.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # #![feature(lazy_cell)]
+    /// use std::sync::{LazyLock, Mutex};
+    ///
+    /// static QUEUE: LazyLock<Mutex<Vec<u32>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// use std::sync::Mutex;
+    ///
+    /// static QUEUE: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub NEEDLESS_LAZY_INIT,
+    style,
+    "initializing a static lazily although its value can be computed at compile time"
+}
+declare_lint_pass!(NeedlessLazyInit => [NEEDLESS_LAZY_INIT]);
+
+impl<'tcx> LateLintPass<'tcx> for NeedlessLazyInit {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        // `static X: Lazy<T> = Lazy::new(|| ..);`
+        if let ExprKind::Call(_, [init]) = expr.kind
+            && let ExprKind::Closure(closure) = init.kind
+            && let Some(Node::Item(item)) = get_parent_node(cx.tcx, expr.hir_id)
+            && let ItemKind::Static(hir_ty, _, _) = item.kind
+            && let TyKind::Path(qpath) = &hir_ty.kind
+            && let Some(inner_ty) = qpath_generic_tys(qpath).next()
+            && let ty::Adt(adt, _) = cx.typeck_results().expr_ty(expr).kind()
+            && (match_def_path(cx, adt.did(), &paths::ONCE_CELL_SYNC_LAZY)
+                || match_def_path(cx, adt.did(), &paths::LAZY_LOCK))
+            && !expr.span.from_expansion()
+        {
+            let body = cx.tcx.hir().body(closure.body).value;
+            if !is_const_evaluatable(cx, body) {
+                return;
+            }
+            span_lint_and_then(
+                cx,
+                NEEDLESS_LAZY_INIT,
+                expr.span,
+                "this static is initialized lazily, although its value can be computed at compile time",
+                |diag| {
+                    let mut app = Applicability::MaybeIncorrect;
+                    let inner_ty = snippet_with_applicability(cx, inner_ty.span, "..", &mut app);
+                    let body = snippet_with_applicability(cx, body.span, "..", &mut app);
+                    diag.multipart_suggestion(
+                        "consider initializing it directly",
+                        vec![(hir_ty.span, inner_ty.into_owned()), (expr.span, body.into_owned())],
+                        app,
+                    );
+                },
+            );
+        }
+    }
+
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        body: &'tcx Body<'_>,
+        span: Span,
+        _: LocalDefId,
+    ) {
+        // `lazy_static!` puts the initializer into a function with this name.
+        if let FnKind::ItemFn(ident, ..) = kind
+            && ident.as_str() == "__static_ref_initialize"
+            && is_from_lazy_static(cx, span)
+            && let ExprKind::Block(block, _) = body.value.kind
+            && let Some(init) = block.expr
+            && !init.span.from_expansion()
+            && is_const_evaluatable(cx, init)
+        {
+            span_lint_and_help(
+                cx,
+                NEEDLESS_LAZY_INIT,
+                init.span,
+                "this static is initialized lazily, although its value can be computed at compile time",
+                None,
+                "consider using a plain `static` or `const` instead of `lazy_static!`",
+            );
+        }
+    }
+}
+
+fn is_from_lazy_static(cx: &LateContext<'_>, span: Span) -> bool {
+    macro_backtrace(span).any(|macro_call| cx.tcx.item_name(macro_call.def_id).as_str() == "lazy_static")
+}
@@ -0,0 +1,124 @@
+use crate::exposed_secret_field::{is_plain_text, is_secret_name};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::{find_binding_init, path_to_local};
+use rustc_hir::{BinOpKind, Expr, ExprKind, UnOp};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::symbol::Ident;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `==` and `!=` comparisons of strings or byte buffers where one side is a secret,
+    /// i.e., a field, parameter or local with a name that looks like a secret, e.g., `password` or
+    /// `api_key`, or a local that is initialized from one.
+    ///
+    /// The names which are treated as secrets can be configured with `secret-field-names`.
+    ///
+    /// ### Why is this bad?
+    /// `==` returns as soon as it finds a difference, so the time it takes tells how much of the
+    /// guess is right. An attacker who can measure it can guess the secret byte by byte.
+    ///
+    /// ### Known problems
+    /// Secrets are only tracked through immutable locals and conversions like `as_bytes`, so a
+    /// secret which is hashed or decoded first isn't recognized.
+    ///
+    /// ### Example
+    /// ```rust
+    /// fn is_authorized(api_key: &str, given: &str) -> bool {
+    ///     given == api_key
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// use subtle::ConstantTimeEq;
+    ///
+    /// fn is_authorized(api_key: &str, given: &str) -> bool {
+    ///     given.as_bytes().ct_eq(api_key.as_bytes()).into()
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub NON_CONSTANT_TIME_COMPARISON,
+    restriction,
+    "comparing secrets with `==`, which doesn't take constant time"
+}
+
+pub struct NonConstantTimeComparison {
+    secret_names: Vec<String>,
+}
+
+impl NonConstantTimeComparison {
+    pub fn new(secret_names: Vec<String>) -> Self {
+        Self { secret_names }
+    }
+
+    /// Finds the field, parameter or local that `e` is, or is converted from, if it's a secret.
+    fn secret_origin<'tcx>(&self, cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> Option<Ident> {
+        let e = peel_conversions(e);
+        if let ExprKind::Field(_, ident) = e.kind {
+            return is_secret_name(&self.secret_names, ident.as_str()).then_some(ident);
+        }
+        let id = path_to_local(e)?;
+        let name = cx.tcx.hir().ident(id);
+        if is_secret_name(&self.secret_names, name.as_str()) {
+            Some(name)
+        } else {
+            self.secret_origin(cx, find_binding_init(cx, id)?)
+        }
+    }
+}
+
+impl_lint_pass!(NonConstantTimeComparison => [NON_CONSTANT_TIME_COMPARISON]);
+
+impl<'tcx> LateLintPass<'tcx> for NonConstantTimeComparison {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::Binary(op, lhs, rhs) = expr.kind
+            && matches!(op.node, BinOpKind::Eq | BinOpKind::Ne)
+            && !matches!(peel_conversions(lhs).kind, ExprKind::Lit(_))
+            && !matches!(peel_conversions(rhs).kind, ExprKind::Lit(_))
+            && is_plain_text(cx, cx.typeck_results().expr_ty(lhs))
+            && is_plain_text(cx, cx.typeck_results().expr_ty(rhs))
+            && let Some(secret) = self.secret_origin(cx, lhs).or_else(|| self.secret_origin(cx, rhs))
+            && !in_external_macro(cx.sess(), expr.span)
+        {
+            span_lint_and_then(
+                cx,
+                NON_CONSTANT_TIME_COMPARISON,
+                expr.span,
+                &format!(
+                    "comparing a secret with `{}` takes time that depends on how much of it matches",
+                    op.node.as_str()
+                ),
+                |diag| {
+                    diag.span_note(secret.span, format!("`{secret}` looks like a secret"));
+                    diag.help("consider comparing in constant time, e.g., with `subtle::ConstantTimeEq`");
+                },
+            );
+        }
+    }
+}
+
+/// Methods which return their receiver, or a copy of it, as a string or bytes.
+const CONVERSIONS: [&str; 10] = [
+    "as_bytes",
+    "as_str",
+    "as_slice",
+    "as_ref",
+    "as_deref",
+    "to_vec",
+    "to_string",
+    "to_owned",
+    "clone",
+    "into_bytes",
+];
+
+/// Removes borrows, derefs and conversions which don't change the contents, e.g., `as_bytes()`.
+fn peel_conversions<'tcx>(mut e: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    loop {
+        e = match e.kind {
+            ExprKind::AddrOf(_, _, inner) | ExprKind::Unary(UnOp::Deref, inner) => inner,
+            ExprKind::MethodCall(path, recv, [], _) if CONVERSIONS.contains(&path.ident.as_str()) => recv,
+            _ => return e,
+        };
+    }
+}
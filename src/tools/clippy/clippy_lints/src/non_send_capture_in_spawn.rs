@@ -0,0 +1,115 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::ty::implements_trait;
+use clippy_utils::{def_path_def_ids, fn_def_id};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{BorrowKind, UpvarCapture};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for closures and async blocks that are passed to functions that run them on another
+    /// thread, and capture values that can't be sent to another thread, such as `Rc`, `RefCell`
+    /// references or raw pointers.
+    ///
+    /// The functions are configured with the `spawn-functions` configuration option, e.g.,
+    /// `spawn-functions = ["my_runtime::spawn"]`.
+    ///
+    /// ### Why is this bad?
+    /// When the spawn function requires the closure to be `Send`, the compiler rejects the call,
+    /// but the error points at the call instead of the capture. When it doesn't, e.g., because it's
+    /// `unsafe` or checks this at runtime, the capture leads to a data race.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// let counter = Rc::new(Cell::new(0));
+    /// my_runtime::spawn(move || counter.set(counter.get() + 1));
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// let counter = Arc::new(AtomicU32::new(0));
+    /// my_runtime::spawn(move || counter.fetch_add(1, Ordering::Relaxed));
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub NON_SEND_CAPTURE_IN_SPAWN,
+    suspicious,
+    "capturing a value that isn't `Send` in a closure that is run on another thread"
+}
+
+#[derive(Debug)]
+pub struct NonSendCaptureInSpawn {
+    conf_spawn_fns: Vec<String>,
+    spawn_fns: FxHashSet<DefId>,
+}
+
+impl NonSendCaptureInSpawn {
+    pub fn new(conf_spawn_fns: Vec<String>) -> Self {
+        Self {
+            conf_spawn_fns,
+            spawn_fns: FxHashSet::default(),
+        }
+    }
+}
+
+impl_lint_pass!(NonSendCaptureInSpawn => [NON_SEND_CAPTURE_IN_SPAWN]);
+
+impl<'tcx> LateLintPass<'tcx> for NonSendCaptureInSpawn {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for path in &self.conf_spawn_fns {
+            let segs: Vec<_> = path.split("::").collect();
+            self.spawn_fns.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        let (ExprKind::Call(_, args) | ExprKind::MethodCall(_, _, args, _)) = expr.kind else {
+            return;
+        };
+        let Some(spawn_fn) = fn_def_id(cx, expr).filter(|id| self.spawn_fns.contains(id)) else {
+            return;
+        };
+        let (Some(send_trait), Some(sync_trait)) = (
+            cx.tcx.get_diagnostic_item(sym::Send),
+            cx.tcx.get_diagnostic_item(sym::Sync),
+        ) else {
+            return;
+        };
+
+        for arg in args {
+            let ExprKind::Closure(closure) = arg.kind else {
+                continue;
+            };
+            for capture in cx.typeck_results().closure_min_captures_flattened(closure.def_id) {
+                let ty = capture.place.ty();
+                // A shared reference can only be sent if the referenced value can be shared.
+                let (by_ref, required) = if capture.info.capture_kind == UpvarCapture::ByRef(BorrowKind::ImmBorrow) {
+                    (" by reference", sync_trait)
+                } else {
+                    ("", send_trait)
+                };
+                if implements_trait(cx, ty, required, &[]) {
+                    continue;
+                }
+                span_lint_and_then(
+                    cx,
+                    NON_SEND_CAPTURE_IN_SPAWN,
+                    capture.get_path_span(cx.tcx),
+                    &format!(
+                        "`{}` is captured{by_ref}, but its type `{ty}` isn't `{}`",
+                        capture.to_string(cx.tcx),
+                        cx.tcx.item_name(required),
+                    ),
+                    |diag| {
+                        diag.span_note(
+                            arg.span,
+                            format!("this is run on another thread by `{}`", cx.tcx.def_path_str(spawn_fn)),
+                        );
+                    },
+                );
+            }
+        }
+    }
+}
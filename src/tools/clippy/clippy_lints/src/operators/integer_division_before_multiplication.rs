@@ -0,0 +1,82 @@
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_sugg};
+use clippy_utils::sugg::{self, Sugg};
+use clippy_utils::SpanlessEq;
+use rustc_ast::ast;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+
+use super::INTEGER_DIVISION_BEFORE_MULTIPLICATION;
+
+pub(crate) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    left: &'tcx Expr<'_>,
+    right: &'tcx Expr<'_>,
+    allow_rounding_division: bool,
+) {
+    if op != BinOpKind::Mul || !cx.typeck_results().expr_ty(expr).is_integral() {
+        return;
+    }
+    let (dividend, divisor, factor, factor_first) = match (left.kind, right.kind) {
+        (ExprKind::Binary(inner, dividend, divisor), _) if inner.node == BinOpKind::Div => {
+            (dividend, divisor, right, false)
+        },
+        (_, ExprKind::Binary(inner, dividend, divisor)) if inner.node == BinOpKind::Div => {
+            (dividend, divisor, left, true)
+        },
+        _ => return,
+    };
+    if is_exact_division(cx, dividend, divisor) {
+        return;
+    }
+    // `x / 8 * 8` rounds `x` down to a multiple of 8, multiplying first would make it a no-op.
+    if SpanlessEq::new(cx).eq_expr(divisor, factor) {
+        if !allow_rounding_division {
+            span_lint_and_help(
+                cx,
+                INTEGER_DIVISION_BEFORE_MULTIPLICATION,
+                expr.span,
+                "integer division before multiplication loses the remainder of the division",
+                None,
+                "if this is meant to round down to a multiple, consider making that explicit, e.g., with a helper \
+                function",
+            );
+        }
+        return;
+    }
+
+    let mut app = Applicability::MaybeIncorrect;
+    let dividend = Sugg::hir_with_applicability(cx, dividend, "..", &mut app);
+    let divisor = Sugg::hir_with_applicability(cx, divisor, "..", &mut app);
+    let factor = Sugg::hir_with_applicability(cx, factor, "..", &mut app);
+    let product = if factor_first {
+        sugg::make_binop(ast::BinOpKind::Mul, &factor, &dividend)
+    } else {
+        sugg::make_binop(ast::BinOpKind::Mul, &dividend, &factor)
+    };
+    span_lint_and_sugg(
+        cx,
+        INTEGER_DIVISION_BEFORE_MULTIPLICATION,
+        expr.span,
+        "integer division before multiplication loses the remainder of the division",
+        "if the product can't overflow, consider multiplying first",
+        sugg::make_binop(ast::BinOpKind::Div, &product, &divisor).to_string(),
+        app,
+    );
+}
+
+/// Checks whether `dividend / divisor` is known to have no remainder, e.g., `60 / 4`.
+fn is_exact_division<'tcx>(cx: &LateContext<'tcx>, dividend: &'tcx Expr<'_>, divisor: &'tcx Expr<'_>) -> bool {
+    if let ty::Uint(_) = cx.typeck_results().expr_ty(dividend).kind()
+        && let Some(Constant::Int(dividend)) = constant(cx, cx.typeck_results(), dividend)
+        && let Some(Constant::Int(divisor)) = constant(cx, cx.typeck_results(), divisor)
+    {
+        divisor != 0 && dividend % divisor == 0
+    } else {
+        false
+    }
+}
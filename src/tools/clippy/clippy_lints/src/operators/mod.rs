@@ -10,6 +10,7 @@
 mod float_equality_without_abs;
 mod identity_op;
 mod integer_division;
+mod integer_division_before_multiplication;
 mod misrefactored_assign_op;
 mod modulo_arithmetic;
 mod modulo_one;
@@ -18,6 +19,7 @@
 mod op_ref;
 mod ptr_eq;
 mod self_assignment;
+mod signed_remainder_as_index;
 mod verbose_bit_mask;
 
 pub(crate) mod arithmetic_side_effects;
@@ -484,6 +486,66 @@
     "integer division may cause loss of precision"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for integer divisions whose result is multiplied, e.g., `a / b * c`.
+    ///
+    /// `x / n * n`, which rounds `x` down to a multiple of `n`, is allowed by default. This can be
+    /// configured with `allow-rounding-division`.
+    ///
+    /// ### Why is this bad?
+    /// The remainder of the division is lost before the multiplication, so the error is
+    /// multiplied as well. Multiplying first only loses the remainder of the final division, as
+    /// long as the product doesn't overflow.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # let (done, total) = (7u32, 9u32);
+    /// let percent = done / total * 100;
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let (done, total) = (7u32, 9u32);
+    /// let percent = done * 100 / total;
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub INTEGER_DIVISION_BEFORE_MULTIPLICATION,
+    pedantic,
+    "multiplying the result of an integer division, which loses its remainder"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for remainders of signed integers which are used as an index or a pointer offset,
+    /// e.g., `items[(i % n) as usize]`, unless the integer is known to not be negative.
+    ///
+    /// ### Why is this bad?
+    /// The remainder has the sign of the dividend, so it's negative if the dividend is. Cast to
+    /// `usize`, it's a huge index which panics. `rem_euclid` is never negative.
+    ///
+    /// ### Known problems
+    /// Only constants, widened unsigned values, and locals which are initialized from them, counted
+    /// from zero by a `for` loop, or checked by an enclosing `if` or an earlier early return, are
+    /// known to not be negative.
+    ///
+    /// ### Example
+    /// ```rust
+    /// fn wrap(items: &[u8], i: i32) -> u8 {
+    ///     items[(i % items.len() as i32) as usize]
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// fn wrap(items: &[u8], i: i32) -> u8 {
+    ///     items[i.rem_euclid(items.len() as i32) as usize]
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub SIGNED_REMAINDER_AS_INDEX,
+    pedantic,
+    "using the remainder of a signed integer which might be negative as an index"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for conversions to owned values just for the sake
@@ -731,6 +793,7 @@
 pub struct Operators {
     arithmetic_context: numeric_arithmetic::Context,
     verbose_bit_mask_threshold: u64,
+    allow_rounding_division: bool,
 }
 impl_lint_pass!(Operators => [
     ABSURD_EXTREME_COMPARISONS,
@@ -749,6 +812,7 @@ pub struct Operators {
     FLOAT_EQUALITY_WITHOUT_ABS,
     IDENTITY_OP,
     INTEGER_DIVISION,
+    INTEGER_DIVISION_BEFORE_MULTIPLICATION,
     CMP_OWNED,
     FLOAT_CMP,
     FLOAT_CMP_CONST,
@@ -757,12 +821,14 @@ pub struct Operators {
     NEEDLESS_BITWISE_BOOL,
     PTR_EQ,
     SELF_ASSIGNMENT,
+    SIGNED_REMAINDER_AS_INDEX,
 ]);
 impl Operators {
-    pub fn new(verbose_bit_mask_threshold: u64) -> Self {
+    pub fn new(verbose_bit_mask_threshold: u64, allow_rounding_division: bool) -> Self {
         Self {
             arithmetic_context: numeric_arithmetic::Context::default(),
             verbose_bit_mask_threshold,
+            allow_rounding_division,
         }
     }
 }
@@ -781,6 +847,15 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) {
                     identity_op::check(cx, e, op.node, lhs, rhs);
                     needless_bitwise_bool::check(cx, e, op.node, lhs, rhs);
                     ptr_eq::check(cx, e, op.node, lhs, rhs);
+                    integer_division_before_multiplication::check(
+                        cx,
+                        e,
+                        op.node,
+                        lhs,
+                        rhs,
+                        self.allow_rounding_division,
+                    );
+                    signed_remainder_as_index::check(cx, e, op.node, lhs, rhs);
                 }
                 self.arithmetic_context.check_binary(cx, e, op.node, lhs, rhs);
                 bit_mask::check(cx, e, op.node, lhs, rhs);
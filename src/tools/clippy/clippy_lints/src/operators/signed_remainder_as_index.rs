@@ -0,0 +1,208 @@
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::sugg::Sugg;
+use clippy_utils::{find_binding_init, get_parent_expr, higher, path_to_local, path_to_local_id, sext};
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind, HirId, Node, Stmt, StmtKind, UnOp};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+
+use super::SIGNED_REMAINDER_AS_INDEX;
+
+/// Methods whose argument is an index into, or an offset from, their receiver.
+const INDEXING_METHODS: [&str; 6] = [
+    "get",
+    "get_mut",
+    "get_unchecked",
+    "get_unchecked_mut",
+    "offset",
+    "wrapping_offset",
+];
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    left: &'tcx Expr<'_>,
+    right: &'tcx Expr<'_>,
+) {
+    if op == BinOpKind::Rem
+        && cx.typeck_results().expr_ty(left).is_signed()
+        && is_used_as_index(cx, expr)
+        && !is_non_negative(cx, left)
+    {
+        let mut app = Applicability::MachineApplicable;
+        let left = Sugg::hir_with_applicability(cx, left, "..", &mut app).maybe_par();
+        let right = snippet_with_applicability(cx, right.span, "..", &mut app);
+        span_lint_and_sugg(
+            cx,
+            SIGNED_REMAINDER_AS_INDEX,
+            expr.span,
+            "the remainder of a value which might be negative is used as an index",
+            "the remainder of a negative value is negative, consider using `rem_euclid`",
+            format!("{left}.rem_euclid({right})"),
+            app,
+        );
+    }
+}
+
+/// Checks whether `expr`, possibly cast, is used to index a slice or to offset a pointer.
+fn is_used_as_index(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    let mut child = expr;
+    while let Some(parent) = get_parent_expr(cx, child) {
+        match parent.kind {
+            ExprKind::Cast(..) => child = parent,
+            ExprKind::Index(_, index) => return index.hir_id == child.hir_id,
+            ExprKind::MethodCall(path, _, [arg], _) => {
+                return arg.hir_id == child.hir_id && INDEXING_METHODS.contains(&path.ident.as_str());
+            },
+            _ => return false,
+        }
+    }
+    false
+}
+
+/// Checks whether the signed integer `e` is known to not be negative, e.g., because it's a
+/// non-negative constant, a widened unsigned value, or a local which is checked by an enclosing
+/// `if` or by an earlier early return.
+fn is_non_negative<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) -> bool {
+    let ty = cx.typeck_results().expr_ty(e);
+    let ty::Int(int_ty) = *ty.kind() else {
+        return true;
+    };
+    if let Some(Constant::Int(value)) = constant(cx, cx.typeck_results(), e) {
+        return sext(cx.tcx, value, int_ty) >= 0;
+    }
+    match e.kind {
+        ExprKind::Cast(inner, _) => match *cx.typeck_results().expr_ty(inner).kind() {
+            ty::Uint(from_ty) => from_ty
+                .bit_width()
+                .zip(int_ty.bit_width())
+                .is_some_and(|(from, to)| from < to),
+            ty::Int(_) => is_non_negative(cx, inner),
+            _ => false,
+        },
+        ExprKind::MethodCall(path, ..) => matches!(path.ident.as_str(), "abs" | "rem_euclid"),
+        ExprKind::Binary(op, lhs, rhs) => match op.node {
+            BinOpKind::Add | BinOpKind::Mul | BinOpKind::Div => is_non_negative(cx, lhs) && is_non_negative(cx, rhs),
+            BinOpKind::Rem | BinOpKind::Shr => is_non_negative(cx, lhs),
+            BinOpKind::BitAnd => is_non_negative(cx, lhs) || is_non_negative(cx, rhs),
+            _ => false,
+        },
+        _ => {
+            let Some(id) = path_to_local(e) else {
+                return false;
+            };
+            find_binding_init(cx, id).is_some_and(|init| is_non_negative(cx, init))
+                || is_counted_from_non_negative(cx, id)
+                || is_checked_non_negative(cx, e.hir_id, id)
+        },
+    }
+}
+
+/// Checks whether the local `id` is bound by a `for` loop over a range which doesn't start below
+/// zero.
+fn is_counted_from_non_negative(cx: &LateContext<'_>, id: HirId) -> bool {
+    cx.tcx.hir().parent_iter(id).any(|(_, node)| {
+        if let Node::Expr(e) = node
+            && let Some(for_loop) = higher::ForLoop::hir(e)
+            && for_loop.pat.hir_id == id
+            && let Some(range) = higher::Range::hir(for_loop.arg)
+        {
+            range.start.is_some_and(|start| is_non_negative(cx, start))
+        } else {
+            false
+        }
+    })
+}
+
+/// Checks whether the local `id`, used at `use_id`, is checked to not be negative, either by the
+/// condition of an enclosing `if`, or by an earlier `if` which diverges when it's negative, e.g.,
+/// `if x < 0 { return; }`.
+fn is_checked_non_negative(cx: &LateContext<'_>, use_id: HirId, id: HirId) -> bool {
+    let mut child = use_id;
+    for (parent_id, node) in cx.tcx.hir().parent_iter(use_id) {
+        match node {
+            Node::Expr(e) => match e.kind {
+                ExprKind::If(cond, then, _) if then.hir_id == child && implies_non_negative(cx, cond, id) => {
+                    return true;
+                },
+                ExprKind::Closure(..) => return false,
+                _ => {},
+            },
+            Node::Block(block) => {
+                if block
+                    .stmts
+                    .iter()
+                    .take_while(|stmt| stmt.hir_id != child)
+                    .any(|stmt| diverges_if_negative(cx, stmt, id))
+                {
+                    return true;
+                }
+            },
+            Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) => return false,
+            _ => {},
+        }
+        child = parent_id;
+    }
+    false
+}
+
+/// Checks whether `stmt` is an `if` which diverges if the local `id` is negative.
+fn diverges_if_negative<'tcx>(cx: &LateContext<'tcx>, stmt: &'tcx Stmt<'tcx>, id: HirId) -> bool {
+    if let StmtKind::Expr(e) | StmtKind::Semi(e) = stmt.kind
+        && let ExprKind::If(cond, then, None) = e.kind
+    {
+        diverges(cx, then) && implies_negative(cx, cond, id)
+    } else {
+        false
+    }
+}
+
+/// Checks whether the block `e` ends by returning, breaking, continuing or panicking.
+fn diverges(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    let ExprKind::Block(block, _) = e.kind else {
+        return false;
+    };
+    let last = block.expr.or_else(|| match block.stmts.last()?.kind {
+        StmtKind::Expr(e) | StmtKind::Semi(e) => Some(e),
+        _ => None,
+    });
+    last.is_some_and(|last| {
+        matches!(
+            last.kind,
+            ExprKind::Ret(_) | ExprKind::Break(..) | ExprKind::Continue(_)
+        ) || cx.typeck_results().expr_ty(last).is_never()
+    })
+}
+
+/// Checks whether `cond` is only true if the local `id` isn't negative, e.g., `x >= 0 && x < n`.
+fn implies_non_negative<'tcx>(cx: &LateContext<'tcx>, cond: &'tcx Expr<'tcx>, id: HirId) -> bool {
+    match cond.kind {
+        ExprKind::DropTemps(cond) => implies_non_negative(cx, cond, id),
+        ExprKind::Unary(UnOp::Not, cond) => implies_negative(cx, cond, id),
+        ExprKind::Binary(op, lhs, rhs) => match op.node {
+            BinOpKind::And => implies_non_negative(cx, lhs, id) || implies_non_negative(cx, rhs, id),
+            BinOpKind::Ge | BinOpKind::Gt => path_to_local_id(lhs, id) && is_non_negative(cx, rhs),
+            BinOpKind::Le | BinOpKind::Lt => path_to_local_id(rhs, id) && is_non_negative(cx, lhs),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Checks whether `cond` is true if the local `id` is negative, e.g., `x < 0 || x >= n`.
+fn implies_negative<'tcx>(cx: &LateContext<'tcx>, cond: &'tcx Expr<'tcx>, id: HirId) -> bool {
+    match cond.kind {
+        ExprKind::DropTemps(cond) => implies_negative(cx, cond, id),
+        ExprKind::Unary(UnOp::Not, cond) => implies_non_negative(cx, cond, id),
+        ExprKind::Binary(op, lhs, rhs) => match op.node {
+            BinOpKind::Or => implies_negative(cx, lhs, id) || implies_negative(cx, rhs, id),
+            BinOpKind::Lt => path_to_local_id(lhs, id) && is_non_negative(cx, rhs),
+            BinOpKind::Gt => path_to_local_id(rhs, id) && is_non_negative(cx, lhs),
+            _ => false,
+        },
+        _ => false,
+    }
+}
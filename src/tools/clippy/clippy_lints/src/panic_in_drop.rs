@@ -0,0 +1,208 @@
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::is_diag_item_method;
+use clippy_utils::macros::root_macro_call_first_node;
+use clippy_utils::visitors::{for_each_expr, Descend};
+use core::ops::ControlFlow;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Body, Expr, ExprKind, Impl, ImplItemKind, Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, TypeckResults};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Span, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls of `unwrap` and `expect`, panicking macros and indexing in `Drop`
+    /// implementations.
+    ///
+    /// With the `check-fns-called-from-drop` configuration option, the functions of the crate
+    /// that are called from `drop` are checked as well, but not the functions they call.
+    ///
+    /// ### Why is this bad?
+    /// `drop` is also called while the thread unwinds because of another panic. Panicking again
+    /// at that point aborts the process, without running the remaining destructors.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// impl Drop for TempDir {
+    ///     fn drop(&mut self) {
+    ///         std::fs::remove_dir_all(&self.path).unwrap();
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// impl Drop for TempDir {
+    ///     fn drop(&mut self) {
+    ///         if let Err(e) = std::fs::remove_dir_all(&self.path) {
+    ///             eprintln!("failed to remove {}: {e}", self.path.display());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub PANIC_IN_DROP,
+    restriction,
+    "code in a `Drop` implementation that may panic"
+}
+
+pub struct PanicInDrop {
+    check_called_fns: bool,
+}
+
+impl PanicInDrop {
+    pub fn new(check_called_fns: bool) -> Self {
+        Self { check_called_fns }
+    }
+}
+
+impl_lint_pass!(PanicInDrop => [PANIC_IN_DROP]);
+
+impl<'tcx> LateLintPass<'tcx> for PanicInDrop {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if let ItemKind::Impl(Impl {
+            of_trait: Some(trait_ref),
+            items,
+            ..
+        }) = item.kind
+            && trait_ref.trait_def_id() == cx.tcx.lang_items().drop_trait()
+        {
+            for impl_item in *items {
+                if let ImplItemKind::Fn(_, body_id) = cx.tcx.hir().impl_item(impl_item.id).kind {
+                    let typeck = cx.tcx.typeck_body(body_id);
+                    for panic in find_panics(cx, typeck, cx.tcx.hir().body(body_id), self.check_called_fns) {
+                        lint_panic(cx, panic);
+                    }
+                }
+            }
+        }
+    }
+}
+
+enum PanicKind {
+    /// `unwrap` or `expect` on an `Option` or `Result`.
+    Method(Symbol),
+    Macro(Symbol),
+    Index,
+    /// A call of a function that contains the given panics.
+    Call(Vec<Span>),
+}
+
+struct Panic {
+    span: Span,
+    kind: PanicKind,
+}
+
+fn lint_panic(cx: &LateContext<'_>, panic: Panic) {
+    let msg = match panic.kind {
+        PanicKind::Method(name) => format!("used `{name}()` in a `Drop` implementation"),
+        PanicKind::Macro(name) => format!("used `{name}!` in a `Drop` implementation"),
+        PanicKind::Index => "indexing may panic in a `Drop` implementation".to_owned(),
+        PanicKind::Call(_) => "called a function that may panic in a `Drop` implementation".to_owned(),
+    };
+    span_lint_and_then(cx, PANIC_IN_DROP, panic.span, &msg, |diag| {
+        if let PanicKind::Call(spans) = panic.kind {
+            diag.span_note(spans, "the called function may panic here");
+        }
+        diag.note("panicking while the thread is already unwinding aborts the process");
+    });
+}
+
+/// Collects the expressions of `body` that may panic. With `check_calls`, calls of the functions
+/// of the crate are included if one of these functions may panic itself.
+fn find_panics<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    body: &'tcx Body<'tcx>,
+    check_calls: bool,
+) -> Vec<Panic> {
+    let mut panics = Vec::new();
+    let _: Option<!> = for_each_expr(body.value, |e| {
+        if let Some(macro_call) = root_macro_call_first_node(cx, e) {
+            let name = cx.tcx.item_name(macro_call.def_id);
+            if matches!(name.as_str(), "panic" | "unreachable" | "todo" | "unimplemented") {
+                panics.push(Panic {
+                    span: macro_call.span,
+                    kind: PanicKind::Macro(name),
+                });
+                return ControlFlow::Continue(Descend::No);
+            }
+        }
+        if e.span.from_expansion() {
+            return ControlFlow::Continue(Descend::Yes);
+        }
+
+        let kind = match e.kind {
+            ExprKind::MethodCall(path, ..) if matches!(path.ident.name, sym::unwrap | sym::expect) => {
+                if let Some(method_id) = typeck.type_dependent_def_id(e.hir_id)
+                    && (is_diag_item_method(cx, method_id, sym::Option)
+                        || is_diag_item_method(cx, method_id, sym::Result))
+                {
+                    Some(PanicKind::Method(path.ident.name))
+                } else {
+                    None
+                }
+            },
+            ExprKind::Index(indexed, index) if !is_in_bounds(cx, typeck, indexed, index) => Some(PanicKind::Index),
+            ExprKind::Call(..) | ExprKind::MethodCall(..) if check_calls => {
+                called_fn_panics(cx, typeck, e).map(PanicKind::Call)
+            },
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            panics.push(Panic { span: e.span, kind });
+        }
+        ControlFlow::Continue(Descend::Yes)
+    });
+    panics
+}
+
+/// Checks whether `indexed[index]` indexes an array with a constant that is in bounds.
+pub(crate) fn is_in_bounds<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    indexed: &'tcx Expr<'_>,
+    index: &'tcx Expr<'_>,
+) -> bool {
+    if let ty::Array(_, len) = typeck.expr_ty(indexed).peel_refs().kind()
+        && let Some(len) = len.try_eval_target_usize(cx.tcx, cx.param_env)
+        && let Some(Constant::Int(index)) = constant(cx, typeck, index)
+    {
+        index < u128::from(len)
+    } else {
+        false
+    }
+}
+
+/// Returns the spans of the panics in the function that is called by `call`, if it's a function
+/// of the crate.
+fn called_fn_panics<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    call: &Expr<'_>,
+) -> Option<Vec<Span>> {
+    let def_id = called_fn(typeck, call)?;
+    // The body of a trait method isn't necessarily the one that is called.
+    if cx.tcx.trait_of_item(def_id).is_some() {
+        return None;
+    }
+    let body_id = cx.tcx.hir().maybe_body_owned_by(def_id.as_local()?)?;
+    let panics = find_panics(cx, cx.tcx.typeck_body(body_id), cx.tcx.hir().body(body_id), false);
+    (!panics.is_empty()).then(|| panics.into_iter().map(|panic| panic.span).collect())
+}
+
+fn called_fn(typeck: &TypeckResults<'_>, call: &Expr<'_>) -> Option<DefId> {
+    match call.kind {
+        ExprKind::Call(callee, _) => match callee.kind {
+            ExprKind::Path(ref qpath) => match typeck.qpath_res(qpath, callee.hir_id) {
+                Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) => Some(def_id),
+                _ => None,
+            },
+            _ => None,
+        },
+        ExprKind::MethodCall(..) => typeck.type_dependent_def_id(call.hir_id),
+        _ => None,
+    }
+}
@@ -0,0 +1,149 @@
+use crate::panic_in_drop::is_in_bounds;
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::macros::root_macro_call_first_node;
+use clippy_utils::visitors::{for_each_expr_with_closures, Descend};
+use clippy_utils::{is_diag_item_method, match_def_path, paths};
+use core::ops::ControlFlow;
+use rustc_hir::{BinOpKind, Body, Expr, ExprKind, Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::TypeckResults;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::{sym, Span};
+use rustc_target::spec::abi::Abi;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#[no_mangle] extern "C"` functions that may panic, e.g., by calling `unwrap`,
+    /// indexing or with integer arithmetic that overflows, outside of `std::panic::catch_unwind`.
+    ///
+    /// ### Why is this bad?
+    /// A panic must not unwind out of an `extern "C"` function. Depending on the compiler, this is
+    /// undefined behavior or aborts the process.
+    ///
+    /// ### Known problems
+    /// Functions that are called from the `extern "C"` function aren't checked.
+    ///
+    /// ### Example
+    /// ```rust
+    /// #[no_mangle]
+    /// pub extern "C" fn first(values: *const u32, len: usize) -> u32 {
+    ///     let values = unsafe { std::slice::from_raw_parts(values, len) };
+    ///     values[0]
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// #[no_mangle]
+    /// pub extern "C" fn first(values: *const u32, len: usize) -> u32 {
+    ///     let values = unsafe { std::slice::from_raw_parts(values, len) };
+    ///     values.first().copied().unwrap_or(0)
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub PANIC_IN_EXTERN_C_FN,
+    pedantic,
+    "`extern \"C\"` functions that may panic"
+}
+declare_lint_pass!(PanicInExternCFn => [PANIC_IN_EXTERN_C_FN]);
+
+impl<'tcx> LateLintPass<'tcx> for PanicInExternCFn {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if let ItemKind::Fn(sig, _, body_id) = item.kind
+            && sig.header.abi == (Abi::C { unwind: false })
+            && cx
+                .tcx
+                .hir()
+                .attrs(item.hir_id())
+                .iter()
+                .any(|attr| attr.has_name(sym::no_mangle))
+        {
+            let panics = find_panics(cx, cx.tcx.typeck_body(body_id), cx.tcx.hir().body(body_id));
+            if !panics.is_empty() {
+                span_lint_and_then(
+                    cx,
+                    PANIC_IN_EXTERN_C_FN,
+                    sig.span,
+                    "`extern \"C\"` function that may panic",
+                    |diag| {
+                        diag.help(
+                            "a panic must not unwind out of this function, consider returning an error or using `std::panic::catch_unwind`",
+                        );
+                        diag.span_note(panics, "the function may panic here");
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Collects the spans of the expressions of `body` that may panic, except for the ones that are
+/// run inside of `catch_unwind`.
+fn find_panics<'tcx>(cx: &LateContext<'tcx>, typeck: &'tcx TypeckResults<'tcx>, body: &'tcx Body<'tcx>) -> Vec<Span> {
+    let mut panics = Vec::new();
+    let _: Option<!> = for_each_expr_with_closures(cx, body.value, |e| {
+        if let Some(macro_call) = root_macro_call_first_node(cx, e) {
+            if matches!(
+                cx.tcx.item_name(macro_call.def_id).as_str(),
+                "panic" | "assert" | "assert_eq" | "assert_ne" | "unreachable" | "todo" | "unimplemented"
+            ) {
+                panics.push(macro_call.span);
+                return ControlFlow::Continue(Descend::No);
+            }
+        }
+        if e.span.from_expansion() {
+            return ControlFlow::Continue(Descend::Yes);
+        }
+
+        match e.kind {
+            ExprKind::Call(callee, _) => {
+                if let ExprKind::Path(ref qpath) = callee.kind
+                    && let Some(def_id) = typeck.qpath_res(qpath, callee.hir_id).opt_def_id()
+                    && match_def_path(cx, def_id, &paths::CATCH_UNWIND)
+                {
+                    return ControlFlow::Continue(Descend::No);
+                }
+            },
+            ExprKind::MethodCall(path, ..) if matches!(path.ident.name, sym::unwrap | sym::expect) => {
+                if let Some(method_id) = typeck.type_dependent_def_id(e.hir_id)
+                    && (is_diag_item_method(cx, method_id, sym::Option)
+                        || is_diag_item_method(cx, method_id, sym::Result))
+                {
+                    panics.push(e.span);
+                }
+            },
+            ExprKind::Index(indexed, index) => {
+                if !is_in_bounds(cx, typeck, indexed, index) {
+                    panics.push(e.span);
+                }
+            },
+            ExprKind::Binary(op, lhs, rhs) | ExprKind::AssignOp(op, lhs, rhs) => {
+                if typeck.expr_ty(lhs).is_integral() && arithmetic_may_panic(cx, typeck, op.node, lhs, rhs) {
+                    panics.push(e.span);
+                }
+            },
+            _ => {},
+        }
+        ControlFlow::Continue(Descend::Yes)
+    });
+    panics
+}
+
+/// Checks whether the integer operation `lhs op rhs` may panic. Divisions panic when dividing by
+/// zero, other operations only when they overflow and overflow checks are enabled.
+fn arithmetic_may_panic<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) -> bool {
+    match op {
+        BinOpKind::Div | BinOpKind::Rem => !matches!(constant(cx, typeck, rhs), Some(Constant::Int(n)) if n != 0),
+        BinOpKind::Add | BinOpKind::Sub | BinOpKind::Mul | BinOpKind::Shl | BinOpKind::Shr => {
+            cx.tcx.sess.overflow_checks()
+                && (constant(cx, typeck, lhs).is_none() || constant(cx, typeck, rhs).is_none())
+        },
+        _ => false,
+    }
+}
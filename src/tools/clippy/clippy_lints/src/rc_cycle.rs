@@ -0,0 +1,212 @@
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::{match_def_path, paths};
+use rustc_data_structures::fx::{FxHashSet, FxIndexMap};
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::{Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for types that hold each other through `Rc` or `Arc`, e.g., a `Parent` with an
+    /// `Rc<Child>` field and a `Child` with an `Rc<Parent>` field.
+    ///
+    /// ### Why is this bad?
+    /// Values that refer to each other through strong references keep each other alive. They're
+    /// never dropped, unless the cycle is broken by hand.
+    ///
+    /// ### Known problems
+    /// Only the types are checked, so the values might never actually form a cycle. Types that
+    /// only refer to themselves, e.g., the nodes of a linked list, aren't linted.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// struct Parent {
+    ///     children: Vec<Rc<Child>>,
+    /// }
+    /// struct Child {
+    ///     parent: RefCell<Option<Rc<Parent>>>,
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::cell::RefCell;
+    /// # use std::rc::{Rc, Weak};
+    /// struct Parent {
+    ///     children: Vec<Rc<Child>>,
+    /// }
+    /// struct Child {
+    ///     parent: RefCell<Option<Weak<Parent>>>,
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub RC_CYCLE,
+    pedantic,
+    "types that hold each other through `Rc` or `Arc`"
+}
+
+/// A field of the type `owner` which holds a `target` through a strong reference.
+struct Edge {
+    owner: LocalDefId,
+    field: LocalDefId,
+    target: LocalDefId,
+    ptr: Symbol,
+}
+
+#[derive(Default)]
+pub struct RcCycle {
+    /// The strong references held by each local type, in the order the types are declared.
+    edges: FxIndexMap<LocalDefId, Vec<Edge>>,
+}
+
+impl_lint_pass!(RcCycle => [RC_CYCLE]);
+
+impl<'tcx> LateLintPass<'tcx> for RcCycle {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if !matches!(
+            item.kind,
+            ItemKind::Struct(..) | ItemKind::Enum(..) | ItemKind::Union(..)
+        ) || in_external_macro(cx.sess(), item.span)
+        {
+            return;
+        }
+        let owner = item.owner_id.def_id;
+        let mut edges = Vec::new();
+        for field in cx.tcx.adt_def(owner).all_fields() {
+            let field_id = field.did.expect_local();
+            collect_strong_refs(
+                cx,
+                cx.tcx.type_of(field.did).instantiate_identity(),
+                None,
+                &mut |target, ptr| {
+                    edges.push(Edge {
+                        owner,
+                        field: field_id,
+                        target,
+                        ptr,
+                    });
+                },
+            );
+        }
+        if !edges.is_empty() {
+            self.edges.insert(owner, edges);
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        let mut done = FxHashSet::default();
+        for &ty in self.edges.keys() {
+            if !done.contains(&ty) {
+                self.find_cycles(cx, ty, &mut Vec::new(), &mut done);
+            }
+        }
+    }
+}
+
+impl RcCycle {
+    /// Searches the strong references starting at `ty` for cycles, and lints the reference that
+    /// completes each of them.
+    fn find_cycles<'a>(
+        &'a self,
+        cx: &LateContext<'_>,
+        ty: LocalDefId,
+        path: &mut Vec<&'a Edge>,
+        done: &mut FxHashSet<LocalDefId>,
+    ) {
+        done.insert(ty);
+        for edge in self.edges.get(&ty).into_iter().flatten() {
+            if edge.target == ty {
+                continue;
+            }
+            if let Some(start) = path.iter().position(|e| e.owner == edge.target) {
+                lint_cycle(cx, &path[start..], edge);
+            } else if !done.contains(&edge.target) {
+                path.push(edge);
+                self.find_cycles(cx, edge.target, path, done);
+                path.pop();
+            }
+        }
+    }
+}
+
+/// Calls `f` with each local type that `ty` holds through a strong reference, along with the name
+/// of the pointer type. `ptr` is the innermost pointer that `ty` is contained in.
+fn collect_strong_refs<'tcx>(
+    cx: &LateContext<'tcx>,
+    ty: Ty<'tcx>,
+    ptr: Option<Symbol>,
+    f: &mut impl FnMut(LocalDefId, Symbol),
+) {
+    match ty.kind() {
+        ty::Adt(adt, args) => {
+            let name = cx.tcx.get_diagnostic_name(adt.did());
+            if adt.is_phantom_data()
+                || match_def_path(cx, adt.did(), &paths::WEAK_RC)
+                || match_def_path(cx, adt.did(), &paths::WEAK_ARC)
+            {
+                return;
+            }
+            if let Some(ptr) = ptr
+                && !matches!(name, Some(sym::Rc | sym::Arc))
+                && let Some(target) = adt.did().as_local()
+            {
+                f(target, ptr);
+                return;
+            }
+            let ptr = if let Some(name @ (sym::Rc | sym::Arc)) = name {
+                Some(name)
+            } else {
+                ptr
+            };
+            for ty in args.types() {
+                collect_strong_refs(cx, ty, ptr, f);
+            }
+        },
+        ty::Array(ty, _) | ty::Slice(ty) => collect_strong_refs(cx, *ty, ptr, f),
+        ty::Tuple(tys) => {
+            for ty in *tys {
+                collect_strong_refs(cx, ty, ptr, f);
+            }
+        },
+        _ => {},
+    }
+}
+
+/// Lints `last`, the reference that completes the cycle of the references in `path`.
+fn lint_cycle(cx: &LateContext<'_>, path: &[&Edge], last: &Edge) {
+    let ty_name = |id: LocalDefId| cx.tcx.item_name(id.to_def_id());
+    span_lint_hir_and_then(
+        cx,
+        RC_CYCLE,
+        cx.tcx.hir().local_def_id_to_hir_id(last.field),
+        cx.tcx.def_span(last.field),
+        &format!(
+            "`{}` refers to `{}` through an `{}`, which completes a cycle of strong references",
+            ty_name(last.owner),
+            ty_name(last.target),
+            last.ptr
+        ),
+        |diag| {
+            for edge in path {
+                diag.span_note(
+                    cx.tcx.def_span(edge.field),
+                    format!(
+                        "`{}` refers to `{}` through an `{}` here",
+                        ty_name(edge.owner),
+                        ty_name(edge.target),
+                        edge.ptr
+                    ),
+                );
+            }
+            diag.help(format!(
+                "consider using a `Weak<{}>` instead, values in the cycle are never dropped",
+                ty_name(last.target)
+            ));
+        },
+    );
+}
@@ -1,19 +1,25 @@
-use clippy_utils::diagnostics::{span_lint, span_lint_and_sugg};
+use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::higher::{get_vec_init_kind, VecInitKind};
-use clippy_utils::source::snippet;
-use clippy_utils::visitors::for_each_expr;
+use clippy_utils::source::snippet_with_context;
+use clippy_utils::visitors::{for_each_expr_with_closures, Descend, Visitable};
+use clippy_utils::{match_def_path, path_to_local, paths};
 use core::ops::ControlFlow;
-use hir::{Expr, ExprKind, Local, PatKind, PathSegment, QPath, StmtKind};
+use hir::def::{DefKind, Res};
+use hir::def_id::DefId;
+use hir::{Expr, ExprKind, HirId, Local, Pat, PatKind, StmtKind, UnOp};
 use rustc_errors::Applicability;
 use rustc_hir as hir;
 use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::TypeckResults;
 use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::{sym, Symbol};
 
 declare_clippy_lint! {
     /// ### What it does
     /// This lint catches reads into a zero-length `Vec`.
     /// Especially in the case of a call to `with_capacity`, this lint warns that read
-    /// gets the number of bytes from the `Vec`'s length, not its capacity.
+    /// gets the number of bytes from the `Vec`'s length, not its capacity. Passing the `Vec` to a
+    /// function of the crate that reads into it is caught as well.
     ///
     /// ### Why is this bad?
     /// Reading zero bytes is almost certainly not the intended behavior.
@@ -35,8 +41,7 @@
     /// ```rust
     /// use std::io;
     /// fn foo<F: io::Read>(mut f: F) {
-    ///     let mut data = Vec::with_capacity(100);
-    ///     data.resize(100, 0);
+    ///     let mut data = vec![0; 100];
     ///     f.read(&mut data).unwrap();
     /// }
     /// ```
@@ -50,84 +55,273 @@
 impl<'tcx> LateLintPass<'tcx> for ReadZeroByteVec {
     fn check_block(&mut self, cx: &LateContext<'tcx>, block: &hir::Block<'tcx>) {
         for (idx, stmt) in block.stmts.iter().enumerate() {
-            if !stmt.span.from_expansion()
-                // matches `let v = Vec::new();`
-                && let StmtKind::Local(local) = stmt.kind
-                && let Local { pat, init: Some(init), .. } = local
-                && let PatKind::Binding(_, _, ident, _) = pat.kind
-                && let Some(vec_init_kind) = get_vec_init_kind(cx, init)
-            {
-                let visitor = |expr: &Expr<'_>| {
-                    if let ExprKind::MethodCall(path, _, [arg], _) = expr.kind
-                        && let PathSegment { ident: read_or_read_exact, .. } = *path
-                        && matches!(read_or_read_exact.as_str(), "read" | "read_exact")
-                        && let ExprKind::AddrOf(_, hir::Mutability::Mut, inner) = arg.kind
-                        && let ExprKind::Path(QPath::Resolved(None, inner_path)) = inner.kind
-                        && let [inner_seg] = inner_path.segments
-                        && ident.name == inner_seg.ident.name
+            if stmt.span.from_expansion() {
+                continue;
+            }
+            let mut inits = Vec::new();
+            match stmt.kind {
+                // matches `let v = Vec::new();` and `let (v, n) = (Vec::new(), 0);`
+                StmtKind::Local(&Local {
+                    pat, init: Some(init), ..
+                }) => collect_pat_inits(pat, init, &mut inits),
+                // matches `self.buf = Vec::new();`
+                StmtKind::Expr(expr) | StmtKind::Semi(expr) => {
+                    if let ExprKind::Assign(lhs, init, _) = expr.kind
+                        && let Some(place) = VecPlace::from_expr(lhs)
                     {
-                        ControlFlow::Break(())
-                    } else {
-                        ControlFlow::Continue(())
-                    }
-                };
-
-                let (read_found, next_stmt_span) =
-                if let Some(next_stmt) = block.stmts.get(idx + 1) {
-                    // case { .. stmt; stmt; .. }
-                    (for_each_expr(next_stmt, visitor).is_some(), next_stmt.span)
-                } else if let Some(e) = block.expr {
-                    // case { .. stmt; expr }
-                    (for_each_expr(e, visitor).is_some(), e.span)
-                } else {
-                    return
-                };
-
-                if read_found && !next_stmt_span.from_expansion() {
-                    let applicability = Applicability::MaybeIncorrect;
-                    match vec_init_kind {
-                        VecInitKind::WithConstCapacity(len) => {
-                            span_lint_and_sugg(
-                                cx,
-                                READ_ZERO_BYTE_VEC,
-                                next_stmt_span,
-                                "reading zero byte data to `Vec`",
-                                "try",
-                                format!("{}.resize({len}, 0); {}",
-                                    ident.as_str(),
-                                    snippet(cx, next_stmt_span, "..")
-                                ),
-                                applicability,
-                            );
-                        }
-                        VecInitKind::WithExprCapacity(hir_id) => {
-                            let e = cx.tcx.hir().expect_expr(hir_id);
-                            span_lint_and_sugg(
-                                cx,
-                                READ_ZERO_BYTE_VEC,
-                                next_stmt_span,
-                                "reading zero byte data to `Vec`",
-                                "try",
-                                format!("{}.resize({}, 0); {}",
-                                    ident.as_str(),
-                                    snippet(cx, e.span, ".."),
-                                    snippet(cx, next_stmt_span, "..")
-                                ),
-                                applicability,
-                            );
-                        }
-                        _ => {
-                            span_lint(
-                                cx,
-                                READ_ZERO_BYTE_VEC,
-                                next_stmt_span,
-                                "reading zero byte data to `Vec`",
-                            );
-
-                        }
+                        inits.push((place, init));
                     }
+                },
+                _ => {},
+            }
+
+            for (place, init) in inits {
+                if let Some(vec_init_kind) = get_vec_init_kind(cx, init) {
+                    check_vec_init(cx, block, idx, &place, init, vec_init_kind);
                 }
             }
         }
     }
 }
+
+/// Lints the first read into the vec initialized by the statement `idx` of `block`, unless
+/// the vec is used in another way before.
+fn check_vec_init<'tcx>(
+    cx: &LateContext<'tcx>,
+    block: &hir::Block<'tcx>,
+    idx: usize,
+    place: &VecPlace,
+    init: &Expr<'_>,
+    vec_init_kind: VecInitKind,
+) {
+    // Find the first statement of the rest of the block that uses the vec. Only lint if that
+    // use reads into it, any other use may have resized it.
+    let typeck = cx.typeck_results();
+    let mut next_uses = block.stmts[idx + 1..]
+        .iter()
+        .map(|next_stmt| (vec_use(cx, typeck, next_stmt, place, true), next_stmt.span))
+        .chain(block.expr.map(|e| (vec_use(cx, typeck, e, place, true), e.span)));
+    let Some((VecUse::Read, read_stmt_span)) = next_uses.find_map(|(first_use, span)| Some((first_use?, span))) else {
+        return;
+    };
+    if read_stmt_span.from_expansion() {
+        return;
+    }
+
+    let mut applicability = Applicability::MachineApplicable;
+    let len = match vec_init_kind {
+        VecInitKind::WithConstCapacity(len) => Some(len.to_string()),
+        VecInitKind::WithExprCapacity(hir_id) => {
+            let len_span = cx.tcx.hir().span(hir_id);
+            let (len, _) = snippet_with_context(cx, len_span, init.span.ctxt(), "..", &mut applicability);
+            Some(len.into_owned())
+        },
+        _ => None,
+    };
+    span_lint_and_then(
+        cx,
+        READ_ZERO_BYTE_VEC,
+        read_stmt_span,
+        "reading zero byte data to `Vec`",
+        |diag| {
+            if let Some(len) = len
+                && !init.span.from_expansion()
+            {
+                diag.span_suggestion_verbose(
+                    init.span,
+                    "initialize the `Vec` with zeroes instead",
+                    format!("vec![0; {len}]"),
+                    applicability,
+                );
+            }
+        },
+    );
+}
+
+/// Collects the bindings of `pat` together with the expression they're initialized with, looking
+/// into tuple patterns that are initialized with a tuple expression.
+fn collect_pat_inits<'tcx>(pat: &Pat<'_>, init: &'tcx Expr<'tcx>, inits: &mut Vec<(VecPlace, &'tcx Expr<'tcx>)>) {
+    match (pat.kind, init.kind) {
+        (PatKind::Binding(_, id, ..), _) => inits.push((VecPlace::local(id), init)),
+        (PatKind::Tuple(pats, dotdot), ExprKind::Tup(tup_inits))
+            if dotdot.as_opt_usize().is_none() && pats.len() == tup_inits.len() =>
+        {
+            for (pat, init) in pats.iter().zip(tup_inits) {
+                collect_pat_inits(pat, init, inits);
+            }
+        },
+        _ => {},
+    }
+}
+
+/// A local, or a field of a local, that holds the vec.
+#[derive(PartialEq, Eq)]
+struct VecPlace {
+    local: HirId,
+    /// The names of the fields that are accessed, starting from the local.
+    fields: Vec<Symbol>,
+}
+
+impl VecPlace {
+    fn local(local: HirId) -> Self {
+        Self {
+            local,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Matches a path to a local followed by any number of field accesses, e.g., `self.buf.0`.
+    fn from_expr(mut expr: &Expr<'_>) -> Option<Self> {
+        let mut fields = Vec::new();
+        while let ExprKind::Field(base, ident) = expr.kind {
+            fields.push(ident.name);
+            expr = base;
+        }
+        fields.reverse();
+        Some(Self {
+            local: path_to_local(expr)?,
+            fields,
+        })
+    }
+
+    /// Checks whether one of the places contains the other. Places of the same local that don't
+    /// overlap are distinct fields.
+    fn overlaps(&self, other: &Self) -> bool {
+        self.local == other.local && self.fields.iter().zip(&other.fields).all(|(a, b)| a == b)
+    }
+}
+
+enum VecUse {
+    /// The vec is read into, e.g., `f.read(&mut v)`.
+    Read,
+    /// Any other use, which may resize the vec, e.g., `v.resize(n, 0)` or `v = vec![0; n]`. This
+    /// includes the use of a value that contains the vec, e.g., `self.reset()` for `self.buf`.
+    Other,
+}
+
+/// Finds the first use of `place` in `node`, whose expressions are typechecked in `typeck`. With
+/// `check_callees`, passing the vec to a function of the crate that reads into it before using it
+/// in any other way counts as a read.
+fn vec_use<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    node: impl Visitable<'tcx>,
+    place: &VecPlace,
+    check_callees: bool,
+) -> Option<VecUse> {
+    for_each_expr_with_closures(cx, node, |expr| {
+        if let Some(buf) = read_buf_arg(cx, typeck, expr)
+            && buf_place(buf).as_ref() == Some(place)
+        {
+            ControlFlow::Break(VecUse::Read)
+        } else if check_callees && is_read_in_callee(cx, typeck, expr, place) {
+            ControlFlow::Break(VecUse::Read)
+        } else if let Some(used) = VecPlace::from_expr(expr)
+            && used.local == place.local
+        {
+            if used.overlaps(place) {
+                ControlFlow::Break(VecUse::Other)
+            } else {
+                // Another field of the same local, which can't hold the vec.
+                ControlFlow::Continue(Descend::No)
+            }
+        } else {
+            ControlFlow::Continue(Descend::Yes)
+        }
+    })
+}
+
+/// Checks whether `expr` passes the vec to a function of the crate that reads into it before
+/// using it in any other way. Only the body of the called function itself is checked, not the
+/// functions it passes the vec on to.
+fn is_read_in_callee<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    place: &VecPlace,
+) -> bool {
+    let Some((def_id, receiver, args)) = resolve_call(typeck, expr) else {
+        return false;
+    };
+    let Some(param_idx) = receiver
+        .into_iter()
+        .chain(args)
+        .position(|arg| buf_place(arg).as_ref() == Some(place))
+    else {
+        return false;
+    };
+    // The body of a trait method isn't necessarily the one that is called.
+    if cx.tcx.trait_of_item(def_id).is_some() {
+        return false;
+    }
+    let Some(body_id) = def_id
+        .as_local()
+        .and_then(|def_id| cx.tcx.hir().maybe_body_owned_by(def_id))
+    else {
+        return false;
+    };
+    let body = cx.tcx.hir().body(body_id);
+    if let Some(param) = body.params.get(param_idx)
+        && let PatKind::Binding(_, param_id, ..) = param.pat.kind
+    {
+        let callee_typeck = cx.tcx.typeck_body(body_id);
+        let param_use = vec_use(cx, callee_typeck, body.value, &VecPlace::local(param_id), false);
+        matches!(param_use, Some(VecUse::Read))
+    } else {
+        false
+    }
+}
+
+/// Resolves the function that `expr` calls, returning it with the receiver of a method call and
+/// the other arguments.
+fn resolve_call<'tcx>(
+    typeck: &TypeckResults<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+) -> Option<(DefId, Option<&'tcx Expr<'tcx>>, &'tcx [Expr<'tcx>])> {
+    match expr.kind {
+        ExprKind::MethodCall(_, receiver, args, _) => {
+            Some((typeck.type_dependent_def_id(expr.hir_id)?, Some(receiver), args))
+        },
+        ExprKind::Call(callee, args) => {
+            let ExprKind::Path(ref qpath) = callee.kind else {
+                return None;
+            };
+            let Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) = typeck.qpath_res(qpath, callee.hir_id) else {
+                return None;
+            };
+            Some((def_id, None, args))
+        },
+        _ => None,
+    }
+}
+
+/// If `expr` calls `read` or `read_exact` of `std::io::Read` or of the `AsyncReadExt` traits of
+/// `futures` and `tokio`, returns the buffer that is read into. Both method calls and calls
+/// through a path, e.g., `Read::read(&mut f, &mut buf)`, are recognized.
+fn read_buf_arg<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck: &TypeckResults<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+) -> Option<&'tcx Expr<'tcx>> {
+    let (def_id, receiver, args) = resolve_call(typeck, expr)?;
+    let trait_id = cx.tcx.trait_of_item(def_id)?;
+    let is_read_trait = cx.tcx.is_diagnostic_item(sym::IoRead, trait_id)
+        || match_def_path(cx, trait_id, &paths::FUTURES_IO_ASYNCREADEXT)
+        || match_def_path(cx, trait_id, &paths::TOKIO_IO_ASYNCREADEXT);
+    if is_read_trait && matches!(cx.tcx.item_name(def_id).as_str(), "read" | "read_exact") {
+        // The buffer is the argument after `self`.
+        receiver.into_iter().chain(args).nth(1)
+    } else {
+        None
+    }
+}
+
+/// Gets the place that a buffer argument refers to, e.g., `v` for `&mut v` or `&mut *v`. A vec
+/// that is already behind a mutable reference, e.g., a parameter of type `&mut Vec<u8>`, can be
+/// passed as is.
+fn buf_place(mut buf: &Expr<'_>) -> Option<VecPlace> {
+    while let ExprKind::AddrOf(_, hir::Mutability::Mut, inner) | ExprKind::Unary(UnOp::Deref, inner) = buf.kind {
+        buf = inner;
+    }
+    VecPlace::from_expr(buf)
+}
@@ -0,0 +1,111 @@
+use crate::utils::conf::DisallowedPath;
+use clippy_utils::consts::constant;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::{def_path_def_ids, fn_def_id, get_enclosing_loop_or_multi_call_closure};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls of expensive constructors inside of loops, when all of their arguments
+    /// are constants, e.g., `Regex::new` with a literal.
+    ///
+    /// The checked constructors are `Regex::new` and `RegexSet::new` of the `regex` crate and
+    /// `Client::new` of the `reqwest` crate. More can be added with the `expensive-constructors`
+    /// configuration option.
+    ///
+    /// ### Why is this bad?
+    /// The same value is built in every iteration of the loop, which is wasted work.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// for line in lines {
+    ///     let date = Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap();
+    ///     if date.is_match(line) {
+    ///         // ..
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// let date = Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap();
+    /// for line in lines {
+    ///     if date.is_match(line) {
+    ///         // ..
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub RECOMPUTE_IN_LOOP,
+    pedantic,
+    "calling an expensive constructor with constant arguments inside of a loop"
+}
+
+const DEFAULT_EXPENSIVE_CONSTRUCTORS: &[&str] = &[
+    "regex::Regex::new",
+    "regex::RegexSet::new",
+    "regex::bytes::Regex::new",
+    "regex::bytes::RegexSet::new",
+    "reqwest::Client::new",
+    "reqwest::blocking::Client::new",
+];
+
+#[derive(Debug)]
+pub struct RecomputeInLoop {
+    conf_constructors: Vec<DisallowedPath>,
+    def_ids: FxHashMap<DefId, DisallowedPath>,
+}
+
+impl RecomputeInLoop {
+    pub fn new(conf_constructors: Vec<DisallowedPath>) -> Self {
+        Self {
+            conf_constructors,
+            def_ids: FxHashMap::default(),
+        }
+    }
+}
+
+impl_lint_pass!(RecomputeInLoop => [RECOMPUTE_IN_LOOP]);
+
+impl<'tcx> LateLintPass<'tcx> for RecomputeInLoop {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        let defaults = DEFAULT_EXPENSIVE_CONSTRUCTORS
+            .iter()
+            .map(|path| DisallowedPath::Simple((*path).to_owned()));
+        for conf in defaults.chain(self.conf_constructors.iter().cloned()) {
+            let segs: Vec<_> = conf.path().split("::").collect();
+            for id in def_path_def_ids(cx, &segs) {
+                self.def_ids.insert(id, conf.clone());
+            }
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::Call(_, args) = expr.kind
+            && !in_external_macro(cx.sess(), expr.span)
+            && let Some(def_id) = fn_def_id(cx, expr)
+            && let Some(conf) = self.def_ids.get(&def_id)
+            && args.iter().all(|arg| constant(cx, cx.typeck_results(), arg).is_some())
+            && get_enclosing_loop_or_multi_call_closure(cx, expr).is_some()
+        {
+            span_lint_and_then(
+                cx,
+                RECOMPUTE_IN_LOOP,
+                expr.span,
+                &format!("`{}` is called with the same arguments in every iteration", conf.path()),
+                |diag| {
+                    if let Some(reason) = conf.reason() {
+                        diag.note(reason);
+                    }
+                    diag.help(
+                        "consider calling it once before the loop, or in a `static` that is initialized on first use",
+                    );
+                },
+            );
+        }
+    }
+}
@@ -124,6 +124,7 @@ fn visit_ty(&mut self, ty: &Ty) {
 }
 
 impl SingleComponentPathImports {
+    #[allow(clippy::linear_contains_in_loop)]
     fn check_mod(&mut self, items: &[P<Item>]) {
         // keep track of imports reused with `self` keyword, such as `self::crypto_hash` in the example
         // below. Removing the `use crypto_hash;` would make this a compile error
@@ -162,6 +163,7 @@ fn check_mod(&mut self, items: &[P<Item>]) {
         }
     }
 
+    #[allow(clippy::linear_contains_in_loop)]
     fn track_uses(
         &mut self,
         item: &Item,
@@ -0,0 +1,116 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::implements_trait;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::{Expr, ExprKind, Item, ItemKind, Mutability, QPath};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::{sym, Span, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `static mut` items and for accesses to them.
+    ///
+    /// ### Why is this bad?
+    /// Every access to a `static mut` is `unsafe`, and it's easy to cause undefined behavior with
+    /// them, e.g., by creating two mutable references to the same static, or by accessing it from
+    /// several threads. There are safe alternatives for most uses, like the atomic types for
+    /// integers, `OnceLock` for values which are only set once, `Mutex` for everything else which
+    /// can be sent between threads, and `thread_local!` for values which can't.
+    ///
+    /// Statics declared in `extern` blocks aren't linted, as they can't be replaced.
+    ///
+    /// ### Example
+    /// ```rust
+    /// static mut COUNTER: u32 = 0;
+    ///
+    /// fn next() -> u32 {
+    ///     unsafe {
+    ///         COUNTER += 1;
+    ///         COUNTER
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    ///
+    /// static COUNTER: AtomicU32 = AtomicU32::new(0);
+    ///
+    /// fn next() -> u32 {
+    ///     COUNTER.fetch_add(1, Ordering::Relaxed) + 1
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub STATIC_MUT,
+    restriction,
+    "declaring or accessing a `static mut`"
+}
+declare_lint_pass!(StaticMut => [STATIC_MUT]);
+
+impl<'tcx> LateLintPass<'tcx> for StaticMut {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if let ItemKind::Static(_, Mutability::Mut, _) = item.kind
+            && !in_external_macro(cx.sess(), item.span)
+        {
+            let ty = cx.tcx.type_of(item.owner_id).instantiate_identity();
+            lint(cx, item.span, "declaration of a `static mut`", ty);
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if let ExprKind::Path(QPath::Resolved(None, path)) = expr.kind
+            && let Res::Def(DefKind::Static(Mutability::Mut), def_id) = path.res
+            && !cx.tcx.is_foreign_item(def_id)
+            && !in_external_macro(cx.sess(), expr.span)
+        {
+            let ty = cx.tcx.type_of(def_id).instantiate_identity();
+            lint(cx, expr.span, "access of a `static mut`", ty);
+        }
+    }
+}
+
+fn lint<'tcx>(cx: &LateContext<'tcx>, span: Span, msg: &str, ty: Ty<'tcx>) {
+    span_lint_and_help(cx, STATIC_MUT, span, msg, None, &replacement_help(cx, ty));
+}
+
+/// Suggests a type which can replace a `static mut` of type `ty`.
+fn replacement_help<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> String {
+    let atomic = match ty.kind() {
+        ty::Bool => Some("AtomicBool".to_owned()),
+        ty::Int(int_ty) if !matches!(int_ty, ty::IntTy::I128) => Some(atomic_int_name(int_ty.name_str())),
+        ty::Uint(uint_ty) if !matches!(uint_ty, ty::UintTy::U128) => Some(atomic_int_name(uint_ty.name_str())),
+        ty::RawPtr(ty_and_mut) => Some(format!("AtomicPtr<{}>", ty_and_mut.ty)),
+        _ => None,
+    };
+    if let Some(atomic) = atomic {
+        return format!("consider using `std::sync::atomic::{atomic}` instead");
+    }
+    if !implements_marker(cx, ty, sym::Send) {
+        return "consider using a `thread_local!` instead".to_owned();
+    }
+    if let ty::Adt(adt, args) = ty.kind()
+        && cx.tcx.is_diagnostic_item(sym::Option, adt.did())
+        && implements_marker(cx, args.type_at(0), sym::Sync)
+    {
+        return format!(
+            "consider using `std::sync::OnceLock<{}>` instead, if the value is only set once",
+            args.type_at(0)
+        );
+    }
+    format!("consider using `std::sync::Mutex<{ty}>` instead")
+}
+
+fn implements_marker<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>, marker: Symbol) -> bool {
+    cx.tcx
+        .get_diagnostic_item(marker)
+        .map_or(false, |id| implements_trait(cx, ty, id, &[]))
+}
+
+/// Gets the name of the atomic type for an integer type, e.g., `AtomicU32` for `u32`.
+fn atomic_int_name(int_name: &str) -> String {
+    let mut chars = int_name.chars();
+    let first = chars.next().map(|c| c.to_ascii_uppercase()).unwrap_or_default();
+    format!("Atomic{first}{}", chars.as_str())
+}
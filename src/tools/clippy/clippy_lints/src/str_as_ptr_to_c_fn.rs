@@ -0,0 +1,113 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::is_type_lang_item;
+use rustc_ast::LitKind;
+use rustc_hir::{Expr, ExprKind, LangItem};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::sym;
+use rustc_target::spec::abi::Abi;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for pointers to the data of a `str` or `String`, created with `as_ptr()`, which are
+    /// passed to `extern "C"` functions expecting a `*const c_char`.
+    ///
+    /// ### Why is this bad?
+    /// C functions taking a `*const c_char` usually expect a NUL-terminated string, but Rust
+    /// strings aren't NUL-terminated, so the function would read past the end of the string.
+    ///
+    /// ### Known problems
+    /// Some C functions take the length of the string as another argument, and don't need the NUL
+    /// byte. String literals which end with `\0` aren't linted.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// use std::ffi::c_char;
+    ///
+    /// extern "C" {
+    ///     fn puts(s: *const c_char) -> i32;
+    /// }
+    ///
+    /// let name = String::from("world");
+    /// unsafe { puts(name.as_ptr().cast()) };
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// use std::ffi::{c_char, CString};
+    ///
+    /// extern "C" {
+    ///     fn puts(s: *const c_char) -> i32;
+    /// }
+    ///
+    /// let name = CString::new("world").unwrap();
+    /// unsafe { puts(name.as_ptr()) };
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub STR_AS_PTR_TO_C_FN,
+    suspicious,
+    "passing a pointer to a Rust string, which isn't NUL-terminated, to a C function"
+}
+declare_lint_pass!(StrAsPtrToCFn => [STR_AS_PTR_TO_C_FN]);
+
+impl<'tcx> LateLintPass<'tcx> for StrAsPtrToCFn {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if let ExprKind::Call(func, args) = expr.kind
+            && let Some(sig) = c_fn_sig(cx, cx.typeck_results().expr_ty(func))
+        {
+            for (arg, &input) in args.iter().zip(sig.inputs()) {
+                if is_c_char_ptr(input) && is_str_as_ptr(cx, arg) && !arg.span.from_expansion() {
+                    span_lint_and_help(
+                        cx,
+                        STR_AS_PTR_TO_C_FN,
+                        arg.span,
+                        "passing a pointer to a string which isn't NUL-terminated to a C function",
+                        None,
+                        "consider converting it to a `CString` with `CString::new`, and passing `as_ptr()` of that \
+                        instead, or using a `CStr`",
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Gets the signature of the function if it uses the C calling convention.
+fn c_fn_sig<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> Option<ty::FnSig<'tcx>> {
+    let sig = match *ty.kind() {
+        ty::FnDef(id, _) => cx.tcx.fn_sig(id).skip_binder().skip_binder(),
+        ty::FnPtr(sig) => sig.skip_binder(),
+        _ => return None,
+    };
+    matches!(sig.abi, Abi::C { .. } | Abi::System { .. }).then_some(sig)
+}
+
+/// Checks for `*const c_char`, i.e., `*const i8` or `*const u8`.
+fn is_c_char_ptr(ty: Ty<'_>) -> bool {
+    if let ty::RawPtr(ty::TypeAndMut { ty, .. }) = ty.kind() {
+        matches!(ty.kind(), ty::Int(ty::IntTy::I8) | ty::Uint(ty::UintTy::U8))
+    } else {
+        false
+    }
+}
+
+/// Checks for `s.as_ptr()`, `s.as_ptr() as *const c_char` or `s.as_ptr().cast()`, where `s` is a
+/// string which isn't a literal ending with a NUL byte.
+fn is_str_as_ptr(cx: &LateContext<'_>, mut e: &Expr<'_>) -> bool {
+    loop {
+        e = match e.kind {
+            ExprKind::Cast(inner, _) => inner,
+            ExprKind::MethodCall(path, inner, [], _) if matches!(path.ident.as_str(), "cast" | "cast_const") => inner,
+            ExprKind::MethodCall(path, recv, [], _) if path.ident.name == sym::as_ptr => {
+                let recv_ty = cx.typeck_results().expr_ty_adjusted(recv).peel_refs();
+                let is_str = recv_ty.is_str() || is_type_lang_item(cx, recv_ty, LangItem::String);
+                let is_nul_terminated_lit = matches!(
+                    recv.kind,
+                    ExprKind::Lit(lit) if matches!(lit.node, LitKind::Str(s, _) if s.as_str().ends_with('\0'))
+                );
+                return is_str && !is_nul_terminated_lit && !recv.span.from_expansion();
+            },
+            _ => return false,
+        };
+    }
+}
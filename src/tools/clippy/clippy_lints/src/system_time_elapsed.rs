@@ -0,0 +1,85 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::ty::match_type;
+use clippy_utils::{expr_or_init, match_def_path, path_def_id, paths};
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for elapsed time measured with `SystemTime`, i.e., `SystemTime::elapsed` and
+    /// `SystemTime::duration_since` calls where both times are taken with `SystemTime::now()`,
+    /// either directly or through a local.
+    ///
+    /// ### Why is this bad?
+    /// `SystemTime` follows the system clock, which can be adjusted, or jump, at any time. The
+    /// measured duration can be wrong, and `duration_since` returns an error if the clock went
+    /// backwards in the meantime. `Instant` is monotonic and is meant for measuring durations.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::time::SystemTime;
+    /// # fn work() {}
+    /// let start = SystemTime::now();
+    /// work();
+    /// let took = SystemTime::now().duration_since(start).unwrap();
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::time::Instant;
+    /// # fn work() {}
+    /// let start = Instant::now();
+    /// work();
+    /// let took = start.elapsed();
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub SYSTEM_TIME_ELAPSED,
+    suspicious,
+    "measuring elapsed time with `SystemTime` instead of `Instant`"
+}
+declare_lint_pass!(SystemTimeElapsed => [SYSTEM_TIME_ELAPSED]);
+
+impl<'tcx> LateLintPass<'tcx> for SystemTimeElapsed {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        let ExprKind::MethodCall(path, recv, args, _) = expr.kind else {
+            return;
+        };
+        let start = match (path.ident.as_str(), args) {
+            ("elapsed", []) => recv,
+            ("duration_since", [earlier]) if is_now_call(cx, expr_or_init(cx, recv)) => earlier,
+            _ => return,
+        };
+        let start_init = expr_or_init(cx, start);
+        if is_now_call(cx, start_init)
+            && match_type(cx, cx.typeck_results().expr_ty(recv), &paths::SYSTEM_TIME)
+            && !in_external_macro(cx.sess(), expr.span)
+        {
+            span_lint_and_then(
+                cx,
+                SYSTEM_TIME_ELAPSED,
+                expr.span,
+                "measuring elapsed time with `SystemTime`",
+                |diag| {
+                    if start_init.hir_id != start.hir_id {
+                        diag.span_note(start_init.span, "the start time is taken here");
+                    }
+                    diag.help(
+                        "`SystemTime` can go backwards when the system clock is adjusted, \
+                        consider using `Instant::now()` and `Instant::elapsed` instead",
+                    );
+                },
+            );
+        }
+    }
+}
+
+fn is_now_call(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    if let ExprKind::Call(f, []) = e.kind
+        && let Some(id) = path_def_id(cx, f)
+    {
+        match_def_path(cx, id, &paths::SYSTEM_TIME_NOW)
+    } else {
+        false
+    }
+}
@@ -0,0 +1,124 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{fn_def_id, get_parent_expr, match_def_path, path_to_local_id, paths};
+use core::ops::ControlFlow;
+use rustc_hir::{BindingAnnotation, Expr, ExprKind, HirId, Node, PatKind, StmtKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `std::thread::spawn` in loops, where the `JoinHandle` of the thread is discarded,
+    /// or bound to a local that is never joined, stored or passed on.
+    ///
+    /// ### Why is this bad?
+    /// Each iteration starts another thread, and nothing waits for them to finish. The number of
+    /// threads is only bounded by the number of iterations, and the threads might still be
+    /// running, or be killed halfway through, when the program exits.
+    ///
+    /// ### Known problems
+    /// A handle that is stored, e.g., pushed to a `Vec`, is assumed to be joined later.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # fn handle(_: u32) {}
+    /// for job in 0..16 {
+    ///     std::thread::spawn(move || handle(job));
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # fn handle(_: u32) {}
+    /// let handles: Vec<_> = (0..16)
+    ///     .map(|job| std::thread::spawn(move || handle(job)))
+    ///     .collect();
+    /// for handle in handles {
+    ///     handle.join().unwrap();
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub THREAD_SPAWN_IN_LOOP,
+    pedantic,
+    "spawning threads in a loop without joining them"
+}
+declare_lint_pass!(ThreadSpawnInLoop => [THREAD_SPAWN_IN_LOOP]);
+
+impl<'tcx> LateLintPass<'tcx> for ThreadSpawnInLoop {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::Call(..) = expr.kind
+            && fn_def_id(cx, expr).map_or(false, |id| match_def_path(cx, id, &paths::STD_THREAD_SPAWN))
+            && !in_external_macro(cx.sess(), expr.span)
+            && is_in_loop(cx, expr.hir_id)
+            && !is_handle_kept(cx, expr)
+        {
+            span_lint_and_help(
+                cx,
+                THREAD_SPAWN_IN_LOOP,
+                expr.span,
+                "spawning a thread in a loop without joining it",
+                None,
+                "consider collecting the `JoinHandle`s and joining them after the loop, or using a thread pool \
+                to bound the number of threads",
+            );
+        }
+    }
+}
+
+/// Checks whether `id` is in the body of a loop in the same function or closure.
+fn is_in_loop(cx: &LateContext<'_>, id: HirId) -> bool {
+    for (_, node) in cx.tcx.hir().parent_iter(id) {
+        match node {
+            Node::Expr(e) => match e.kind {
+                ExprKind::Loop(..) => return true,
+                ExprKind::Closure(..) => return false,
+                _ => {},
+            },
+            Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) => return false,
+            _ => {},
+        }
+    }
+    false
+}
+
+/// Checks whether the `JoinHandle` returned by `spawn` is used, either directly or through the
+/// local it's bound to. Handles that are only dropped don't count.
+fn is_handle_kept<'tcx>(cx: &LateContext<'tcx>, spawn: &'tcx Expr<'tcx>) -> bool {
+    if let Some(parent) = get_parent_expr(cx, spawn) {
+        return !matches!(
+            parent.kind,
+            ExprKind::Call(_, [arg]) if arg.hir_id == spawn.hir_id
+                && fn_def_id(cx, parent).map_or(false, |id| cx.tcx.is_diagnostic_item(sym::mem_drop, id))
+        );
+    }
+    match cx.tcx.hir().find_parent(spawn.hir_id) {
+        Some(Node::Local(local)) => match local.pat.kind {
+            PatKind::Binding(BindingAnnotation::NONE | BindingAnnotation::MUT, id, ..) => is_local_kept(cx, id),
+            PatKind::Wild => false,
+            _ => true,
+        },
+        Some(Node::Stmt(stmt)) => !matches!(stmt.kind, StmtKind::Semi(_)),
+        _ => true,
+    }
+}
+
+/// Checks whether the handle bound to the local `id` is joined or moved somewhere in the enclosing
+/// function.
+fn is_local_kept(cx: &LateContext<'_>, id: HirId) -> bool {
+    let Some(body) = cx.enclosing_body else {
+        return true;
+    };
+    for_each_expr(cx.tcx.hir().body(body).value, |e| {
+        if !path_to_local_id(e, id) {
+            return ControlFlow::Continue(());
+        }
+        match get_parent_expr(cx, e).map(|parent| parent.kind) {
+            Some(ExprKind::MethodCall(path, recv, ..)) if recv.hir_id == e.hir_id && path.ident.as_str() != "join" => {
+                ControlFlow::Continue(())
+            },
+            _ => ControlFlow::Break(()),
+        }
+    })
+    .is_some()
+}
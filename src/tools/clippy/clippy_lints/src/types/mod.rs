@@ -216,6 +216,8 @@
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for `Rc<T>` and `Arc<T>` when `T` is a mutable buffer type such as `String` or `Vec`.
+    /// Locals are only checked if they're initialized with `Rc::new` or `Arc::new`, which is then
+    /// replaced with a conversion, e.g., `let s: Rc<str> = string.into();`.
     ///
     /// ### Why is this bad?
     /// Expressions such as `Rc<String>` usually have no advantage over `Rc<str>`, since
@@ -422,6 +424,9 @@ fn check_trait_item(&mut self, cx: &LateContext<'tcx>, item: &TraitItem<'_>) {
     }
 
     fn check_local(&mut self, cx: &LateContext<'_>, local: &Local<'_>) {
+        if rc_buffer::check_local(cx, local) {
+            return;
+        }
         if let Some(ty) = local.ty {
             self.check_ty(
                 cx,
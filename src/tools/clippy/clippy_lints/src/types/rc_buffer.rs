@@ -1,104 +1,99 @@
-use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::diagnostics::{span_lint_and_sugg, span_lint_and_then};
 use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::sugg::Sugg;
 use clippy_utils::{path_def_id, qpath_generic_tys};
 use rustc_errors::Applicability;
 use rustc_hir::def_id::DefId;
-use rustc_hir::{self as hir, QPath, TyKind};
+use rustc_hir::{self as hir, ExprKind, Local, QPath, TyKind};
 use rustc_lint::LateContext;
-use rustc_span::symbol::sym;
+use rustc_middle::ty;
+use rustc_span::symbol::{sym, Symbol};
 
 use super::RC_BUFFER;
 
 pub(super) fn check(cx: &LateContext<'_>, hir_ty: &hir::Ty<'_>, qpath: &QPath<'_>, def_id: DefId) -> bool {
-    let app = Applicability::Unspecified;
-    if cx.tcx.is_diagnostic_item(sym::Rc, def_id) {
-        if let Some(alternate) = match_buffer_type(cx, qpath) {
-            span_lint_and_sugg(
-                cx,
-                RC_BUFFER,
-                hir_ty.span,
-                "usage of `Rc<T>` when T is a buffer type",
-                "try",
-                format!("Rc<{alternate}>"),
-                app,
-            );
-        } else {
-            let Some(ty) = qpath_generic_tys(qpath).next() else {
-                return false;
-            };
-            let Some(id) = path_def_id(cx, ty) else { return false };
-            if !cx.tcx.is_diagnostic_item(sym::Vec, id) {
-                return false;
-            }
-            let TyKind::Path(qpath) = &ty.kind else { return false };
-            let inner_span = match qpath_generic_tys(qpath).next() {
-                Some(ty) => ty.span,
-                None => return false,
-            };
-            let mut applicability = app;
-            span_lint_and_sugg(
-                cx,
-                RC_BUFFER,
-                hir_ty.span,
-                "usage of `Rc<T>` when T is a buffer type",
-                "try",
-                format!(
-                    "Rc<[{}]>",
-                    snippet_with_applicability(cx, inner_span, "..", &mut applicability)
-                ),
-                app,
-            );
-            return true;
-        }
-    } else if cx.tcx.is_diagnostic_item(sym::Arc, def_id) {
-        if let Some(alternate) = match_buffer_type(cx, qpath) {
-            span_lint_and_sugg(
-                cx,
-                RC_BUFFER,
-                hir_ty.span,
-                "usage of `Arc<T>` when T is a buffer type",
-                "try",
-                format!("Arc<{alternate}>"),
-                app,
-            );
-        } else if let Some(ty) = qpath_generic_tys(qpath).next() {
-            let Some(id) = path_def_id(cx, ty) else { return false };
-            if !cx.tcx.is_diagnostic_item(sym::Vec, id) {
-                return false;
-            }
-            let TyKind::Path(qpath) = &ty.kind else { return false };
-            let inner_span = match qpath_generic_tys(qpath).next() {
-                Some(ty) => ty.span,
-                None => return false,
-            };
-            let mut applicability = app;
-            span_lint_and_sugg(
-                cx,
-                RC_BUFFER,
-                hir_ty.span,
-                "usage of `Arc<T>` when T is a buffer type",
-                "try",
-                format!(
-                    "Arc<[{}]>",
-                    snippet_with_applicability(cx, inner_span, "..", &mut applicability)
-                ),
-                app,
-            );
-            return true;
-        }
-    }
+    let Some(rc) = shared_pointer_name(cx, def_id) else {
+        return false;
+    };
+    let mut app = Applicability::Unspecified;
+    let Some(alternate) = alternate_type(cx, qpath, &mut app) else {
+        return false;
+    };
+    span_lint_and_sugg(
+        cx,
+        RC_BUFFER,
+        hir_ty.span,
+        &format!("usage of `{rc}<T>` when T is a buffer type"),
+        "try",
+        format!("{rc}<{alternate}>"),
+        app,
+    );
+    true
+}
 
+/// Checks `let x: Rc<String> = Rc::new(s);`, where the value can be converted with `s.into()`
+/// instead.
+pub(super) fn check_local(cx: &LateContext<'_>, local: &Local<'_>) -> bool {
+    if let Some(hir_ty) = local.ty
+        && let TyKind::Path(qpath) = &hir_ty.kind
+        && let Some(def_id) = cx.qpath_res(qpath, hir_ty.hir_id).opt_def_id()
+        && let Some(rc) = shared_pointer_name(cx, def_id)
+        && let Some(init) = local.init
+        && let ExprKind::Call(func, [value]) = init.kind
+        && let ExprKind::Path(QPath::TypeRelative(_, segment)) = func.kind
+        && segment.ident.name == sym::new
+        && let ty::Adt(adt, _) = cx.typeck_results().expr_ty(init).kind()
+        && adt.did() == def_id
+        && !init.span.from_expansion()
+    {
+        let mut app = Applicability::MaybeIncorrect;
+        let Some(alternate) = alternate_type(cx, qpath, &mut app) else {
+            return false;
+        };
+        let value = Sugg::hir_with_applicability(cx, value, "..", &mut app).maybe_par();
+        span_lint_and_then(
+            cx,
+            RC_BUFFER,
+            hir_ty.span,
+            &format!("usage of `{rc}<T>` when T is a buffer type"),
+            |diag| {
+                diag.multipart_suggestion(
+                    "try",
+                    vec![
+                        (hir_ty.span, format!("{rc}<{alternate}>")),
+                        (init.span, format!("{value}.into()")),
+                    ],
+                    app,
+                );
+            },
+        );
+        return true;
+    }
     false
 }
 
-fn match_buffer_type(cx: &LateContext<'_>, qpath: &QPath<'_>) -> Option<&'static str> {
+fn shared_pointer_name(cx: &LateContext<'_>, def_id: DefId) -> Option<Symbol> {
+    match cx.tcx.get_diagnostic_name(def_id) {
+        Some(name @ (sym::Rc | sym::Arc)) => Some(name),
+        _ => None,
+    }
+}
+
+/// Gets the unsized type which the buffer type in `Rc<T>` or `Arc<T>` can be replaced with, e.g.,
+/// `str` for `String`.
+fn alternate_type(cx: &LateContext<'_>, qpath: &QPath<'_>, app: &mut Applicability) -> Option<String> {
     let ty = qpath_generic_tys(qpath).next()?;
     let id = path_def_id(cx, ty)?;
     let path = match cx.tcx.get_diagnostic_name(id) {
         Some(sym::OsString) => "std::ffi::OsStr",
         Some(sym::PathBuf) => "std::path::Path",
+        Some(sym::Vec) => {
+            let TyKind::Path(qpath) = &ty.kind else { return None };
+            let inner = qpath_generic_tys(qpath).next()?;
+            return Some(format!("[{}]", snippet_with_applicability(cx, inner.span, "..", app)));
+        },
         _ if Some(id) == cx.tcx.lang_items().string() => "str",
         _ => return None,
     };
-    Some(path)
+    Some(path.to_owned())
 }
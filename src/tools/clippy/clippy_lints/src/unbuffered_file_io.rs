@@ -0,0 +1,124 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::macros::root_macro_call_first_node;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::{get_enclosing_loop_or_multi_call_closure, is_trait_method, path_to_local};
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for reads from and writes to a `std::fs::File` inside of loops, when the file isn't
+    /// wrapped in a `BufReader` or `BufWriter`.
+    ///
+    /// ### Why is this bad?
+    /// Every read from and write to a `File` is a system call. Doing many of them in a loop is
+    /// much slower than going through a buffer, which only reads from or writes to the file once
+    /// it's empty or full.
+    ///
+    /// ### Known problems
+    /// Reading or writing large chunks of data at once, e.g., when copying a file, doesn't
+    /// benefit from buffering. `read_exact` is only linted for buffers of less than 16 bytes.
+    ///
+    /// ### Example
+    /// ```rust,no_run
+    /// # use std::fs::File;
+    /// # use std::io::Write;
+    /// let mut file = File::create("numbers.txt").unwrap();
+    /// for i in 0..100 {
+    ///     writeln!(file, "{i}").unwrap();
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,no_run
+    /// # use std::fs::File;
+    /// # use std::io::{BufWriter, Write};
+    /// let mut file = BufWriter::new(File::create("numbers.txt").unwrap());
+    /// for i in 0..100 {
+    ///     writeln!(file, "{i}").unwrap();
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub UNBUFFERED_FILE_IO,
+    pedantic,
+    "reading from or writing to a `File` in a loop without buffering"
+}
+declare_lint_pass!(UnbufferedFileIo => [UNBUFFERED_FILE_IO]);
+
+/// Reads of exactly this many bytes or more aren't considered to be small.
+const SMALL_READ_LEN: u64 = 16;
+
+impl<'tcx> LateLintPass<'tcx> for UnbufferedFileIo {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        let ExprKind::MethodCall(path, recv, args, _) = expr.kind else {
+            return;
+        };
+        // `write!` and `writeln!` expand to a call of `write_fmt`. They're external macros, so only
+        // the span of their call is checked for coming from one.
+        let span = if expr.span.from_expansion() {
+            match root_macro_call_first_node(cx, expr) {
+                Some(macro_call)
+                    if matches!(
+                        cx.tcx.get_diagnostic_name(macro_call.def_id),
+                        Some(sym::write_macro | sym::writeln_macro)
+                    ) =>
+                {
+                    macro_call.span
+                },
+                _ => return,
+            }
+        } else {
+            expr.span
+        };
+        if in_external_macro(cx.sess(), span) {
+            return;
+        }
+
+        let (msg, help) = match (path.ident.as_str(), args) {
+            ("read", _) => ("unbuffered read from a `File` in a loop", "BufReader"),
+            ("read_exact", [buf]) if is_small_buf(cx, buf) => ("unbuffered read from a `File` in a loop", "BufReader"),
+            ("write" | "write_all" | "write_fmt", _) => ("unbuffered write to a `File` in a loop", "BufWriter"),
+            _ => return,
+        };
+        if !(is_trait_method(cx, expr, sym::IoRead) || is_trait_method(cx, expr, sym::IoWrite))
+            || !is_type_diagnostic_item(cx, cx.typeck_results().expr_ty_adjusted(recv).peel_refs(), sym::File)
+        {
+            return;
+        }
+
+        let Some(loop_expr) = get_enclosing_loop_or_multi_call_closure(cx, expr) else {
+            return;
+        };
+        // A file that is opened in every iteration is only read from or written to a few times
+        // before it's closed again.
+        if let Some(file_id) = path_to_local(recv.peel_borrows())
+            && cx.tcx.hir().parent_id_iter(file_id).any(|id| id == loop_expr.hir_id)
+        {
+            return;
+        }
+
+        span_lint_and_help(
+            cx,
+            UNBUFFERED_FILE_IO,
+            span,
+            msg,
+            None,
+            &format!("consider wrapping the file in a `{help}`"),
+        );
+    }
+}
+
+/// Checks whether `buf` is an array, or a reference to one, of less than `SMALL_READ_LEN`
+/// elements.
+fn is_small_buf(cx: &LateContext<'_>, buf: &Expr<'_>) -> bool {
+    if let ty::Array(_, len) = cx.typeck_results().expr_ty(buf).peel_refs().kind()
+        && let Some(len) = len.try_eval_target_usize(cx.tcx, cx.param_env)
+    {
+        len < SMALL_READ_LEN
+    } else {
+        false
+    }
+}
@@ -0,0 +1,167 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::ty::is_type_lang_item;
+use clippy_utils::visitors::{for_each_expr, for_each_expr_with_closures};
+use clippy_utils::{expr_or_init, higher, last_path_segment, SpanlessEq};
+use core::ops::ControlFlow;
+use rustc_ast::LitKind;
+use rustc_hir::{BinOpKind, Expr, ExprKind, LangItem, QPath};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for slicing, splitting and truncating a string at a byte index that is computed with
+    /// arithmetic, e.g., `&s[..s.len() - 1]`, unless the index is checked with
+    /// `is_char_boundary` or computed with `char_indices`, or the string is checked with
+    /// `starts_with` or `ends_with`.
+    ///
+    /// ### Why is this bad?
+    /// This panics if the index falls in the middle of a character that is encoded with more than
+    /// one byte, which often only happens for some inputs.
+    ///
+    /// ### Known problems
+    /// Only the function that contains the expression is searched for checks of the index.
+    ///
+    /// ### Example
+    /// ```rust
+    /// fn shorten(s: &str, max: usize) -> &str {
+    ///     &s[..max.min(s.len())]
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// fn shorten(s: &str, max: usize) -> &str {
+    ///     let end = s.char_indices().nth(max).map_or(s.len(), |(i, _)| i);
+    ///     &s[..end]
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub UNCHECKED_CHAR_BOUNDARY,
+    pedantic,
+    "slicing a string at a computed byte index without checking that it's a char boundary"
+}
+declare_lint_pass!(UncheckedCharBoundary => [UNCHECKED_CHAR_BOUNDARY]);
+
+/// Methods that give byte indices which are known to be char boundaries, or that check them.
+const CHAR_AWARE_METHODS: [&str; 12] = [
+    "find",
+    "rfind",
+    "match_indices",
+    "rmatch_indices",
+    "char_indices",
+    "len_utf8",
+    "floor_char_boundary",
+    "ceil_char_boundary",
+    "trim_start",
+    "trim_end",
+    "trim_start_matches",
+    "trim_end_matches",
+];
+
+impl<'tcx> LateLintPass<'tcx> for UncheckedCharBoundary {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+        let (recv, msg, index) = match expr.kind {
+            ExprKind::Index(recv, index) => {
+                let Some(range) = higher::Range::hir(index) else {
+                    return;
+                };
+                let index = [range.start, range.end]
+                    .into_iter()
+                    .flatten()
+                    .find(|e| is_byte_arithmetic(cx, e));
+                (recv, "slicing a string at a computed byte index", index)
+            },
+            ExprKind::MethodCall(path, recv, [arg], _) => {
+                let msg = match path.ident.as_str() {
+                    "truncate" => "truncating a string at a computed byte index",
+                    "split_at" => "splitting a string at a computed byte index",
+                    _ => return,
+                };
+                (recv, msg, Some(arg).filter(|arg| is_byte_arithmetic(cx, arg)))
+            },
+            _ => return,
+        };
+        let recv_ty = cx.typeck_results().expr_ty_adjusted(recv).peel_refs();
+        if let Some(index) = index
+            && (recv_ty.is_str() || is_type_lang_item(cx, recv_ty, LangItem::String))
+            && !matches!(recv.peel_borrows().kind, ExprKind::Lit(lit) if matches!(lit.node, LitKind::Str(..)))
+            && !is_checked(cx, recv)
+        {
+            span_lint_and_help(
+                cx,
+                UNCHECKED_CHAR_BOUNDARY,
+                expr.span,
+                msg,
+                Some(index.span),
+                "this panics if the index is inside of a multi-byte character, consider checking it with \
+                `is_char_boundary` first",
+            );
+        }
+    }
+}
+
+/// Checks whether `e` is computed with arithmetic, and none of its operands are known to be
+/// char boundaries.
+fn is_byte_arithmetic<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> bool {
+    let e = expr_or_init(cx, e);
+    let is_arithmetic = match e.kind {
+        ExprKind::Binary(op, ..) => matches!(
+            op.node,
+            BinOpKind::Add | BinOpKind::Sub | BinOpKind::Mul | BinOpKind::Div | BinOpKind::Rem
+        ),
+        ExprKind::MethodCall(path, ..) => {
+            matches!(path.ident.as_str(), "min" | "max" | "saturating_add" | "saturating_sub")
+        },
+        _ => false,
+    };
+    is_arithmetic && !is_char_aware(cx, e)
+}
+
+/// Checks whether any part of `e`, or of the initializers of the locals it uses, comes from a
+/// method that is aware of chars, e.g., `find`. This includes closures, e.g., the one passed to
+/// `filter_map` in `lines().filter_map(|l| l.find(..)).min()`.
+fn is_char_aware<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> bool {
+    for_each_expr_with_closures(cx, e, |e| {
+        let init = expr_or_init(cx, e);
+        if init.hir_id != e.hir_id && is_char_aware(cx, init) {
+            return ControlFlow::Break(());
+        }
+        match e.kind {
+            ExprKind::MethodCall(path, ..) if CHAR_AWARE_METHODS.contains(&path.ident.as_str()) => {
+                ControlFlow::Break(())
+            },
+            // Methods that are passed as a function, e.g., `map_or(0, char::len_utf8)`.
+            ExprKind::Path(ref qpath @ (QPath::Resolved(..) | QPath::TypeRelative(..)))
+                if CHAR_AWARE_METHODS.contains(&last_path_segment(qpath).ident.as_str()) =>
+            {
+                ControlFlow::Break(())
+            },
+            _ => ControlFlow::Continue(()),
+        }
+    })
+    .is_some()
+}
+
+/// Checks whether the function containing the use of `recv` checks for char boundaries of it,
+/// e.g., with `is_char_boundary`, or checks which chars it starts or ends with.
+fn is_checked(cx: &LateContext<'_>, recv: &Expr<'_>) -> bool {
+    let Some(body) = cx.enclosing_body else {
+        return false;
+    };
+    for_each_expr(cx.tcx.hir().body(body).value, |e| {
+        if let ExprKind::MethodCall(path, checked, ..) = e.kind
+            && (matches!(path.ident.as_str(), "is_char_boundary" | "starts_with" | "ends_with")
+                || CHAR_AWARE_METHODS.contains(&path.ident.as_str()))
+            && SpanlessEq::new(cx).eq_expr(checked.peel_borrows(), recv.peel_borrows())
+        {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_some()
+}
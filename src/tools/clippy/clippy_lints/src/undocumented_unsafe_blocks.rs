@@ -2,17 +2,21 @@
 
 use clippy_utils::diagnostics::span_lint_and_help;
 use clippy_utils::source::walk_span_to_context;
+use clippy_utils::ty::implements_trait;
 use clippy_utils::visitors::{for_each_expr_with_closures, Descend};
-use clippy_utils::{get_parent_node, is_lint_allowed};
+use clippy_utils::{get_parent_node, is_lint_allowed, match_def_path, paths};
 use hir::HirId;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::sync::Lrc;
 use rustc_hir as hir;
+use rustc_hir::def_id::DefId;
 use rustc_hir::{Block, BlockCheckMode, ItemKind, Node, UnsafeSource};
 use rustc_lexer::{tokenize, TokenKind};
 use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, Ty};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
-use rustc_span::{BytePos, Pos, Span, SyntaxContext};
+use rustc_span::{sym, BytePos, Pos, Span, SyntaxContext};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -38,6 +42,10 @@
     /// );
     /// ```
     ///
+    /// With the `require-safety-comment-per-unsafe-call` configuration, unsafe blocks which
+    /// contain more than one unsafe call need a safety comment for each statement with an unsafe
+    /// call as well, so every call is justified on its own.
+    ///
     /// ### Why is this bad?
     /// Undocumented unsafe blocks and impls can make it difficult to
     /// read and maintain code, as well as uncover unsoundness
@@ -92,22 +100,71 @@
     "annotating safe code with a safety comment"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `unsafe impl Send` and `unsafe impl Sync` without a `// SAFETY: ` comment, if the
+    /// type contains raw pointers or `UnsafeCell`, directly or in one of its fields.
+    ///
+    /// This is a subset of `undocumented_unsafe_blocks`, and isn't linted when that lint is
+    /// enabled.
+    ///
+    /// ### Why is this bad?
+    /// Such types don't implement `Send` or `Sync` on their own because the compiler can't tell
+    /// whether the pointed-to data or the interior mutability is accessed safely from several
+    /// threads. The invariant which makes this sound should be written down, so it's kept when
+    /// the type changes.
+    ///
+    /// ### Example
+    /// ```rust
+    /// struct Buffer {
+    ///     ptr: *mut u8,
+    ///     len: usize,
+    /// }
+    ///
+    /// unsafe impl Send for Buffer {}
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// struct Buffer {
+    ///     ptr: *mut u8,
+    ///     len: usize,
+    /// }
+    ///
+    /// // SAFETY: `Buffer` owns the allocation `ptr` points to, and it's never shared.
+    /// unsafe impl Send for Buffer {}
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub UNDOCUMENTED_UNSAFE_SEND_SYNC,
+    pedantic,
+    "implementing `Send` or `Sync` for a type with raw pointers or `UnsafeCell` without explaining why it is safe"
+}
+
 #[derive(Copy, Clone)]
 pub struct UndocumentedUnsafeBlocks {
     accept_comment_above_statement: bool,
     accept_comment_above_attributes: bool,
+    require_safety_comment_per_unsafe_call: bool,
 }
 
 impl UndocumentedUnsafeBlocks {
-    pub fn new(accept_comment_above_statement: bool, accept_comment_above_attributes: bool) -> Self {
+    pub fn new(
+        accept_comment_above_statement: bool,
+        accept_comment_above_attributes: bool,
+        require_safety_comment_per_unsafe_call: bool,
+    ) -> Self {
         Self {
             accept_comment_above_statement,
             accept_comment_above_attributes,
+            require_safety_comment_per_unsafe_call,
         }
     }
 }
 
-impl_lint_pass!(UndocumentedUnsafeBlocks => [UNDOCUMENTED_UNSAFE_BLOCKS, UNNECESSARY_SAFETY_COMMENT]);
+impl_lint_pass!(UndocumentedUnsafeBlocks => [
+    UNDOCUMENTED_UNSAFE_BLOCKS,
+    UNNECESSARY_SAFETY_COMMENT,
+    UNDOCUMENTED_UNSAFE_SEND_SYNC,
+]);
 
 impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
     fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
@@ -140,6 +197,15 @@ fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
             );
         }
 
+        if self.require_safety_comment_per_unsafe_call
+            && block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided)
+            && !in_external_macro(cx.tcx.sess, block.span)
+            && !is_lint_allowed(cx, UNDOCUMENTED_UNSAFE_BLOCKS, block.hir_id)
+            && !is_unsafe_from_proc_macro(cx, block.span)
+        {
+            check_unsafe_calls_in_block(cx, block);
+        }
+
         if let Some(tail) = block.expr
             && !is_lint_allowed(cx, UNNECESSARY_SAFETY_COMMENT, tail.hir_id)
             && !in_external_macro(cx.tcx.sess, tail.span)
@@ -201,16 +267,17 @@ fn check_item(&mut self, cx: &LateContext<'_>, item: &hir::Item<'_>) {
         match (&item.kind, item_has_safety_comment) {
             // lint unsafe impl without safety comment
             (hir::ItemKind::Impl(impl_), HasSafetyComment::No) if impl_.unsafety == hir::Unsafety::Unsafe => {
-                if !is_lint_allowed(cx, UNDOCUMENTED_UNSAFE_BLOCKS, item.hir_id())
-                    && !is_unsafe_from_proc_macro(cx, item.span)
-                {
-                    let source_map = cx.tcx.sess.source_map();
-                    let span = if source_map.is_multiline(item.span) {
-                        source_map.span_until_char(item.span, '\n')
-                    } else {
-                        item.span
-                    };
+                if is_unsafe_from_proc_macro(cx, item.span) {
+                    return;
+                }
+                let source_map = cx.tcx.sess.source_map();
+                let span = if source_map.is_multiline(item.span) {
+                    source_map.span_until_char(item.span, '\n')
+                } else {
+                    item.span
+                };
 
+                if !is_lint_allowed(cx, UNDOCUMENTED_UNSAFE_BLOCKS, item.hir_id()) {
                     span_lint_and_help(
                         cx,
                         UNDOCUMENTED_UNSAFE_BLOCKS,
@@ -219,6 +286,8 @@ fn check_item(&mut self, cx: &LateContext<'_>, item: &hir::Item<'_>) {
                         None,
                         "consider adding a safety comment on the preceding line",
                     );
+                } else if !is_lint_allowed(cx, UNDOCUMENTED_UNSAFE_SEND_SYNC, item.hir_id()) {
+                    check_unsafe_send_sync(cx, item, impl_, span);
                 }
             },
             // lint safe impl with unnecessary safety comment
@@ -279,6 +348,129 @@ fn check_item(&mut self, cx: &LateContext<'_>, item: &hir::Item<'_>) {
     }
 }
 
+/// Lints an `unsafe impl` of `Send` or `Sync` without a safety comment when the type contains
+/// raw pointers or `UnsafeCell`.
+fn check_unsafe_send_sync(cx: &LateContext<'_>, item: &hir::Item<'_>, impl_: &hir::Impl<'_>, span: Span) {
+    if let Some(trait_ref) = &impl_.of_trait
+        && let Some(trait_id) = trait_ref.trait_def_id()
+        && let Some(name @ (sym::Send | sym::Sync)) = cx.tcx.get_diagnostic_name(trait_id)
+    {
+        let self_ty = cx.tcx.type_of(item.owner_id).instantiate_identity();
+        if !has_raw_pointer_or_unsafe_cell(cx, self_ty, &mut FxHashSet::default()) {
+            return;
+        }
+        span_lint_and_help(
+            cx,
+            UNDOCUMENTED_UNSAFE_SEND_SYNC,
+            span,
+            &format!("unsafe impl of `{name}` missing a safety comment"),
+            None,
+            &format!(
+                "`{self_ty}` contains raw pointers or `UnsafeCell`, consider adding a safety comment on the \
+                preceding line explaining why implementing `{name}` is sound"
+            ),
+        );
+    }
+}
+
+/// Checks whether `ty` is or contains a raw pointer, `NonNull`, or interior mutability without
+/// synchronization, like `UnsafeCell` or `Cell`. Only the fields of local types are looked at,
+/// and the type arguments of other types.
+fn has_raw_pointer_or_unsafe_cell<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>, seen: &mut FxHashSet<DefId>) -> bool {
+    match *ty.kind() {
+        ty::RawPtr(_) => true,
+        ty::Array(ty, _) | ty::Slice(ty) => has_raw_pointer_or_unsafe_cell(cx, ty, seen),
+        ty::Tuple(tys) => tys.iter().any(|ty| has_raw_pointer_or_unsafe_cell(cx, ty, seen)),
+        ty::Adt(adt, args) if adt.did().is_local() => {
+            seen.insert(adt.did())
+                && adt
+                    .all_fields()
+                    .any(|field| has_raw_pointer_or_unsafe_cell(cx, field.ty(cx.tcx, args), seen))
+        },
+        // The fields of other types aren't looked at, but their type arguments are, e.g., the
+        // `NonNull` in `Option<NonNull<T>>`.
+        ty::Adt(adt, args) => {
+            match_def_path(cx, adt.did(), &paths::PTR_NON_NULL)
+                || (!ty.is_freeze(cx.tcx, cx.param_env)
+                    && cx
+                        .tcx
+                        .get_diagnostic_item(sym::Sync)
+                        .map_or(false, |sync| !implements_trait(cx, ty, sync, &[])))
+                || args.types().any(|ty| has_raw_pointer_or_unsafe_cell(cx, ty, seen))
+        },
+        _ => false,
+    }
+}
+
+/// Checks that each statement with an unsafe call has its own safety comment, if `block` contains
+/// more than one unsafe call.
+fn check_unsafe_calls_in_block<'tcx>(cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
+    let mut calls = Vec::new();
+    for_each_expr_with_closures(cx, block, |e| {
+        match e.kind {
+            // nested unsafe blocks are checked on their own
+            hir::ExprKind::Block(
+                Block {
+                    rules: BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided),
+                    ..
+                },
+                _,
+            ) => return ControlFlow::Continue(Descend::No),
+            _ if is_unsafe_call(cx, e) => calls.push(e),
+            _ => (),
+        }
+        ControlFlow::<(), Descend>::Continue(Descend::Yes)
+    });
+    if calls.len() < 2 {
+        return;
+    }
+
+    let mut checked_stmts = FxHashSet::default();
+    for call in calls {
+        // Find the statement, or the trailing expression, of the innermost block which the call is
+        // in. That's where the safety comment has to be.
+        let mut stmt = (call.span, call.hir_id);
+        for (id, node) in cx.tcx.hir().parent_iter(call.hir_id) {
+            match node {
+                Node::Block(_) => break,
+                Node::Stmt(hir::Stmt { span, .. }) | Node::Expr(hir::Expr { span, .. }) => stmt = (*span, id),
+                _ => (),
+            }
+        }
+        let (span, hir_id) = stmt;
+        if checked_stmts.insert(hir_id)
+            && !call.span.from_expansion()
+            && !matches!(stmt_has_safety_comment(cx, span, hir_id), HasSafetyComment::Yes(_))
+        {
+            span_lint_and_help(
+                cx,
+                UNDOCUMENTED_UNSAFE_BLOCKS,
+                call.span,
+                "unsafe call missing a safety comment",
+                None,
+                "consider adding a safety comment on the line preceding the statement, as the unsafe block \
+                contains several unsafe calls",
+            );
+        }
+    }
+}
+
+fn is_unsafe_call<'tcx>(cx: &LateContext<'tcx>, e: &'tcx hir::Expr<'_>) -> bool {
+    let sig = match e.kind {
+        hir::ExprKind::Call(func, _) => match *cx.typeck_results().expr_ty(func).peel_refs().kind() {
+            ty::FnDef(id, _) => cx.tcx.fn_sig(id).skip_binder().skip_binder(),
+            ty::FnPtr(sig) => sig.skip_binder(),
+            _ => return false,
+        },
+        hir::ExprKind::MethodCall(..) => match cx.typeck_results().type_dependent_def_id(e.hir_id) {
+            Some(id) => cx.tcx.fn_sig(id).skip_binder().skip_binder(),
+            None => return false,
+        },
+        _ => return false,
+    };
+    sig.unsafety == hir::Unsafety::Unsafe
+}
+
 fn expr_has_unnecessary_safety_comment<'tcx>(
     cx: &LateContext<'tcx>,
     expr: &'tcx hir::Expr<'tcx>,
@@ -724,6 +916,7 @@ fn text_has_safety_comment(src: &str, line_starts: &[BytePos], offset: usize) ->
     let (mut line_start, mut line) = (line_start, line);
     loop {
         if line.starts_with("/*") {
+            #[allow(clippy::unchecked_char_boundary)]
             let src = &src[line_start..line_starts.last().unwrap().to_usize() - offset];
             let mut tokens = tokenize(src);
             return (src[..tokens.next().unwrap().len as usize]
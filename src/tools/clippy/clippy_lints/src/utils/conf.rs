@@ -36,6 +36,7 @@
 ];
 const DEFAULT_DISALLOWED_NAMES: &[&str] = &["foo", "baz", "quux"];
 const DEFAULT_ALLOWED_IDENTS_BELOW_MIN_CHARS: &[&str] = &["i", "j", "x", "y", "z", "w", "n"];
+const DEFAULT_SECRET_FIELD_NAMES: &[&str] = &["password", "passwd", "secret", "token", "api_key", "private_key"];
 
 /// Holds information used by `MISSING_ENFORCED_IMPORT_RENAMES` lint.
 #[derive(Clone, Debug, Deserialize)]
@@ -547,10 +548,60 @@ pub fn get_configuration_metadata() -> Vec<ClippyConfiguration> {
     ///
     /// Whether to accept a safety comment to be placed above the attributes for the `unsafe` block
     (accept_comment_above_attributes: bool = false),
+    /// Lint: UNDOCUMENTED_UNSAFE_BLOCKS.
+    ///
+    /// Whether to require a safety comment for each statement with an unsafe call, in `unsafe` blocks which contain more than one unsafe call
+    (require_safety_comment_per_unsafe_call: bool = false),
     /// Lint: UNNECESSARY_RAW_STRING_HASHES.
     ///
     /// Whether to allow `r#""#` when `r""` can be used
     (allow_one_hash_in_raw_strings: bool = false),
+    /// Lint: RECOMPUTE_IN_LOOP.
+    ///
+    /// Paths of functions that are expensive to call, in addition to the ones checked by default, e.g.,
+    /// `["crate::Parser::new"]`
+    (expensive_constructors: Vec<crate::utils::conf::DisallowedPath> = Vec::new()),
+    /// Lint: PANIC_IN_DROP.
+    ///
+    /// Whether to also check the functions of the crate that are called from `drop`
+    (check_fns_called_from_drop: bool = false),
+    /// Lint: NON_SEND_CAPTURE_IN_SPAWN, DETACHED_TASK.
+    ///
+    /// Paths of functions that run the closure or future that is passed to them on another thread, e.g.,
+    /// `["my_runtime::spawn"]`. For `detached_task`, these are checked in addition to the spawn functions of
+    /// `tokio` and `async-std`
+    (spawn_functions: Vec<String> = Vec::new()),
+    /// Lint: EXPOSED_SECRET_FIELD, NON_CONSTANT_TIME_COMPARISON, WEAK_KEY_RANDOMNESS.
+    ///
+    /// Names of fields, parameters and locals that are treated as secrets. A name matches if it contains one of
+    /// these as a whole `_`-separated part, e.g., `token` matches `access_token` but not `tokens`. `".."` can be
+    /// used as part of the list to indicate that the configured values should be appended to the default
+    /// configuration of Clippy. By default, any configuration will replace the default value.
+    (secret_field_names: Vec<String> = super::DEFAULT_SECRET_FIELD_NAMES.iter().map(ToString::to_string).collect()),
+    /// Lint: WEAK_KEY_RANDOMNESS.
+    ///
+    /// Paths of functions which create keys or other secrets from their arguments, e.g.,
+    /// `["ed25519_dalek::SigningKey::generate"]`
+    (key_constructors: Vec<String> = Vec::new()),
+    /// Lint: INTEGER_DIVISION_BEFORE_MULTIPLICATION.
+    ///
+    /// Whether to allow `x / n * n`, which rounds `x` down to a multiple of `n`, e.g., to align it
+    (allow_rounding_division: bool = true),
+    /// Lint: LARGE_CLONE_INTO_CLOSURE.
+    ///
+    /// The size in bytes above which cloning a value into a closure which only borrows it is linted
+    (clone_into_closure_size_threshold: u64 = 256),
+    /// Lint: BLOCK_ON_IN_ASYNC.
+    ///
+    /// Paths of functions that block the thread until the future that is passed to them completes, e.g.,
+    /// `["my_runtime::block_on"]`. These are checked in addition to the `block_on` functions of `futures`,
+    /// `tokio` and `async-std`
+    (block_on_functions: Vec<String> = Vec::new()),
+    /// Lint: HARDCODED_PLATFORM_PATH.
+    ///
+    /// Whether to allow hard-coded paths into `/tmp` and Windows paths like `C:\\`, for projects which only
+    /// target a single platform. Paths into home directories are still linted
+    (allow_platform_specific_paths: bool = false),
 }
 
 /// Search for the configuration file.
@@ -618,6 +669,7 @@ pub fn read(sess: &Session, path: &Path) -> TryConf {
         Ok(mut conf) => {
             extend_vec_if_indicator_present(&mut conf.conf.doc_valid_idents, DEFAULT_DOC_VALID_IDENTS);
             extend_vec_if_indicator_present(&mut conf.conf.disallowed_names, DEFAULT_DISALLOWED_NAMES);
+            extend_vec_if_indicator_present(&mut conf.conf.secret_field_names, DEFAULT_SECRET_FIELD_NAMES);
             // TODO: THIS SHOULD BE TESTED, this comment will be gone soon
             if conf.conf.allowed_idents_below_min_chars.contains(&"..".to_owned()) {
                 conf.conf
@@ -0,0 +1,210 @@
+use crate::exposed_secret_field::is_secret_name;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{def_path_def_ids, find_binding_init, fn_def_id, match_def_path, path_to_local, paths};
+use core::ops::ControlFlow;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind, Local, PatKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, Ty};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::symbol::Ident;
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for values from `rand::thread_rng`, `rand::random`, `SmallRng` or `fastrand` which
+    /// are used as key material, i.e., which are bound to, assigned to or filled into a field or
+    /// local with a name that looks like a secret, or which are passed to one of the functions
+    /// configured with `key-constructors`. Values are tracked through immutable locals.
+    ///
+    /// The names which are treated as secrets can be configured with `secret-field-names`.
+    ///
+    /// ### Why is this bad?
+    /// `SmallRng` and `fastrand` are predictable, anyone who sees a few of their outputs can
+    /// compute the next ones. `ThreadRng` is a CSPRNG, but it's a userspace generator which keeps
+    /// its state in memory between reseeds, which isn't recommended for long-lived keys.
+    /// `OsRng` gets each value from the operating system.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// use rand::Rng;
+    ///
+    /// let api_key: [u8; 32] = rand::thread_rng().gen();
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// use rand::rngs::OsRng;
+    /// use rand::Rng;
+    ///
+    /// let api_key: [u8; 32] = OsRng.gen();
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub WEAK_KEY_RANDOMNESS,
+    restriction,
+    "generating keys or other secrets with a random number generator that isn't meant for them"
+}
+
+pub struct WeakKeyRandomness {
+    secret_names: Vec<String>,
+    conf_key_constructors: Vec<String>,
+    key_constructors: FxHashSet<DefId>,
+}
+
+impl WeakKeyRandomness {
+    pub fn new(secret_names: Vec<String>, conf_key_constructors: Vec<String>) -> Self {
+        Self {
+            secret_names,
+            conf_key_constructors,
+            key_constructors: FxHashSet::default(),
+        }
+    }
+
+    /// If `e` is a field or local with a secret name, gets the name.
+    fn secret_place(&self, cx: &LateContext<'_>, e: &Expr<'_>) -> Option<Ident> {
+        let name = match e.kind {
+            ExprKind::Field(_, ident) => ident,
+            _ => cx.tcx.hir().ident(path_to_local(e)?),
+        };
+        is_secret_name(&self.secret_names, name.as_str()).then_some(name)
+    }
+}
+
+impl_lint_pass!(WeakKeyRandomness => [WEAK_KEY_RANDOMNESS]);
+
+impl<'tcx> LateLintPass<'tcx> for WeakKeyRandomness {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for path in &self.conf_key_constructors {
+            let segs: Vec<_> = path.split("::").collect();
+            self.key_constructors.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
+    fn check_local(&mut self, cx: &LateContext<'tcx>, local: &'tcx Local<'tcx>) {
+        if let PatKind::Binding(_, _, name, None) = local.pat.kind
+            && is_secret_name(&self.secret_names, name.as_str())
+            && let Some(init) = local.init
+            && let Some(source) = find_weak_source(cx, init)
+        {
+            lint(cx, source, name.span, &format!("the value is used for `{name}` here"));
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        match expr.kind {
+            ExprKind::Assign(place, value, _) => {
+                if let Some(name) = self.secret_place(cx, place)
+                    && let Some(source) = find_weak_source(cx, value)
+                {
+                    lint(cx, source, place.span, &format!("the value is used for `{name}` here"));
+                }
+            },
+            ExprKind::Struct(_, fields, _) => {
+                for field in fields {
+                    if !field.is_shorthand
+                        && is_secret_name(&self.secret_names, field.ident.as_str())
+                        && let Some(source) = find_weak_source(cx, field.expr)
+                    {
+                        lint(
+                            cx,
+                            source,
+                            field.span,
+                            &format!("the value is used for `{}` here", field.ident),
+                        );
+                    }
+                }
+            },
+            ExprKind::Call(_, args) | ExprKind::MethodCall(_, _, args, _) => {
+                if let Some(id) = fn_def_id(cx, expr)
+                    && self.key_constructors.contains(&id)
+                    && let Some(source) = args.iter().find_map(|arg| find_weak_source(cx, arg))
+                {
+                    lint(
+                        cx,
+                        source,
+                        expr.span,
+                        &format!("the value is passed to `{}` here", cx.tcx.def_path_str(id)),
+                    );
+                } else if is_weak_source(cx, expr) || is_weak_rng_method_call(cx, expr) {
+                    // e.g., `rng.fill_bytes(&mut key)`
+                    for arg in args {
+                        if let ExprKind::AddrOf(_, _, place) = arg.kind
+                            && let Some(name) = self.secret_place(cx, place)
+                        {
+                            lint(cx, expr, place.span, &format!("the value is filled into `{name}` here"));
+                        }
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+fn lint(cx: &LateContext<'_>, source: &Expr<'_>, sink: Span, note: &str) {
+    if in_external_macro(cx.sess(), source.span) {
+        return;
+    }
+    span_lint_and_then(
+        cx,
+        WEAK_KEY_RANDOMNESS,
+        source.span,
+        "this random number generator isn't meant for generating secrets",
+        |diag| {
+            diag.span_note(sink, note.to_string());
+            diag.help("consider using `rand::rngs::OsRng`, which gets its values from the operating system");
+        },
+    );
+}
+
+/// Finds a value from a weak random number generator in `e`, or in the initializer of a local
+/// which `e` uses.
+fn find_weak_source<'tcx>(cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) -> Option<&'tcx Expr<'tcx>> {
+    for_each_expr(e, |e| {
+        if is_weak_source(cx, e) {
+            return ControlFlow::Break(e);
+        }
+        match path_to_local(e)
+            .and_then(|id| find_binding_init(cx, id))
+            .and_then(|init| find_weak_source(cx, init))
+        {
+            Some(source) => ControlFlow::Break(source),
+            None => ControlFlow::Continue(()),
+        }
+    })
+}
+
+/// Checks whether `e` is a weak random number generator, or a call to a function which returns
+/// a random value from one, e.g., `rand::random()` or `fastrand::u64(..)`.
+fn is_weak_source(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    if let ExprKind::Call(..) = e.kind
+        && let Some(id) = fn_def_id(cx, e)
+        && (match_def_path(cx, id, &paths::RAND_RANDOM) || is_fastrand(cx, id))
+    {
+        return true;
+    }
+    is_weak_rng_ty(cx, cx.typeck_results().expr_ty(e).peel_refs())
+}
+
+/// Checks whether `e` is a method call on a weak random number generator, e.g., `rng.fill(..)`.
+fn is_weak_rng_method_call(cx: &LateContext<'_>, e: &Expr<'_>) -> bool {
+    matches!(e.kind, ExprKind::MethodCall(_, recv, ..)
+        if is_weak_rng_ty(cx, cx.typeck_results().expr_ty_adjusted(recv).peel_refs()))
+}
+
+fn is_weak_rng_ty(cx: &LateContext<'_>, ty: Ty<'_>) -> bool {
+    match ty.kind() {
+        ty::Adt(adt, _) => {
+            match_def_path(cx, adt.did(), &paths::RAND_THREAD_RNG)
+                || match_def_path(cx, adt.did(), &paths::RAND_SMALL_RNG)
+                || is_fastrand(cx, adt.did())
+        },
+        _ => false,
+    }
+}
+
+fn is_fastrand(cx: &LateContext<'_>, id: DefId) -> bool {
+    cx.tcx.crate_name(id.krate).as_str() == "fastrand"
+}
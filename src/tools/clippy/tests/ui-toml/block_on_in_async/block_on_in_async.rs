@@ -0,0 +1,21 @@
+#![warn(clippy::block_on_in_async)]
+
+use std::future::Future;
+
+struct Executor;
+
+impl Executor {
+    fn run_until<F: Future>(&self, fut: F) -> F::Output {
+        let _ = fut;
+        unimplemented!()
+    }
+}
+
+async fn load() -> u32 {
+    1
+}
+
+fn main() {
+    let executor = Executor;
+    let _ = async move { executor.run_until(load()) };
+}
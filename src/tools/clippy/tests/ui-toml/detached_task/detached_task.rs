@@ -0,0 +1,26 @@
+#![warn(clippy::detached_task)]
+
+struct Handle;
+
+struct Pool;
+
+impl Pool {
+    fn spawn<F: FnOnce() + Send + 'static>(&self, f: F) -> Handle {
+        f();
+        Handle
+    }
+
+    fn execute<F: FnOnce() + Send + 'static>(&self, f: F) -> Handle {
+        f();
+        Handle
+    }
+}
+
+fn main() {
+    let pool = Pool;
+    pool.spawn(|| println!("working"));
+
+    // don't lint
+    pool.execute(|| println!("working"));
+    let _ = pool.spawn(|| println!("working"));
+}
@@ -0,0 +1,11 @@
+#![warn(clippy::exposed_secret_field)]
+
+#[derive(Debug)]
+struct Card {
+    number: String,
+    pin: String,
+    password: String,
+    pinned_note: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#![warn(clippy::hardcoded_platform_path)]
+
+use std::fs::File;
+use std::path::Path;
+
+fn main() {
+    let _ = File::open("/home/ferris/config.toml");
+
+    // don't lint
+    let _ = File::open("/tmp/input.txt");
+    let _ = Path::new(r"C:\Users\ferris\data");
+}
@@ -0,0 +1,6 @@
+#![warn(clippy::integer_division_before_multiplication)]
+
+fn main() {
+    let size = 13usize;
+    let _ = size / 8 * 8;
+}
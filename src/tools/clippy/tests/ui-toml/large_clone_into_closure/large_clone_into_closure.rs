@@ -0,0 +1,15 @@
+#![warn(clippy::large_clone_into_closure)]
+
+fn main() {
+    let name = String::from("clippy");
+    let boxed = Box::new(7u8);
+
+    let name_copy = name.clone();
+    let greet = move || println!("hello, {}", name_copy.trim());
+    greet();
+
+    // don't lint
+    let boxed_copy = boxed.clone();
+    let show = move || println!("{}", &boxed_copy);
+    show();
+}
@@ -0,0 +1,47 @@
+#![warn(clippy::non_send_capture_in_spawn)]
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn spawn<F: FnOnce() + 'static>(f: F) {
+    f();
+}
+
+struct Pool;
+
+impl Pool {
+    fn execute<F: FnOnce()>(&self, f: F) {
+        f();
+    }
+}
+
+fn run<F: FnOnce()>(f: F) {
+    f();
+}
+
+fn main() {
+    let rc = Rc::new(1);
+    spawn(move || drop(rc));
+
+    let ptr: *const i32 = &1;
+    spawn(move || assert!(!ptr.is_null()));
+
+    let cell = Cell::new(1);
+    Pool.execute(|| cell.set(2));
+
+    let shared = Arc::new(RefCell::new(0));
+    Pool.execute(|| *shared.borrow_mut() += 1);
+
+    // `Send` captures
+    let arc = Arc::new(1);
+    spawn(move || drop(arc));
+    let value = 1;
+    Pool.execute(|| {
+        value.count_ones();
+    });
+
+    // Not a spawn function
+    let rc = Rc::new(1);
+    run(move || drop(rc));
+}
@@ -0,0 +1,35 @@
+#![warn(clippy::panic_in_drop)]
+
+struct Helper(Option<u32>);
+
+impl Helper {
+    fn value(&self) -> u32 {
+        self.0.unwrap()
+    }
+
+    fn value_or_zero(&self) -> u32 {
+        self.0.unwrap_or(0)
+    }
+
+    fn indirect(&self) -> u32 {
+        self.value()
+    }
+}
+
+fn flush(buf: &[u8]) {
+    let _ = buf[0];
+}
+
+impl Drop for Helper {
+    fn drop(&mut self) {
+        let _ = self.value();
+        flush(&[]);
+        let _ = self.value_or_zero();
+        // Only the functions that are called from `drop` are checked
+        let _ = self.indirect();
+        // Functions of other crates aren't checked
+        let _ = std::env::current_dir();
+    }
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#![warn(clippy::recompute_in_loop)]
+
+struct Grammar;
+
+impl Grammar {
+    fn load(_: &str) -> Self {
+        Grammar
+    }
+}
+
+fn checksum(_: u32, _: u32) -> u32 {
+    0
+}
+
+fn main() {
+    for i in 0..10 {
+        let _ = Grammar::load("rust.grammar");
+        let _ = checksum(1, 2);
+        let _ = checksum(1, i);
+    }
+}
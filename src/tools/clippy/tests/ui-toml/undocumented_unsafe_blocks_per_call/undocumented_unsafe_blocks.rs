@@ -0,0 +1,54 @@
+#![warn(clippy::undocumented_unsafe_blocks)]
+
+unsafe fn read(p: *const u32) -> u32 {
+    *p
+}
+
+unsafe fn write(p: *mut u32, v: u32) {
+    *p = v;
+}
+
+fn undocumented_calls(p: *mut u32) {
+    // SAFETY: `p` is valid for reads and writes
+    unsafe {
+        let v = read(p);
+        write(p, v + 1);
+    }
+}
+
+fn partly_documented_calls(p: *mut u32) -> u32 {
+    // SAFETY: `p` is valid for reads and writes
+    unsafe {
+        // SAFETY: `p` is valid for reads
+        let v = read(p);
+        write(p, v + 1);
+        read(p)
+    }
+}
+
+fn documented_calls(p: *mut u32) -> u32 {
+    // SAFETY: `p` is valid for reads and writes
+    unsafe {
+        // SAFETY: `p` is valid for reads
+        let v = read(p);
+        // SAFETY: `p` is valid for writes
+        write(p, v + 1);
+        // SAFETY: `p` is valid for reads
+        read(p)
+    }
+}
+
+fn single_call(p: *const u32) -> u32 {
+    // SAFETY: `p` is valid for reads
+    unsafe { read(p) }
+}
+
+fn one_statement(p: *mut u32) {
+    // SAFETY: `p` is valid for reads and writes
+    unsafe {
+        // SAFETY: `p` is valid for reads and writes
+        write(p, read(p));
+    }
+}
+
+fn main() {}
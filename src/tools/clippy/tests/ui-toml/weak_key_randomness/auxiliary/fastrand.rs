@@ -0,0 +1,7 @@
+//! A stand-in for `fastrand`.
+
+pub fn u64(_range: std::ops::RangeFull) -> u64 {
+    4
+}
+
+pub fn fill(_bytes: &mut [u8]) {}
@@ -0,0 +1,44 @@
+//@aux-build:rand.rs
+//@aux-build:fastrand.rs
+#![warn(clippy::weak_key_randomness)]
+
+extern crate fastrand;
+extern crate rand;
+
+use rand::rngs::small::SmallRng;
+use rand::rngs::OsRng;
+
+pub struct SigningKey;
+
+impl SigningKey {
+    pub fn generate(_rng: &mut impl Sized) -> Self {
+        SigningKey
+    }
+}
+
+struct Session {
+    id: u64,
+    token: u64,
+}
+
+fn main() {
+    let api_key: [u8; 32] = rand::thread_rng().gen();
+    let mut rng = SmallRng::seed_from_u64(4);
+    let bytes: u64 = rng.gen();
+    let session_token = bytes;
+    let mut private_key = [0u8; 32];
+    fastrand::fill(&mut private_key);
+    let session = Session {
+        id: rand::random(),
+        token: fastrand::u64(..),
+    };
+    let signing_key = SigningKey::generate(&mut rand::thread_rng());
+
+    // OK
+    let api_key: [u8; 32] = OsRng.gen();
+    OsRng.fill_bytes(&mut private_key);
+    let signing_key = SigningKey::generate(&mut OsRng);
+    let nonce: u64 = rng.gen();
+    let mut buf = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut buf);
+}
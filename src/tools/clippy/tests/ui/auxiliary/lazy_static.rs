@@ -0,0 +1,24 @@
+//! A minimal stand-in for `lazy_static!`.
+
+#[macro_export]
+macro_rules! lazy_static {
+    ($(static ref $name:ident: $ty:ty = $init:expr;)*) => {
+        $(
+            #[allow(non_camel_case_types)]
+            struct $name {
+                __private_field: (),
+            }
+            static $name: $name = $name { __private_field: () };
+            impl ::std::ops::Deref for $name {
+                type Target = $ty;
+                fn deref(&self) -> &$ty {
+                    fn __static_ref_initialize() -> $ty {
+                        $init
+                    }
+                    static LAZY: ::std::sync::OnceLock<$ty> = ::std::sync::OnceLock::new();
+                    LAZY.get_or_init(__static_ref_initialize)
+                }
+            }
+        )*
+    };
+}
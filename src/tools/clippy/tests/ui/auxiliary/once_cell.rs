@@ -0,0 +1,27 @@
+//! A minimal stand-in for `once_cell::sync::Lazy`.
+
+pub mod sync {
+    use std::ops::Deref;
+    use std::sync::OnceLock;
+
+    pub struct Lazy<T, F = fn() -> T> {
+        cell: OnceLock<T>,
+        init: F,
+    }
+
+    impl<T, F> Lazy<T, F> {
+        pub const fn new(init: F) -> Self {
+            Self {
+                cell: OnceLock::new(),
+                init,
+            }
+        }
+    }
+
+    impl<T, F: Fn() -> T> Deref for Lazy<T, F> {
+        type Target = T;
+        fn deref(&self) -> &T {
+            self.cell.get_or_init(&self.init)
+        }
+    }
+}
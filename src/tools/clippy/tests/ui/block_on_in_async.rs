@@ -0,0 +1,23 @@
+#![warn(clippy::block_on_in_async)]
+#![allow(clippy::let_underscore_future)]
+
+use futures::executor::block_on;
+
+async fn load() -> u32 {
+    1
+}
+
+async fn run() -> u32 {
+    let a = block_on(load());
+    let b = futures::executor::block_on(async { load().await + 1 });
+    a + b
+}
+
+fn main() {
+    // don't lint
+    let _ = block_on(run());
+    let _ = async {
+        let read = || block_on(load());
+        read()
+    };
+}
@@ -0,0 +1,118 @@
+#![warn(clippy::busy_wait_loop)]
+#![allow(clippy::while_let_loop)]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::{Mutex, RwLock};
+use std::thread;
+use std::time::Duration;
+
+fn wait_for_message(rx: &Receiver<String>) -> String {
+    loop {
+        if let Ok(message) = rx.try_recv() {
+            break message;
+        }
+    }
+}
+
+fn handle_messages(rx: &Receiver<String>) {
+    loop {
+        match rx.try_recv() {
+            Ok(message) => println!("{message}"),
+            Err(_) => continue,
+        }
+    }
+}
+
+fn wait_for_lock(lock: &RwLock<Vec<u8>>) {
+    while lock.try_write().is_err() {}
+}
+
+fn wait_for_flag(ready: &AtomicBool) {
+    loop {
+        if ready.load(Ordering::Acquire) {
+            break;
+        }
+    }
+}
+
+fn wait_for_count(count: &AtomicUsize) {
+    while count.load(Ordering::Acquire) < 4 {}
+}
+
+fn sleeps(rx: &Receiver<String>, ready: &AtomicBool) -> String {
+    while !ready.load(Ordering::Acquire) {
+        thread::sleep(Duration::from_millis(10));
+    }
+    loop {
+        if let Ok(message) = rx.try_recv() {
+            break message;
+        }
+        thread::yield_now();
+    }
+}
+
+fn spins(lock: &Mutex<Vec<u8>>) {
+    while lock.try_lock().is_err() {
+        std::hint::spin_loop();
+    }
+}
+
+async fn awaits(rx: &Receiver<String>) -> String {
+    loop {
+        if let Ok(message) = rx.try_recv() {
+            break message;
+        }
+        std::future::ready(()).await;
+    }
+}
+
+fn drains(rx: &Receiver<String>) {
+    loop {
+        match rx.try_recv() {
+            Ok(message) => println!("{message}"),
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => return,
+        }
+    }
+    loop {
+        if let Ok(message) = rx.try_recv() {
+            println!("{message}");
+        } else {
+            break;
+        }
+    }
+    while let Ok(message) = rx.try_recv() {
+        println!("{message}");
+    }
+}
+
+fn does_other_work(rx: &Receiver<String>, stop: &AtomicBool) {
+    loop {
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        println!("working");
+    }
+    loop {
+        if let Ok(message) = rx.try_recv() {
+            println!("{message}");
+        }
+        println!("working");
+    }
+}
+
+fn blocks(rx: &Receiver<String>, lock: &Mutex<Vec<u8>>) {
+    loop {
+        if let Ok(message) = rx.try_recv() {
+            println!("{message}");
+        } else if let Ok(message) = rx.recv() {
+            println!("{message}");
+        }
+    }
+    while lock.try_lock().is_err() {
+        let _guard = lock.lock();
+    }
+}
+
+fn main() {}
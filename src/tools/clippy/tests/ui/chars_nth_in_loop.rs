@@ -0,0 +1,33 @@
+#![warn(clippy::chars_nth_in_loop)]
+
+fn main() {
+    let s = String::from("héllo");
+    for i in 0..s.len() {
+        if let Some(c) = s.chars().nth(i) {
+            println!("{i}: {c}");
+        }
+    }
+
+    let word = "hello";
+    let count = word.chars().count();
+    for i in 1..count {
+        let _ = word.chars().nth(i) == word.chars().nth(i - 1);
+    }
+
+    let mut i = 0;
+    while i < word.len() {
+        let _ = word.chars().nth(i);
+        i += 1;
+    }
+
+    // don't lint
+    for i in 0..3 {
+        let _ = word.chars().nth(2);
+        let _ = word.chars().nth(i + 1);
+    }
+    let mut i = 0;
+    while i < word.len() {
+        let _ = word.chars().nth(i);
+        i += 2;
+    }
+}
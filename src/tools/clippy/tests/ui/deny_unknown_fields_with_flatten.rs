@@ -0,0 +1,44 @@
+#![warn(clippy::deny_unknown_fields_with_flatten)]
+
+extern crate serde;
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct Options {
+    verbose: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Request {
+    id: u32,
+    #[serde(flatten)]
+    options: Options,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct Response {
+    #[serde(default, flatten)]
+    extra: HashMap<String, String>,
+}
+
+// `flatten` without `deny_unknown_fields`
+#[derive(Deserialize)]
+struct Lenient {
+    id: u32,
+    #[serde(flatten)]
+    options: Options,
+}
+
+// `deny_unknown_fields` without `flatten`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Strict {
+    id: u32,
+    options: Options,
+}
+
+fn main() {}
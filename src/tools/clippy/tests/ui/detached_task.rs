@@ -0,0 +1,20 @@
+//@aux-build:async_std.rs
+#![warn(clippy::detached_task)]
+
+use async_std::task;
+
+async fn save() -> Result<(), String> {
+    Ok(())
+}
+
+fn main() {
+    async_std::task::spawn(async {
+        save().await.unwrap();
+    });
+    task::spawn_local(save());
+
+    // don't lint
+    let _ = task::spawn(save());
+    _ = task::spawn(save());
+    let _handle = task::spawn(save());
+}
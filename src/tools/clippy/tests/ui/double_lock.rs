@@ -0,0 +1,72 @@
+#![warn(clippy::double_lock)]
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+static COUNTER: Mutex<u32> = Mutex::new(0);
+
+struct Cache {
+    items: Mutex<Vec<String>>,
+}
+
+impl Cache {
+    fn insert(&self, item: String) -> usize {
+        let mut items = self.items.lock().unwrap();
+        items.push(item);
+        self.items.lock().unwrap().len()
+    }
+
+    fn try_insert(&self, item: String) -> Result<(), PoisonError<()>> {
+        let items = self.items.lock().map_err(|_| PoisonError::new(()))?;
+        if items.is_empty() {
+            self.items.lock().unwrap().push(item);
+        }
+        Ok(())
+    }
+
+    fn insert_dropped(&self, item: String) -> usize {
+        let mut items = self.items.lock().unwrap();
+        items.push(item);
+        drop(items);
+        self.items.lock().unwrap().len()
+    }
+
+    fn insert_scoped(&self, item: String) -> usize {
+        {
+            let mut items = self.items.lock().unwrap();
+            items.push(item);
+        }
+        self.items.lock().unwrap().len()
+    }
+}
+
+fn shared(counter: Arc<Mutex<u32>>) {
+    let guard = counter.lock().unwrap_or_else(PoisonError::into_inner);
+    let value = *guard;
+    *counter.lock().unwrap() = value + 1;
+}
+
+fn statics() {
+    let _guard = COUNTER.lock().unwrap();
+    *COUNTER.lock().unwrap() += 1;
+}
+
+fn temporaries() {
+    *COUNTER.lock().unwrap() += 1;
+    *COUNTER.lock().unwrap() += 1;
+}
+
+fn different_mutexes(a: &Mutex<u32>, b: &Mutex<u32>) {
+    let a_guard = a.lock().unwrap();
+    let b_guard = b.lock().unwrap();
+    println!("{}", *a_guard + *b_guard);
+}
+
+fn moved(counter: &Mutex<u32>) {
+    let guard = counter.lock().unwrap();
+    consume(guard);
+    *counter.lock().unwrap() += 1;
+}
+
+fn consume(_: std::sync::MutexGuard<'_, u32>) {}
+
+fn main() {}
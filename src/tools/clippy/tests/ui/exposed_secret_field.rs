@@ -0,0 +1,49 @@
+#![warn(clippy::exposed_secret_field)]
+
+extern crate serde;
+
+use serde::Serialize;
+
+#[derive(Debug)]
+struct Login {
+    user: String,
+    password: String,
+}
+
+#[derive(Serialize)]
+struct Config {
+    endpoint: String,
+    github_api_key: Option<String>,
+    #[serde(skip_serializing)]
+    db_password: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Session<'a> {
+    access_token: &'a [u8],
+    #[serde(skip)]
+    refresh_token: Vec<u8>,
+}
+
+struct Redacted(String);
+
+#[derive(Debug, Serialize)]
+struct Fine {
+    tokens: Vec<String>,
+    token_count: usize,
+    password: Redacted,
+}
+
+impl Serialize for Redacted {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("<redacted>")
+    }
+}
+
+impl std::fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#![warn(clippy::format_in_shell_command)]
+
+use std::process::Command;
+
+fn main() {
+    let file = std::env::args().nth(1).unwrap();
+
+    Command::new("sh").arg("-c").arg(format!("wc -l {file}"));
+    Command::new("/bin/bash").arg("-c").arg(format!("cat {}", file));
+    Command::new("cmd").arg("/C").arg(format!("type {file}"));
+
+    let mut command = Command::new("sh");
+    command.current_dir("/tmp").arg("-c").arg(format!("rm {file}"));
+
+    // Only literals
+    Command::new("sh").arg("-c").arg(format!("echo {}", 1));
+    // Not run as a script
+    Command::new("wc").arg("-l").arg(format!("{file}.txt"));
+    Command::new("sh").arg(format!("{file}.sh"));
+    // Data passed as an argument of the script
+    Command::new("sh").arg("-c").arg(r#"wc -l "$1""#).arg("sh").arg(file);
+}
@@ -0,0 +1,25 @@
+#![warn(clippy::hardcoded_platform_path)]
+#![allow(clippy::needless_borrow)]
+
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let _ = File::open("/tmp/input.txt");
+    let _ = fs::read_to_string("/home/ferris/config.toml");
+    let _ = Path::new(r"C:\Users\ferris\data");
+    let _ = PathBuf::from("/Users/ferris/Library");
+    let _ = OpenOptions::new().write(true).open("D:/logs/app.log");
+    let _ = fs::create_dir_all(&"/tmp");
+
+    // don't lint
+    let _ = File::open("/tmpfiles/input.txt");
+    let _ = File::open("/etc/hosts");
+    let _ = Path::new("data/input.txt");
+    let _ = PathBuf::from("C:");
+    let _ = File::open(concat!("/tmp", "/input.txt"));
+    let _ = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let _ = String::from("/tmp/input.txt");
+    let tmp = "/tmp/input.txt";
+    let _ = File::open(tmp);
+}
@@ -0,0 +1,92 @@
+#![warn(clippy::inconsistent_hash_and_partial_eq)]
+
+use std::hash::{Hash, Hasher};
+
+struct User {
+    id: u32,
+    name: String,
+    email: String,
+}
+
+impl PartialEq for User {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for User {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.name.hash(state);
+        self.email.hash(state);
+    }
+}
+
+struct Point(i32, i32);
+
+impl PartialEq for Point {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Hash for Point {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+// Hashing fewer fields than are compared is fine.
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.major == other.major && self.minor == other.minor
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.major.hash(state);
+    }
+}
+
+// `PartialEq` is derived, so it compares every field.
+#[derive(PartialEq)]
+struct Derived {
+    a: u32,
+}
+
+impl Hash for Derived {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.a.hash(state);
+    }
+}
+
+// `self` is used for more than accessing its fields.
+struct Key {
+    parts: Vec<String>,
+}
+
+impl Key {
+    fn normalized(&self) -> String {
+        self.parts.join("/")
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.parts.hash(state);
+    }
+}
+
+fn main() {}
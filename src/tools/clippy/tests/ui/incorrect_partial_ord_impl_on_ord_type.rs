@@ -116,3 +116,23 @@ fn partial_cmp(&self, other: &u32) -> Option<Ordering> {
         todo!();
     }
 }
+
+// lint, and point out the fields that only one of them compares
+
+#[derive(Eq, PartialEq)]
+struct G {
+    major: u32,
+    minor: u32,
+}
+
+impl Ord for G {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major.cmp(&other.major)
+    }
+}
+
+impl PartialOrd for G {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.minor.partial_cmp(&other.minor)
+    }
+}
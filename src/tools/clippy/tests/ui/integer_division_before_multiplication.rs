@@ -0,0 +1,16 @@
+#![warn(clippy::integer_division_before_multiplication)]
+
+fn main() {
+    let (done, total, width) = (7u32, 9u32, 80u32);
+    let _ = done / total * 100;
+    let _ = width * (done / total);
+    let _ = (done + 1) / total * (width - 1);
+
+    // Rounds down to a multiple
+    let _ = done / 8 * 8;
+    let _ = done / total * total;
+    // Has no remainder
+    let _ = 60 / 4 * done;
+    // Floats
+    let _ = 7.0 / 9.0 * 100.0;
+}
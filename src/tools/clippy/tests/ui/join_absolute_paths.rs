@@ -0,0 +1,23 @@
+#![warn(clippy::join_absolute_paths)]
+
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let path = Path::new("/bin");
+    let _ = path.join("/sh");
+    let _ = path.join("\\sh");
+    let _ = path.join(r"C:\Windows");
+    let _ = path.join("c:/Windows");
+
+    let path_buf = PathBuf::from("/bin");
+    let _ = path_buf.join("/sh");
+
+    // Relative paths
+    let _ = path.join("sh");
+    let _ = path.join("./sh");
+    let _ = path.join("C:");
+    let _ = path.join("");
+
+    // Not a `Path`
+    let _ = ["a", "b"].join("/");
+}
@@ -0,0 +1,41 @@
+#![warn(clippy::large_clone_into_closure)]
+
+#[derive(Clone)]
+struct Config {
+    table: [u64; 64],
+    name: String,
+}
+
+fn use_config(_: &Config) {}
+
+fn consume(_: Config) {}
+
+fn main() {
+    let config = Config {
+        table: [0; 64],
+        name: String::new(),
+    };
+    let names: [String; 16] = Default::default();
+
+    let cloned = config.clone();
+    let print = move || use_config(&cloned);
+    print();
+
+    let names_copy = names.clone();
+    let count = move || names_copy.len();
+    count();
+
+    // don't lint
+    let name = config.name.clone();
+    let greet = move || println!("{}", name.len());
+    greet();
+
+    let owned = config.clone();
+    let take = move || consume(owned);
+    take();
+
+    let shared = config.clone();
+    use_config(&shared);
+    let print = move || use_config(&shared);
+    print();
+}
@@ -0,0 +1,37 @@
+#![warn(clippy::linear_contains_in_loop)]
+#![allow(clippy::useless_vec)]
+
+#[derive(PartialEq, PartialOrd, Eq, Ord)]
+struct Id(u32);
+
+fn main() {
+    let items = vec![1, 2, 3];
+    let banned = vec![2];
+    let allowed: Vec<_> = items.iter().filter(|item| !banned.contains(item)).collect();
+
+    let ids = [Id(1), Id(2)];
+    let known: &[Id] = &[Id(1)];
+    for id in &ids {
+        if known.contains(id) {
+            println!("known");
+        }
+    }
+
+    // don't lint
+    for item in &items {
+        let local = vec![1, 2];
+        let _ = local.contains(item);
+        let _ = [1, 2, 3].contains(item);
+    }
+    let mut seen = Vec::new();
+    for item in &items {
+        if !seen.contains(item) {
+            seen.push(*item);
+        }
+    }
+    let floats = vec![1.0, 2.0];
+    for x in [1.0, 3.0] {
+        let _ = floats.contains(&x);
+    }
+    let _ = allowed.contains(&&1);
+}
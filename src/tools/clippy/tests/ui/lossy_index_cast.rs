@@ -0,0 +1,21 @@
+//@ignore-32bit
+#![warn(clippy::lossy_index_cast)]
+#![allow(clippy::useless_vec)]
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let last = v.len() as u32 - 1;
+    let _ = v[last as usize];
+    let _ = v.get((v.len() as i32 / 2) as usize);
+    let mid = (v.len() as u16).saturating_sub(1);
+    let _ = &v[mid as usize..];
+    let p = v.as_ptr();
+    let _ = unsafe { p.add(v.len() as u8 as usize - 1) };
+
+    // Don't lint.
+    let len = v.len() as u32;
+    println!("{len}");
+    let _ = v[(v.len() as u64 - 1) as usize];
+    let _ = v[(v.len() as isize - 1) as usize];
+    let _ = v.len() as u32 > 2;
+}
@@ -0,0 +1,53 @@
+#![warn(clippy::manual_from_bytes)]
+#![allow(clippy::identity_op)]
+
+fn chains(array: [u8; 4], bytes: &[u8], header: &[u8; 2]) {
+    let _ = (array[0] as u32) << 24 | (array[1] as u32) << 16 | (array[2] as u32) << 8 | array[3] as u32;
+    let _ = array[0] as u32 | (array[1] as u32) << 8 | (array[2] as u32) << 16 | (array[3] as u32) << 24;
+    let _ = (u16::from(bytes[4]) << 8) + u16::from(bytes[5]);
+    let _ = (header[0] as u16) << 8 | header[1] as u16;
+    // partial width
+    let _ = (bytes[0] as u64) << 8 | bytes[1] as u64;
+    let _ = (array[0] as i32) << 24 | (array[1] as i32) << 16 | (array[2] as i32) << 8 | array[3] as i32;
+
+    // don't lint
+    let _ = (array[0] as u32) << 8 | array[2] as u32;
+    let _ = (array[0] as u32) << 16 | array[1] as u32;
+    let _ = (array[0] as u32) << 8 | bytes[1] as u32;
+    let _ = (array[0] as u16) << 8 | array[0] as u16;
+    let _ = (array[0] as u16) << 8 | 1;
+    let _ = (array[0] as u32) << 16 | (array[1] as u32) << 8 | array[2] as u32;
+    let _ = array[0] << 4 | array[1];
+}
+
+fn loops(array: [u8; 4], bytes: &[u8]) {
+    let mut n = 0;
+    for b in array {
+        n = n << 8 | b as u32;
+    }
+
+    let mut n = 0u64;
+    for &b in bytes[..8].iter().rev() {
+        n <<= 8;
+        n |= b as u64;
+    }
+
+    // don't lint
+    let mut n = 0u32;
+    for b in bytes {
+        n = n << 8 | *b as u32;
+    }
+
+    let mut n = 1u32;
+    for b in array {
+        n = n << 8 | b as u32;
+    }
+
+    let mut n = 0u32;
+    for b in array {
+        n = n << 4 | b as u32;
+    }
+    let _ = n;
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+#![warn(clippy::manual_path_join)]
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let dir = "data";
+    let file = String::from("config.toml");
+
+    let _ = File::open(format!("{}/{}", dir, file));
+    let _ = Path::new(&format!("{dir}/{file}"));
+
+    let path = dir.to_owned() + "/" + "logs" + "/" + &file;
+    let _ = fs::read_to_string(&path);
+    let _ = fs::remove_file(path);
+
+    let path = format!("{}/{}", file, dir);
+    let _ = PathBuf::from(path);
+
+    // don't lint
+    let _ = File::open(format!("{}\\{}", dir, file));
+    let _ = File::open(format!("{}/{}.bak", dir, file));
+    let _ = File::open(format!("{}/{:?}", dir, file));
+    let _ = File::open(format!("{}/{}", dir, 1));
+    let _ = File::open(file.clone() + "." + "bak");
+    let url = format!("{}/{}", dir, file);
+    let _ = url.len();
+    let mut path = format!("{}/{}", dir, file);
+    path.push('~');
+    let _ = File::open(path);
+}
@@ -238,3 +238,16 @@ fn _msrv_118() {
     let mut hash_map: HashMap<i8, i8> = (0..8).map(|x| (x, x * 10)).collect();
     hash_map = hash_map.into_iter().filter(|(k, _)| k % 2 == 0).collect();
 }
+
+fn cloned_before_filter() {
+    let mut vec = vec![0, 1, 2];
+    // Do lint.
+    vec = vec.iter().cloned().filter(|x| x % 2 == 0).collect();
+    vec = vec.iter().copied().filter(|&x| x > 0).collect();
+    let mut hash_set = HashSet::from([1, 2, 3]);
+    hash_set = hash_set.iter().copied().filter(|x| *x != 2).collect();
+
+    // Do not lint, because it is an assignment to a different variable.
+    let other = [3, 4];
+    vec = other.iter().cloned().filter(|x| x % 2 == 0).collect();
+}
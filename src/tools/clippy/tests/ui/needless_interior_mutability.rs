@@ -0,0 +1,102 @@
+#![warn(clippy::needless_interior_mutability)]
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+
+fn locals() {
+    let names = Arc::new(Mutex::new(vec!["a", "b"]));
+    let names2 = Arc::clone(&names);
+    std::thread::spawn(move || println!("{}", names2.lock().unwrap().len()));
+    let guard = names.lock().unwrap();
+    println!("{:?}", *guard);
+    drop(guard);
+
+    let config = Arc::new(RwLock::new(String::new()));
+    let len = config.read().unwrap().len();
+    let config2 = config.clone();
+    let _ = config2.write().unwrap().is_empty();
+
+    let counter = Rc::new(RefCell::new(0));
+    let value = *counter.borrow();
+}
+
+fn locals_mutated() {
+    let names = Arc::new(Mutex::new(vec!["a", "b"]));
+    let names2 = Arc::clone(&names);
+    std::thread::spawn(move || names2.lock().unwrap().push("c"));
+    let len = names.lock().unwrap().len();
+
+    let counter = Arc::new(Mutex::new(0));
+    *counter.lock().unwrap() += 1;
+
+    let counter = Arc::new(Mutex::new(0));
+    let mut guard = counter.lock().unwrap();
+    *guard = 1;
+
+    let counter = Rc::new(RefCell::new(0));
+    counter.replace(1);
+
+    let counter = Rc::new(RefCell::new(0));
+    let counter2 = counter.clone();
+    let mut value = counter2.borrow_mut();
+    *value += 1;
+}
+
+fn locals_unknown(shared: Arc<Mutex<i32>>) {
+    // Passed to a function.
+    let counter = Arc::new(Mutex::new(0));
+    let _ = takes_counter(&counter);
+
+    // Shares the value of another `Arc`.
+    let counter = shared.clone();
+    let value = *counter.lock().unwrap();
+
+    // The content already has interior mutability.
+    let counter = Rc::new(RefCell::new(Cell::new(0)));
+    counter.borrow().set(1);
+}
+
+fn takes_counter(counter: &Mutex<i32>) -> i32 {
+    *counter.lock().unwrap()
+}
+
+struct Service {
+    config: Arc<RwLock<Vec<String>>>,
+    cache: Arc<Mutex<Vec<String>>>,
+    pub public: Arc<Mutex<Vec<String>>>,
+}
+
+impl Service {
+    fn new() -> Self {
+        Self {
+            config: Arc::new(RwLock::new(Vec::new())),
+            cache: Arc::new(Mutex::new(Vec::new())),
+            public: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn config_len(&self) -> usize {
+        self.config.read().unwrap().len()
+    }
+
+    fn cache(&self, value: String) {
+        self.cache.lock().unwrap().push(value);
+    }
+
+    fn public_len(&self) -> usize {
+        self.public.lock().unwrap().len()
+    }
+}
+
+struct Shared {
+    data: Rc<RefCell<String>>,
+}
+
+fn shared(data: Rc<RefCell<String>>) -> usize {
+    let shared = Shared { data };
+    let len = shared.data.borrow().len();
+    len
+}
+
+fn main() {}
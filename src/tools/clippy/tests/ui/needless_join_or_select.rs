@@ -0,0 +1,30 @@
+#![warn(clippy::needless_join_or_select)]
+
+use futures::FutureExt;
+
+async fn load() -> u32 {
+    1
+}
+
+async fn try_load() -> Result<u32, ()> {
+    Ok(1)
+}
+
+async fn run() -> Result<(), ()> {
+    let _ = futures::join!(load());
+    let _ = futures::try_join!(try_load(),)?;
+    let _ = futures::select! {
+        value = load().fuse() => value,
+    };
+
+    // don't lint
+    let _ = futures::join!(load(), load());
+    let _ = futures::try_join!(try_load(), async { Ok::<u32, ()>(2) })?;
+    let _ = futures::select! {
+        value = load().fuse() => value,
+        complete => 0,
+    };
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+//@aux-build:once_cell.rs
+//@aux-build:lazy_static.rs
+#![feature(lazy_cell)]
+#![warn(clippy::needless_lazy_init)]
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+const LIMIT: usize = 16;
+
+static NAMES: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static DOUBLE_LIMIT: LazyLock<usize> = LazyLock::new(|| LIMIT * 2);
+
+lazy_static::lazy_static! {
+    static ref GREETING: &'static str = "hello";
+}
+
+// don't lint
+static MAP: Lazy<HashMap<u32, &str>> = Lazy::new(|| HashMap::from([(1, "one")]));
+static NAME: LazyLock<String> = LazyLock::new(|| String::from("name"));
+static COUNT: usize = 3;
+static COUNT_COPY: Lazy<usize> = Lazy::new(|| COUNT);
+static FROM_ENV: LazyLock<Option<String>> = LazyLock::new(|| std::env::var("NOT_SET").ok());
+
+lazy_static::lazy_static! {
+    static ref USERS: Mutex<HashMap<u32, String>> = Mutex::new(HashMap::new());
+}
+
+fn main() {
+    NAMES.lock().unwrap().push((*NAME).clone());
+    let _ = (*DOUBLE_LIMIT, *GREETING, MAP.len(), *COUNT_COPY, FROM_ENV.is_some());
+    USERS.lock().unwrap().clear();
+}
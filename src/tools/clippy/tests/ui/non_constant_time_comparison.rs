@@ -0,0 +1,30 @@
+#![warn(clippy::non_constant_time_comparison)]
+#![allow(clippy::comparison_to_empty)]
+
+struct Credentials {
+    user: String,
+    password: String,
+}
+
+fn check_password(creds: &Credentials, given: &str) -> bool {
+    given == creds.password
+}
+
+fn check_token(access_token: &[u8], given: &[u8]) -> bool {
+    access_token != given
+}
+
+fn check_key(creds: &Credentials, given: String) -> bool {
+    let expected = creds.password.as_bytes();
+    let stored = expected;
+    stored == given.as_bytes()
+}
+
+fn not_secret(creds: &Credentials, given: &str, tokens: &[u8]) -> bool {
+    let name = &creds.user;
+    let is_empty = creds.password == "";
+    let count = tokens.len() == 4;
+    name == given && tokens == b"abc"
+}
+
+fn main() {}
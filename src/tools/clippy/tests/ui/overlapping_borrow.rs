@@ -0,0 +1,51 @@
+#![warn(clippy::overlapping_borrow)]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+struct Registry {
+    names: RefCell<Vec<String>>,
+}
+
+impl Registry {
+    fn register(&self, name: &str) {
+        let names = self.names.borrow();
+        if !names.iter().any(|n| n == name) {
+            self.names.borrow_mut().push(name.to_owned());
+        }
+    }
+
+    fn rename(&self, from: &str, to: &str) {
+        let mut names = self.names.borrow_mut();
+        let count = self.names.borrow().len();
+        names.retain(|n| n != from);
+        names.push(to.to_owned());
+    }
+
+    fn register_dropped(&self, name: &str) {
+        let names = self.names.borrow();
+        let known = names.iter().any(|n| n == name);
+        drop(names);
+        if !known {
+            self.names.borrow_mut().push(name.to_owned());
+        }
+    }
+}
+
+fn shared(counter: Rc<RefCell<u32>>) {
+    let first = counter.borrow_mut();
+    let second = counter.borrow_mut();
+}
+
+fn reads(counter: &RefCell<u32>) -> u32 {
+    let first = counter.borrow();
+    let second = counter.borrow();
+    *first + *second
+}
+
+fn temporaries(counter: &RefCell<u32>) {
+    let value = *counter.borrow();
+    *counter.borrow_mut() += value;
+}
+
+fn main() {}
@@ -0,0 +1,56 @@
+#![warn(clippy::panic_in_drop)]
+
+struct Unwrap(Option<u32>);
+
+impl Drop for Unwrap {
+    fn drop(&mut self) {
+        self.0.unwrap();
+        std::fs::remove_file("lock").expect("failed to remove the lock file");
+    }
+}
+
+struct Macros(bool);
+
+impl Drop for Macros {
+    fn drop(&mut self) {
+        if self.0 {
+            panic!("dropped twice");
+        }
+        unreachable!();
+    }
+}
+
+struct Index(Vec<u32>, [u32; 2]);
+
+impl Drop for Index {
+    fn drop(&mut self) {
+        let _ = self.0[0];
+        let _ = self.1[1];
+        let _ = self.1[self.0.len()];
+    }
+}
+
+struct Helper(Option<u32>);
+
+impl Helper {
+    fn value(&self) -> u32 {
+        self.0.unwrap()
+    }
+}
+
+impl Drop for Helper {
+    fn drop(&mut self) {
+        // Called functions aren't checked by default
+        let _ = self.value();
+        if let Err(e) = std::fs::remove_file("lock") {
+            eprintln!("{e}");
+        }
+        let _ = self.0.unwrap_or(0);
+    }
+}
+
+fn not_drop(x: Option<u32>) {
+    x.unwrap();
+}
+
+fn main() {}
@@ -0,0 +1,62 @@
+#![feature(c_unwind)]
+#![warn(clippy::panic_in_extern_c_fn)]
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::panic::catch_unwind;
+
+#[no_mangle]
+pub extern "C" fn read_value(value: *const u32) -> u32 {
+    unsafe { value.as_ref() }.copied().unwrap()
+}
+
+#[no_mangle]
+pub extern "C" fn value_at(values: *const u32, len: usize, i: usize) -> u32 {
+    let values = unsafe { std::slice::from_raw_parts(values, len) };
+    values[i]
+}
+
+#[no_mangle]
+pub extern "C" fn check_value(value: *const u32) -> u32 {
+    assert!(!value.is_null());
+    0
+}
+
+#[no_mangle]
+pub extern "C" fn ratio(a: i32, b: i32) -> i32 {
+    a / b
+}
+
+#[no_mangle]
+pub extern "C" fn total(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[no_mangle]
+pub extern "C" fn max_incremented(values: *const u32, len: usize) -> u32 {
+    let values = unsafe { std::slice::from_raw_parts(values, len) };
+    values.iter().map(|v| v.checked_add(1).unwrap()).max().unwrap_or(0)
+}
+
+#[no_mangle]
+pub extern "C" fn first_value(values: *const u32, len: usize) -> u32 {
+    let values = unsafe { std::slice::from_raw_parts(values, len) };
+    catch_unwind(|| values[0]).unwrap_or(0)
+}
+
+#[no_mangle]
+pub extern "C" fn no_panics(a: u32, values: &[u32; 4]) -> u32 {
+    let _ = 1 + 2;
+    let _ = a / 2;
+    values[3]
+}
+
+#[no_mangle]
+pub extern "C-unwind" fn unwinding(value: u32) -> u32 {
+    value.checked_add(1).unwrap()
+}
+
+pub extern "C" fn not_exported(value: u32) -> u32 {
+    value.checked_add(1).unwrap()
+}
+
+fn main() {}
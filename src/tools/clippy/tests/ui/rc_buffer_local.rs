@@ -0,0 +1,21 @@
+//@run-rustfix
+#![warn(clippy::rc_buffer)]
+
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn main() {
+    let name = String::from("clippy");
+    let bytes = vec![1u8, 2, 3];
+
+    let shared: Rc<String> = Rc::new(name.clone());
+    let data: Arc<Vec<u8>> = Arc::new(bytes);
+    let path: Arc<PathBuf> = Arc::new(PathBuf::from("/"));
+    let _ = (shared, data, path);
+
+    // don't lint
+    let shared = Rc::new(name.clone());
+    let copied: Rc<String> = shared.clone();
+    let _ = copied;
+}
@@ -0,0 +1,60 @@
+#![warn(clippy::rc_cycle)]
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::sync::{Arc, Mutex};
+
+struct Parent {
+    children: Vec<Rc<Child>>,
+}
+
+struct Child {
+    parent: RefCell<Option<Rc<Parent>>>,
+}
+
+struct Owner {
+    middle: Arc<Mutex<Middle>>,
+}
+
+struct Middle {
+    leaf: Option<Arc<Leaf>>,
+}
+
+enum Leaf {
+    Owner(Arc<Owner>),
+    Empty,
+}
+
+// Don't lint.
+struct Tree {
+    root: Rc<TreeNode>,
+}
+
+struct TreeNode {
+    tree: Weak<Tree>,
+    parent: RefCell<Weak<TreeNode>>,
+    children: RefCell<Vec<Rc<TreeNode>>>,
+}
+
+struct List {
+    next: Option<Rc<List>>,
+}
+
+struct Borrowed<'a> {
+    lender: &'a Lender,
+}
+
+struct Lender {
+    borrowed: Rc<Borrowed<'static>>,
+}
+
+struct Allowed {
+    owner: Rc<AllowedOwner>,
+}
+
+struct AllowedOwner {
+    #[allow(clippy::rc_cycle)]
+    allowed: Rc<Allowed>,
+}
+
+fn main() {}
@@ -1,5 +1,5 @@
 #![warn(clippy::read_zero_byte_vec)]
-#![allow(clippy::unused_io_amount, clippy::needless_pass_by_ref_mut)]
+#![allow(unused_assignments, clippy::unused_io_amount, clippy::needless_pass_by_ref_mut, clippy::ptr_arg)]
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
@@ -44,12 +44,12 @@ fn test() -> io::Result<()> {
     let mut buf = [0u8; 100];
     f.read(&mut buf)?;
 
-    // should not lint
+    // should lint
     let mut empty = vec![];
     let mut data7 = vec![];
     f.read(&mut empty);
 
-    // should not lint
+    // should lint
     f.read(&mut data7);
 
     // should not lint
@@ -61,6 +61,132 @@ fn test() -> io::Result<()> {
     let mut data9 = vec![1, 2, 3];
     f.read_exact(&mut data9)?;
 
+    // should lint
+    let mut data10 = Vec::with_capacity(cap);
+    println!("reading");
+    f.read_exact(&mut data10)?;
+
+    // should not lint
+    let mut data11 = Vec::with_capacity(cap);
+    println!("reading");
+    data11.resize(cap, 0);
+    f.read_exact(&mut data11)?;
+
+    // should not lint
+    let mut data12 = Vec::new();
+    println!("reading");
+    data12 = vec![0; cap];
+    f.read_exact(&mut data12)?;
+
+    // should not lint
+    let mut data13 = Vec::new();
+    if cap > 0 {
+        data13.extend_from_slice(&[0; 10]);
+    }
+    f.read_exact(&mut data13)?;
+
+    // should lint
+    let mut data14 = Vec::new();
+    std::io::Read::read(&mut f, &mut data14)?;
+
+    // should lint
+    let mut data15 = Vec::new();
+    <File as Read>::read_exact(&mut f, &mut data15)?;
+
+    // should not lint
+    let mut data16 = Vec::new();
+    data16.extend_from_slice(&[0; 10]);
+    Read::read_exact(&mut f, &mut data16)?;
+
+    Ok(())
+}
+
+fn test_dyn(r: &mut dyn Read) -> io::Result<()> {
+    // should lint
+    let mut data = Vec::new();
+    r.read(&mut data)?;
+
+    // should lint
+    let mut data2 = Vec::new();
+    Read::read_exact(r, &mut data2)?;
+
+    // should lint
+    let mut data3 = Vec::new();
+    <dyn Read>::read(r, &mut data3)?;
+
+    Ok(())
+}
+
+struct Reader {
+    buf: Vec<u8>,
+    header: Vec<u8>,
+}
+
+impl Reader {
+    fn reset(&mut self) {
+        self.buf = vec![0; 100];
+    }
+
+    fn test_fields(&mut self, f: &mut File) -> io::Result<()> {
+        // should lint
+        self.buf = Vec::with_capacity(100);
+        self.header.clear();
+        f.read_exact(&mut self.buf)?;
+
+        // should not lint
+        self.buf = Vec::new();
+        self.buf.resize(100, 0);
+        f.read_exact(&mut self.buf)?;
+
+        // should not lint
+        self.buf = Vec::new();
+        self.reset();
+        f.read_exact(&mut self.buf)?;
+
+        Ok(())
+    }
+}
+
+fn test_patterns(f: &mut File) -> io::Result<()> {
+    // should lint
+    let (mut data, len) = (Vec::with_capacity(10), 10);
+    f.read_exact(&mut data)?;
+    assert_eq!(data.len(), len);
+
+    // should lint
+    let (mut data2, (mut data3, _)) = (Vec::new(), (Vec::new(), 0));
+    f.read(&mut data2)?;
+    f.read(&mut data3)?;
+
+    Ok(())
+}
+
+fn fill(f: &mut File, buf: &mut Vec<u8>) -> io::Result<()> {
+    f.read_exact(buf)
+}
+
+fn fill_resized(f: &mut File, buf: &mut Vec<u8>) -> io::Result<()> {
+    buf.resize(10, 0);
+    f.read_exact(buf)
+}
+
+fn fill_indirect(f: &mut File, buf: &mut Vec<u8>) -> io::Result<()> {
+    fill(f, buf)
+}
+
+fn test_helpers(f: &mut File) -> io::Result<()> {
+    // should lint
+    let mut data = Vec::with_capacity(10);
+    fill(f, &mut data)?;
+
+    // should not lint
+    let mut data2 = Vec::new();
+    fill_resized(f, &mut data2)?;
+
+    // should not lint
+    let mut data3 = Vec::new();
+    fill_indirect(f, &mut data3)?;
+
     Ok(())
 }
 
@@ -0,0 +1,60 @@
+#![warn(clippy::recompute_in_loop)]
+
+extern crate regex;
+
+use regex::{Regex, RegexSet};
+
+const DATE: &str = r"^\d{4}-\d{2}-\d{2}$";
+
+fn in_loops(lines: &[&str]) {
+    for line in lines {
+        let re = Regex::new(r"^\s*#").unwrap();
+        if re.is_match(line) {
+            continue;
+        }
+    }
+
+    let mut i = 0;
+    while i < lines.len() {
+        let _ = Regex::new(DATE).unwrap().is_match(lines[i]);
+        let _ = RegexSet::new([r"\w+", r"\d+"]).unwrap();
+        i += 1;
+    }
+
+    loop {
+        let re = regex::bytes::Regex::new("a+").unwrap();
+        if re.is_match(b"aaa") {
+            break;
+        }
+    }
+}
+
+fn in_closures(lines: &[&str]) {
+    let _: Vec<_> = lines
+        .iter()
+        .filter(|line| Regex::new("^a").unwrap().is_match(line))
+        .collect();
+}
+
+fn ok(lines: &[&str], pattern: &str) {
+    // Constructed once
+    let re = Regex::new(r"^\s*#").unwrap();
+    for line in lines {
+        let _ = re.is_match(line);
+    }
+
+    // Arguments that change in every iteration
+    for line in lines {
+        let _ = Regex::new(line);
+    }
+
+    // Arguments that aren't constants
+    for line in lines {
+        let _ = Regex::new(pattern).unwrap().is_match(line);
+    }
+
+    // Closures that are called only once
+    let _ = Some(1).map(|_| Regex::new("a+"));
+}
+
+fn main() {}
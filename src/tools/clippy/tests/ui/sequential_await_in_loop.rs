@@ -0,0 +1,55 @@
+#![warn(clippy::sequential_await_in_loop)]
+
+struct Client;
+
+impl Client {
+    async fn fetch(&self, id: u32) -> String {
+        id.to_string()
+    }
+
+    async fn send(&mut self, id: u32) {
+        let _ = id;
+    }
+}
+
+async fn fetch_all(client: &Client, ids: &[u32]) -> Vec<String> {
+    let mut pages = Vec::new();
+    for &id in ids {
+        pages.push(client.fetch(id).await);
+    }
+    for id in 0..10 {
+        let key = id * 2;
+        let page = client.fetch(key).await;
+        println!("{page}");
+    }
+    pages
+}
+
+async fn dependent(client: &mut Client, ids: &[u32]) {
+    // don't lint
+    let mut last = 0;
+    for &id in ids {
+        let page = client.fetch(last + id).await;
+        last = page.len() as u32;
+    }
+    for &id in ids {
+        client.send(id).await;
+    }
+    for &id in ids {
+        if id > 3 {
+            client.fetch(id).await;
+        }
+    }
+    for &id in ids {
+        let page = client.fetch(id).await;
+        if page.is_empty() {
+            break;
+        }
+    }
+    for &id in ids {
+        client.fetch(id).await;
+        client.fetch(id + 1).await;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+//@run-rustfix
+#![warn(clippy::signed_remainder_as_index)]
+
+fn lookup(items: &[u8], offset: i32, steps: i64) {
+    let len = items.len() as i32;
+    let _ = items[(offset % len) as usize];
+    let _ = items.get(((offset - 1) % len) as usize);
+    let start = offset * 2;
+    let _ = items[(start % len) as usize];
+    let ptr = items.as_ptr();
+    let _ = unsafe { ptr.offset((steps % 4) as isize) };
+}
+
+fn non_negative(items: &[u8], offset: i32, small: u16) {
+    let len = items.len() as i32;
+    let _ = items[(small as i32 % len) as usize];
+    let _ = items[(offset.rem_euclid(len) % len) as usize];
+    let _ = items[(7 % len) as usize];
+    for i in 0..len {
+        let _ = items[((i + 3) % len) as usize];
+    }
+    if offset >= 0 {
+        let _ = items[(offset % len) as usize];
+    }
+    let _ = (offset % len) as usize;
+}
+
+fn early_return(items: &[u8], offset: i32) -> u8 {
+    let len = items.len() as i32;
+    if offset < 0 {
+        return 0;
+    }
+    items[(offset % len) as usize]
+}
+
+fn asserted(items: &[u8], offset: i32) -> u8 {
+    assert!(offset >= 0);
+    items[(offset % items.len() as i32) as usize]
+}
+
+fn main() {}
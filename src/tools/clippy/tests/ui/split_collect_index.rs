@@ -0,0 +1,18 @@
+#![warn(clippy::split_collect_index)]
+
+fn main() {
+    let line = "key=value";
+    let _ = line.split('=').collect::<Vec<_>>()[0];
+    let _ = line.splitn(2, '=').collect::<Vec<&str>>()[1];
+    let i = 1;
+    let _ = line.split_whitespace().collect::<Vec<_>>()[i];
+    let owned = String::from("a b");
+    let _ = owned.rsplit(' ').collect::<Vec<_>>()[1];
+
+    // Don't lint.
+    let _ = &line.split('=').collect::<Vec<_>>()[1..];
+    let parts: Vec<_> = line.split('=').collect();
+    let _ = parts[1];
+    let _ = [1, 2, 3].split(|x| *x == 2).collect::<Vec<_>>()[0];
+    let _ = line.chars().collect::<Vec<_>>()[0];
+}
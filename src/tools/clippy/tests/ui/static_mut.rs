@@ -0,0 +1,26 @@
+#![warn(clippy::static_mut)]
+
+use std::rc::Rc;
+
+static mut COUNTER: u32 = 0;
+static mut ENABLED: bool = false;
+static mut BUFFER: *mut u8 = std::ptr::null_mut();
+static mut CONFIG: Option<String> = None;
+static mut NAMES: Vec<String> = Vec::new();
+static mut SHARED: Option<Rc<u32>> = None;
+static mut TOTAL: u128 = 0;
+
+extern "C" {
+    static mut errno: i32;
+}
+
+// don't lint
+static LIMIT: u32 = 8;
+
+fn main() {
+    unsafe {
+        COUNTER += 1;
+        let _ = errno;
+    }
+    let _ = LIMIT;
+}
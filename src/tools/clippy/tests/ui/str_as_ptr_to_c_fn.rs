@@ -0,0 +1,35 @@
+#![warn(clippy::str_as_ptr_to_c_fn)]
+
+use std::ffi::{c_char, c_int, CString};
+
+extern "C" {
+    fn puts(s: *const c_char) -> c_int;
+    fn write(fd: c_int, buf: *const u8, len: usize) -> isize;
+}
+
+extern "C" fn log_message(msg: *const c_char) {
+    let _ = msg;
+}
+
+fn main() {
+    let name = String::from("world");
+    let greeting: &str = "hello";
+    unsafe {
+        puts(name.as_ptr().cast());
+        puts(greeting.as_ptr() as *const c_char);
+    }
+    log_message("message".as_ptr().cast());
+    let callback: extern "C" fn(*const c_char) = log_message;
+    callback(name.as_str().as_ptr().cast());
+
+    // don't lint
+    let c_name = CString::new("world").unwrap();
+    unsafe {
+        puts(c_name.as_ptr());
+        puts("hello\0".as_ptr().cast());
+    }
+    let bytes = name.as_bytes();
+    let _ = bytes.as_ptr();
+    fn rust_fn(_: *const u8) {}
+    rust_fn(greeting.as_ptr());
+}
@@ -0,0 +1,29 @@
+#![warn(clippy::system_time_elapsed)]
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn work() {}
+
+fn main() {
+    let start = SystemTime::now();
+    work();
+    let _ = SystemTime::now().duration_since(start).unwrap();
+    let end = SystemTime::now();
+    let _ = end.duration_since(start);
+    let _ = start.elapsed().unwrap();
+
+    // Timestamps
+    let _ = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let _ = start.duration_since(UNIX_EPOCH).unwrap();
+}
+
+fn since(modified: SystemTime) -> Duration {
+    SystemTime::now().duration_since(modified).unwrap_or_default()
+}
+
+fn reassigned() {
+    let mut last = SystemTime::now();
+    work();
+    last = UNIX_EPOCH;
+    let _ = last.elapsed();
+}
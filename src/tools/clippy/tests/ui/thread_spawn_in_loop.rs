@@ -0,0 +1,70 @@
+#![warn(clippy::thread_spawn_in_loop)]
+
+use std::thread;
+
+fn discarded(jobs: Vec<u32>) {
+    for job in jobs {
+        thread::spawn(move || println!("{job}"));
+    }
+    let mut running = true;
+    while running {
+        let _ = thread::spawn(|| {});
+        running = false;
+    }
+    loop {
+        drop(thread::spawn(|| {}));
+    }
+}
+
+fn never_joined() {
+    for _ in 0..4 {
+        let _handle = thread::spawn(|| {});
+    }
+    for _ in 0..4 {
+        let handle = thread::spawn(|| {});
+        println!("{:?}", handle.thread().id());
+    }
+}
+
+fn joined() {
+    for _ in 0..4 {
+        let handle = thread::spawn(|| {});
+        handle.join().unwrap();
+    }
+    for _ in 0..4 {
+        thread::spawn(|| {}).join().unwrap();
+    }
+}
+
+fn stored() {
+    let mut handles = Vec::new();
+    for _ in 0..4 {
+        handles.push(thread::spawn(|| {}));
+    }
+    for _ in 0..4 {
+        let handle = thread::spawn(|| {});
+        handles.push(handle);
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+fn not_in_loop() {
+    thread::spawn(|| {});
+    let handle = thread::spawn(|| {
+        for _ in 0..4 {
+            println!("working");
+        }
+    });
+    (0..4).for_each(|_| {
+        thread::spawn(|| {});
+    });
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {});
+        }
+    });
+}
+
+fn main() {}
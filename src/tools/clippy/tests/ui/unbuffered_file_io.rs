@@ -0,0 +1,54 @@
+#![warn(clippy::unbuffered_file_io)]
+#![allow(clippy::unused_io_amount)]
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
+fn reads(file: &mut File) -> io::Result<()> {
+    let mut buf = [0; 4096];
+    while file.read(&mut buf)? > 0 {}
+
+    let mut len = [0; 4];
+    for _ in 0..10 {
+        file.read_exact(&mut len)?;
+        // the buffer isn't small
+        file.read_exact(&mut buf)?;
+    }
+
+    // not in a loop
+    file.read_exact(&mut len)?;
+
+    let mut reader = BufReader::new(file);
+    for _ in 0..10 {
+        reader.read_exact(&mut len)?;
+    }
+
+    Ok(())
+}
+
+fn writes(file: &mut File, lines: &[String]) -> io::Result<()> {
+    for line in lines {
+        file.write_all(line.as_bytes())?;
+        writeln!(file, "{line}")?;
+    }
+
+    lines.iter().try_for_each(|line| file.write(line.as_bytes()).map(drop))?;
+
+    // not in a loop
+    writeln!(file, "done")?;
+
+    // every file is only written to once
+    for (i, line) in lines.iter().enumerate() {
+        let mut file = File::create(format!("{i}.txt"))?;
+        file.write_all(line.as_bytes())?;
+    }
+
+    let mut writer = BufWriter::new(file);
+    for line in lines {
+        writeln!(writer, "{line}")?;
+    }
+
+    Ok(())
+}
+
+fn main() {}
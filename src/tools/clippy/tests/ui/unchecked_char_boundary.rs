@@ -0,0 +1,54 @@
+#![warn(clippy::unchecked_char_boundary)]
+#![allow(clippy::manual_strip)]
+
+fn computed(s: &str, mut owned: String, max: usize) {
+    let _ = &s[..s.len() - 1];
+    let end = max.min(s.len());
+    let _ = &s[end..];
+    let _ = s.split_at(s.len() / 2);
+    let _ = owned.split_at(max + 1);
+    owned.truncate(max - 3);
+}
+
+fn not_computed(s: &str, mut owned: String, max: usize) {
+    let _ = &s[..max];
+    let _ = s.split_at(max);
+    owned.truncate(max);
+}
+
+fn char_aware(s: &str, t: &str) {
+    let i = t.find(':').unwrap_or(0);
+    let _ = &s[i + 1..];
+    let end = t.len() - t.chars().last().map_or(0, char::len_utf8);
+    let _ = &s[..end];
+}
+
+fn checked(s: &str, mut owned: String, max: usize) {
+    if s.is_char_boundary(max - 3) {
+        let _ = &s[..max - 3];
+    }
+    for (i, _) in owned.char_indices() {}
+    owned.truncate(max - 3);
+}
+
+fn checked_affix(s: &str) {
+    if s.ends_with('_') {
+        let _ = &s[..s.len() - 1];
+    }
+}
+
+fn trimmed(s: &str, mut owned: String) {
+    let indent = owned.len() - owned.trim_start().len();
+    owned.truncate(indent);
+    let min = s.lines().filter_map(|l| l.find('x')).min().unwrap_or(0);
+    let _ = s.split_at(min + 1);
+}
+
+fn not_a_string(v: &mut Vec<u8>, max: usize) {
+    let _ = &v[..v.len() - 1];
+    let _ = v.split_at(v.len() / 2);
+    v.truncate(max - 3);
+    let _ = &"hello"[..max + 1];
+}
+
+fn main() {}
@@ -0,0 +1,53 @@
+#![warn(clippy::undocumented_unsafe_send_sync)]
+#![allow(clippy::missing_safety_doc)]
+
+use std::cell::{Cell, UnsafeCell};
+use std::ptr::NonNull;
+
+struct Buffer {
+    ptr: *mut u8,
+    len: usize,
+}
+
+unsafe impl Send for Buffer {}
+
+struct Slot<T> {
+    value: UnsafeCell<Option<T>>,
+}
+
+unsafe impl<T: Send> Sync for Slot<T> {}
+
+struct Node {
+    next: Option<NonNull<Node>>,
+}
+
+unsafe impl Send for Node {}
+
+struct Counter {
+    hits: [Cell<u32>; 4],
+}
+
+unsafe impl Sync for Counter {}
+
+// don't lint
+struct Owned {
+    ptr: *const u8,
+}
+
+// SAFETY: `ptr` points to a static string.
+unsafe impl Send for Owned {}
+
+/* SAFETY: `ptr` points to a static string. */
+unsafe impl Sync for Owned {}
+
+struct Plain {
+    data: Vec<u8>,
+}
+
+unsafe impl Send for Plain {}
+
+unsafe trait Marker {}
+
+unsafe impl Marker for Buffer {}
+
+fn main() {}
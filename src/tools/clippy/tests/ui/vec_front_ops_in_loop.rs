@@ -0,0 +1,52 @@
+#![warn(clippy::vec_front_ops_in_loop)]
+
+struct Queue {
+    items: Vec<u32>,
+}
+
+fn process(_: u32) {}
+
+fn main() {
+    let mut queue = vec![1, 2, 3];
+    while !queue.is_empty() {
+        process(queue.remove(0));
+    }
+
+    let mut reversed = Vec::new();
+    for i in 0..10 {
+        reversed.insert(0, i);
+    }
+
+    let mut q = Queue { items: vec![1, 2, 3] };
+    loop {
+        if q.items.is_empty() {
+            break;
+        }
+        q.items.remove(0);
+    }
+
+    let mut v = vec![1, 2, 3];
+    (0..3).for_each(|i| v.insert(0, i));
+
+    // don't lint
+    let mut v = vec![1, 2, 3];
+    v.remove(0);
+    for _ in 0..3 {
+        v.remove(1);
+    }
+    for i in 0..3 {
+        let mut fresh = vec![1, 2, 3];
+        fresh.remove(0);
+        fresh.insert(0, i);
+    }
+    for i in 0..3 {
+        if i == 1 {
+            v.remove(0);
+            break;
+        }
+    }
+    let mut s = String::new();
+    for _ in 0..3 {
+        s.insert(0, 'a');
+    }
+}
@@ -0,0 +1,19 @@
+#![warn(clippy::zip_length_mismatch)]
+
+const LEN: usize = 4;
+
+fn main() {
+    let names = ["a", "b", "c"];
+    let values = [1, 2];
+    let _ = names.iter().zip(values);
+    let _ = names.iter().zip(values.iter());
+    let _ = [1, 2, 3, 4, 5].into_iter().zip(values.iter().copied());
+    let _ = names.into_iter().zip([0; LEN]);
+
+    // Don't lint.
+    let _ = names.iter().zip([1, 2, 3]);
+    let _ = names.iter().zip(vec![1, 2]);
+    let _ = names.iter().zip(values.iter().cycle());
+    let _ = names.iter().zip(0..2);
+    let _ = names.iter().take(2).zip(values);
+}
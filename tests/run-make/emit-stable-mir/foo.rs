@@ -0,0 +1,28 @@
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+pub static LIMIT: u64 = 100;
+
+pub fn bar(x: u32) -> u32 {
+    x + 1
+}
+
+pub fn point(x: i32) -> Point {
+    Point { x, y: 0 }
+}
+
+pub fn pair(x: u8) -> (u8, u8) {
+    (x, x)
+}
+
+pub fn widen(x: u8) -> u64 {
+    x as u64
+}
+
+pub fn repeat(x: u8) -> [u8; 4] {
+    [x; 4]
+}
@@ -0,0 +1,129 @@
+// run-pass
+// Test that users are able to find out why a body aborts the program.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::mir::{Body, Terminator, UnwindTerminateReason};
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    // `helper` is inlined in both functions. When it unwinds out of `no_unwind`, the program
+    // is aborted, as `extern "C"` functions can't unwind.
+    assert_eq!(aborts(&body(tcx, "no_unwind")), [UnwindTerminateReason::Abi]);
+    assert!(aborts(&body(tcx, "unwinds")).is_empty());
+}
+
+/// The reasons of the `Abort` terminators of `body`.
+fn aborts(body: &Body) -> Vec<UnwindTerminateReason> {
+    body.blocks
+        .iter()
+        .filter_map(|block| match block.terminator {
+            Terminator::Abort(reason) => Some(reason),
+            _ => None,
+        })
+        .collect()
+}
+
+fn body(tcx: TyCtxt<'_>, name: &str) -> Body {
+    stable_mir::all_local_items()
+        .into_iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+        })
+        .unwrap()
+        .body()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// It will invoke the compiler using a custom Callback implementation, which will
+/// invoke Stable MIR APIs after the compiler has finished its analysis.
+fn main() {
+    let path = "abort_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        // Make sure that `helper` gets inlined.
+        "-O".to_string(),
+        "-Zinline-mir-threshold=1000".to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #![feature(c_unwind)]
+
+    pub extern "C" fn no_unwind() {{
+        helper(String::new());
+    }}
+
+    pub fn unwinds() {{
+        helper(String::new());
+    }}
+
+    pub fn helper<T>(t: T) {{
+        may_panic();
+        drop(t);
+    }}
+
+    #[inline(never)]
+    pub fn may_panic() {{
+        if std::hint::black_box(true) {{
+            panic!()
+        }}
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,117 @@
+// run-pass
+// Test that users are able to retrieve the borrow checker facts of a body, and that the facts
+// don't leak into a later compilation in the same process.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::mir::borrowck::PointKind;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>, collect_facts: bool) {
+    let items = stable_mir::all_local_items();
+    let first = items
+        .iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == "first"
+        })
+        .unwrap();
+
+    if !collect_facts {
+        assert!(first.borrowck_facts().is_none());
+        return;
+    }
+    let facts = first.borrowck_facts().unwrap();
+    // The lifetime of the signature, and `'static`.
+    assert!(facts.universal_region.len() >= 2);
+    // `&pair.0` is the only borrow of the body. `pair` is a mutable reference, because borrows
+    // through shared references don't issue loans.
+    assert_eq!(facts.loan_issued_at.len(), 1);
+    let (_, loan, issued_at) = facts.loan_issued_at[0];
+    assert_eq!(issued_at.kind, PointKind::Mid);
+    assert!(facts.loan_invalidated_at.iter().all(|&(_, invalidated)| invalidated == loan));
+    assert!(!facts.cfg_edge.is_empty());
+    // The argument is a variable of the body.
+    assert!(facts.var_used_at.iter().any(|&(var, _)| var == 1));
+}
+
+fn main() {
+    let path = "borrowck_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    // The second compilation doesn't collect the facts, so it mustn't see the ones of the first.
+    for collect_facts in [true, false] {
+        rustc_driver::catch_fatal_errors(|| {
+            RunCompiler::new(&args, &mut SMirCalls { collect_facts }).run().unwrap();
+        })
+        .unwrap();
+    }
+}
+
+struct SMirCalls {
+    collect_facts: bool,
+}
+
+impl Callbacks for SMirCalls {
+    fn config(&mut self, config: &mut interface::Config) {
+        if self.collect_facts {
+            config.override_queries = Some(|_session, providers, _extern_providers| {
+                rustc_internal::provide_borrowck_facts(providers);
+            });
+        }
+    }
+
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx, self.collect_facts));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn first<'a>(pair: &'a mut (u32, u32)) -> &'a u32 {{
+        let first = &pair.0;
+        first
+    }}"#
+    )?;
+    Ok(())
+}
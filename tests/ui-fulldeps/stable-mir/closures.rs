@@ -0,0 +1,110 @@
+// run-pass
+// Test that users are able to retrieve the places captured by closures.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::mir::mono::{Instance, InstanceKind};
+use stable_mir::ty::{CaptureBorrowKind, CaptureKind, ClosureKind, RigidTy, TyKind, UintTy};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    let items = stable_mir::all_local_items();
+    let closure = items
+        .iter()
+        .find(|item| tcx.def_kind(rustc_internal::item_def_id(item)) == DefKind::Closure)
+        .unwrap();
+    let TyKind::RigidTy(RigidTy::Closure(def, args)) = closure.ty().kind() else {
+        panic!("expected a closure type");
+    };
+    assert_eq!(def.parent_item().name(), "counter");
+
+    let captures = def.captures();
+    assert_eq!(captures.len(), 2);
+    let count = captures.iter().find(|captured| captured.var_name == "count").unwrap();
+    assert_eq!(count.path, "count");
+    assert_eq!(count.kind, CaptureKind::ByRef(CaptureBorrowKind::Mut));
+    assert_matches!(count.ty.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+    let step = captures.iter().find(|captured| captured.var_name == "step").unwrap();
+    assert_eq!(step.path, "step.0");
+    assert_eq!(step.kind, CaptureKind::ByRef(CaptureBorrowKind::Shared));
+
+    // The closure only implements `FnOnce` through a shim that calls its `FnMut` body.
+    let call_once = Instance::resolve_closure(&def, &args, ClosureKind::FnOnce).unwrap();
+    assert_eq!(call_once.kind(), InstanceKind::ClosureOnceShim);
+    call_once.body().unwrap().validate().unwrap();
+    let call_mut = Instance::resolve_closure(&def, &args, ClosureKind::FnMut).unwrap();
+    assert_eq!(call_mut.kind(), InstanceKind::Item);
+}
+
+fn main() {
+    let path = "closures_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--edition=2021".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn counter(mut count: u32, step: (u32, u32)) -> u32 {{
+        let mut incr = || count += step.0;
+        incr();
+        count
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,100 @@
+// run-pass
+// Test that users are able to retrieve the coverage instrumentation of functions.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// needs-profiler-support
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::mir::coverage::CoverageTerm;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    let items = stable_mir::all_local_items();
+    let abs = items
+        .iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == "abs"
+        })
+        .unwrap();
+
+    let info = abs.coverage_info().unwrap();
+    assert!(info.num_counters >= 2);
+    assert_eq!(info.expressions.len() as u32, info.num_expressions);
+    assert!(!info.mappings.is_empty());
+    for mapping in &info.mappings {
+        assert!(mapping.region.file_name.ends_with("coverage_input.rs"));
+        assert!(mapping.region.start_line <= mapping.region.end_line);
+        if let Some(CoverageTerm::Expression(id)) = mapping.term {
+            assert!(info.expressions.iter().any(|expression| expression.id == id));
+        }
+    }
+}
+
+fn main() {
+    let path = "coverage_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "-Cinstrument-coverage".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn abs(x: i32) -> i32 {{
+        if x < 0 {{ -x }} else {{ x }}
+    }}"#
+    )?;
+    Ok(())
+}
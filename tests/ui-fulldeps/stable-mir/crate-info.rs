@@ -29,6 +29,14 @@
 
 /// This function uses the Stable MIR APIs to get information about the test crate.
 fn test_stable_mir(tcx: TyCtxt<'_>) {
+    assert_eq!(stable_mir::check_compatibility(), Ok(()));
+
+    // The input is compiled with the default options.
+    let options = stable_mir::compiler_options();
+    assert_eq!(options.opt_level, stable_mir::OptLevel::No);
+    assert!(options.debug_assertions);
+    assert!(options.overflow_checks);
+
     // Get the local crate using stable_mir API.
     let local = stable_mir::local_crate();
     assert_eq!(&local.name, CRATE_NAME);
@@ -39,10 +47,74 @@ fn test_stable_mir(tcx: TyCtxt<'_>) {
     let items = stable_mir::all_local_items();
     assert!(get_item(tcx, &items, (DefKind::Fn, "foo::bar")).is_some());
 
+    // Items can also be looked up lazily.
+    assert_eq!(stable_mir::local_items().len(), items.len());
+    assert!(stable_mir::local_items().any(|item| item.name() == "foo::bar"));
+    assert_eq!(stable_mir::local_items().nth(items.len()), None);
+
+    // Converting bodies from multiple threads yields the same result as doing it serially.
+    let fingerprints = std::sync::Mutex::new(vec![]);
+    stable_mir::par_for_each_item(items.clone(), |item| {
+        fingerprints.lock().unwrap().push((item.name(), item.body().fingerprint()));
+    });
+    let mut fingerprints = fingerprints.into_inner().unwrap();
+    fingerprints.sort();
+    let mut expected: Vec<_> =
+        items.iter().map(|item| (item.name(), item.body().fingerprint())).collect();
+    expected.sort();
+    assert_eq!(fingerprints, expected);
+
+    // Bodies converted by the compiler are well-formed.
+    for item in &items {
+        item.body().validate().unwrap();
+    }
+
+    // The test isn't cross-compiled, so the target is the machine running it.
+    let machine = stable_mir::target::MachineInfo::target();
+    assert_eq!(machine.pointer_width.bytes(), std::mem::size_of::<usize>());
+    let endian = if cfg!(target_endian = "little") {
+        stable_mir::target::Endian::Little
+    } else {
+        stable_mir::target::Endian::Big
+    };
+    assert_eq!(machine.endian, endian);
+
     // Find the `std` crate.
     assert!(stable_mir::find_crate("std").is_some());
 
+    // List the items of an external crate.
+    let core = stable_mir::find_crate("core").unwrap();
+    let core_items = core.items();
+    let swap = stable_mir::find_item_by_path("core::mem::swap").unwrap();
+    assert!(swap.has_body());
+    assert!(core_items.contains(&swap));
+    assert_eq!(local.items().len(), items.len());
+
+    // Find items by their path.
+    let foo_bar = stable_mir::find_item_by_path("input::foo::bar").unwrap();
+    assert_eq!(foo_bar.name(), "foo::bar");
+    assert_eq!(stable_mir::find_item_by_path(&foo_bar.name()), Some(foo_bar.clone()));
+    assert!(stable_mir::find_item_by_path("std::mem::swap").is_some());
+    assert!(stable_mir::find_item_by_path("std::mem::does_not_exist").is_none());
+
+    // Check the visibility of items.
+    assert_eq!(foo_bar.visibility(), stable_mir::Visibility::Public);
+    let identity = stable_mir::find_item_by_path("input::foo::identity").unwrap();
+    assert_eq!(identity.visibility(), stable_mir::Visibility::Restricted("crate".to_string()));
+    let negate = stable_mir::find_item_by_path("input::foo::inner::negate").unwrap();
+    assert_eq!(negate.visibility(), stable_mir::Visibility::Restricted("crate::foo".to_string()));
+    let answer = stable_mir::find_item_by_path("input::ANSWER").unwrap();
+    assert_eq!(answer.visibility(), stable_mir::Visibility::Private);
+
+    // Check attributes that affect symbol names.
+    assert_eq!(foo_bar.linkage_attrs(), Default::default());
+    let exported = stable_mir::find_item_by_path("input::exported").unwrap();
+    assert!(exported.linkage_attrs().no_mangle);
+    let renamed = stable_mir::find_item_by_path("input::renamed").unwrap();
+    assert_eq!(renamed.linkage_attrs().export_name.as_deref(), Some("input_renamed"));
+
     let bar = get_item(tcx, &items, (DefKind::Fn, "bar")).unwrap();
+    assert_eq!(bar.kind(), stable_mir::DefKind::Fn);
     let body = bar.body();
     assert_eq!(body.locals.len(), 2);
     assert_eq!(body.blocks.len(), 1);
@@ -56,6 +128,17 @@ fn test_stable_mir(tcx: TyCtxt<'_>) {
         stable_mir::mir::Terminator::Return => {}
         other => panic!("{other:?}"),
     }
+    let bar_ty = bar.ty();
+    assert_matches!(
+        bar_ty.kind(),
+        stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::FnDef(..))
+    );
+    assert_eq!(bar_ty.fn_sig().unwrap().value.inputs_and_output.len(), 2);
+    let bar_def = rustc_internal::fn_def(rustc_internal::item_def_id(bar));
+    let no_args = stable_mir::ty::GenericArgs(vec![]);
+    let reified = stable_mir::mir::mono::Instance::resolve_for_fn_ptr(&bar_def, &no_args).unwrap();
+    assert!(reified.mangled_name().contains("bar"));
+    assert_eq!(reified.kind(), stable_mir::mir::mono::InstanceKind::Item);
 
     let foo_bar = get_item(tcx, &items, (DefKind::Fn, "foo_bar")).unwrap();
     let body = foo_bar.body();
@@ -63,48 +146,134 @@ fn test_stable_mir(tcx: TyCtxt<'_>) {
     assert_eq!(body.blocks.len(), 4);
     let block = &body.blocks[0];
     match &block.terminator {
-        stable_mir::mir::Terminator::Call { .. } => {}
+        stable_mir::mir::Terminator::Call { func, .. } => {
+            let stable_mir::mir::Callee::Direct(def, args) = func.callee(&body.locals) else {
+                panic!("expected a direct call");
+            };
+            let callee = stable_mir::find_item_by_path("input::foo::bar").unwrap();
+            assert_eq!(def, rustc_internal::fn_def(rustc_internal::item_def_id(&callee)));
+            let instance = stable_mir::mir::mono::Instance::resolve(&def, &args).unwrap();
+            assert!(instance.mangled_name().contains("bar"));
+        }
         other => panic!("{other:?}"),
     }
+    assert!(!body.source_scopes.is_empty());
+    assert_eq!(body.source_scopes[0].parent_scope, None);
+    assert!(body.inlined_call_sites(block.terminator_source.scope).is_empty());
 
     let types = get_item(tcx, &items, (DefKind::Fn, "types")).unwrap();
     let body = types.body();
     assert_eq!(body.locals.len(), 6);
     assert_matches!(
-        body.locals[0].kind(),
+        body.locals[0].ty.kind(),
         stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Bool)
     );
     assert_matches!(
-        body.locals[1].kind(),
+        body.locals[1].ty.kind(),
         stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Bool)
     );
     assert_matches!(
-        body.locals[2].kind(),
+        body.locals[2].ty.kind(),
         stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Char)
     );
     assert_matches!(
-        body.locals[3].kind(),
+        body.locals[3].ty.kind(),
         stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Int(stable_mir::ty::IntTy::I32))
     );
     assert_matches!(
-        body.locals[4].kind(),
+        body.locals[4].ty.kind(),
         stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Uint(stable_mir::ty::UintTy::U64))
     );
     assert_matches!(
-        body.locals[5].kind(),
+        body.locals[5].ty.kind(),
         stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Float(
             stable_mir::ty::FloatTy::F64
         ))
     );
+    assert_eq!(body.locals[4].ty.to_string(), "u64");
 
     let drop = get_item(tcx, &items, (DefKind::Fn, "drop")).unwrap();
     let body = drop.body();
     assert_eq!(body.blocks.len(), 2);
+    assert_eq!(body.locals[1].ty.to_string(), "std::string::String");
     let block = &body.blocks[0];
     match &block.terminator {
         stable_mir::mir::Terminator::Drop { .. } => {}
         other => panic!("{other:?}"),
     }
+    let drop_glue = stable_mir::mir::mono::Instance::resolve_drop_in_place(body.locals[1].ty);
+    assert!(!drop_glue.is_empty_shim());
+    assert_eq!(drop_glue.kind(), stable_mir::mir::mono::InstanceKind::DropGlue);
+    assert_eq!(drop_glue.args().0.len(), 1);
+    // Dropping a `String` drops its buffer.
+    let glue_body = drop_glue.body().unwrap();
+    assert_matches!(
+        glue_body.locals[1].ty.kind(),
+        stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::RawPtr(..))
+    );
+    assert!(
+        glue_body
+            .blocks
+            .iter()
+            .any(|block| matches!(block.terminator, stable_mir::mir::Terminator::Drop { .. }))
+    );
+    glue_body.validate().unwrap();
+    let drop_glue = stable_mir::mir::mono::Instance::resolve_drop_in_place(body.locals[0].ty);
+    assert!(drop_glue.is_empty_shim());
+
+    let greeting = get_item(tcx, &items, (DefKind::Fn, "greeting")).unwrap();
+    let body = greeting.body();
+    match &body.blocks[0].statements[0] {
+        stable_mir::mir::Statement::Assign(
+            _,
+            stable_mir::mir::Rvalue::Use(stable_mir::mir::Operand::Constant(constant)),
+        ) => {
+            assert_eq!(constant.literal.try_as_str().as_deref(), Some("hello"));
+            assert_eq!(constant.literal.try_as_bytes().as_deref(), Some(&b"hello"[..]));
+        }
+        other => panic!("{other:?}"),
+    }
+
+    let static_ref = get_item(tcx, &items, (DefKind::Fn, "static_ref")).unwrap();
+    let body = static_ref.body();
+    match &body.blocks[0].statements[0] {
+        stable_mir::mir::Statement::Assign(
+            _,
+            stable_mir::mir::Rvalue::Use(stable_mir::mir::Operand::Constant(constant)),
+        ) => {
+            let provenance = constant.literal.provenance();
+            assert_eq!(provenance.len(), 1);
+            assert_eq!(provenance[0].0, 0);
+            assert_matches!(
+                provenance[0].1.global_alloc(),
+                stable_mir::mir::alloc::GlobalAlloc::Static(_)
+            );
+            let answer = match stable_mir::target::MachineInfo::target().endian {
+                stable_mir::target::Endian::Little => 42i32.to_le_bytes(),
+                stable_mir::target::Endian::Big => 42i32.to_be_bytes(),
+            };
+            let expected: Vec<_> = answer.iter().map(|byte| Some(*byte)).collect();
+            assert_eq!(provenance[0].1.read_bytes(0..4), Some(expected));
+            assert_eq!(provenance[0].1.read_bytes(0..8), None);
+        }
+        other => panic!("{other:?}"),
+    }
+
+    let impls = stable_mir::all_local_impls();
+    assert_eq!(impls.len(), 2);
+    let inherent = impls.iter().find(|impl_def| impl_def.trait_ref().is_none()).unwrap();
+    let methods = inherent.items();
+    assert_eq!(methods.len(), 1);
+    assert_eq!(methods[0].name(), "Counter::get");
+    assert_eq!(methods[0].kind(), stable_mir::DefKind::AssocFn);
+    let stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Adt(counter, _)) =
+        inherent.self_ty().kind()
+    else {
+        panic!("expected an ADT");
+    };
+    assert_eq!(counter.inherent_impls(), vec![inherent.clone()]);
+    let trait_impl = impls.iter().find(|impl_def| impl_def.trait_ref().is_some()).unwrap();
+    assert_eq!(trait_impl.trait_ref().unwrap().self_ty(), inherent.self_ty());
 
     let assert = get_item(tcx, &items, (DefKind::Fn, "assert")).unwrap();
     let body = assert.body();
@@ -114,6 +283,67 @@ fn test_stable_mir(tcx: TyCtxt<'_>) {
         stable_mir::mir::Terminator::Assert { .. } => {}
         other => panic!("{other:?}"),
     }
+
+    let array_len = get_item(tcx, &items, (DefKind::Fn, "array_len")).unwrap();
+    let body = array_len.body();
+    let stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Array(_, len)) =
+        body.locals[1].ty.kind()
+    else {
+        panic!("expected an array argument");
+    };
+    match len.kind {
+        stable_mir::ty::ConstantKind::Param(param) => {
+            assert_eq!(param.index, 0);
+            assert_eq!(param.name, "N");
+        }
+        other => panic!("{other:?}"),
+    }
+
+    let mono_items = stable_mir::collect_mono_items();
+    let fn_names: Vec<String> = mono_items
+        .iter()
+        .filter_map(|item| match item {
+            stable_mir::mir::mono::MonoItem::Fn(instance) => Some(instance.mangled_name()),
+            _ => None,
+        })
+        .collect();
+    assert!(fn_names.iter().any(|name| name.contains("foo_bar")));
+    // The drop glue of the `String` argument of `drop`.
+    assert!(fn_names.iter().any(|name| name.contains("drop_in_place")));
+    assert!(
+        mono_items
+            .iter()
+            .any(|item| matches!(item, stable_mir::mir::mono::MonoItem::Static(_)))
+    );
+
+    let bounded = get_item(tcx, &items, (DefKind::Fn, "bounded")).unwrap();
+    let bounds = bounded.param_bounds(0);
+    // `Clone`, the implicit `Sized` and `'static`.
+    assert_eq!(bounds.len(), 3);
+    for bound in &bounds {
+        if let stable_mir::ty::ParamBound::Trait(trait_ref) = bound {
+            assert_matches!(trait_ref.self_ty().kind(), stable_mir::ty::TyKind::Param(_));
+        }
+    }
+    assert_eq!(
+        bounds
+            .iter()
+            .filter(|bound| matches!(bound, stable_mir::ty::ParamBound::Outlives(_)))
+            .count(),
+        1
+    );
+
+    // Instantiating `name_of::<Counter>` resolves its return type to the `Name` of `Counter`.
+    let name_of = get_item(tcx, &items, (DefKind::Fn, "name_of")).unwrap();
+    let output = *name_of.ty().fn_sig().unwrap().value.inputs_and_output.last().unwrap();
+    assert_matches!(output.kind(), stable_mir::ty::TyKind::Alias(..));
+    let args = stable_mir::ty::GenericArgs(vec![stable_mir::ty::GenericArgKind::Type(
+        inherent.self_ty(),
+    )]);
+    assert_matches!(
+        output.normalize(&args).kind(),
+        stable_mir::ty::TyKind::RigidTy(stable_mir::ty::RigidTy::Uint(stable_mir::ty::UintTy::U32))
+    );
 }
 
 // Use internal API to find a function in a crate.
@@ -176,6 +406,16 @@ mod foo {{
         pub fn bar(i: i32) -> i64 {{
             i as i64
         }}
+
+        pub(crate) fn identity(i: i64) -> i64 {{
+            i
+        }}
+
+        mod inner {{
+            pub(super) fn negate(i: i64) -> i64 {{
+                -i
+            }}
+        }}
     }}
 
     pub fn bar(x: i32) -> i32 {{
@@ -194,9 +434,65 @@ pub fn types(b: bool, _: char, _: i32, _: u64, _: f64) -> bool {{
 
     pub fn drop(_: String) {{}}
 
+    pub fn greeting() -> &'static str {{
+        "hello"
+    }}
+
+    static ANSWER: i32 = 42;
+
+    pub struct Counter {{
+        count: u32,
+    }}
+
+    impl Counter {{
+        pub fn get(&self) -> u32 {{
+            self.count
+        }}
+    }}
+
+    pub trait Named {{
+        type Name;
+
+        fn name(&self) -> u32;
+    }}
+
+    impl Named for Counter {{
+        type Name = u32;
+
+        fn name(&self) -> u32 {{
+            self.count
+        }}
+    }}
+
+    pub fn static_ref() -> &'static i32 {{
+        &ANSWER
+    }}
+
     pub fn assert(x: i32) -> i32 {{
         x + 1
-    }}"#
+    }}
+
+    pub fn identity(x: i64) -> i64 {{
+        foo::identity(x)
+    }}
+
+    pub fn array_len<const N: usize>(_: [u8; N]) -> usize {{
+        N
+    }}
+
+    pub fn bounded<T: Clone + 'static>(x: &T) -> T {{
+        x.clone()
+    }}
+
+    pub fn name_of<N: Named>(name: N::Name) -> N::Name {{
+        name
+    }}
+
+    #[no_mangle]
+    pub extern "C" fn exported() {{}}
+
+    #[export_name = "input_renamed"]
+    pub fn renamed() {{}}"#
     )?;
     Ok(())
 }
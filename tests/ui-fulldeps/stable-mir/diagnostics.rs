@@ -0,0 +1,104 @@
+// run-pass
+// check-run-results
+// Test that diagnostics emitted through stable MIR are reported by the compiler at the given
+// span and level.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::DiagnosticLevel;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to emit a diagnostic of each level.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    for item in stable_mir::all_local_items() {
+        let name = tcx.def_path_str(rustc_internal::item_def_id(&item));
+        let level = match name.as_str() {
+            "add" => DiagnosticLevel::Warning,
+            "sub" => DiagnosticLevel::Note,
+            "mul" => DiagnosticLevel::Error,
+            _ => unreachable!("unexpected item `{name}`"),
+        };
+        stable_mir::emit_diagnostic(level, &format!("found `{name}`"), item.span());
+    }
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// It will invoke the compiler using a custom Callback implementation, which will
+/// invoke Stable MIR APIs after the compiler has finished its analysis.
+fn main() {
+    let path = "diagnostics_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    let result =
+        rustc_driver::catch_fatal_errors(|| RunCompiler::new(&args, &mut SMirCalls {}).run())
+            .unwrap();
+    // The error emitted by the test makes the compilation fail.
+    assert!(result.is_err());
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"#![allow(dead_code)]
+
+fn add(a: u32, b: u32) -> u32 {{
+    a + b
+}}
+
+fn sub(a: u32, b: u32) -> u32 {{
+    a - b
+}}
+
+fn mul(a: u32, b: u32) -> u32 {{
+    a * b
+}}
+"#
+    )?;
+    Ok(())
+}
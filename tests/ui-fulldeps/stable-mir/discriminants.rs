@@ -0,0 +1,133 @@
+// run-pass
+// Test that users are able to retrieve the discriminants of enum variants.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::ty::{AdtDef, IntTy, RigidTy, TyKind};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    let implicit = arg_adt(tcx, "implicit");
+    let discrs = implicit.discriminants();
+    assert_eq!(discrs.iter().map(|discr| discr.val).collect::<Vec<_>>(), [0, 1, 2]);
+    for discr in &discrs {
+        assert_matches!(discr.ty.kind(), TyKind::RigidTy(RigidTy::Int(IntTy::Isize)));
+    }
+
+    let explicit = arg_adt(tcx, "explicit");
+    let discrs = explicit.discriminants();
+    assert_eq!(discrs.iter().map(|discr| discr.val).collect::<Vec<_>>(), [10, 11, 20]);
+
+    // Negative discriminants are truncated to the size of the `repr` type.
+    let signed = arg_adt(tcx, "signed");
+    let discrs = signed.discriminants();
+    assert_eq!(
+        discrs.iter().map(|discr| discr.val).collect::<Vec<_>>(),
+        [0x80, 0xFE, 0xFF, 0, 5, 6]
+    );
+    for discr in &discrs {
+        assert_matches!(discr.ty.kind(), TyKind::RigidTy(RigidTy::Int(IntTy::I8)));
+    }
+
+    let not_enum = arg_adt(tcx, "not_enum");
+    assert!(not_enum.discriminants().is_empty());
+}
+
+/// Get the ADT of the only argument of the function `name`.
+fn arg_adt(tcx: TyCtxt<'_>, name: &str) -> AdtDef {
+    let item = stable_mir::all_local_items()
+        .into_iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+        })
+        .unwrap();
+    let TyKind::RigidTy(RigidTy::Adt(def, _)) = item.body().locals[1].ty.kind() else {
+        panic!("expected `{name}` to take an ADT");
+    };
+    def
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// It will invoke the compiler using a custom Callback implementation, which will
+/// invoke Stable MIR APIs after the compiler has finished its analysis.
+fn main() {
+    let path = "discriminants_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub enum Implicit {{ A, B, C }}
+
+    pub enum Explicit {{ A = 10, B, C = 20 }}
+
+    #[repr(i8)]
+    pub enum Signed {{ Min = -128, Neg = -2, NextNeg, Zero = 0, Pos = 5, NextPos }}
+
+    pub struct NotEnum {{ pub field: u8 }}
+
+    pub fn implicit(_: Implicit) {{}}
+    pub fn explicit(_: Explicit) {{}}
+    pub fn signed(_: Signed) {{}}
+    pub fn not_enum(_: NotEnum) {{}}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,152 @@
+// run-pass
+// Test that the fingerprint of a body doesn't depend on the compilation session, or on the order
+// in which types and items are interned.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// Compute the fingerprint of every function of the test crate, converting the bodies in reverse
+/// if `reverse` is set, so that types are interned in a different order.
+fn fingerprints(tcx: TyCtxt<'_>, reverse: bool) -> BTreeMap<String, u64> {
+    let mut items: Vec<_> = stable_mir::all_local_items()
+        .into_iter()
+        .filter(|item| tcx.def_kind(rustc_internal::item_def_id(item)) == DefKind::Fn)
+        .collect();
+    if reverse {
+        items.reverse();
+    }
+    items
+        .iter()
+        .map(|item| {
+            let name = tcx.def_path_str(rustc_internal::item_def_id(item));
+            (name, item.body().fingerprint())
+        })
+        .collect()
+}
+
+fn main() {
+    let path = "fingerprint_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    let mut runs = Vec::new();
+    for reverse in [false, true] {
+        let mut callbacks = SMirCalls { reverse, fingerprints: BTreeMap::new() };
+        rustc_driver::catch_fatal_errors(|| {
+            RunCompiler::new(&args, &mut callbacks).run().unwrap();
+        })
+        .unwrap();
+        runs.push(callbacks.fingerprints);
+    }
+
+    let first = &runs[0];
+    assert_eq!(first.len(), 10);
+    assert_eq!(first, &runs[1]);
+    // Only the contents of a body matter, not where it is.
+    assert_eq!(first["add_one"], first["add_one_again"]);
+    assert_ne!(first["add_one"], first["double"]);
+    assert_ne!(first["add_one"], first["len"]);
+    // Constants which point to memory are hashed by their contents, not by their allocation.
+    assert_eq!(first["greeting"], first["greeting_again"]);
+    assert_ne!(first["greeting"], first["farewell"]);
+    assert_eq!(first["bytes"], first["bytes_again"]);
+    assert_ne!(first["bytes"], first["other_bytes"]);
+}
+
+struct SMirCalls {
+    reverse: bool,
+    fingerprints: BTreeMap<String, u64>,
+}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || {
+                self.fingerprints = fingerprints(tcx, self.reverse);
+            });
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn add_one(x: u32) -> u32 {{
+        x + 1
+    }}
+
+    pub fn double(x: u32) -> u32 {{
+        x * 2
+    }}
+
+    pub fn len(s: &str) -> usize {{
+        s.len()
+    }}
+
+    pub fn add_one_again(x: u32) -> u32 {{
+        x + 1
+    }}
+
+    pub fn greeting() -> &'static str {{
+        "hello"
+    }}
+
+    pub fn greeting_again() -> &'static str {{
+        "hello"
+    }}
+
+    pub fn farewell() -> &'static str {{
+        "goodbye"
+    }}
+
+    pub fn bytes() -> &'static [u8; 3] {{
+        b"abc"
+    }}
+
+    pub fn bytes_again() -> &'static [u8; 3] {{
+        b"abc"
+    }}
+
+    pub fn other_bytes() -> &'static [u8; 3] {{
+        b"abd"
+    }}"#
+    )?;
+    Ok(())
+}
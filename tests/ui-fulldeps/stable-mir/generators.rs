@@ -0,0 +1,113 @@
+// run-pass
+// Test that users are able to inspect the types generators keep alive across suspension points.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::ty::{GenericArgKind, RigidTy, TyKind};
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    let items = stable_mir::all_local_items();
+    let wait = items
+        .iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == "wait"
+        })
+        .unwrap();
+
+    let sig = wait.ty().fn_sig().unwrap();
+    let output = *sig.value.inputs_and_output.last().unwrap();
+    let TyKind::RigidTy(RigidTy::Generator(_, args, _)) = output.resolve_opaque().unwrap().kind()
+    else {
+        panic!("expected `wait` to return a generator");
+    };
+    let witness = args
+        .0
+        .iter()
+        .find_map(|arg| match arg {
+            GenericArgKind::Type(ty) => match ty.kind() {
+                TyKind::RigidTy(kind @ RigidTy::GeneratorWitness(_))
+                | TyKind::RigidTy(kind @ RigidTy::GeneratorWitnessMIR(..)) => Some(kind),
+                _ => None,
+            },
+            _ => None,
+        })
+        .unwrap();
+    if let RigidTy::GeneratorWitness(types) = witness {
+        // The `String` argument is kept alive across the `await`.
+        assert!(types.value.iter().any(|ty| ty.to_string().contains("String")));
+    }
+}
+
+fn main() {
+    let path = "generators_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--edition=2021".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    async fn ready() {{}}
+
+    pub async fn wait(name: String) -> usize {{
+        ready().await;
+        name.len()
+    }}"#
+    )?;
+    Ok(())
+}
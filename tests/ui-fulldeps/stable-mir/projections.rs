@@ -0,0 +1,117 @@
+// run-pass
+// Test that users are able to inspect the projections of places and compute their types.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::mir::{Operand, ProjectionElem, Rvalue, Statement};
+use stable_mir::ty::{RigidTy, TyKind, UintTy};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    let items = stable_mir::all_local_items();
+    let index = items
+        .iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == "index"
+        })
+        .unwrap();
+    let body = index.body();
+
+    // Look for the place which is indexed, wherever it shows up in the body.
+    let place = body
+        .blocks
+        .iter()
+        .flat_map(|block| &block.statements)
+        .flat_map(|statement| match statement {
+            Statement::Assign(lhs, Rvalue::Use(Operand::Copy(rhs) | Operand::Move(rhs))) => {
+                vec![lhs, rhs]
+            }
+            Statement::Assign(lhs, _) => vec![lhs],
+            _ => vec![],
+        })
+        .find(|place| place.projection.iter().any(|elem| matches!(elem, ProjectionElem::Index(_))))
+        .unwrap();
+    assert_eq!(place.local, 1);
+    let [ProjectionElem::Deref, ProjectionElem::Field(1, _), ProjectionElem::Index(index)] =
+        place.projection[..]
+    else {
+        panic!("unexpected projection: {:?}", place.projection);
+    };
+    assert_matches!(body.locals[index].ty.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::Usize)));
+    assert_eq!(place.ty(&body.locals).to_string(), "u16");
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// It will invoke the compiler using a custom Callback implementation, which will
+/// invoke Stable MIR APIs after the compiler has finished its analysis.
+fn main() {
+    let path = "projections_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn index(t: &(u8, [u16; 4]), i: usize) -> u16 {{
+        t.1[i]
+    }}"#
+    )?;
+    Ok(())
+}
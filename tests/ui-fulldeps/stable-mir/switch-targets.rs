@@ -0,0 +1,143 @@
+// run-pass
+// Test that users are able to inspect the targets of `SwitchInt` terminators.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::mir::{SwitchTargets, Terminator};
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    // `match x { 1 => .., 5 => .., _ => .. }`
+    let targets = switch_targets(tcx, "classify");
+    assert_eq!(targets.len(), 2);
+    assert!(!targets.is_empty());
+    let branches: Vec<_> = targets.branches().collect();
+    assert_eq!(branches.iter().map(|(value, _)| *value).collect::<Vec<_>>(), [1, 5]);
+    assert_eq!(targets.target_for_value(1), branches[0].1);
+    assert_eq!(targets.target_for_value(5), branches[1].1);
+    assert_eq!(targets.target_for_value(2), targets.otherwise());
+    assert_eq!(targets.all_targets(), [branches[0].1, branches[1].1, targets.otherwise()]);
+    assert_ne!(branches[0].1, branches[1].1);
+    assert_ne!(branches[0].1, targets.otherwise());
+
+    // `if b { .. } else { .. }` switches on `false`.
+    let targets = switch_targets(tcx, "choose");
+    let [(0, else_)] = targets.branches().collect::<Vec<_>>()[..] else {
+        panic!("unexpected branches: {targets:?}");
+    };
+    let then = targets.otherwise();
+    assert_ne!(then, else_);
+    assert_eq!(targets.target_for_value(0), else_);
+    assert_eq!(targets.target_for_value(1), then);
+    assert_eq!(targets.all_targets(), [else_, then]);
+
+    // The constructors build the same targets.
+    let static_if = SwitchTargets::static_if(0, else_, then);
+    assert_eq!(static_if.branches().collect::<Vec<_>>(), [(0, else_)]);
+    assert_eq!(static_if.otherwise(), then);
+    let new = SwitchTargets::new([(0, else_)], then);
+    assert_eq!(new.all_targets(), static_if.all_targets());
+    assert!(SwitchTargets::new([], then).is_empty());
+}
+
+/// Get the targets of the only `SwitchInt` terminator in the body of the function `name`.
+fn switch_targets(tcx: TyCtxt<'_>, name: &str) -> SwitchTargets {
+    let item = stable_mir::all_local_items()
+        .into_iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+        })
+        .unwrap();
+    let mut switches = item.body().blocks.into_iter().filter_map(|block| match block.terminator {
+        Terminator::SwitchInt { targets, .. } => Some(targets),
+        _ => None,
+    });
+    let targets = switches.next().unwrap();
+    assert!(switches.next().is_none());
+    targets
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// It will invoke the compiler using a custom Callback implementation, which will
+/// invoke Stable MIR APIs after the compiler has finished its analysis.
+fn main() {
+    let path = "switch_targets_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn classify(x: u8) -> u32 {{
+        match x {{
+            1 => one(),
+            5 => five(),
+            _ => other(),
+        }}
+    }}
+
+    pub fn choose(b: bool) -> u32 {{
+        if b {{ one() }} else {{ other() }}
+    }}
+
+    fn one() -> u32 {{ 1 }}
+    fn five() -> u32 {{ 5 }}
+    fn other() -> u32 {{ 0 }}"#
+    )?;
+    Ok(())
+}
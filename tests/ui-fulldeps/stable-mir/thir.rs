@@ -0,0 +1,112 @@
+// run-pass
+// Test that users are able to inspect the THIR of bodies before MIR is built.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::thir::{ExprKind, PatKind, Stmt};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    let items = stable_mir::all_local_items();
+    let sum = items
+        .iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == "sum"
+        })
+        .unwrap();
+
+    let thir = sum.thir_body().unwrap();
+    assert_eq!(thir.params.len(), 1);
+    let Some(param) = &thir.params[0].pat else { panic!("expected a parameter pattern") };
+    let PatKind::Binding { name, var: values, .. } = &param.kind else {
+        panic!("expected a binding, found {:?}", param.kind)
+    };
+    assert_eq!(name, "values");
+
+    // The `for` loop is desugared into a `match` on the iterator, with a `loop` inside.
+    assert!(thir.exprs.iter().any(|expr| matches!(expr.kind, ExprKind::Match { .. })));
+    assert!(thir.exprs.iter().any(|expr| matches!(expr.kind, ExprKind::Loop { .. })));
+    assert!(thir.exprs.iter().any(|expr| matches!(expr.kind, ExprKind::AssignOp { .. })));
+    assert!(
+        thir.exprs
+            .iter()
+            .any(|expr| matches!(expr.kind, ExprKind::VarRef { var } if var == *values))
+    );
+    assert!(thir.stmts.iter().any(|stmt| {
+        matches!(stmt, Stmt::Let { pattern, .. }
+            if matches!(&pattern.kind, PatKind::Binding { name, .. } if name == "total"))
+    }));
+    assert_matches!(thir.exprs[thir.value].kind, ExprKind::Scope { .. });
+}
+
+fn main() {
+    let path = "thir_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after expansion, before MIR is built from the THIR of bodies. Return value
+    /// instructs the compiler whether to continue the compilation afterwards (defaults to
+    /// `Compilation::Continue`)
+    fn after_expansion<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn sum(values: &[u32]) -> u32 {{
+        let mut total = 0;
+        for value in values {{
+            total += *value;
+        }}
+        total
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,153 @@
+// run-pass
+// Test the helpers that users can call to inspect a type.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::mir::Mutability;
+use stable_mir::ty::{RigidTy, TyKind, TypeAndMut};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    let item = stable_mir::all_local_items()
+        .into_iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == "helpers"
+        })
+        .unwrap();
+    let body = item.body();
+    let [unit, r, rr, p, m, b, v] = [0, 1, 2, 3, 4, 5, 6].map(|local| body.locals[local].ty);
+
+    assert!(unit.is_unit());
+    assert!(!v.is_unit());
+    assert!(!unit.is_ref());
+    assert_eq!(unit.peel_refs(), unit);
+    assert_matches!(unit.builtin_deref(true), None);
+
+    // `&u8`
+    assert!(r.is_ref());
+    assert!(!r.is_raw_ptr());
+    assert_eq!(r.peel_refs(), v);
+    assert_matches!(
+        r.builtin_deref(false),
+        Some(TypeAndMut { ty, mutability: Mutability::Not }) if ty == v
+    );
+
+    // `&&mut u8`
+    assert!(rr.is_ref());
+    assert_eq!(rr.peel_refs(), v);
+    let Some(TypeAndMut { ty: inner, mutability: Mutability::Not }) = rr.builtin_deref(false)
+    else {
+        panic!("expected `&&mut u8` to deref to `&mut u8`");
+    };
+    assert!(inner.is_ref());
+    assert_matches!(inner.kind(), TyKind::RigidTy(RigidTy::Ref(_, _, Mutability::Mut)));
+    assert_matches!(
+        inner.builtin_deref(false),
+        Some(TypeAndMut { ty, mutability: Mutability::Mut }) if ty == v
+    );
+
+    // `*const u8` and `*mut u8` are only dereferenced explicitly, and aren't references.
+    assert!(!p.is_ref());
+    assert!(p.is_raw_ptr());
+    assert_eq!(p.peel_refs(), p);
+    assert_matches!(p.builtin_deref(false), None);
+    assert_matches!(
+        p.builtin_deref(true),
+        Some(TypeAndMut { ty, mutability: Mutability::Not }) if ty == v
+    );
+    assert!(m.is_raw_ptr());
+    assert_matches!(
+        m.builtin_deref(true),
+        Some(TypeAndMut { ty, mutability: Mutability::Mut }) if ty == v
+    );
+
+    // `Box<u8>`
+    assert!(b.is_box());
+    assert!(!b.is_ref());
+    assert_eq!(b.peel_refs(), b);
+    assert_matches!(
+        b.builtin_deref(false),
+        Some(TypeAndMut { ty, mutability: Mutability::Not }) if ty == v
+    );
+
+    // `u8`
+    assert!(!v.is_ref());
+    assert!(!v.is_box());
+    assert_eq!(v.peel_refs(), v);
+    assert_matches!(v.builtin_deref(true), None);
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// It will invoke the compiler using a custom Callback implementation, which will
+/// invoke Stable MIR APIs after the compiler has finished its analysis.
+fn main() {
+    let path = "ty_helpers_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn helpers(r: &u8, rr: &&mut u8, p: *const u8, m: *mut u8, b: Box<u8>, v: u8) {{
+        let _ = (r, rr, p, m, b, v);
+    }}"#
+    )?;
+    Ok(())
+}
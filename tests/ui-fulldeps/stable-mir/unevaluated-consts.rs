@@ -0,0 +1,151 @@
+// run-pass
+// Test that users are able to inspect unevaluated constants, such as uses of associated constants
+// and promoteds.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::mir::{Operand, Rvalue, Statement};
+use stable_mir::ty::{Const, ConstantKind, GenericArgKind, TyKind};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    let max_def = tcx
+        .hir_crate_items(())
+        .definitions()
+        .find(|def_id| tcx.def_path_str(def_id.to_def_id()) == "Limits::MAX")
+        .unwrap();
+
+    // `T::MAX` can't be evaluated, as it depends on `T`.
+    let max = constants(tcx, "max").pop().unwrap();
+    let ConstantKind::Unevaluated(uv) = &max.kind else {
+        panic!("expected an unevaluated constant, found {max:?}");
+    };
+    assert_eq!(uv.def, rustc_internal::const_def(max_def.to_def_id()));
+    assert_eq!(uv.promoted, None);
+    let [GenericArgKind::Type(arg)] = &uv.args.0[..] else {
+        panic!("unexpected arguments: {:?}", uv.args);
+    };
+    assert_matches!(arg.kind(), TyKind::Param(param) if param.name == "T");
+    assert_matches!(max.eval().kind, ConstantKind::Unevaluated(_));
+
+    // `&[1, 2]` is promoted.
+    let pair_item = item(tcx, "pair");
+    let pair = constants(tcx, "pair").pop().unwrap();
+    let ConstantKind::Unevaluated(uv) = &pair.kind else {
+        panic!("expected an unevaluated constant, found {pair:?}");
+    };
+    assert_eq!(uv.def, rustc_internal::const_def(rustc_internal::item_def_id(&pair_item)));
+    assert_eq!(uv.promoted, Some(0));
+    assert!(uv.args.0.is_empty());
+    let value = pair.eval();
+    assert_matches!(value.kind, ConstantKind::Value);
+    assert_eq!(value.provenance().len(), 1);
+}
+
+/// The constant operands assigned to locals in the body of the function `name`.
+fn constants(tcx: TyCtxt<'_>, name: &str) -> Vec<Const> {
+    item(tcx, name)
+        .body()
+        .blocks
+        .into_iter()
+        .flat_map(|block| block.statements)
+        .filter_map(|statement| match statement {
+            Statement::Assign(_, Rvalue::Use(Operand::Constant(constant))) => {
+                Some(constant.literal)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn item(tcx: TyCtxt<'_>, name: &str) -> stable_mir::CrateItem {
+    stable_mir::all_local_items()
+        .into_iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+        })
+        .unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// It will invoke the compiler using a custom Callback implementation, which will
+/// invoke Stable MIR APIs after the compiler has finished its analysis.
+fn main() {
+    let path = "unevaluated_consts_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub trait Limits {{
+        const MAX: u32;
+    }}
+
+    pub fn max<T: Limits>() -> u32 {{
+        T::MAX
+    }}
+
+    pub fn pair() -> &'static [u32] {{
+        &[1, 2]
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,137 @@
+// run-pass
+// Test that users are able to find the operations that require `unsafe` in a body.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::mir::unsafety::{UnsafeOperationKind, UnsafeSource};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    let items = stable_mir::all_local_items();
+    let get_item = |name: &str| {
+        items
+            .iter()
+            .find(|item| {
+                let def_id = rustc_internal::item_def_id(item);
+                tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+            })
+            .unwrap()
+    };
+
+    let read = get_item("read").unsafety().unwrap();
+    assert_eq!(read.unsafe_blocks.len(), 3);
+    assert_eq!(read.unsafe_blocks.iter().filter(|block| block.used).count(), 2);
+    let deref = read
+        .operations
+        .iter()
+        .find(|op| op.kind == UnsafeOperationKind::DerefOfRawPointer)
+        .unwrap();
+    assert_eq!(deref.source, UnsafeSource::Block(read.unsafe_blocks[0].span));
+    let union_read = read
+        .operations
+        .iter()
+        .find(|op| op.kind == UnsafeOperationKind::AccessToUnionField)
+        .unwrap();
+    assert_eq!(union_read.source, UnsafeSource::Block(read.unsafe_blocks[1].span));
+
+    let bump = get_item("bump").unsafety().unwrap();
+    assert!(bump.unsafe_blocks.is_empty());
+    assert!(bump.operations.iter().all(|op| op.source == UnsafeSource::UnsafeFn));
+    let kinds: Vec<_> = bump.operations.iter().map(|op| op.kind).collect();
+    assert!(kinds.contains(&UnsafeOperationKind::CallToUnsafeFunction));
+    assert!(kinds.contains(&UnsafeOperationKind::UseOfMutableStatic));
+
+    let safe = get_item("safe").unsafety().unwrap();
+    assert_matches!(&safe.operations[..], []);
+}
+
+fn main() {
+    let path = "unsafety_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub union Bits {{
+        pub int: u32,
+        pub float: f32,
+    }}
+
+    static mut COUNTER: u32 = 0;
+
+    pub unsafe fn danger() {{}}
+
+    pub fn read(ptr: *const u32, bits: &Bits) -> u32 {{
+        let value = unsafe {{ *ptr }};
+        let int = unsafe {{ bits.int }};
+        #[allow(unused_unsafe)]
+        unsafe {{}}
+        value.wrapping_add(int)
+    }}
+
+    pub unsafe fn bump() {{
+        danger();
+        COUNTER = COUNTER.wrapping_add(1);
+    }}
+
+    pub fn safe(value: Box<u32>) -> u32 {{
+        *value
+    }}"#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,198 @@
+// run-pass
+// Test that users are able to walk and substitute the types of a generic signature.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_smir;
+
+use rustc_driver::{Callbacks, Compilation, RunCompiler};
+use rustc_hir::def::DefKind;
+use rustc_interface::{interface, Queries};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::EarlyErrorHandler;
+use rustc_smir::{rustc_internal, stable_mir};
+use stable_mir::mir::{Callee, Terminator};
+use stable_mir::ty::visit::{Foldable, TyFolder, TyVisitor, Visitable};
+use stable_mir::ty::{
+    Const, ConstantKind, GenericArgKind, GenericArgs, RigidTy, Ty, TyKind, UintTy,
+};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// Collects the names of the generic parameters used in a value.
+#[derive(Default)]
+struct ParamCollector {
+    tys: Vec<String>,
+    consts: Vec<String>,
+}
+
+impl TyVisitor for ParamCollector {
+    type Break = ();
+
+    fn visit_ty(&mut self, ty: &Ty) -> ControlFlow<()> {
+        if let TyKind::Param(param) = ty.kind() {
+            self.tys.push(param.name);
+        }
+        ty.super_visit(self)
+    }
+
+    fn visit_const(&mut self, constant: &Const) -> ControlFlow<()> {
+        if let ConstantKind::Param(param) = &constant.kind {
+            self.consts.push(param.name.clone());
+        }
+        constant.super_visit(self)
+    }
+}
+
+/// Replaces the generic parameters used in a value by the given arguments.
+struct Subst<'a> {
+    args: &'a GenericArgs,
+}
+
+impl TyFolder for Subst<'_> {
+    type Break = ();
+
+    fn fold_ty(&mut self, ty: &Ty) -> ControlFlow<(), Ty> {
+        match ty.kind() {
+            TyKind::Param(param) => match &self.args.0[param.index as usize] {
+                GenericArgKind::Type(ty) => ControlFlow::Continue(*ty),
+                _ => ControlFlow::Break(()),
+            },
+            _ => ty.super_fold(self),
+        }
+    }
+
+    fn fold_const(&mut self, constant: &Const) -> ControlFlow<(), Const> {
+        match &constant.kind {
+            ConstantKind::Param(param) => match &self.args.0[param.index as usize] {
+                GenericArgKind::Const(constant) => ControlFlow::Continue(constant.clone()),
+                _ => ControlFlow::Break(()),
+            },
+            _ => constant.super_fold(self),
+        }
+    }
+}
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) {
+    // The return type and the two arguments of `generic`.
+    let generic = get_fn(tcx, "generic").body();
+    let signature: Vec<Ty> = generic.locals[..3].iter().map(|decl| decl.ty).collect();
+
+    let mut params = ParamCollector::default();
+    assert_eq!(signature.visit(&mut params), ControlFlow::Continue(()));
+    assert_eq!(params.tys, ["T", "T", "T"]);
+    assert_eq!(params.consts, ["N"]);
+
+    let caller = get_fn(tcx, "caller").body();
+    let Terminator::Call { func, .. } = &caller.blocks[0].terminator else {
+        panic!("expected `caller` to call `generic`");
+    };
+    let Callee::Direct(_, args) = func.callee(&caller.locals) else {
+        panic!("expected a direct call");
+    };
+    assert_matches!(&args.0[..], [GenericArgKind::Type(_), GenericArgKind::Const(_)]);
+
+    let ControlFlow::Continue(folded) = signature.fold(&mut Subst { args: &args }) else {
+        panic!("unexpected generic argument kind");
+    };
+    for (ty, folded) in signature.iter().zip(&folded) {
+        assert_eq!(*folded, ty.normalize(&args));
+    }
+
+    // `Option<T>` and `&mut dyn Iterator<Item = T>` are the signature of `caller`.
+    assert_eq!(folded[0], caller.locals[0].ty);
+    assert_eq!(folded[2], caller.locals[1].ty);
+    assert_eq!(folded[2].to_string(), "&mut dyn std::iter::Iterator<Item = u8>");
+
+    // `[T; N]` is the type of the array `caller` builds.
+    let TyKind::RigidTy(RigidTy::Array(elem, len)) = folded[1].kind() else {
+        panic!("expected an array, found `{}`", folded[1]);
+    };
+    assert_matches!(elem.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U8)));
+    assert_matches!(len.kind, ConstantKind::Value);
+    assert!(caller.locals.iter().any(|decl| decl.ty == folded[1]));
+}
+
+fn get_fn(tcx: TyCtxt<'_>, name: &str) -> stable_mir::CrateItem {
+    stable_mir::all_local_items()
+        .into_iter()
+        .find(|item| {
+            let def_id = rustc_internal::item_def_id(item);
+            tcx.def_kind(def_id) == DefKind::Fn && tcx.def_path_str(def_id) == name
+        })
+        .unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// It will invoke the compiler using a custom Callback implementation, which will
+/// invoke Stable MIR APIs after the compiler has finished its analysis.
+fn main() {
+    let path = "visit_fold_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    rustc_driver::catch_fatal_errors(|| {
+        RunCompiler::new(&args, &mut SMirCalls {}).run().unwrap();
+    })
+    .unwrap();
+}
+
+struct SMirCalls {}
+
+impl Callbacks for SMirCalls {
+    /// Called after analysis. Return value instructs the compiler whether to
+    /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    fn after_analysis<'tcx>(
+        &mut self,
+        _handler: &EarlyErrorHandler,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().enter(|tcx| {
+            rustc_smir::rustc_internal::run(tcx, || test_stable_mir(tcx));
+        });
+        // No need to keep going.
+        Compilation::Stop
+    }
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn generic<T, const N: usize>(
+        arr: [T; N],
+        iter: &mut dyn Iterator<Item = T>,
+    ) -> Option<T> {{
+        drop(arr);
+        iter.next()
+    }}
+
+    pub fn caller(iter: &mut dyn Iterator<Item = u8>) -> Option<u8> {{
+        generic::<u8, 4>([0; 4], iter)
+    }}"#
+    )?;
+    Ok(())
+}